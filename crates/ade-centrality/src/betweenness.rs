@@ -0,0 +1,177 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::VecDeque;
+
+// Marks a node not reached by the breadth-first search
+const UNREACHED: usize = usize::MAX;
+
+/// Computes the betweenness centrality of every node with Brandes' algorithm.
+///
+/// The betweenness of a node `v` sums, over all the ordered pairs `(s, t)` of other nodes
+/// with `t` reachable from `s`, the fraction of the shortest paths from `s` to `t` that go
+/// through `v`. Nodes with a high betweenness are the bridges that much of the traffic of
+/// a network goes through. Paths follow edge directions and every edge has length one;
+/// dividing by `(n - 1)(n - 2)` gives values between zero and one.
+///
+/// A breadth-first search from every node counts the shortest paths, and their
+/// dependencies are accumulated in reverse order of distance, in `O(n m)` time and
+/// `O(n + m)` memory.
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the betweenness of every node.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_centrality::betweenness_centrality;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 → 1 → 2 and 0 → 3 → 2: 1 and 3 each carry half of the paths from 0 to 2
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (0, 3), (3, 2)]);
+/// assert_eq!(betweenness_centrality(&graph), vec![0.0, 0.5, 0.0, 0.5]);
+/// ```
+pub fn betweenness_centrality<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<f64> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let n = graph.node_count();
+    let successors: Vec<Vec<u32>> = (0..n as u32)
+        .map(|key| graph.get_successors_keys(key).collect())
+        .collect();
+
+    let mut betweenness = vec![0.0; n];
+    let mut distance = vec![UNREACHED; n];
+    let mut paths = vec![0.0; n];
+    let mut dependency = vec![0.0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut queue = VecDeque::new();
+    for source in 0..n {
+        distance.fill(UNREACHED);
+        paths.fill(0.0);
+        dependency.fill(0.0);
+        order.clear();
+
+        // Number of shortest paths from the source to every node, in order of distance
+        distance[source] = 0;
+        paths[source] = 1.0;
+        queue.push_back(source);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &w in &successors[v] {
+                let w = w as usize;
+                if distance[w] == UNREACHED {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    paths[w] += paths[v];
+                }
+            }
+        }
+
+        // Every node passes its dependency on to the nodes right before it on shortest paths
+        for &w in order.iter().rev() {
+            for &x in &successors[w] {
+                let x = x as usize;
+                if distance[x] == distance[w] + 1 {
+                    dependency[w] += paths[w] / paths[x] * (1.0 + dependency[x]);
+                }
+            }
+            if w != source {
+                betweenness[w] += dependency[w];
+            }
+        }
+    }
+    betweenness
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    // Counts the shortest paths between every pair and through every node, from the
+    // distances between all pairs
+    fn naive_betweenness(graph: &impl GraphViewTrait<Node, Edge>) -> Vec<f64> {
+        let n = graph.node_count();
+        let mut distance = vec![vec![UNREACHED; n]; n];
+        let mut count = vec![vec![0.0; n]; n];
+        for s in 0..n {
+            distance[s][s] = 0;
+            count[s][s] = 1.0;
+            let mut frontier = vec![s];
+            let mut d = 0;
+            while !frontier.is_empty() {
+                d += 1;
+                let mut next = Vec::new();
+                for &v in &frontier {
+                    for w in graph.get_successors_keys(v as u32) {
+                        let w = w as usize;
+                        if distance[s][w] == UNREACHED {
+                            distance[s][w] = d;
+                            next.push(w);
+                        }
+                        if distance[s][w] == d {
+                            count[s][w] += count[s][v];
+                        }
+                    }
+                }
+                frontier = next;
+            }
+        }
+
+        let mut betweenness = vec![0.0; n];
+        for s in 0..n {
+            for t in (0..n).filter(|&t| t != s && distance[s][t] != UNREACHED) {
+                for v in (0..n).filter(|&v| v != s && v != t) {
+                    if distance[s][v] != UNREACHED
+                        && distance[v][t] != UNREACHED
+                        && distance[s][v] + distance[v][t] == distance[s][t]
+                    {
+                        betweenness[v] += count[s][v] * count[v][t] / count[s][t];
+                    }
+                }
+            }
+        }
+        betweenness
+    }
+
+    #[test]
+    fn test_path_and_cycle() {
+        let path = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(betweenness_centrality(&path), vec![0.0, 2.0, 2.0, 0.0]);
+
+        // In a directed cycle of four nodes, each node is inside the paths of three pairs
+        let cycle =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(betweenness_centrality(&cycle), vec![3.0; 4]);
+    }
+
+    #[test]
+    fn test_panics() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(betweenness_centrality(&graph), INVALID_KEY_SEQUENCE);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive_betweenness(graph in arb_graph::<Node, Edge>(12, 30)) {
+            let expected = naive_betweenness(&graph);
+            for (found, expected) in betweenness_centrality(&graph).iter().zip(expected) {
+                prop_assert!((found - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::VecDeque;
+
+// Marks a node not reached by the breadth-first search
+const UNREACHED: usize = usize::MAX;
+
+/// Computes the closeness centrality of every node, from its distances to the nodes it can
+/// reach.
+///
+/// The closeness of a node is the inverse of its average distance to the other nodes:
+/// nodes that reach the rest of the graph in few steps score high. Paths follow edge
+/// directions, and every edge has length one. So that graphs that are not strongly
+/// connected can be compared, the Wasserman–Faust variant scales the closeness of a node
+/// that reaches `r` of the `n - 1` other nodes, at total distance `d`, to
+/// `(r / (n - 1)) · (r / d)`. A node that reaches no other node has a closeness of zero.
+///
+/// A breadth-first search from every node takes `O(n m)` time overall.
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the closeness of every node, between zero and one.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_centrality::closeness_centrality;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 reaches 1 and 2 in one step, 1 reaches only 2
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (1, 2)]);
+/// assert_eq!(closeness_centrality(&graph), vec![1.0, 0.5, 0.0]);
+/// ```
+pub fn closeness_centrality<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<f64> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let n = graph.node_count();
+    let mut distance = vec![UNREACHED; n];
+    let mut queue = VecDeque::new();
+    (0..n)
+        .map(|source| {
+            distance.fill(UNREACHED);
+            distance[source] = 0;
+            queue.push_back(source);
+            let (mut reached, mut total) = (0usize, 0usize);
+            while let Some(v) = queue.pop_front() {
+                for w in graph.get_successors_keys(v as u32) {
+                    let w = w as usize;
+                    if distance[w] == UNREACHED {
+                        distance[w] = distance[v] + 1;
+                        reached += 1;
+                        total += distance[w];
+                        queue.push_back(w);
+                    }
+                }
+            }
+            if reached == 0 {
+                return 0.0;
+            }
+            let r = reached as f64;
+            (r / (n - 1) as f64) * (r / total as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_cycle_and_unreachable_nodes() {
+        // In a directed cycle of four nodes, distances are 1, 2 and 3 from every node
+        let cycle =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(closeness_centrality(&cycle), vec![0.5; 4]);
+
+        // 0 → 1 → 2 and the isolated node 3: 0 reaches two of three nodes at distance 3
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (3, 3)]);
+        let closeness = closeness_centrality(&graph);
+        assert!((closeness[0] - 4.0 / 9.0).abs() < 1e-12);
+        assert!((closeness[1] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(closeness[2..], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_panics() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(closeness_centrality(&graph), INVALID_KEY_SEQUENCE);
+    }
+}
@@ -0,0 +1,110 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Computes the in-degree centrality of every node: the fraction of the other nodes that
+/// have an edge to it.
+///
+/// The in-degree of every node is divided by `n - 1`, the largest in-degree possible
+/// without self-loops; a self-loop counts as an incoming edge, so values can exceed one.
+/// Graphs with fewer than two nodes have centralities of zero. Runs in `O(n + m)` time.
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the centrality of every node.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_centrality::in_degree_centrality;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 2), (1, 2), (2, 0)]);
+/// assert_eq!(in_degree_centrality(&graph), vec![0.5, 0.0, 1.0]);
+/// ```
+pub fn in_degree_centrality<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<f64> {
+    degree_centrality(graph, |key| graph.in_degree(key))
+}
+
+/// Computes the out-degree centrality of every node: the fraction of the other nodes that
+/// it has an edge to.
+///
+/// The out-degree of every node is divided by `n - 1`, as in [`in_degree_centrality`].
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the centrality of every node.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_centrality::out_degree_centrality;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (2, 0)]);
+/// assert_eq!(out_degree_centrality(&graph), vec![1.0, 0.0, 0.5]);
+/// ```
+pub fn out_degree_centrality<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<f64> {
+    degree_centrality(graph, |key| graph.out_degree(key))
+}
+
+// Degree of every node divided by n - 1
+fn degree_centrality<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    degree: impl Fn(u32) -> usize,
+) -> Vec<f64> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let n = graph.node_count();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+    (0..n as u32)
+        .map(|key| degree(key) as f64 / (n - 1) as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_star_and_self_loops() {
+        let star = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 1), (0, 2), (0, 3), (0, 4), (4, 4)],
+        );
+        assert_eq!(out_degree_centrality(&star), vec![1.0, 0.0, 0.0, 0.0, 0.25]);
+        assert_eq!(
+            in_degree_centrality(&star),
+            vec![0.0, 0.25, 0.25, 0.25, 0.5]
+        );
+
+        let single = build_graph::<Node, Edge>(vec![0], vec![(0, 0)]);
+        assert_eq!(in_degree_centrality(&single), vec![0.0]);
+    }
+
+    #[test]
+    fn test_panics() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(in_degree_centrality(&graph), INVALID_KEY_SEQUENCE);
+        assert_panics_with!(out_degree_centrality(&graph), INVALID_KEY_SEQUENCE);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod betweenness;
+pub mod closeness;
+pub mod degree;
+pub mod pagerank;
+
+pub use betweenness::betweenness_centrality;
+pub use closeness::closeness_centrality;
+pub use degree::{in_degree_centrality, out_degree_centrality};
+pub use pagerank::{pagerank, PageRankConfig};
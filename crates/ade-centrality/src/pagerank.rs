@@ -0,0 +1,191 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// The options of [`pagerank`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRankConfig {
+    /// The probability of following an edge rather than jumping to a random node, between
+    /// 0 and 1.
+    pub damping: f64,
+    /// The iteration stops when the ranks change by less than this in total.
+    pub tolerance: f64,
+    /// The iteration stops after this many steps, even if the ranks have not converged.
+    pub max_iterations: usize,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+        }
+    }
+}
+
+/// Computes the PageRank of every node by power iteration.
+///
+/// The PageRank of a node is the probability of being on it after many steps of a random
+/// walk that, at every step, follows a random outgoing edge with probability `damping` and
+/// jumps to a random node otherwise. Nodes pointed to by many highly ranked nodes get a high
+/// rank. A node without outgoing edges jumps to a random node with probability one, so no
+/// rank is lost.
+///
+/// Every iteration takes `O(n + m)` time, and stops once the sum of the absolute changes of
+/// the ranks is below `tolerance`, or after `max_iterations` iterations.
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the rank of every node. The ranks add up to one.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and
+/// panics if `damping` is not between 0 and 1.
+///
+/// # Examples
+///
+/// ```
+/// use ade_centrality::{pagerank, PageRankConfig};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 1, 2 and 3 all point to 0, which points back to 1
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(1, 0), (2, 0), (3, 0), (0, 1)]);
+/// let ranks = pagerank(&graph, &PageRankConfig::default());
+///
+/// assert!(ranks[0] > ranks[1] && ranks[1] > ranks[2]);
+/// assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+/// ```
+pub fn pagerank<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    config: &PageRankConfig,
+) -> Vec<f64> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    if !(0.0..=1.0).contains(&config.damping) {
+        panic!("Damping must be between 0 and 1, got {}", config.damping);
+    }
+
+    let n = graph.node_count();
+    if n == 0 {
+        return Vec::new();
+    }
+    let out_degree: Vec<usize> = (0..n as u32).map(|key| graph.out_degree(key)).collect();
+
+    let uniform = 1.0 / n as f64;
+    let mut ranks = vec![uniform; n];
+    for _ in 0..config.max_iterations {
+        // Rank of the nodes without outgoing edges, spread over all nodes
+        let dangling: f64 = (0..n)
+            .filter(|&i| out_degree[i] == 0)
+            .map(|i| ranks[i])
+            .sum();
+        let base = (1.0 - config.damping + config.damping * dangling) * uniform;
+
+        let next: Vec<f64> = (0..n as u32)
+            .map(|key| {
+                let incoming: f64 = graph
+                    .get_predecessors_keys(key)
+                    .map(|predecessor| {
+                        ranks[predecessor as usize] / out_degree[predecessor as usize] as f64
+                    })
+                    .sum();
+                base + config.damping * incoming
+            })
+            .collect();
+
+        let change: f64 = next.iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+        ranks = next;
+        if change < config.tolerance {
+            break;
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_cycle_and_dangling_nodes() {
+        // Every node of a cycle has the same rank
+        let cycle = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        for rank in pagerank(&cycle, &PageRankConfig::default()) {
+            assert!((rank - 1.0 / 3.0).abs() < 1e-9);
+        }
+
+        // Without damping, 0 → 1 sends all the rank of 0 to 1, and 1 spreads it evenly
+        let config = PageRankConfig {
+            damping: 1.0,
+            tolerance: 1e-12,
+            max_iterations: 1000,
+        };
+        let ranks = pagerank(
+            &build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]),
+            &config,
+        );
+        assert!((ranks[0] - 1.0 / 3.0).abs() < 1e-9);
+        assert!((ranks[1] - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_graph_and_panics() {
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+        assert!(pagerank(&empty, &PageRankConfig::default()).is_empty());
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(
+            pagerank(&graph, &PageRankConfig::default()),
+            INVALID_KEY_SEQUENCE
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![0], vec![]);
+        let config = PageRankConfig {
+            damping: 1.5,
+            ..PageRankConfig::default()
+        };
+        assert_panics_with!(
+            pagerank(&graph, &config),
+            "Damping must be between 0 and 1, got 1.5"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_ranks_are_a_stationary_distribution(graph in arb_graph::<Node, Edge>(15, 40)) {
+            let config = PageRankConfig {
+                tolerance: 1e-12,
+                max_iterations: 1000,
+                ..PageRankConfig::default()
+            };
+            let ranks = pagerank(&graph, &config);
+            let n = ranks.len();
+            if n == 0 {
+                return Ok(());
+            }
+            prop_assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+            // One more step of the random walk leaves the ranks unchanged
+            for key in 0..n as u32 {
+                let dangling: f64 = (0..n as u32)
+                    .filter(|&k| graph.out_degree(k) == 0)
+                    .map(|k| ranks[k as usize])
+                    .sum();
+                let incoming: f64 = graph
+                    .get_predecessors_keys(key)
+                    .map(|p| ranks[p as usize] / graph.out_degree(p) as f64)
+                    .sum();
+                let expected = (0.15 + 0.85 * dangling) / n as f64 + 0.85 * incoming;
+                prop_assert!((ranks[key as usize] - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+/// Limits on the work an exponential algorithm may do before returning.
+///
+/// Algorithms such as the enumeration of elementary circuits can run for an exponential time
+/// on adversarial inputs. An execution budget bounds them by wall-clock time, by the number
+/// of node expansions (the unit of work of each algorithm, for instance a step of a
+/// depth-first search), or both. When the budget is exhausted the algorithm stops and
+/// returns what it found so far, flagged as [`truncated`](Budgeted::truncated).
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::ExecutionBudget;
+/// use std::time::Duration;
+///
+/// let budget = ExecutionBudget::unlimited()
+///     .with_time_limit(Duration::from_millis(50))
+///     .with_max_expansions(1_000_000);
+///
+/// assert_eq!(budget.time_limit(), Some(Duration::from_millis(50)));
+/// assert_eq!(budget.max_expansions(), Some(1_000_000));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionBudget {
+    time_limit: Option<Duration>,
+    max_expansions: Option<u64>,
+}
+
+impl ExecutionBudget {
+    /// Creates a budget without limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stops the algorithm once `limit` has elapsed since it started.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Stops the algorithm once it has expanded `max` nodes.
+    pub fn with_max_expansions(mut self, max: u64) -> Self {
+        self.max_expansions = Some(max);
+        self
+    }
+
+    /// Returns the wall-clock limit, if any.
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    /// Returns the node-expansion limit, if any.
+    pub fn max_expansions(&self) -> Option<u64> {
+        self.max_expansions
+    }
+
+    /// Starts spending the budget, the clock starts now.
+    pub fn start(&self) -> BudgetTracker {
+        BudgetTracker {
+            deadline: self.time_limit.map(|limit| Instant::now() + limit),
+            max_expansions: self.max_expansions,
+            expansions: 0,
+            exhausted: false,
+        }
+    }
+}
+
+/// Spends an [`ExecutionBudget`] while an algorithm runs.
+///
+/// Algorithms call [`expand`](BudgetTracker::expand) once per node expansion and stop as soon
+/// as it returns `false`. Reading the clock is comparatively slow, so the time limit is only
+/// checked every [`CLOCK_INTERVAL`](BudgetTracker::CLOCK_INTERVAL) expansions.
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::ExecutionBudget;
+///
+/// let mut tracker = ExecutionBudget::unlimited().with_max_expansions(3).start();
+/// let expanded = (0..10).take_while(|_| tracker.expand()).count();
+///
+/// assert_eq!(expanded, 3);
+/// assert!(tracker.is_exhausted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BudgetTracker {
+    deadline: Option<Instant>,
+    max_expansions: Option<u64>,
+    expansions: u64,
+    exhausted: bool,
+}
+
+impl BudgetTracker {
+    /// Number of expansions between two reads of the clock.
+    pub const CLOCK_INTERVAL: u64 = 1024;
+
+    /// Records one node expansion, and returns `false` if the budget does not allow it.
+    pub fn expand(&mut self) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        if self
+            .max_expansions
+            .is_some_and(|max| self.expansions >= max)
+        {
+            self.exhausted = true;
+            return false;
+        }
+        if let Some(deadline) = self.deadline {
+            if self.expansions.is_multiple_of(Self::CLOCK_INTERVAL) && Instant::now() >= deadline {
+                self.exhausted = true;
+                return false;
+            }
+        }
+        self.expansions += 1;
+        true
+    }
+
+    /// Returns the number of expansions allowed so far.
+    pub fn expansions(&self) -> u64 {
+        self.expansions
+    }
+
+    /// Returns `true` if the algorithm was denied an expansion.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+/// The result of an algorithm run under an [`ExecutionBudget`].
+///
+/// When `truncated` is `true` the budget ran out, and `value` only holds the part of the
+/// result found before that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Budgeted<T> {
+    /// The complete result, or a partial one if `truncated` is `true`.
+    pub value: T,
+    /// Whether the algorithm stopped because the budget was exhausted.
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_budget() {
+        let mut tracker = ExecutionBudget::unlimited().start();
+        assert!((0..10_000).all(|_| tracker.expand()));
+        assert_eq!(tracker.expansions(), 10_000);
+        assert!(!tracker.is_exhausted());
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let mut tracker = ExecutionBudget::unlimited()
+            .with_time_limit(Duration::ZERO)
+            .start();
+        assert!(!tracker.expand());
+        assert!(tracker.is_exhausted());
+        assert_eq!(tracker.expansions(), 0);
+    }
+
+    #[test]
+    fn test_exhausted_budget_stays_exhausted() {
+        let mut tracker = ExecutionBudget::unlimited().with_max_expansions(0).start();
+        assert!(!tracker.expand());
+        assert!(!tracker.expand());
+        assert!(tracker.is_exhausted());
+    }
+}
@@ -1,3 +1,13 @@
+pub mod budget;
+pub mod progress;
+pub mod rng;
+
+pub use budget::{BudgetTracker, Budgeted, ExecutionBudget};
+pub use progress::{
+    Cancellable, CancellationToken, Cancelled, NoProgress, PhaseProgress, Progress,
+};
+pub use rng::SeededRng;
+
 /// Error message used when validating key sequences in graph structures.
 ///
 /// This constant provides a standard error message for cases where a collection of keys
@@ -33,12 +43,12 @@ macro_rules! assert_panics_with {
         // Save the current hook and set an empty hook to silence the output
         let old_hook = std::panic::take_hook();
         std::panic::set_hook(Box::new(|_| {}));
-        
+
         let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $expr));
-        
+
         // Restore the original hook
         std::panic::set_hook(old_hook);
-        
+
         match res {
             Ok(_) => panic!(
                 "expected panic with {:?}, but code did not panic",
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Receives progress updates from a long-running algorithm and tells it when to stop.
+///
+/// Algorithms that accept a `Progress` call [`report`](Progress::report) with the name of
+/// the phase they are in and the completed fraction of that phase, between `0.0` and `1.0`.
+/// They also poll [`is_cancelled`](Progress::is_cancelled) at regular intervals and return
+/// [`Cancelled`] as soon as it is `true`, so a caller can abort them cooperatively.
+///
+/// Closures taking the phase and the fraction implement `Progress` and are never cancelled.
+/// [`CancellationToken`] implements `Progress` without reporting anything, and
+/// [`with_cancellation`](Progress::with_cancellation) combines the two.
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::Progress;
+///
+/// let mut last = 0.0;
+/// let mut progress = |_phase: &str, fraction: f64| last = fraction;
+/// progress.report("search", 0.5);
+/// assert!(!progress.is_cancelled());
+/// assert_eq!(last, 0.5);
+/// ```
+pub trait Progress {
+    /// Reports that `fraction` of `phase` has been completed.
+    fn report(&mut self, phase: &str, fraction: f64);
+
+    /// Returns `true` if the algorithm should stop as soon as possible.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
+    /// Combines this progress with a [`CancellationToken`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_common::{CancellationToken, Progress};
+    ///
+    /// let token = CancellationToken::new();
+    /// let progress = (|_: &str, _: f64| {}).with_cancellation(token.clone());
+    ///
+    /// token.cancel();
+    /// assert!(progress.is_cancelled());
+    /// ```
+    fn with_cancellation(self, token: CancellationToken) -> Cancellable<Self>
+    where
+        Self: Sized,
+    {
+        Cancellable {
+            progress: self,
+            token,
+        }
+    }
+}
+
+impl<F: FnMut(&str, f64)> Progress for F {
+    fn report(&mut self, phase: &str, fraction: f64) {
+        self(phase, fraction)
+    }
+}
+
+/// A [`Progress`] that ignores updates and is never cancelled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn report(&mut self, _phase: &str, _fraction: f64) {}
+}
+
+/// A flag that cancels an algorithm from another thread.
+///
+/// Clones share the same flag: keep one clone and pass another to the algorithm, then call
+/// [`cancel`](CancellationToken::cancel) to stop it.
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::{CancellationToken, Progress};
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// assert!(!token.is_cancelled());
+///
+/// std::thread::spawn(move || handle.cancel()).join().unwrap();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every algorithm holding a clone of this token to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Progress for CancellationToken {
+    fn report(&mut self, _phase: &str, _fraction: f64) {}
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// A [`Progress`] combined with a [`CancellationToken`], created by
+/// [`Progress::with_cancellation`].
+#[derive(Debug, Clone)]
+pub struct Cancellable<P> {
+    progress: P,
+    token: CancellationToken,
+}
+
+impl<P: Progress> Progress for Cancellable<P> {
+    fn report(&mut self, phase: &str, fraction: f64) {
+        self.progress.report(phase, fraction);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled() || self.progress.is_cancelled()
+    }
+}
+
+/// Error returned by an algorithm that stopped because its [`Progress`] was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Operation cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Tracks the progress of one phase of an algorithm.
+///
+/// The phase is made of `total` steps. [`advance`](PhaseProgress::advance) reports the
+/// completed fraction at most once per percent, so that algorithms can call it for every
+/// step without flooding the callback, and checks for cancellation.
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::{Cancelled, PhaseProgress};
+///
+/// let mut reports = Vec::new();
+/// let mut progress = |_: &str, fraction: f64| reports.push(fraction);
+///
+/// let mut phase = PhaseProgress::new("nodes", 1000);
+/// for _ in 0..1000 {
+///     phase.advance(1, &mut progress)?;
+/// }
+/// phase.finish(&mut progress);
+///
+/// assert_eq!(reports.len(), 101);
+/// assert_eq!(reports.last(), Some(&1.0));
+/// # Ok::<(), Cancelled>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhaseProgress {
+    phase: &'static str,
+    total: usize,
+    done: usize,
+    next_report: usize,
+}
+
+impl PhaseProgress {
+    /// Starts tracking a phase made of `total` steps.
+    pub fn new(phase: &'static str, total: usize) -> Self {
+        Self {
+            phase,
+            total,
+            done: 0,
+            next_report: 0,
+        }
+    }
+
+    /// Records `steps` completed steps, reporting the new fraction if it grew by at least
+    /// one percent since the last report.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cancelled`] if `progress` is cancelled.
+    pub fn advance(&mut self, steps: usize, progress: &mut impl Progress) -> Result<(), Cancelled> {
+        self.done = (self.done + steps).min(self.total);
+        if self.done >= self.next_report && self.done < self.total {
+            progress.report(self.phase, self.fraction());
+            self.next_report = self.done + self.total.div_ceil(100);
+        }
+        if progress.is_cancelled() {
+            return Err(Cancelled);
+        }
+        Ok(())
+    }
+
+    /// Reports that the phase is complete.
+    pub fn finish(&mut self, progress: &mut impl Progress) {
+        self.done = self.total;
+        progress.report(self.phase, 1.0);
+    }
+
+    // Completed fraction of the phase, an empty phase is complete
+    fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_progress_reports_each_percent() {
+        let mut reports = Vec::new();
+        let mut progress = |phase: &str, fraction: f64| reports.push((phase.to_string(), fraction));
+
+        let mut phase = PhaseProgress::new("phase", 250);
+        for _ in 0..250 {
+            phase.advance(1, &mut progress).unwrap();
+        }
+        phase.finish(&mut progress);
+
+        // One report every 3 steps before the last one, plus the final report
+        assert_eq!(reports.len(), 84);
+        assert!(reports.windows(2).all(|w| w[0].1 < w[1].1));
+        assert_eq!(reports.last(), Some(&("phase".to_string(), 1.0)));
+    }
+
+    #[test]
+    fn test_phase_progress_stops_when_cancelled() {
+        let token = CancellationToken::new();
+        let mut progress = token.clone();
+        let mut phase = PhaseProgress::new("phase", 10);
+
+        assert_eq!(phase.advance(1, &mut progress), Ok(()));
+        token.cancel();
+        assert_eq!(phase.advance(1, &mut progress), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_cancellable_reports_to_inner_progress() {
+        let token = CancellationToken::new();
+        let mut count = 0;
+        let mut progress = (|_: &str, _: f64| count += 1).with_cancellation(token.clone());
+
+        progress.report("phase", 0.5);
+        assert!(!progress.is_cancelled());
+        token.cancel();
+        assert!(progress.is_cancelled());
+        drop(progress);
+        assert_eq!(count, 1);
+    }
+}
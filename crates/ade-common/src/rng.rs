@@ -0,0 +1,140 @@
+/// A small deterministic pseudo-random number generator.
+///
+/// Every randomized function in the ADE crates takes a `u64` seed and draws its numbers
+/// from a `SeededRng`, so that the same seed gives the same result on every platform and
+/// across crates. Each step advances a linear congruential generator and scrambles its state
+/// with the SplitMix64 finalizer. The generator is fast and well distributed, but it is not
+/// cryptographically secure.
+///
+/// # Examples
+///
+/// ```
+/// use ade_common::SeededRng;
+///
+/// let mut a = SeededRng::new(42);
+/// let mut b = SeededRng::new(42);
+/// assert_eq!(a.next_u64(), b.next_u64());
+///
+/// let value = a.below(10);
+/// assert!(value < 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(1664525).wrapping_add(1013904223);
+        mix64(self.state)
+    }
+
+    /// Returns a random number in `0..bound`.
+    ///
+    /// The result is the next `u64` modulo `bound`, whose bias is negligible for the small
+    /// bounds used on graphs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            panic!("Cannot draw a number below 0");
+        }
+        self.next_u64() % bound
+    }
+
+    /// Returns a random index in `0..len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Returns `true` with probability `p`, clamped to `0.0..=1.0`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        // 53 random bits give a uniform float in [0, 1)
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    /// Shuffles a slice in place with the Fisher–Yates algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_common::SeededRng;
+    ///
+    /// let mut keys: Vec<u32> = (0..10).collect();
+    /// SeededRng::new(7).shuffle(&mut keys);
+    ///
+    /// keys.sort_unstable();
+    /// assert_eq!(keys, (0..10).collect::<Vec<u32>>());
+    /// ```
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+// SplitMix64 finalizer, spreads the low-quality bits of the LCG over the whole word
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(123);
+        let mut b = SeededRng::new(123);
+        let mut c = SeededRng::new(456);
+
+        let seq_a: Vec<u64> = (0..20).map(|_| a.next_u64()).collect();
+        let seq_b: Vec<u64> = (0..20).map(|_| b.next_u64()).collect();
+        let seq_c: Vec<u64> = (0..20).map(|_| c.next_u64()).collect();
+
+        assert_eq!(seq_a, seq_b);
+        assert_ne!(seq_a, seq_c);
+    }
+
+    #[test]
+    fn test_below_covers_range() {
+        let mut rng = SeededRng::new(1);
+        let mut seen = [false; 7];
+        for _ in 0..1000 {
+            seen[rng.index(7)] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_chance_bounds() {
+        let mut rng = SeededRng::new(9);
+        assert!((0..100).all(|_| !rng.chance(0.0)));
+        assert!((0..100).all(|_| rng.chance(1.0)));
+
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2_000..3_000).contains(&hits));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot draw a number below 0")]
+    fn test_below_zero() {
+        SeededRng::new(0).below(0);
+    }
+}
@@ -0,0 +1,208 @@
+//! An interactive shell to build a small graph and run the ADE algorithms on it.
+//!
+//! ```text
+//! cargo run -p ade-compound-layout --example shell
+//! ade> add node 1
+//! ade> add node 2
+//! ade> add edge 1 2
+//! ade> layout
+//! ```
+use ade_compound_layout::{layout, LayoutConfig};
+use ade_elementary_circuits::elementary_circuits;
+use ade_graph::implementations::{Edge, Graph, Node};
+use ade_graph::normalize::with_normalized_keys;
+use ade_graph::Path;
+use ade_io::dot::{to_dot, DotConfig};
+use ade_strongly_connected_components::scc_iterative;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  add node <key>             adds a node
+  add edge <source> <target> adds an edge between two nodes
+  scc                        lists the strongly connected components
+  cycles                     lists the elementary circuits
+  layout                     prints the position of every node
+  export dot                 prints the graph as Graphviz DOT text
+  help                       shows this message
+  quit                       leaves the shell";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    AddNode(u32),
+    AddEdge(u32, u32),
+    Scc,
+    Cycles,
+    Layout,
+    ExportDot,
+    Help,
+    Quit,
+}
+
+// Parses a line of input, ignoring extra whitespace
+fn parse(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let key = |word: &str| {
+        word.parse::<u32>()
+            .map_err(|_| format!("Invalid node key: {}", word))
+    };
+    match words.as_slice() {
+        ["add", "node", k] => Ok(Command::AddNode(key(k)?)),
+        ["add", "edge", source, target] => Ok(Command::AddEdge(key(source)?, key(target)?)),
+        ["scc"] => Ok(Command::Scc),
+        ["cycles"] => Ok(Command::Cycles),
+        ["layout"] => Ok(Command::Layout),
+        ["export", "dot"] => Ok(Command::ExportDot),
+        ["help"] => Ok(Command::Help),
+        ["quit"] | ["exit"] => Ok(Command::Quit),
+        _ => Err(format!("Unknown command: {}", line.trim())),
+    }
+}
+
+struct Shell {
+    graph: Graph<Node, Edge>,
+}
+
+impl Shell {
+    fn new() -> Self {
+        Shell {
+            graph: Graph::new(Vec::new(), Vec::new()),
+        }
+    }
+
+    // Runs a command on the graph and returns the text to print
+    fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::AddNode(key) => {
+                if self.graph.add_node(Node::new(key)).is_some() {
+                    format!("Node {} already exists", key)
+                } else {
+                    format!("Added node {}", key)
+                }
+            }
+            Command::AddEdge(source, target) => {
+                match self.graph.try_add_edge(Edge::new(source, target)) {
+                    Ok(None) => format!("Added edge {} → {}", source, target),
+                    Ok(Some(_)) => format!("Edge {} → {} already exists", source, target),
+                    Err(error) => error.to_string(),
+                }
+            }
+            Command::Scc => {
+                let mut components = scc_iterative(&self.graph);
+                for component in &mut components {
+                    component.sort_unstable();
+                }
+                components.sort_unstable();
+                let lines: Vec<String> = components.iter().map(|c| format!("{:?}", c)).collect();
+                lines.join("\n")
+            }
+            Command::Cycles => {
+                // Circuits are rotated once their keys are back to the keys of the graph
+                let circuits: Vec<Vec<u32>> = with_normalized_keys(&self.graph, |g| {
+                    elementary_circuits(g)
+                        .into_iter()
+                        .map(Path::into_keys)
+                        .collect()
+                });
+                if circuits.is_empty() {
+                    return "No cycles".to_string();
+                }
+                let mut circuits: Vec<Path> = circuits
+                    .into_iter()
+                    .map(|keys| Path::new(keys).rotated_to_min())
+                    .collect();
+                circuits.sort_unstable();
+                let lines: Vec<String> = circuits.iter().map(Path::to_string).collect();
+                lines.join("\n")
+            }
+            Command::Layout => {
+                let result = layout(&self.graph, &LayoutConfig::default());
+                let lines: Vec<String> = result
+                    .positions()
+                    .map(|(key, (x, y))| format!("{}: ({}, {})", key, x, y))
+                    .collect();
+                lines.join("\n")
+            }
+            Command::ExportDot => to_dot(&self.graph, &DotConfig::default())
+                .trim_end()
+                .to_string(),
+            Command::Help => HELP.to_string(),
+            Command::Quit => String::new(),
+        }
+    }
+}
+
+fn main() {
+    let mut shell = Shell::new();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("ade> ");
+        io::stdout().flush().unwrap();
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse(&line) {
+            Ok(Command::Quit) => break,
+            Ok(command) => println!("{}", shell.execute(command)),
+            Err(error) => println!("{}\n{}", error, HELP),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs each line through the shell and returns the last output
+    fn run(shell: &mut Shell, lines: &[&str]) -> String {
+        let mut output = String::new();
+        for line in lines {
+            output = shell.execute(parse(line).unwrap());
+        }
+        output
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("add node 3"), Ok(Command::AddNode(3)));
+        assert_eq!(parse("  add   edge 1 2 "), Ok(Command::AddEdge(1, 2)));
+        assert_eq!(parse("export dot"), Ok(Command::ExportDot));
+        assert_eq!(parse("exit"), Ok(Command::Quit));
+        assert_eq!(parse("add node x"), Err("Invalid node key: x".to_string()));
+        assert_eq!(
+            parse("remove 3"),
+            Err("Unknown command: remove 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut shell = Shell::new();
+        let setup = [
+            "add node 10",
+            "add node 20",
+            "add node 30",
+            "add edge 10 20",
+            "add edge 20 10",
+            "add edge 20 30",
+        ];
+        assert_eq!(run(&mut shell, &setup), "Added edge 20 → 30");
+        assert_eq!(run(&mut shell, &["add node 10"]), "Node 10 already exists");
+        assert_eq!(run(&mut shell, &["add edge 30 40"]), "Node 40 not found");
+
+        assert_eq!(run(&mut shell, &["scc"]), "[10, 20]\n[30]");
+        assert_eq!(run(&mut shell, &["cycles"]), "10 → 20 → 10");
+        assert_eq!(
+            run(&mut shell, &["export dot"]),
+            "digraph G {\n    10;\n    20;\n    30;\n    10 -> 20;\n    20 -> 10;\n    20 -> 30;\n}"
+        );
+
+        let positions = run(&mut shell, &["layout"]);
+        assert_eq!(positions.lines().count(), 3);
+        assert!(positions.starts_with("10: ("));
+    }
+}
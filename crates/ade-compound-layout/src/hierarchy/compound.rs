@@ -0,0 +1,529 @@
+use crate::{HierarchyNode, NodeType};
+use ade_graph::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// The error returned by [`CompoundGraph::set_parent`] when the containment hierarchy
+/// would stop being a forest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompoundError {
+    /// `parent` is `child` itself or one of its descendants, so `child` would contain
+    /// itself.
+    ContainmentCycle { child: u32, parent: u32 },
+    /// `key` already has border nodes, so the hierarchy can no longer change around it.
+    BordersInserted { key: u32 },
+}
+
+impl fmt::Display for CompoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompoundError::ContainmentCycle { child, parent } => write!(
+                f,
+                "Node {} cannot be the parent of node {}: containment must be a forest",
+                parent, child
+            ),
+            CompoundError::BordersInserted { key } => {
+                write!(f, "Node {} is part of a cluster with border nodes", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompoundError {}
+
+/// The box of a cluster once its border nodes are positioned, as returned by
+/// [`CompoundGraph::cluster_bounds`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClusterBounds {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+// Border nodes of a cluster; the cluster node itself is its upper border
+#[derive(Debug, Clone, Default)]
+struct Borders {
+    lower: u32,
+    // Left and right border nodes, one per layer from the upper to the lower border
+    left: Vec<u32>,
+    right: Vec<u32>,
+}
+
+/// A hierarchy graph whose nodes may be nested in clusters.
+///
+/// Every node may have a parent, a cluster node that contains it, and the containment
+/// hierarchy is kept a forest: [`set_parent`](Self::set_parent) refuses to make a node
+/// contain itself. A node with children is a cluster. Clusters take part in the layout
+/// through border nodes, in the phases of a layered layout:
+///
+/// 1. [`insert_nesting_borders`](Self::insert_nesting_borders), before layering, turns each
+///    cluster node into its [`NodeType::UpperBorder`] and adds a [`NodeType::LowerBorder`],
+///    with edges that keep the contents of the cluster between the two.
+/// 2. [`insert_side_borders`](Self::insert_side_borders), after layering, adds a
+///    [`NodeType::LeftBorder`] and a [`NodeType::RightBorder`] on every layer the cluster
+///    spans.
+/// 3. [`arrange_clusters`](Self::arrange_clusters), after ordering, makes the nodes of each
+///    cluster contiguous in every layer, between its side borders.
+/// 4. [`cluster_bounds`](Self::cluster_bounds), after positioning, returns the box of each
+///    cluster.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{CompoundGraph, HierarchyNode};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::NodeTrait;
+///
+/// let nodes = (0..4).map(HierarchyNode::new).collect();
+/// let mut compound = CompoundGraph::new(Graph::new(nodes, vec![Edge::new(1, 2)]));
+///
+/// // Cluster 0 contains 1 and 3, cluster 3 contains 2
+/// compound.set_parent(1, 0).unwrap();
+/// compound.set_parent(3, 0).unwrap();
+/// compound.set_parent(2, 3).unwrap();
+/// assert_eq!(compound.children(0).collect::<Vec<_>>(), vec![1, 3]);
+/// assert_eq!(compound.parent(2), Some(3));
+///
+/// // 0 cannot be nested in its own descendant
+/// assert!(compound.set_parent(0, 2).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompoundGraph<E> {
+    graph: Graph<HierarchyNode, E>,
+    parents: HashMap<u32, u32>,
+    children: HashMap<u32, BTreeSet<u32>>,
+    borders: HashMap<u32, Borders>,
+}
+
+impl<E: EdgeTrait> CompoundGraph<E> {
+    /// Creates a compound graph where no node has a parent yet.
+    pub fn new(graph: Graph<HierarchyNode, E>) -> Self {
+        Self {
+            graph,
+            parents: HashMap::new(),
+            children: HashMap::new(),
+            borders: HashMap::new(),
+        }
+    }
+
+    /// Returns the underlying graph, border nodes included.
+    pub fn graph(&self) -> &Graph<HierarchyNode, E> {
+        &self.graph
+    }
+
+    /// Returns the underlying graph mutably, for the layout phases that update nodes.
+    pub fn graph_mut(&mut self) -> &mut Graph<HierarchyNode, E> {
+        &mut self.graph
+    }
+
+    /// Consumes the compound graph and returns the underlying graph.
+    pub fn into_graph(self) -> Graph<HierarchyNode, E> {
+        self.graph
+    }
+
+    /// Nests `child` in the cluster `parent`, replacing its previous parent if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompoundError::ContainmentCycle`] if `parent` is `child` or one of its
+    /// descendants, and [`CompoundError::BordersInserted`] if one of them is a cluster
+    /// whose border nodes were already inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `child` or `parent` is not a node of the graph.
+    pub fn set_parent(&mut self, child: u32, parent: u32) -> Result<(), CompoundError> {
+        for key in [child, parent] {
+            if !self.graph.has_node(key) {
+                panic!("Node {} not found", key);
+            }
+        }
+        if self.ancestors(parent).any(|ancestor| ancestor == child) || child == parent {
+            return Err(CompoundError::ContainmentCycle { child, parent });
+        }
+        if let Some(key) = [child, parent]
+            .into_iter()
+            .find(|key| self.borders.contains_key(key))
+        {
+            return Err(CompoundError::BordersInserted { key });
+        }
+
+        self.remove_parent(child);
+        self.parents.insert(child, parent);
+        self.children.entry(parent).or_default().insert(child);
+        Ok(())
+    }
+
+    /// Takes `child` out of its cluster, if it has one, and returns its previous parent.
+    pub fn remove_parent(&mut self, child: u32) -> Option<u32> {
+        let parent = self.parents.remove(&child)?;
+        let siblings = self.children.get_mut(&parent).unwrap();
+        siblings.remove(&child);
+        if siblings.is_empty() {
+            self.children.remove(&parent);
+        }
+        Some(parent)
+    }
+
+    /// Returns the parent cluster of `key`, if any.
+    pub fn parent(&self, key: u32) -> Option<u32> {
+        self.parents.get(&key).copied()
+    }
+
+    /// Returns the nodes directly nested in `key`, in ascending order of key.
+    pub fn children(&self, key: u32) -> impl Iterator<Item = u32> + '_ {
+        self.children.get(&key).into_iter().flatten().copied()
+    }
+
+    /// Returns whether `key` is a cluster, a node with children.
+    pub fn is_cluster(&self, key: u32) -> bool {
+        self.children.contains_key(&key)
+    }
+
+    /// Returns the clusters, in ascending order of key.
+    pub fn clusters(&self) -> Vec<u32> {
+        let mut clusters: Vec<u32> = self.children.keys().copied().collect();
+        clusters.sort_unstable();
+        clusters
+    }
+
+    /// Returns the ancestors of `key`, from its parent to the outermost cluster.
+    pub fn ancestors(&self, key: u32) -> impl Iterator<Item = u32> + '_ {
+        std::iter::successors(self.parent(key), |&key| self.parent(key))
+    }
+
+    /// Adds the upper and lower border of each cluster, before layering.
+    ///
+    /// The cluster node becomes the [`NodeType::UpperBorder`] of the cluster and a new
+    /// [`NodeType::LowerBorder`] node is added as its child. Edges from the upper border to
+    /// every child, and from every child to the lower border, make any layering place the
+    /// contents of the cluster strictly between its two borders. Nested clusters are
+    /// connected through their own borders. New nodes get the keys following the largest
+    /// key of the graph. Clusters that already have borders are skipped.
+    ///
+    /// Cluster nodes must not have edges of their own: the border edges are the only ones
+    /// that the layout can honor.
+    pub fn insert_nesting_borders(&mut self) {
+        let mut next_key = self.next_key();
+        for cluster in self.clusters() {
+            if self.borders.contains_key(&cluster) {
+                continue;
+            }
+            let mut upper = self.graph.get_node(cluster).clone();
+            upper.set_node_type(NodeType::UpperBorder);
+            self.graph.add_node(upper);
+            self.graph
+                .add_node(HierarchyNode::with_type(next_key, NodeType::LowerBorder));
+            self.borders.insert(
+                cluster,
+                Borders {
+                    lower: next_key,
+                    ..Borders::default()
+                },
+            );
+            next_key += 1;
+        }
+
+        for cluster in self.clusters() {
+            let lower = self.borders[&cluster].lower;
+            let children: Vec<u32> = self.children(cluster).collect();
+            for child in children {
+                // A nested cluster is entered through its upper border, the cluster node
+                let last = self
+                    .borders
+                    .get(&child)
+                    .map_or(child, |borders| borders.lower);
+                self.add_border_edge(cluster, child);
+                self.add_border_edge(last, lower);
+            }
+            self.children.get_mut(&cluster).unwrap().insert(lower);
+            self.parents.insert(lower, cluster);
+        }
+    }
+
+    /// Adds a left and a right border node on every layer spanned by each cluster, from its
+    /// upper to its lower border, after layering.
+    ///
+    /// Side borders are children of their cluster, chained from top to bottom by edges so
+    /// that they line up vertically. The layers of the nodes must be set, and
+    /// [`insert_nesting_borders`](Self::insert_nesting_borders) must have run.
+    pub fn insert_side_borders(&mut self) {
+        let mut next_key = self.next_key();
+        for cluster in self.clusters() {
+            let Some(borders) = self.borders.get(&cluster) else {
+                continue;
+            };
+            if !borders.left.is_empty() {
+                continue;
+            }
+            let top = self.graph.get_node(cluster).layer();
+            let bottom = self.graph.get_node(borders.lower).layer();
+
+            let mut sides = [Vec::new(), Vec::new()];
+            for (side, node_type) in [NodeType::LeftBorder, NodeType::RightBorder]
+                .into_iter()
+                .enumerate()
+            {
+                for layer in top..=bottom {
+                    let mut node = HierarchyNode::with_type(next_key, node_type);
+                    node.set_layer(layer);
+                    self.graph.add_node(node);
+                    self.parents.insert(next_key, cluster);
+                    self.children.get_mut(&cluster).unwrap().insert(next_key);
+                    if let Some(&above) = sides[side].last() {
+                        self.add_border_edge(above, next_key);
+                    }
+                    sides[side].push(next_key);
+                    next_key += 1;
+                }
+            }
+            let [left, right] = sides;
+            let borders = self.borders.get_mut(&cluster).unwrap();
+            borders.left = left;
+            borders.right = right;
+        }
+    }
+
+    /// Reorders each layer so that the nodes of every cluster are contiguous and framed by
+    /// its left and right borders.
+    ///
+    /// `order` lists the keys of each layer from left to right, for instance as returned by
+    /// `ade_layering::order_layers`. Clusters and the nodes outside them are sorted by the
+    /// average of their current positions, so the order found by crossing minimization is
+    /// kept as much as possible.
+    pub fn arrange_clusters(&self, order: &mut [Vec<u32>]) {
+        for layer in order.iter_mut() {
+            let positions: HashMap<u32, usize> =
+                layer.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+            *layer = self.arrange(layer, None, &positions);
+        }
+    }
+
+    /// Returns the box of `cluster`, spanned by its positioned border nodes, or `None` if
+    /// it has no side borders.
+    pub fn cluster_bounds(&self, cluster: u32) -> Option<ClusterBounds> {
+        let borders = self.borders.get(&cluster)?;
+        if borders.left.is_empty() {
+            return None;
+        }
+        let x = |key: &u32| self.graph.get_node(*key).x();
+        Some(ClusterBounds {
+            left: borders.left.iter().map(x).fold(f64::INFINITY, f64::min),
+            top: self.graph.get_node(cluster).y(),
+            right: borders
+                .right
+                .iter()
+                .map(x)
+                .fold(f64::NEG_INFINITY, f64::max),
+            bottom: self.graph.get_node(borders.lower).y(),
+        })
+    }
+
+    // Whether border nodes were inserted for some cluster
+    pub(crate) fn has_borders(&self) -> bool {
+        !self.borders.is_empty()
+    }
+
+    // The innermost cluster whose box contains both nodes, if any
+    pub(crate) fn common_cluster(&self, a: u32, b: u32) -> Option<u32> {
+        let enclosing = |key: u32| std::iter::successors(self.owner(key), |&c| self.parent(c));
+        let around_b: BTreeSet<u32> = enclosing(b).collect();
+        enclosing(a).find(|cluster| around_b.contains(cluster))
+    }
+
+    // Nests a node added by the layout, such as a dummy node, directly in `cluster`
+    pub(crate) fn nest(&mut self, key: u32, cluster: u32) {
+        self.parents.insert(key, cluster);
+        self.children.entry(cluster).or_default().insert(key);
+    }
+
+    fn next_key(&self) -> u32 {
+        self.graph.get_node_keys().max().map_or(0, |key| key + 1)
+    }
+
+    fn add_border_edge(&mut self, source: u32, target: u32) {
+        if !self.graph.has_edge(source, target) {
+            self.graph.add_edge(E::new(source, target));
+        }
+    }
+
+    // The innermost cluster whose box contains the node: a cluster node, as upper border,
+    // is inside its own box
+    fn owner(&self, key: u32) -> Option<u32> {
+        if self.borders.contains_key(&key) {
+            Some(key)
+        } else {
+            self.parent(key)
+        }
+    }
+
+    // Arranges nodes that are all inside `cluster`, or anywhere if it is None
+    fn arrange(
+        &self,
+        keys: &[u32],
+        cluster: Option<u32>,
+        positions: &HashMap<u32, usize>,
+    ) -> Vec<u32> {
+        let borders = cluster.and_then(|cluster| self.borders.get(&cluster));
+        let is_side = |key: &u32| {
+            borders.is_some_and(|borders| borders.left.contains(key) || borders.right.contains(key))
+        };
+
+        // Nodes directly inside the cluster stand alone; the others are grouped by the
+        // cluster nested in it that contains them
+        let mut groups: Vec<(Option<u32>, Vec<u32>)> = Vec::new();
+        for &key in keys.iter().filter(|key| !is_side(key)) {
+            let mut chain = std::iter::successors(self.owner(key), |&c| self.parent(c));
+            let group = if self.owner(key) == cluster {
+                None
+            } else {
+                chain.find(|&c| self.parent(c) == cluster)
+            };
+            match groups
+                .iter_mut()
+                .find(|(g, _)| group.is_some() && *g == group)
+            {
+                Some((_, members)) => members.push(key),
+                None => groups.push((group, vec![key])),
+            }
+        }
+        let mean = |members: &[u32]| {
+            members.iter().map(|key| positions[key] as f64).sum::<f64>() / members.len() as f64
+        };
+        groups.sort_by(|(_, a), (_, b)| mean(a).total_cmp(&mean(b)));
+
+        let mut arranged = Vec::with_capacity(keys.len());
+        arranged.extend(
+            keys.iter()
+                .filter(|key| borders.is_some_and(|b| b.left.contains(key))),
+        );
+        for (group, members) in groups {
+            match group {
+                Some(nested) => arranged.extend(self.arrange(&members, Some(nested), positions)),
+                None => arranged.extend(members),
+            }
+        }
+        arranged.extend(
+            keys.iter()
+                .filter(|key| borders.is_some_and(|b| b.right.contains(key))),
+        );
+        arranged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assign_coordinates;
+    use ade_graph::implementations::Edge;
+    use ade_layering::{layer_assignment, layer_positions, order_layers, OrderingHeuristic};
+    use ade_traits::NodeTrait;
+
+    // 0 → 1 → 2 → 3 with cluster 4 around 1 and 2, itself nested in cluster 5 with 6
+    fn nested() -> CompoundGraph<Edge> {
+        let nodes = (0..7).map(HierarchyNode::new).collect();
+        let edges = vec![
+            Edge::new(0, 1),
+            Edge::new(1, 2),
+            Edge::new(2, 3),
+            Edge::new(6, 3),
+        ];
+        let mut compound = CompoundGraph::new(Graph::new(nodes, edges));
+        compound.set_parent(1, 4).unwrap();
+        compound.set_parent(2, 4).unwrap();
+        compound.set_parent(4, 5).unwrap();
+        compound.set_parent(6, 5).unwrap();
+        compound
+    }
+
+    #[test]
+    fn test_containment_is_a_forest() {
+        let mut compound = nested();
+        assert_eq!(compound.ancestors(1).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(compound.clusters(), vec![4, 5]);
+        assert_eq!(
+            compound.set_parent(5, 1),
+            Err(CompoundError::ContainmentCycle {
+                child: 5,
+                parent: 1
+            })
+        );
+        assert!(compound.set_parent(3, 3).is_err());
+
+        // Moving a node updates both clusters
+        compound.set_parent(6, 4).unwrap();
+        assert_eq!(compound.children(4).collect::<Vec<_>>(), vec![1, 2, 6]);
+        assert_eq!(compound.children(5).collect::<Vec<_>>(), vec![4]);
+        assert_eq!(compound.remove_parent(6), Some(4));
+        assert_eq!(compound.remove_parent(6), None);
+    }
+
+    #[test]
+    fn test_borders_frame_clusters() {
+        let mut compound = nested();
+        compound.insert_nesting_borders();
+        assert_eq!(
+            *compound.graph().get_node(4).node_type(),
+            NodeType::UpperBorder
+        );
+        assert_eq!(
+            compound.set_parent(3, 4),
+            Err(CompoundError::BordersInserted { key: 4 })
+        );
+
+        let layers = layer_assignment(compound.graph());
+        for (key, &layer) in layers.iter().enumerate() {
+            let mut node = compound.graph().get_node(key as u32).clone();
+            node.set_layer(layer);
+            compound.graph_mut().add_node(node);
+        }
+        compound.insert_side_borders();
+
+        let graph = compound.graph();
+        let layers: Vec<usize> = (0..graph.node_count() as u32)
+            .map(|key| graph.get_node(key).layer())
+            .collect();
+        let mut order = order_layers(graph, &layers, OrderingHeuristic::Barycenter, 4);
+        compound.arrange_clusters(&mut order);
+
+        // Every layer lists the members of each cluster contiguously between its borders
+        for layer in &order {
+            for cluster in compound.clusters() {
+                let inside: Vec<usize> = (0..layer.len())
+                    .filter(|&i| {
+                        let key = layer[i];
+                        compound.owner(key) == Some(cluster)
+                            || compound.ancestors(key).any(|a| a == cluster)
+                    })
+                    .collect();
+                if let (Some(&first), Some(&last)) = (inside.first(), inside.last()) {
+                    assert_eq!(last - first + 1, inside.len());
+                    let (left, right) = (graph.get_node(layer[first]), graph.get_node(layer[last]));
+                    assert_eq!(*left.node_type(), NodeType::LeftBorder);
+                    assert_eq!(*right.node_type(), NodeType::RightBorder);
+                }
+            }
+        }
+
+        let positions = layer_positions(&order);
+        for (key, &rank) in positions.iter().enumerate() {
+            let mut node = compound.graph().get_node(key as u32).clone();
+            node.set_rank(rank);
+            compound.graph_mut().add_node(node);
+        }
+        let widths = vec![10.0; compound.graph().node_count()];
+        assign_coordinates(compound.graph_mut(), &widths, 10.0, 50.0);
+
+        let inner = compound.cluster_bounds(4).unwrap();
+        let outer = compound.cluster_bounds(5).unwrap();
+        for key in [1, 2] {
+            let node = compound.graph().get_node(key);
+            assert!(inner.left < node.x() && node.x() < inner.right);
+            assert!(inner.top < node.y() && node.y() < inner.bottom);
+        }
+        assert!(outer.left < inner.left && inner.right < outer.right);
+        assert!(outer.top < inner.top && inner.bottom < outer.bottom);
+    }
+}
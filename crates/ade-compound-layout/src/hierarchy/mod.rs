@@ -1 +1,2 @@
+pub mod compound;
 pub mod node;
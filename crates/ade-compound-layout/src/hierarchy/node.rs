@@ -1,18 +1,49 @@
-use graph::node::Node;
-use graph::NodeTrait;
+use ade_graph::implementations::Node;
+use ade_traits::NodeTrait;
 use std::collections::HashSet;
 
-#[derive(Debug, PartialEq)]
+/// The role of a node in a layered hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeType {
+    /// A node of the original graph.
     Base,
+    /// An inner point of a long edge, added by
+    /// [`normalize_long_edges`](crate::normalize_long_edges).
     Dummy,
+    /// The top of a cluster, added by
+    /// [`insert_nesting_borders`](crate::CompoundGraph::insert_nesting_borders).
     UpperBorder,
+    /// The bottom of a cluster, added with its upper border.
     LowerBorder,
+    /// The left side of a cluster on one layer, added by
+    /// [`insert_side_borders`](crate::CompoundGraph::insert_side_borders).
     LeftBorder,
+    /// The right side of a cluster on one layer, added with its left border.
     RightBorder,
 }
 
-#[derive(Debug)]
+/// A node of a layered hierarchy, which carries the results of the layout phases.
+///
+/// Besides its key and adjacency, each node stores its [`NodeType`], its
+/// [`layer`](Self::layer), set by [`assign_layers`](crate::assign_layers), its
+/// [`rank`](Self::rank) within the layer, set by [`assign_ranks`](crate::assign_ranks), and
+/// its coordinates, set by [`assign_coordinates`](crate::assign_coordinates). New nodes are
+/// [`NodeType::Base`] nodes on layer 0, at rank 0 and at the origin.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{HierarchyNode, NodeType};
+/// use ade_graph::NodeTrait;
+///
+/// let mut node = HierarchyNode::with_type(4, NodeType::Dummy);
+/// node.set_layer(2);
+///
+/// assert_eq!(node.key(), 4);
+/// assert_eq!(node.node_type(), &NodeType::Dummy);
+/// assert_eq!((node.layer(), node.rank()), (2, 0));
+/// ```
+#[derive(Debug, Clone)]
 pub struct HierarchyNode {
     node: Node,
     node_type: NodeType,
@@ -23,9 +54,10 @@ pub struct HierarchyNode {
 }
 
 impl HierarchyNode {
-    pub fn new(id: &str, node_type: NodeType) -> Self {
+    /// Creates a node of the given type.
+    pub fn with_type(key: u32, node_type: NodeType) -> Self {
         HierarchyNode {
-            node: Node::new(id),
+            node: Node::new(key),
             node_type,
             layer: 0,
             rank: 0,
@@ -34,70 +66,95 @@ impl HierarchyNode {
         }
     }
 
+    /// Returns the type of the node.
     pub fn node_type(&self) -> &NodeType {
         &self.node_type
     }
 
+    /// Sets the type of the node.
+    pub fn set_node_type(&mut self, node_type: NodeType) {
+        self.node_type = node_type;
+    }
+
+    /// Returns the layer of the node, 0 being the first one.
     pub fn layer(&self) -> usize {
         self.layer
     }
 
+    /// Sets the layer of the node.
     pub fn set_layer(&mut self, layer: usize) {
         self.layer = layer;
     }
 
+    /// Returns the position of the node within its layer, 0 being the leftmost one.
     pub fn rank(&self) -> usize {
         self.rank
     }
 
+    /// Sets the position of the node within its layer.
     pub fn set_rank(&mut self, rank: usize) {
         self.rank = rank;
     }
 
+    /// Returns the horizontal coordinate of the center of the node.
     pub fn x(&self) -> f64 {
         self.x
     }
 
+    /// Returns the vertical coordinate of the center of the node.
     pub fn y(&self) -> f64 {
         self.y
     }
 
+    /// Sets the horizontal coordinate of the center of the node.
     pub fn set_x(&mut self, x: f64) {
         self.x = x;
     }
 
+    /// Sets the vertical coordinate of the center of the node.
     pub fn set_y(&mut self, y: f64) {
         self.y = y;
     }
 }
 
 impl NodeTrait for HierarchyNode {
-    fn id(&self) -> &str {
-        self.node.id()
+    fn new(key: u32) -> Self {
+        HierarchyNode::with_type(key, NodeType::Base)
+    }
+
+    fn key(&self) -> u32 {
+        self.node.key()
     }
 
-    fn predecessors(&self) -> &HashSet<String> {
+    fn predecessors(&self) -> &HashSet<u32> {
         self.node.predecessors()
     }
 
-    fn successors(&self) -> &HashSet<String> {
+    fn successors(&self) -> &HashSet<u32> {
         self.node.successors()
     }
 
-    fn add_predecessor(&mut self, id: &str) {
-        self.node.add_predecessor(id);
+    fn add_predecessor(&mut self, key: u32) {
+        self.node.add_predecessor(key);
     }
 
-    fn add_successor(&mut self, id: &str) {
-        self.node.add_successor(id);
+    fn add_successor(&mut self, key: u32) {
+        self.node.add_successor(key);
     }
 
-    fn remove_predecessor(&mut self, id: &str) {
-        self.node.remove_predecessor(id);
+    fn remove_predecessor(&mut self, key: u32) {
+        self.node.remove_predecessor(key);
     }
 
-    fn remove_successor(&mut self, id: &str) {
-        self.node.remove_successor(id);
+    fn remove_successor(&mut self, key: u32) {
+        self.node.remove_successor(key);
+    }
+
+    fn with_key(&self, key: u32) -> Self {
+        HierarchyNode {
+            node: Node::new(key),
+            ..self.clone()
+        }
     }
 }
 
@@ -107,8 +164,8 @@ mod tests {
 
     #[test]
     fn test_hierarchy_node_creation() {
-        let node = HierarchyNode::new("A", NodeType::Base);
-        assert_eq!(node.id(), "A");
+        let node = HierarchyNode::with_type(0, NodeType::Base);
+        assert_eq!(node.key(), 0);
         assert_eq!(node.node_type(), &NodeType::Base);
         assert_eq!(node.layer(), 0);
         assert_eq!(node.rank(), 0);
@@ -118,7 +175,7 @@ mod tests {
 
     #[test]
     fn test_hierarchy_node_setters() {
-        let mut node = HierarchyNode::new("A", NodeType::Base);
+        let mut node = HierarchyNode::with_type(0, NodeType::Base);
         node.set_layer(1);
         node.set_rank(2);
         node.set_x(3.0);
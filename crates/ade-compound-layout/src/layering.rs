@@ -0,0 +1,104 @@
+use crate::HierarchyNode;
+use ade_graph::implementations::Graph;
+use ade_layering::rank_network_simplex;
+use ade_traits::{EdgeTrait, GraphViewTrait};
+
+/// Assigns the layer of each node of a directed acyclic hierarchy, the first phase of the
+/// layout.
+///
+/// Layers come from the network simplex of [`rank_network_simplex`], which keeps every
+/// edge at least one layer long and minimizes the total length of the edges, each weighted
+/// by `edge_weight_fn`. Sources that can be moved down toward their successors are, so
+/// edges stay short. The result is stored with [`HierarchyNode::set_layer`], the first
+/// layer being 0, and read by [`assign_ranks`](crate::assign_ranks) and
+/// [`assign_coordinates`](crate::assign_coordinates).
+///
+/// Cycles must be broken first, for example by reversing the edges of a
+/// [`feedback_arc_set`](ade_feedback_arc_set::feedback_arc_set).
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0, and if it contains a
+/// cycle.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{assign_layers, HierarchyNode};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+///
+/// // 0 → 1 → 2 and 3 → 2: 3 is moved down next to 1
+/// let nodes = (0..4).map(HierarchyNode::new).collect();
+/// let edges = vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(3, 2)];
+/// let mut graph = Graph::new(nodes, edges);
+///
+/// assign_layers(&mut graph, |_| 1);
+/// let layers: Vec<usize> = (0..4).map(|key| graph.get_node(key).layer()).collect();
+/// assert_eq!(layers, vec![0, 1, 2, 1]);
+/// ```
+pub fn assign_layers<E: EdgeTrait>(
+    graph: &mut Graph<HierarchyNode, E>,
+    edge_weight_fn: impl Fn(&E) -> u32,
+) {
+    let layers = rank_network_simplex(graph, |_| 1, edge_weight_fn);
+    for (key, layer) in layers.into_iter().enumerate() {
+        let mut node = graph.get_node(key as u32).clone();
+        node.set_layer(layer);
+        graph.add_node(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::{assert_panics_with, INVALID_KEY_SEQUENCE};
+    use ade_graph::implementations::{DataEdge, Edge};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+    use ade_traits::{DataEdgeTrait, NodeTrait};
+
+    #[test]
+    fn test_assign_layers_keeps_edges_downward() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(40, 90, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let mut graph = build_graph::<HierarchyNode, Edge>(nodes, edges);
+
+            assign_layers(&mut graph, |_| 1);
+            for edge in graph.get_edges() {
+                let layer = |key| graph.get_node(key).layer();
+                assert!(layer(edge.source()) < layer(edge.target()));
+            }
+            assert_eq!(graph.get_nodes().map(|node| node.layer()).min(), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_assign_layers_weights() {
+        // The heavy edge 0 → 3 is kept short, so 3 stays right below 0
+        let nodes = (0..4).map(HierarchyNode::new).collect();
+        let edges = vec![
+            DataEdge::with_data(0, 1, 1),
+            DataEdge::with_data(1, 2, 1),
+            DataEdge::with_data(0, 3, 10),
+            DataEdge::with_data(3, 2, 1),
+        ];
+        let mut graph = Graph::new(nodes, edges);
+        assign_layers(&mut graph, |edge| *edge.data());
+        assert_eq!(graph.get_node(3).layer(), 1);
+        assert_eq!(graph.get_node(2).layer(), 2);
+    }
+
+    #[test]
+    fn test_assign_layers_invalid_input() {
+        let mut graph = build_graph::<HierarchyNode, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+        assert_panics_with!(
+            assign_layers(&mut graph, |_| 1),
+            "Graph contains a cycle: 0 → 1 → 0"
+        );
+
+        let mut graph = build_graph::<HierarchyNode, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(assign_layers(&mut graph, |_| 1), INVALID_KEY_SEQUENCE);
+    }
+}
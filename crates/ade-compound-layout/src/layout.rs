@@ -0,0 +1,850 @@
+use crate::ordering::set_ranks;
+use crate::{
+    assign_coordinates, assign_layers, normalize_long_edges, ClusterBounds, CompoundGraph,
+    HierarchyNode,
+};
+use ade_common::{Cancelled, NoProgress, PhaseProgress, Progress};
+use ade_feedback_arc_set::feedback_arc_set;
+use ade_graph::implementations::{DataEdge, Graph};
+use ade_layering::{order_layers_with_progress, OrderingHeuristic, ORDERING_PHASE};
+use ade_traits::{DataEdgeTrait, EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Number of down and up sweeps of crossing minimization
+const SWEEPS: usize = 8;
+
+/// Names of the phases reported by [`layout_with_progress`], in the order they run.
+pub const LAYOUT_PHASES: [&str; 6] = [
+    "cycle removal",
+    "ranking",
+    "dummy insertion",
+    ORDERING_PHASE,
+    "positioning",
+    "edge routing",
+];
+
+/// The direction in which the layers of a [`layout`] follow each other, the direction of
+/// most edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankDirection {
+    /// Top to bottom (`TB`): sources at the top, edges pointing down.
+    #[default]
+    TopBottom,
+    /// Bottom to top (`BT`): sources at the bottom, edges pointing up.
+    BottomTop,
+    /// Left to right (`LR`): sources on the left, edges pointing right.
+    LeftRight,
+    /// Right to left (`RL`): sources on the right, edges pointing left.
+    RightLeft,
+}
+
+impl RankDirection {
+    fn is_horizontal(self) -> bool {
+        matches!(self, RankDirection::LeftRight | RankDirection::RightLeft)
+    }
+}
+
+/// The options of a [`layout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutConfig {
+    /// The width of every node.
+    pub node_width: f64,
+    /// The height of every node.
+    pub node_height: f64,
+    /// The direction of the layers.
+    pub rank_direction: RankDirection,
+    /// The minimum gap between two consecutive nodes of a layer, or between a node and an
+    /// edge bend.
+    pub node_separation: f64,
+    /// The gap between two consecutive layers.
+    pub rank_separation: f64,
+    /// The heuristic used to reduce edge crossings.
+    pub ordering: OrderingHeuristic,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            node_width: 50.0,
+            node_height: 30.0,
+            rank_direction: RankDirection::default(),
+            node_separation: 50.0,
+            rank_separation: 50.0,
+            ordering: OrderingHeuristic::default(),
+        }
+    }
+}
+
+/// The positions of nodes and edges computed by [`layout`].
+///
+/// Coordinates grow rightwards and downwards, and the drawing fits in the box from
+/// `(0, 0)` to `(width, height)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutResult {
+    nodes: BTreeMap<u32, (f64, f64)>,
+    edges: BTreeMap<(u32, u32), Vec<(f64, f64)>>,
+    clusters: BTreeMap<u32, ClusterBounds>,
+    width: f64,
+    height: f64,
+}
+
+impl LayoutResult {
+    /// Returns the center of the node `key`, or `None` if it is not a node of the graph.
+    pub fn position(&self, key: u32) -> Option<(f64, f64)> {
+        self.nodes.get(&key).copied()
+    }
+
+    /// Returns the node keys and centers, in ascending order of key.
+    pub fn positions(&self) -> impl Iterator<Item = (u32, (f64, f64))> + '_ {
+        self.nodes.iter().map(|(&key, &point)| (key, point))
+    }
+
+    /// Returns the points of the edge from `source` to `target` drawn as a polyline, or
+    /// `None` if it is not an edge of the graph.
+    ///
+    /// The first point is on the border of the source and the last point on the border of
+    /// the target, with a bend on every layer crossed by the edge.
+    pub fn edge_points(&self, source: u32, target: u32) -> Option<&[(f64, f64)]> {
+        self.edges.get(&(source, target)).map(Vec::as_slice)
+    }
+
+    /// Returns the edges and their points, in ascending order of `(source, target)`.
+    pub fn edges(&self) -> impl Iterator<Item = ((u32, u32), &[(f64, f64)])> + '_ {
+        self.edges
+            .iter()
+            .map(|(&key, points)| (key, points.as_slice()))
+    }
+
+    /// Returns the box of the cluster `key` in a [`layout_compound`], or `None` if it is
+    /// not a cluster.
+    pub fn cluster_bounds(&self, key: u32) -> Option<ClusterBounds> {
+        self.clusters.get(&key).copied()
+    }
+
+    /// Returns the clusters and their boxes, in ascending order of key.
+    pub fn clusters(&self) -> impl Iterator<Item = (u32, ClusterBounds)> + '_ {
+        self.clusters.iter().map(|(&key, &bounds)| (key, bounds))
+    }
+
+    /// Returns the width of the drawing.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// Returns the height of the drawing.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+}
+
+/// Computes a layered drawing of a directed graph, in the style of Graphviz `dot` and
+/// dagre.
+///
+/// The layout runs the phases of the Sugiyama framework:
+///
+/// 1. Cycle removal: the edges of a [`feedback_arc_set`] are reversed, so that the graph
+///    becomes acyclic. Self-loops are set aside.
+/// 2. Ranking: [`rank_network_simplex`] assigns layers that keep edges short.
+/// 3. Dummy insertion: [`normalize_long_edges`] splits the edges spanning several layers.
+/// 4. Ordering: [`order_layers`] reduces the edge crossings between adjacent layers.
+/// 5. Positioning: [`assign_coordinates`] places nodes with the Brandes–Köpf algorithm.
+/// 6. Edge routing: every edge becomes a polyline through its dummy nodes, clipped at the
+///    borders of its endpoints. Reversed edges point back in their original direction,
+///    and self-loops are drawn as a small loop on the side of their node.
+///
+/// Finally the drawing is rotated or mirrored according to
+/// [`rank_direction`](LayoutConfig::rank_direction). Keys need not be sequential.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{layout, LayoutConfig, RankDirection};
+/// use ade_graph::implementations::{Edge, Node};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (0, 2)]);
+/// let config = LayoutConfig {
+///     node_width: 40.0,
+///     node_height: 20.0,
+///     rank_separation: 30.0,
+///     ..LayoutConfig::default()
+/// };
+/// let result = layout(&graph, &config);
+///
+/// // One layer every 20 + 30 units, from the top
+/// let (_, y0) = result.position(0).unwrap();
+/// let (_, y2) = result.position(2).unwrap();
+/// assert_eq!((y0, y2), (10.0, 110.0));
+/// assert_eq!(result.height(), 120.0);
+///
+/// // 0 → 2 bends around 1, on the middle layer
+/// assert_eq!(result.edge_points(0, 2).unwrap().len(), 3);
+///
+/// let config = LayoutConfig { rank_direction: RankDirection::LeftRight, ..config };
+/// let (x0, _) = layout(&graph, &config).position(0).unwrap();
+/// assert_eq!(x0, 20.0);
+/// ```
+pub fn layout<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    config: &LayoutConfig,
+) -> LayoutResult {
+    match layout_with_progress(graph, config, &mut NoProgress) {
+        Ok(result) => result,
+        Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+    }
+}
+
+/// Computes a layered drawing of a directed graph, reporting progress and allowing
+/// cancellation.
+///
+/// Behaves like [`layout`], and reports to `progress` each phase of the pipeline by one
+/// of the names of [`LAYOUT_PHASES`]: crossing minimization reports the fraction of its
+/// sweeps done so far, edge routing the fraction of routed edges, and the other phases
+/// report when they complete. Cancellation is checked between phases and within
+/// crossing minimization and edge routing, so a GUI can show the progress of a large
+/// layout and offer to abort it.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if `progress` was cancelled before the layout completed.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{layout_with_progress, LayoutConfig, LAYOUT_PHASES};
+/// use ade_common::{CancellationToken, Cancelled};
+/// use ade_graph::implementations::{Edge, Node};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (0, 2)]);
+/// let config = LayoutConfig::default();
+///
+/// let mut phases = Vec::new();
+/// let mut progress = |phase: &str, fraction: f64| {
+///     if fraction == 1.0 {
+///         phases.push(phase.to_string());
+///     }
+/// };
+/// let result = layout_with_progress(&graph, &config, &mut progress)?;
+/// assert_eq!(result.positions().count(), 3);
+/// assert_eq!(phases, LAYOUT_PHASES);
+///
+/// let mut token = CancellationToken::new();
+/// token.cancel();
+/// assert_eq!(layout_with_progress(&graph, &config, &mut token), Err(Cancelled));
+/// # Ok::<(), Cancelled>(())
+/// ```
+pub fn layout_with_progress<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    config: &LayoutConfig,
+    progress: &mut impl Progress,
+) -> Result<LayoutResult, Cancelled> {
+    run(graph, &HashMap::new(), config, progress)
+}
+
+/// Computes a layered drawing of a compound graph, with the nodes of each cluster drawn
+/// inside its box.
+///
+/// The pipeline is the one of [`layout`], run on the graph of `compound` with the border
+/// nodes of its clusters, as described in [`CompoundGraph`]: nesting borders keep the
+/// contents of each cluster between its upper and lower border when ranking, side borders
+/// frame them on every layer, crossing minimization is followed by
+/// [`arrange_clusters`](CompoundGraph::arrange_clusters) so that the members of a cluster,
+/// nested clusters included, are contiguous within each layer, and positioning keeps them
+/// between the side borders. Dummy nodes of long edges between members of a cluster stay
+/// inside it.
+///
+/// Cluster nodes are not drawn: [`LayoutResult::position`] returns `None` for them and
+/// [`LayoutResult::cluster_bounds`] gives their box instead. Keys need not be sequential.
+///
+/// # Panics
+///
+/// Panics if a cluster node has edges, or if the border nodes of `compound` were already
+/// inserted.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{layout_compound, CompoundGraph, HierarchyNode, LayoutConfig};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::NodeTrait;
+///
+/// // 0 → 1 → 2, with 1 and 2 in the cluster 3
+/// let nodes = (0..4).map(HierarchyNode::new).collect();
+/// let mut compound = CompoundGraph::new(Graph::new(nodes, vec![Edge::new(0, 1), Edge::new(1, 2)]));
+/// compound.set_parent(1, 3).unwrap();
+/// compound.set_parent(2, 3).unwrap();
+///
+/// let result = layout_compound(&compound, &LayoutConfig::default());
+/// let bounds = result.cluster_bounds(3).unwrap();
+/// for key in [1, 2] {
+///     let (x, y) = result.position(key).unwrap();
+///     assert!(bounds.left < x && x < bounds.right);
+///     assert!(bounds.top < y && y < bounds.bottom);
+/// }
+/// let (x, y) = result.position(0).unwrap();
+/// assert!(x < bounds.left || x > bounds.right || y < bounds.top || y > bounds.bottom);
+/// assert_eq!(result.position(3), None);
+/// ```
+pub fn layout_compound<E: EdgeTrait>(
+    compound: &CompoundGraph<E>,
+    config: &LayoutConfig,
+) -> LayoutResult {
+    match layout_compound_with_progress(compound, config, &mut NoProgress) {
+        Ok(result) => result,
+        Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+    }
+}
+
+/// Computes a layered drawing of a compound graph, reporting progress and allowing
+/// cancellation.
+///
+/// Behaves like [`layout_compound`], and reports progress as [`layout_with_progress`]
+/// does.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if `progress` was cancelled before the layout completed.
+///
+/// # Panics
+///
+/// Panics as [`layout_compound`] does.
+pub fn layout_compound_with_progress<E: EdgeTrait>(
+    compound: &CompoundGraph<E>,
+    config: &LayoutConfig,
+    progress: &mut impl Progress,
+) -> Result<LayoutResult, Cancelled> {
+    if compound.has_borders() {
+        panic!("Border nodes are already inserted");
+    }
+    let graph = compound.graph();
+    for cluster in compound.clusters() {
+        if graph.in_degree(cluster) + graph.out_degree(cluster) > 0 {
+            panic!("Cluster {} cannot have edges", cluster);
+        }
+    }
+    let parents: HashMap<u32, u32> = graph
+        .get_node_keys()
+        .filter_map(|key| compound.parent(key).map(|parent| (key, parent)))
+        .collect();
+    run(graph, &parents, config, progress)
+}
+
+// The layout pipeline, with the parent cluster of the nested nodes
+fn run<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    parents: &HashMap<u32, u32>,
+    config: &LayoutConfig,
+    progress: &mut impl Progress,
+) -> Result<LayoutResult, Cancelled> {
+    let [cycle_removal, ranking, dummy_insertion, _, positioning, edge_routing] = LAYOUT_PHASES;
+    if progress.is_cancelled() {
+        return Err(Cancelled);
+    }
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let index: HashMap<u32, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i as u32))
+        .collect();
+    let n = keys.len();
+
+    // Phase 1: edges of the acyclic graph, weighted by the number of original edges they
+    // stand for, since an edge and the reversal of its opposite become parallel
+    let edges: Vec<(u32, u32)> = graph
+        .get_edges()
+        .map(|edge| (index[&edge.source()], index[&edge.target()]))
+        .collect();
+    let reversed: HashSet<(u32, u32)> = feedback_arc_set(graph)
+        .into_iter()
+        .map(|(source, target)| (index[&source], index[&target]))
+        .collect();
+    let mut weights: BTreeMap<(u32, u32), u32> = BTreeMap::new();
+    for &(source, target) in &edges {
+        if source == target {
+            continue;
+        }
+        let key = if reversed.contains(&(source, target)) {
+            (target, source)
+        } else {
+            (source, target)
+        };
+        *weights.entry(key).or_default() += 1;
+    }
+    let mut compound = CompoundGraph::new(Graph::new(
+        (0..n as u32).map(HierarchyNode::new).collect(),
+        weights
+            .into_iter()
+            .map(|((source, target), weight)| DataEdge::with_data(source, target, weight))
+            .collect(),
+    ));
+    for (&child, &parent) in parents {
+        compound
+            .set_parent(index[&child], index[&parent])
+            .expect("the containment of a compound graph is a forest");
+    }
+    let clusters = compound.clusters();
+    compound.insert_nesting_borders();
+    complete(cycle_removal, progress)?;
+
+    // Phase 2, where border edges weigh as much as a single edge
+    assign_layers(compound.graph_mut(), |edge| (*edge.data()).max(1));
+    compound.insert_side_borders();
+    complete(ranking, progress)?;
+
+    // Phase 3, nesting the dummy nodes in the innermost cluster around both endpoints
+    let long_edges = normalize_long_edges(compound.graph_mut());
+    for long_edge in &long_edges {
+        let (source, target) = long_edge.edge().key();
+        if let Some(cluster) = compound.common_cluster(source, target) {
+            for &dummy in long_edge.dummies() {
+                compound.nest(dummy, cluster);
+            }
+        }
+    }
+    let dummies: HashMap<(u32, u32), Vec<u32>> = long_edges
+        .into_iter()
+        .map(|long_edge| (long_edge.edge().key(), long_edge.dummies().to_vec()))
+        .collect();
+    complete(dummy_insertion, progress)?;
+
+    // Phase 4
+    let hierarchy = compound.graph();
+    let layers: Vec<usize> = (0..hierarchy.node_count() as u32)
+        .map(|key| hierarchy.get_node(key).layer())
+        .collect();
+    let mut order =
+        order_layers_with_progress(hierarchy, &layers, config.ordering, SWEEPS, progress)?;
+    if !clusters.is_empty() {
+        compound.arrange_clusters(&mut order);
+    }
+    let hierarchy = compound.graph_mut();
+    set_ranks(hierarchy, &order);
+
+    // Phase 5, drawing top to bottom: `across` is the extent of nodes along a layer and
+    // `along` their extent across layers
+    let (across, along) = if config.rank_direction.is_horizontal() {
+        (config.node_height, config.node_width)
+    } else {
+        (config.node_width, config.node_height)
+    };
+    let is_cluster = |key: u32| clusters.binary_search(&key).is_ok();
+    let widths: Vec<f64> = (0..hierarchy.node_count())
+        .map(|key| {
+            if key < n && !is_cluster(key as u32) {
+                across
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    assign_coordinates(
+        hierarchy,
+        &widths,
+        config.node_separation,
+        along + config.rank_separation,
+    );
+    let hierarchy = compound.graph();
+    let center = |key: u32| {
+        let node = hierarchy.get_node(key);
+        (node.x(), node.y() + along / 2.0)
+    };
+    complete(positioning, progress)?;
+
+    // Phase 6
+    let mut routes: BTreeMap<(u32, u32), Vec<(f64, f64)>> = BTreeMap::new();
+    let mut routing = PhaseProgress::new(edge_routing, edges.len());
+    for &(source, target) in &edges {
+        routing.advance(1, progress)?;
+        let points = if source == target {
+            let (x, y) = center(source);
+            let side = x + across / 2.0;
+            vec![
+                (side, y - along / 4.0),
+                (side + config.node_separation / 2.0, y),
+                (side, y + along / 4.0),
+            ]
+        } else {
+            let is_reversed = reversed.contains(&(source, target));
+            let (upper, lower) = if is_reversed {
+                (target, source)
+            } else {
+                (source, target)
+            };
+            let mut points: Vec<(f64, f64)> = std::iter::once(upper)
+                .chain(dummies.get(&(upper, lower)).into_iter().flatten().copied())
+                .chain(std::iter::once(lower))
+                .map(center)
+                .collect();
+            let last = points.len() - 1;
+            points[0] = clip(points[0], points[1], across, along);
+            points[last] = clip(points[last], points[last - 1], across, along);
+            if is_reversed {
+                points.reverse();
+            }
+            points
+        };
+        routes.insert((keys[source as usize], keys[target as usize]), points);
+    }
+    routing.finish(progress);
+
+    // The drawing spans the nodes, their left borders being at 0, and the self-loops
+    let layer_count = order.len();
+    let mut width = (0..n as u32)
+        .filter(|&key| !is_cluster(key))
+        .map(|key| center(key).0 + across / 2.0)
+        .fold(0.0, f64::max);
+    for points in routes.values() {
+        width = points.iter().map(|&(x, _)| x).fold(width, f64::max);
+    }
+    // Cluster boxes run through the centers of their upper and lower borders
+    let bounds: Vec<(u32, ClusterBounds)> = clusters
+        .iter()
+        .map(|&cluster| {
+            let mut bounds = compound.cluster_bounds(cluster).unwrap();
+            bounds.top += along / 2.0;
+            bounds.bottom += along / 2.0;
+            width = width.max(bounds.right);
+            (keys[cluster as usize], bounds)
+        })
+        .collect();
+    let height = if layer_count == 0 {
+        0.0
+    } else {
+        layer_count as f64 * along + (layer_count - 1) as f64 * config.rank_separation
+    };
+
+    let transform = |(x, y): (f64, f64)| match config.rank_direction {
+        RankDirection::TopBottom => (x, y),
+        RankDirection::BottomTop => (x, height - y),
+        RankDirection::LeftRight => (y, x),
+        RankDirection::RightLeft => (height - y, x),
+    };
+    let nodes = keys
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| !is_cluster(i as u32))
+        .map(|(i, &key)| (key, transform(center(i as u32))))
+        .collect();
+    let clusters = bounds
+        .into_iter()
+        .map(|(key, bounds)| {
+            let (x1, y1) = transform((bounds.left, bounds.top));
+            let (x2, y2) = transform((bounds.right, bounds.bottom));
+            let bounds = ClusterBounds {
+                left: x1.min(x2),
+                top: y1.min(y2),
+                right: x1.max(x2),
+                bottom: y1.max(y2),
+            };
+            (key, bounds)
+        })
+        .collect();
+    for points in routes.values_mut() {
+        for point in points.iter_mut() {
+            *point = transform(*point);
+        }
+    }
+    let (width, height) = if config.rank_direction.is_horizontal() {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    Ok(LayoutResult {
+        nodes,
+        edges: routes,
+        clusters,
+        width,
+        height,
+    })
+}
+
+// Reports that a phase is complete, and stops the layout if it was cancelled
+fn complete(phase: &str, progress: &mut impl Progress) -> Result<(), Cancelled> {
+    progress.report(phase, 1.0);
+    if progress.is_cancelled() {
+        return Err(Cancelled);
+    }
+    Ok(())
+}
+
+// The point where the segment from the center of a node towards `toward` leaves the node
+fn clip((x, y): (f64, f64), toward: (f64, f64), width: f64, height: f64) -> (f64, f64) {
+    let (dx, dy) = (toward.0 - x, toward.1 - y);
+    let (w, h) = (width / 2.0, height / 2.0);
+    if dx == 0.0 && dy == 0.0 {
+        return (x, y);
+    }
+    if dy.abs() * w > dx.abs() * h {
+        let h = h.copysign(dy);
+        (x + h * dx / dy, y + h)
+    } else {
+        let w = w.copysign(dx);
+        (x + w, y + w * dy / dx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::{assert_panics_with, CancellationToken};
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    fn square_config(rank_direction: RankDirection) -> LayoutConfig {
+        LayoutConfig {
+            node_width: 20.0,
+            node_height: 20.0,
+            rank_direction,
+            ..LayoutConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_layout_chain() {
+        let graph = build_graph::<Node, Edge>(vec![3, 5, 8], vec![(3, 5), (5, 8)]);
+        let result = layout(&graph, &LayoutConfig::default());
+
+        let positions: Vec<(u32, (f64, f64))> = result.positions().collect();
+        assert_eq!(
+            positions,
+            vec![(3, (25.0, 15.0)), (5, (25.0, 95.0)), (8, (25.0, 175.0))]
+        );
+        assert_eq!((result.width(), result.height()), (50.0, 190.0));
+        assert_eq!(
+            result.edge_points(3, 5).unwrap(),
+            &[(25.0, 30.0), (25.0, 80.0)]
+        );
+        assert_eq!(result.edge_points(5, 3), None);
+        assert_eq!(result.position(0), None);
+    }
+
+    #[test]
+    fn test_layout_cycles_keep_edge_direction() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (1, 2), (2, 0), (1, 0), (2, 3), (3, 3)],
+        );
+        let result = layout(&graph, &LayoutConfig::default());
+
+        assert_eq!(result.edges().count(), 6);
+        for ((source, target), points) in result.edges() {
+            assert!(points.len() >= 2);
+            let (sx, sy) = result.position(source).unwrap();
+            let (tx, ty) = result.position(target).unwrap();
+            // Endpoints lie on the borders of the nodes, 50 × 30 by default
+            let (first, last) = (points[0], points[points.len() - 1]);
+            let on_border = |(px, py): (f64, f64), (cx, cy): (f64, f64)| {
+                let (dx, dy) = ((px - cx).abs(), (py - cy).abs());
+                (dx <= 25.0 + 1e-9 && dy <= 15.0 + 1e-9)
+                    && ((dx - 25.0).abs() < 1e-9 || (dy - 15.0).abs() < 1e-9)
+            };
+            assert!(on_border(first, (sx, sy)));
+            assert!(on_border(last, (tx, ty)));
+        }
+        // The self-loop goes around the right side of 3
+        let (x, _) = result.position(3).unwrap();
+        assert!(result
+            .edge_points(3, 3)
+            .unwrap()
+            .iter()
+            .all(|&(px, _)| px >= x + 25.0));
+    }
+
+    #[test]
+    fn test_layout_rank_directions() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 1), (0, 2), (1, 3), (2, 3), (0, 4), (4, 3), (0, 3)],
+        );
+        let tb = layout(&graph, &square_config(RankDirection::TopBottom));
+        let (width, height) = (tb.width(), tb.height());
+
+        let bt = layout(&graph, &square_config(RankDirection::BottomTop));
+        let lr = layout(&graph, &square_config(RankDirection::LeftRight));
+        let rl = layout(&graph, &square_config(RankDirection::RightLeft));
+        assert_eq!((lr.width(), lr.height()), (height, width));
+        for (key, (x, y)) in tb.positions() {
+            assert_eq!(bt.position(key), Some((x, height - y)));
+            assert_eq!(lr.position(key), Some((y, x)));
+            assert_eq!(rl.position(key), Some((height - y, x)));
+        }
+        let (x, y) = tb.edge_points(0, 3).unwrap()[1];
+        assert_eq!(rl.edge_points(0, 3).unwrap()[1], (height - y, x));
+    }
+
+    #[test]
+    fn test_layout_random_graphs() {
+        let config = LayoutConfig::default();
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(30, 60, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let result = layout(&graph, &config);
+
+            // Nodes of a layer do not overlap and fit in the drawing
+            let positions: Vec<(f64, f64)> = result.positions().map(|(_, point)| point).collect();
+            for (i, &(x1, y1)) in positions.iter().enumerate() {
+                assert!(x1 >= 25.0 && x1 <= result.width() - 25.0);
+                assert!(y1 >= 15.0 && y1 <= result.height() - 15.0);
+                for &(x2, y2) in &positions[i + 1..] {
+                    assert!(y1 != y2 || (x1 - x2).abs() >= 50.0 + config.node_separation - 1e-9);
+                }
+            }
+            assert_eq!(result.edges().count(), graph.get_edges().count());
+        }
+    }
+
+    #[test]
+    fn test_layout_cancelled_between_phases() {
+        let (nodes, edges) = generate_random_graph_data(30, 60, 1);
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let config = LayoutConfig::default();
+
+        let mut reports: Vec<(String, f64)> = Vec::new();
+        let mut progress = |phase: &str, fraction: f64| reports.push((phase.to_string(), fraction));
+        let result = layout_with_progress(&graph, &config, &mut progress);
+        assert_eq!(result, Ok(layout(&graph, &config)));
+        assert!(reports
+            .iter()
+            .all(|(phase, _)| LAYOUT_PHASES.contains(&phase.as_str())));
+
+        // Stop once ranking is complete: no later phase is reported
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        let mut phases = Vec::new();
+        let mut progress = (|phase: &str, _: f64| {
+            phases.push(phase.to_string());
+            if phase == LAYOUT_PHASES[1] {
+                handle.cancel();
+            }
+        })
+        .with_cancellation(token);
+        assert_eq!(
+            layout_with_progress(&graph, &config, &mut progress),
+            Err(Cancelled)
+        );
+        drop(progress);
+        assert_eq!(phases, &LAYOUT_PHASES[..2]);
+    }
+
+    // Random edges between nodes 0 to 29, with cluster 30 around 0 to 9 and cluster 31,
+    // itself around 10 to 14, and cluster 32 around 20 to 24
+    fn random_compound(seed: u64) -> CompoundGraph<Edge> {
+        let (mut nodes, edges) = generate_random_graph_data(30, 60, seed);
+        nodes.extend([30, 31, 32]);
+        let mut compound = CompoundGraph::new(build_graph::<HierarchyNode, Edge>(nodes, edges));
+        let members = [(0..10, 30), (10..15, 31), (20..25, 32)];
+        for (children, cluster) in members {
+            for child in children {
+                compound.set_parent(child, cluster).unwrap();
+            }
+        }
+        compound.set_parent(31, 30).unwrap();
+        compound
+    }
+
+    #[test]
+    fn test_layout_compound_keeps_clusters_contiguous() {
+        for seed in 0..5 {
+            let compound = random_compound(seed);
+            let result = layout_compound(&compound, &LayoutConfig::default());
+            let members = |cluster: u32| -> Vec<u32> {
+                (0..30)
+                    .filter(|&key| compound.ancestors(key).any(|a| a == cluster))
+                    .collect()
+            };
+
+            assert_eq!(result.positions().count(), 30);
+            assert_eq!(result.position(30), None);
+            for cluster in [30, 31, 32] {
+                let bounds = result.cluster_bounds(cluster).unwrap();
+                let inside = members(cluster);
+                for &key in &inside {
+                    let (x, y) = result.position(key).unwrap();
+                    assert!(bounds.left < x && x < bounds.right);
+                    assert!(bounds.top < y && y < bounds.bottom);
+                }
+                // On every layer, no other node lies between the members of the cluster
+                for (key, (x, y)) in result.positions() {
+                    if inside.contains(&key) {
+                        continue;
+                    }
+                    let row: Vec<f64> = inside
+                        .iter()
+                        .map(|&member| result.position(member).unwrap())
+                        .filter(|&(_, other_y)| other_y == y)
+                        .map(|(other_x, _)| other_x)
+                        .collect();
+                    let min = row.iter().copied().fold(f64::INFINITY, f64::min);
+                    let max = row.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    assert!(row.is_empty() || x < min || x > max);
+                }
+            }
+            let (inner, outer) = (
+                result.cluster_bounds(31).unwrap(),
+                result.cluster_bounds(30).unwrap(),
+            );
+            assert!(outer.left < inner.left && inner.right < outer.right);
+            assert!(outer.top < inner.top && inner.bottom < outer.bottom);
+            assert!(result.clusters().all(|(_, b)| b.right <= result.width()));
+            assert_eq!(result.edges().count(), compound.graph().get_edges().count());
+        }
+    }
+
+    #[test]
+    fn test_layout_compound_rank_directions() {
+        let compound = random_compound(7);
+        let tb = layout_compound(&compound, &square_config(RankDirection::TopBottom));
+        let rl = layout_compound(&compound, &square_config(RankDirection::RightLeft));
+        assert_eq!(
+            tb,
+            layout_compound(&compound, &square_config(RankDirection::TopBottom))
+        );
+        let height = tb.height();
+        for (key, (x, y)) in tb.positions() {
+            assert_eq!(rl.position(key), Some((height - y, x)));
+        }
+        for (cluster, bounds) in tb.clusters() {
+            let expected = ClusterBounds {
+                left: height - bounds.bottom,
+                top: bounds.left,
+                right: height - bounds.top,
+                bottom: bounds.right,
+            };
+            assert_eq!(rl.cluster_bounds(cluster), Some(expected));
+        }
+        assert_eq!(
+            layout(&compound.graph().clone(), &LayoutConfig::default())
+                .clusters()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_layout_compound_invalid_input() {
+        let mut compound = random_compound(0);
+        compound.graph_mut().add_edge(Edge::new(32, 0));
+        assert_panics_with!(
+            layout_compound(&compound, &LayoutConfig::default()),
+            "Cluster 32 cannot have edges"
+        );
+
+        let mut compound = random_compound(0);
+        compound.insert_nesting_borders();
+        assert_panics_with!(
+            layout_compound(&compound, &LayoutConfig::default()),
+            "Border nodes are already inserted"
+        );
+    }
+
+    #[test]
+    fn test_layout_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        let result = layout(&graph, &LayoutConfig::default());
+        assert_eq!((result.width(), result.height()), (0.0, 0.0));
+        assert_eq!(result.positions().count(), 0);
+    }
+}
@@ -0,0 +1,18 @@
+pub mod hierarchy;
+pub mod layering;
+pub mod layout;
+pub mod normalize;
+pub mod ordering;
+pub mod position;
+pub mod snapshot;
+
+pub use hierarchy::compound::{ClusterBounds, CompoundError, CompoundGraph};
+pub use hierarchy::node::{HierarchyNode, NodeType};
+pub use layering::assign_layers;
+pub use layout::{
+    layout, layout_compound, layout_compound_with_progress, layout_with_progress, LayoutConfig,
+    LayoutResult, RankDirection, LAYOUT_PHASES,
+};
+pub use normalize::{normalize_long_edges, restore_long_edges, LongEdge};
+pub use ordering::assign_ranks;
+pub use position::assign_coordinates;
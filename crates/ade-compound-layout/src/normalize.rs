@@ -0,0 +1,223 @@
+use crate::{HierarchyNode, NodeType};
+use ade_graph::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait};
+
+/// An edge spanning more than one layer, replaced by a chain of dummy nodes by
+/// [`normalize_long_edges`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongEdge<E> {
+    edge: E,
+    dummies: Vec<u32>,
+}
+
+impl<E: EdgeTrait> LongEdge<E> {
+    /// Returns the original edge.
+    pub fn edge(&self) -> &E {
+        &self.edge
+    }
+
+    /// Returns the keys of the dummy nodes that replace the edge, from its source to its
+    /// target, one per crossed layer.
+    pub fn dummies(&self) -> &[u32] {
+        &self.dummies
+    }
+
+    /// Returns the points of the edge drawn as a polyline, from the center of its source
+    /// through the dummy nodes to the center of its target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source, the target or one of the dummy nodes is not in the graph.
+    pub fn polyline(&self, graph: &Graph<HierarchyNode, E>) -> Vec<(f64, f64)> {
+        std::iter::once(self.edge.source())
+            .chain(self.dummies.iter().copied())
+            .chain(std::iter::once(self.edge.target()))
+            .map(|key| {
+                let node = graph.get_node(key);
+                (node.x(), node.y())
+            })
+            .collect()
+    }
+}
+
+/// Splits the edges spanning more than one layer into chains of [`NodeType::Dummy`] nodes,
+/// one per crossed layer, so that every edge joins adjacent layers.
+///
+/// Crossing minimization and coordinate assignment only consider edges between adjacent
+/// layers, so this step comes right after layering. Dummy nodes get the keys following
+/// the largest key of the graph, in order of the long edges, so sequential keys stay
+/// sequential. Each segment is built with [`EdgeTrait::with_endpoints`] and keeps the
+/// payload of the original edge. Edges within a layer are left unchanged.
+///
+/// The returned [`LongEdge`]s record the replaced edges, to draw them as polylines once
+/// dummy nodes are positioned and to put them back with [`restore_long_edges`].
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{normalize_long_edges, HierarchyNode, NodeType};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+///
+/// let mut nodes: Vec<HierarchyNode> = (0..2).map(HierarchyNode::new).collect();
+/// nodes[1].set_layer(3);
+/// let mut graph = Graph::new(nodes, vec![Edge::new(0, 1)]);
+///
+/// let long_edges = normalize_long_edges(&mut graph);
+/// assert_eq!(long_edges[0].dummies(), &[2, 3]);
+/// assert_eq!(*graph.get_node(3).node_type(), NodeType::Dummy);
+/// assert_eq!(graph.get_node(3).layer(), 2);
+/// assert!(graph.has_edge(0, 2) && graph.has_edge(2, 3) && graph.has_edge(3, 1));
+/// ```
+pub fn normalize_long_edges<E: EdgeTrait>(graph: &mut Graph<HierarchyNode, E>) -> Vec<LongEdge<E>> {
+    let layer = |graph: &Graph<HierarchyNode, E>, key: u32| graph.get_node(key).layer();
+    let mut keys: Vec<(u32, u32)> = graph
+        .get_edges()
+        .map(|edge| edge.key())
+        .filter(|&(source, target)| layer(graph, source).abs_diff(layer(graph, target)) > 1)
+        .collect();
+    keys.sort_unstable();
+
+    let mut next_key = graph.get_node_keys().max().map_or(0, |key| key + 1);
+    let mut long_edges = Vec::with_capacity(keys.len());
+    for (source, target) in keys {
+        let edge = graph.remove_edge(source, target).unwrap();
+        let (from, to) = (layer(graph, source), layer(graph, target));
+        let crossed: Vec<usize> = if from < to {
+            (from + 1..to).collect()
+        } else {
+            (to + 1..from).rev().collect()
+        };
+
+        let mut dummies = Vec::with_capacity(crossed.len());
+        for dummy_layer in crossed {
+            let mut dummy = HierarchyNode::with_type(next_key, NodeType::Dummy);
+            dummy.set_layer(dummy_layer);
+            graph.add_node(dummy);
+            dummies.push(next_key);
+            next_key += 1;
+        }
+
+        let chain: Vec<u32> = std::iter::once(source)
+            .chain(dummies.iter().copied())
+            .chain(std::iter::once(target))
+            .collect();
+        for pair in chain.windows(2) {
+            graph.add_edge(edge.with_endpoints(pair[0], pair[1]));
+        }
+        long_edges.push(LongEdge { edge, dummies });
+    }
+    long_edges
+}
+
+/// Removes the dummy nodes inserted by [`normalize_long_edges`] and puts the original
+/// edges back.
+///
+/// Read the [`polyline`](LongEdge::polyline)s of the long edges first if they are needed,
+/// as the positions of dummy nodes are lost.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{normalize_long_edges, restore_long_edges, HierarchyNode};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+///
+/// let mut nodes: Vec<HierarchyNode> = (0..2).map(HierarchyNode::new).collect();
+/// nodes[1].set_layer(2);
+/// let mut graph = Graph::new(nodes, vec![Edge::new(0, 1)]);
+///
+/// let long_edges = normalize_long_edges(&mut graph);
+/// assert_eq!(graph.node_count(), 3);
+///
+/// restore_long_edges(&mut graph, long_edges);
+/// assert_eq!(graph.node_count(), 2);
+/// assert!(graph.has_edge(0, 1));
+/// ```
+pub fn restore_long_edges<E: EdgeTrait>(
+    graph: &mut Graph<HierarchyNode, E>,
+    long_edges: Vec<LongEdge<E>>,
+) {
+    for long_edge in long_edges {
+        for dummy in long_edge.dummies {
+            graph.remove_node(dummy);
+        }
+        graph.add_edge(long_edge.edge);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assign_coordinates;
+    use ade_graph::implementations::{DataEdge, Edge};
+    use ade_graph::{DataEdgeTrait, NodeTrait};
+
+    fn hierarchy<E: EdgeTrait>(layers: &[usize], edges: Vec<E>) -> Graph<HierarchyNode, E> {
+        let mut nodes: Vec<HierarchyNode> =
+            (0..layers.len() as u32).map(HierarchyNode::new).collect();
+        for (node, &layer) in nodes.iter_mut().zip(layers) {
+            node.set_layer(layer);
+        }
+        Graph::new(nodes, edges)
+    }
+
+    #[test]
+    fn test_every_edge_joins_adjacent_layers() {
+        // 0 → 3 spans three layers, 3 → 1 goes up two, 1 → 2 is short and 2 → 4 is flat
+        let edges = vec![
+            Edge::new(0, 3),
+            Edge::new(3, 1),
+            Edge::new(1, 2),
+            Edge::new(2, 4),
+        ];
+        let mut graph = hierarchy(&[0, 1, 2, 3, 2], edges);
+        let long_edges = normalize_long_edges(&mut graph);
+
+        assert_eq!(long_edges.len(), 2);
+        assert_eq!(long_edges[0].edge().key(), (0, 3));
+        assert_eq!(long_edges[0].dummies(), &[5, 6]);
+        assert_eq!(long_edges[1].edge().key(), (3, 1));
+        assert_eq!(long_edges[1].dummies(), &[7]);
+        assert!(graph.has_sequential_keys());
+        assert_eq!(graph.get_node(7).layer(), 2);
+        for edge in graph.get_edges() {
+            let (s, t) = (graph.get_node(edge.source()), graph.get_node(edge.target()));
+            assert!(s.layer().abs_diff(t.layer()) <= 1);
+        }
+        assert!(graph.has_edge(2, 4));
+
+        restore_long_edges(&mut graph, long_edges);
+        assert_eq!(graph.node_count(), 5);
+        assert_eq!(graph.get_edges().count(), 4);
+        assert!(graph.has_edge(0, 3) && graph.has_edge(3, 1));
+    }
+
+    #[test]
+    fn test_segments_keep_payload() {
+        let mut graph = hierarchy(&[0, 2], vec![DataEdge::with_data(0, 1, "calls")]);
+        normalize_long_edges(&mut graph);
+        assert_eq!(*graph.get_edge(0, 2).data(), "calls");
+        assert_eq!(*graph.get_edge(2, 1).data(), "calls");
+    }
+
+    #[test]
+    fn test_polyline() {
+        let mut graph = hierarchy(
+            &[0, 0, 1, 2],
+            vec![Edge::new(0, 3), Edge::new(1, 2), Edge::new(2, 3)],
+        );
+        let long_edges = normalize_long_edges(&mut graph);
+        for (rank, key) in [(1, 1), (1, 2), (0, 3), (0, 4)] {
+            let mut node = graph.get_node(key).clone();
+            node.set_rank(rank);
+            graph.add_node(node);
+        }
+
+        assign_coordinates(&mut graph, &[10.0; 5], 10.0, 50.0);
+        let polyline = long_edges[0].polyline(&graph);
+        assert_eq!(polyline.len(), 3);
+        assert_eq!(polyline[1], (graph.get_node(4).x(), 50.0));
+        assert_eq!(polyline[2].1, 100.0);
+    }
+}
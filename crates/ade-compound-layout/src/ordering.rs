@@ -0,0 +1,118 @@
+use crate::HierarchyNode;
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_graph::implementations::Graph;
+use ade_layering::{layer_positions, order_layers, OrderingHeuristic};
+use ade_traits::{EdgeTrait, GraphViewTrait};
+
+/// Orders the nodes within each layer of a hierarchy to reduce edge crossings, the phase
+/// between [`assign_layers`](crate::assign_layers) and
+/// [`assign_coordinates`](crate::assign_coordinates).
+///
+/// The layer of each node is read from [`layer`](HierarchyNode::layer). The layers are
+/// reordered by [`order_layers`] with the given `heuristic` and number of `sweeps`, and the
+/// position of each node in its layer, from 0 on the left, is stored with
+/// [`HierarchyNode::set_rank`]. Only edges between adjacent layers are taken into account,
+/// so long edges should be split by [`normalize_long_edges`](crate::normalize_long_edges)
+/// first.
+///
+/// # Returns
+///
+/// The keys of each layer, from left to right.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{assign_layers, assign_ranks, HierarchyNode};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+/// use ade_layering::OrderingHeuristic;
+///
+/// // 0 → 3 and 1 → 2 cross when both layers are in key order
+/// let nodes = (0..4).map(HierarchyNode::new).collect();
+/// let mut graph = Graph::new(nodes, vec![Edge::new(0, 3), Edge::new(1, 2)]);
+/// assign_layers(&mut graph, |_| 1);
+///
+/// let order = assign_ranks(&mut graph, OrderingHeuristic::Barycenter, 4);
+/// assert_eq!(order, vec![vec![0, 1], vec![3, 2]]);
+/// assert_eq!(graph.get_node(3).rank(), 0);
+/// ```
+pub fn assign_ranks<E: EdgeTrait>(
+    graph: &mut Graph<HierarchyNode, E>,
+    heuristic: OrderingHeuristic,
+    sweeps: usize,
+) -> Vec<Vec<u32>> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let layers: Vec<usize> = (0..graph.node_count() as u32)
+        .map(|key| graph.get_node(key).layer())
+        .collect();
+    let order = order_layers(graph, &layers, heuristic, sweeps);
+    set_ranks(graph, &order);
+    order
+}
+
+// Stores the position of each node within its layer of `order`
+pub(crate) fn set_ranks<E: EdgeTrait>(graph: &mut Graph<HierarchyNode, E>, order: &[Vec<u32>]) {
+    for (key, rank) in layer_positions(order).into_iter().enumerate() {
+        let mut node = graph.get_node(key as u32).clone();
+        node.set_rank(rank);
+        graph.add_node(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assign_layers, normalize_long_edges};
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::Edge;
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+    use ade_layering::crossing_count;
+
+    #[test]
+    fn test_assign_ranks_matches_order() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(30, 60, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let mut graph = build_graph::<HierarchyNode, Edge>(nodes, edges);
+            assign_layers(&mut graph, |_| 1);
+            normalize_long_edges(&mut graph);
+
+            let initial: Vec<Vec<u32>> = {
+                let mut layers: Vec<Vec<u32>> = Vec::new();
+                for key in 0..graph.node_count() as u32 {
+                    let layer = graph.get_node(key).layer();
+                    if layers.len() <= layer {
+                        layers.resize(layer + 1, Vec::new());
+                    }
+                    layers[layer].push(key);
+                }
+                layers
+            };
+            let order = assign_ranks(&mut graph, OrderingHeuristic::Median, 8);
+
+            assert!(crossing_count(&graph, &order) <= crossing_count(&graph, &initial));
+            for (layer, keys) in order.iter().enumerate() {
+                for (rank, &key) in keys.iter().enumerate() {
+                    assert_eq!(graph.get_node(key).layer(), layer);
+                    assert_eq!(graph.get_node(key).rank(), rank);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_ranks_non_sequential_keys() {
+        let mut graph = build_graph::<HierarchyNode, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(
+            assign_ranks(&mut graph, OrderingHeuristic::Barycenter, 1),
+            INVALID_KEY_SEQUENCE
+        );
+    }
+}
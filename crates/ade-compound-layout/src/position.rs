@@ -0,0 +1,125 @@
+use crate::{HierarchyNode, NodeType};
+use ade_graph::implementations::Graph;
+use ade_layering::horizontal_coordinates;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Assigns the `x` and `y` coordinates of the nodes of a layered hierarchy.
+///
+/// The layer and the position within the layer of each node are read from
+/// [`layer`](HierarchyNode::layer) and [`rank`](HierarchyNode::rank), as set by the
+/// layering and ordering phases. Horizontal coordinates come from the Brandes–Köpf
+/// algorithm of [`horizontal_coordinates`], which balances the layout and centers nodes
+/// over their neighbors, with [`NodeType::Dummy`] nodes treated as the inner points of
+/// long edges, and the side borders of clusters kept in vertical lines. `widths` gives the
+/// width of each node, indexed by key, and consecutive nodes of a layer are at least
+/// `node_separation` apart. Layer `i` is placed at `y = i * layer_separation`.
+///
+/// `x` and `y` are the center of each node, and the left border of the leftmost node is at
+/// 0.
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0, and if `widths`
+/// does not have one entry per node.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::{assign_coordinates, HierarchyNode, NodeType};
+/// use ade_graph::implementations::{Edge, Graph};
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+///
+/// let mut nodes: Vec<HierarchyNode> = (0..3).map(HierarchyNode::new).collect();
+/// for (node, (layer, rank)) in nodes.iter_mut().zip([(0, 0), (1, 0), (1, 1)]) {
+///     node.set_layer(layer);
+///     node.set_rank(rank);
+/// }
+/// let mut graph = Graph::new(nodes, vec![Edge::new(0, 1), Edge::new(0, 2)]);
+///
+/// assign_coordinates(&mut graph, &[10.0; 3], 10.0, 40.0);
+/// let root = graph.get_node(0);
+/// assert_eq!((root.x(), root.y()), (15.0, 0.0));
+/// assert_eq!(graph.get_node(2).y(), 40.0);
+/// ```
+pub fn assign_coordinates<E: EdgeTrait>(
+    graph: &mut Graph<HierarchyNode, E>,
+    widths: &[f64],
+    node_separation: f64,
+    layer_separation: f64,
+) {
+    let layer_count = graph
+        .get_nodes()
+        .map(|node| node.layer() + 1)
+        .max()
+        .unwrap_or(0);
+    let mut order: Vec<Vec<u32>> = vec![Vec::new(); layer_count];
+    for node in graph.get_nodes() {
+        order[node.layer()].push(node.key());
+    }
+    for layer in &mut order {
+        layer.sort_by_key(|&key| (graph.get_node(key).rank(), key));
+    }
+
+    let xs = horizontal_coordinates(graph, &order, widths, node_separation, |key| {
+        matches!(
+            graph.get_node(key).node_type(),
+            NodeType::Dummy | NodeType::LeftBorder | NodeType::RightBorder
+        )
+    });
+    for (key, x) in xs.into_iter().enumerate() {
+        let mut node = graph.get_node(key as u32).clone();
+        node.set_x(x);
+        node.set_y(node.layer() as f64 * layer_separation);
+        graph.add_node(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::Edge;
+
+    #[test]
+    fn test_assign_coordinates() {
+        // 0 → 2 is a long edge through the dummy node 1, next to 3 → 4 → 5
+        let layers = [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)];
+        let mut nodes = Vec::new();
+        for (key, &(layer, rank)) in layers.iter().enumerate() {
+            let node_type = if key == 1 {
+                NodeType::Dummy
+            } else {
+                NodeType::Base
+            };
+            let mut node = HierarchyNode::with_type(key as u32, node_type);
+            node.set_layer(layer);
+            node.set_rank(rank);
+            nodes.push(node);
+        }
+        let edges = vec![
+            Edge::new(0, 1),
+            Edge::new(1, 2),
+            Edge::new(3, 4),
+            Edge::new(4, 5),
+        ];
+        let mut graph = Graph::new(nodes, edges);
+
+        assign_coordinates(&mut graph, &[20.0, 0.0, 20.0, 10.0, 10.0, 10.0], 5.0, 30.0);
+        let position = |key| (graph.get_node(key).x(), graph.get_node(key).y());
+        assert_eq!(position(0), (10.0, 0.0));
+        assert_eq!(position(1), (10.0, 30.0));
+        assert_eq!(position(2), (10.0, 60.0));
+        assert_eq!(position(5), (30.0, 60.0));
+        assert_eq!(*graph.get_node(1).node_type(), NodeType::Dummy);
+        assert!(graph.has_edge(1, 2));
+    }
+
+    #[test]
+    fn test_invalid_widths() {
+        let mut graph = Graph::<HierarchyNode, Edge>::new(vec![HierarchyNode::new(0)], vec![]);
+        assert_panics_with!(
+            assign_coordinates(&mut graph, &[], 1.0, 1.0),
+            "Expected a width for each of the 1 nodes, got 0"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use crate::LayoutResult;
+use std::fs;
+use std::path::Path;
+
+// Set to a non-empty value to rewrite golden files instead of comparing with them
+const UPDATE_VARIABLE: &str = "ADE_UPDATE_SNAPSHOTS";
+
+impl LayoutResult {
+    /// Returns a text rendering of the layout, stable across runs, to compare layouts as
+    /// text.
+    ///
+    /// The first line gives the size of the drawing, followed by one line per node, in
+    /// ascending order of key, one line per cluster of a
+    /// [`layout_compound`](crate::layout_compound), in ascending order of key, and one line
+    /// per edge, in ascending order of `(source, target)`:
+    ///
+    /// ```text
+    /// size <width> <height>
+    /// node <key> <x> <y>
+    /// cluster <key> <left> <top> <right> <bottom>
+    /// edge <source> <target> <x>,<y> <x>,<y> ...
+    /// ```
+    ///
+    /// Coordinates are rounded to `precision` decimal places, without trailing zeros, so
+    /// that floating-point noise does not show up as a change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_compound_layout::{layout, LayoutConfig};
+    /// use ade_graph::implementations::{Edge, Node};
+    /// use ade_graph::utils::build::build_graph;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+    /// let result = layout(&graph, &LayoutConfig::default());
+    ///
+    /// assert_eq!(
+    ///     result.to_snapshot(2),
+    ///     "size 50 110\nnode 0 25 15\nnode 1 25 95\nedge 0 1 25,30 25,80\n"
+    /// );
+    /// ```
+    pub fn to_snapshot(&self, precision: usize) -> String {
+        let number = |value: f64| format_number(value, precision);
+        let mut lines = vec![format!(
+            "size {} {}",
+            number(self.width()),
+            number(self.height())
+        )];
+        for (key, (x, y)) in self.positions() {
+            lines.push(format!("node {} {} {}", key, number(x), number(y)));
+        }
+        for (key, bounds) in self.clusters() {
+            lines.push(format!(
+                "cluster {} {} {} {} {}",
+                key,
+                number(bounds.left),
+                number(bounds.top),
+                number(bounds.right),
+                number(bounds.bottom)
+            ));
+        }
+        for ((source, target), points) in self.edges() {
+            let points: Vec<String> = points
+                .iter()
+                .map(|&(x, y)| format!("{},{}", number(x), number(y)))
+                .collect();
+            lines.push(format!("edge {} {} {}", source, target, points.join(" ")));
+        }
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+}
+
+/// Renders the differences between two snapshots as a line diff, or returns `None` if
+/// they are equal.
+///
+/// Lines only in `expected` start with `-`, lines only in `actual` with `+`, and common
+/// lines with a space.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::snapshot::snapshot_diff;
+///
+/// let diff = snapshot_diff("node 0 1 1\nnode 1 2 2\n", "node 0 1 1\nnode 1 3 2\n");
+/// assert_eq!(diff.unwrap(), "  node 0 1 1\n- node 1 2 2\n+ node 1 3 2\n");
+/// assert_eq!(snapshot_diff("size 1 1\n", "size 1 1\n"), None);
+/// ```
+pub fn snapshot_diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // Length of the longest common subsequence of the suffixes old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    Some(diff)
+}
+
+/// Compares a layout with the golden file at `path`, as rendered by
+/// [`LayoutResult::to_snapshot`].
+///
+/// If the file does not exist, or if the `ADE_UPDATE_SNAPSHOTS` environment variable is
+/// set to a non-empty value, the snapshot is written to `path` instead, creating its
+/// directory if needed. Changes to a layout can then be reviewed as diffs of the golden
+/// files.
+///
+/// # Panics
+///
+/// Panics with the [`snapshot_diff`] of the two snapshots if they differ, and if the
+/// golden file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// use ade_compound_layout::snapshot::assert_snapshot;
+/// use ade_compound_layout::{layout, LayoutConfig};
+/// use ade_graph::implementations::{Edge, Node};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2)]);
+/// let path = std::env::temp_dir().join("ade_compound_layout_snapshot_doc.txt");
+/// # std::fs::remove_file(&path).ok();
+///
+/// // The first run writes the golden file, the next ones compare with it
+/// assert_snapshot(&path, &layout(&graph, &LayoutConfig::default()), 3);
+/// assert_snapshot(&path, &layout(&graph, &LayoutConfig::default()), 3);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn assert_snapshot(path: impl AsRef<Path>, result: &LayoutResult, precision: usize) {
+    let path = path.as_ref();
+    let actual = result.to_snapshot(precision);
+    let update = std::env::var_os(UPDATE_VARIABLE).is_some_and(|value| !value.is_empty());
+    if update || !path.exists() {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .unwrap_or_else(|err| panic!("Cannot create {}: {}", directory.display(), err));
+        }
+        fs::write(path, &actual)
+            .unwrap_or_else(|err| panic!("Cannot write {}: {}", path.display(), err));
+        return;
+    }
+
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Cannot read {}: {}", path.display(), err));
+    if let Some(diff) = snapshot_diff(&expected, &actual) {
+        panic!(
+            "Layout does not match the snapshot {}, set {} to update it:\n{}",
+            path.display(),
+            UPDATE_VARIABLE,
+            diff
+        );
+    }
+}
+
+// Rounds to `precision` decimal places and drops trailing zeros, writing -0 as 0
+fn format_number(value: f64, precision: usize) -> String {
+    let mut text = format!("{:.*}", precision, value);
+    if text.contains('.') {
+        text.truncate(text.trim_end_matches('0').trim_end_matches('.').len());
+    }
+    if text == "-0" {
+        text = "0".to_string();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout, layout_compound, CompoundGraph, HierarchyNode, LayoutConfig};
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Graph, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::NodeTrait;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(25.0, 3), "25");
+        assert_eq!(format_number(1.0 / 3.0, 3), "0.333");
+        assert_eq!(format_number(2.5000001, 3), "2.5");
+        assert_eq!(format_number(-0.0001, 2), "0");
+        assert_eq!(format_number(-1.25, 1), "-1.2");
+        assert_eq!(format_number(120.0, 0), "120");
+    }
+
+    #[test]
+    fn test_snapshot_is_stable() {
+        let graph = build_graph::<Node, Edge>(vec![4, 2, 9], vec![(9, 2), (4, 2), (2, 9)]);
+        let result = layout(&graph, &LayoutConfig::default());
+        let snapshot = result.to_snapshot(3);
+
+        assert_eq!(
+            snapshot,
+            layout(&graph, &LayoutConfig::default()).to_snapshot(3)
+        );
+        let kinds: Vec<&str> = snapshot
+            .lines()
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["size", "node", "node", "node", "edge", "edge", "edge"]
+        );
+        assert!(snapshot.lines().nth(1).unwrap().starts_with("node 2 "));
+    }
+
+    #[test]
+    fn test_snapshot_of_clusters() {
+        let nodes = (0..3).map(HierarchyNode::new).collect();
+        let mut compound = CompoundGraph::new(Graph::new(nodes, vec![Edge::new(0, 1)]));
+        compound.set_parent(1, 2).unwrap();
+        let result = layout_compound(&compound, &LayoutConfig::default());
+        let bounds = result.cluster_bounds(2).unwrap();
+
+        let snapshot = result.to_snapshot(3);
+        let lines: Vec<&str> = snapshot.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[3],
+            format!(
+                "cluster 2 {} {} {} {}",
+                format_number(bounds.left, 3),
+                format_number(bounds.top, 3),
+                format_number(bounds.right, 3),
+                format_number(bounds.bottom, 3)
+            )
+        );
+        assert!(lines[4].starts_with("edge 0 1 "));
+    }
+
+    #[test]
+    fn test_diff_and_golden_files() {
+        let diff = snapshot_diff("a\nb\nc\n", "a\nc\nd\n").unwrap();
+        assert_eq!(diff, "  a\n- b\n  c\n+ d\n");
+
+        let path = std::env::temp_dir()
+            .join(format!("ade_compound_layout_{}", std::process::id()))
+            .join("chain.txt");
+        let chain = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        let result = layout(&chain, &LayoutConfig::default());
+        assert_snapshot(&path, &result, 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), result.to_snapshot(3));
+
+        let wider = LayoutConfig {
+            node_width: 60.0,
+            ..LayoutConfig::default()
+        };
+        let message = format!(
+            "Layout does not match the snapshot {}, set {} to update it:\n{}",
+            path.display(),
+            UPDATE_VARIABLE,
+            "- size 50 110\n- node 0 25 15\n- node 1 25 95\n- edge 0 1 25,30 25,80\n\
+             + size 60 110\n+ node 0 30 15\n+ node 1 30 95\n+ edge 0 1 30,30 30,80\n"
+        );
+        assert_panics_with!(
+            assert_snapshot(&path, &layout(&chain, &wider), 3),
+            message.as_str()
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
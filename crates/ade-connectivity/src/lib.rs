@@ -0,0 +1,7 @@
+pub mod stats;
+pub mod strong;
+pub mod undirected;
+
+pub use stats::GraphStats;
+pub use strong::{strong_articulation_points, strong_bridges};
+pub use undirected::{articulation_points, bridges};
@@ -0,0 +1,145 @@
+use ade_strongly_connected_components::{scc_iterative, weakly_connected_components};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// A summary of the size, density, degrees and components of a graph.
+///
+/// The degree of a node is the sum of its in-degree and out-degree, so a self-loop adds 2
+/// to the degree of its node. Useful to describe the graphs used in benchmarks, or to check
+/// the output of a generator.
+///
+/// # Examples
+///
+/// ```
+/// use ade_connectivity::GraphStats;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // A cycle 0 → 1 → 2 → 0, a self-loop on 3 and the isolated node 4
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4],
+///     vec![(0, 1), (1, 2), (2, 0), (3, 3)],
+/// );
+/// let stats = GraphStats::compute(&graph);
+///
+/// assert_eq!(stats.edge_count, 4);
+/// assert_eq!(stats.density, 3.0 / 20.0);
+/// assert_eq!((stats.min_degree, stats.max_degree), (0, 2));
+/// assert_eq!(stats.self_loop_count, 1);
+/// assert_eq!(stats.isolated_node_count, 1);
+/// assert_eq!(stats.strongly_connected_components, 3);
+/// assert_eq!(stats.weakly_connected_components, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    /// The number of nodes.
+    pub node_count: usize,
+    /// The number of edges, self-loops included.
+    pub edge_count: usize,
+    /// The number of edges between distinct nodes, divided by the `n (n - 1)` possible
+    /// ones. It is 0 for graphs with fewer than two nodes.
+    pub density: f64,
+    /// The smallest degree, or 0 for the empty graph.
+    pub min_degree: usize,
+    /// The largest degree, or 0 for the empty graph.
+    pub max_degree: usize,
+    /// The mean degree, `2m / n`, or 0 for the empty graph.
+    pub mean_degree: f64,
+    /// The number of edges from a node to itself.
+    pub self_loop_count: usize,
+    /// The number of nodes without edges.
+    pub isolated_node_count: usize,
+    /// The number of strongly connected components.
+    pub strongly_connected_components: usize,
+    /// The number of weakly connected components.
+    pub weakly_connected_components: usize,
+}
+
+impl GraphStats {
+    /// Computes the statistics of a graph, in `O(n + m α(n))` time. Keys need not be
+    /// sequential.
+    pub fn compute<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Self {
+        let node_count = graph.node_count();
+        let edge_count = graph.edge_count();
+        let self_loop_count = graph
+            .get_edges()
+            .filter(|edge| edge.source() == edge.target())
+            .count();
+
+        let mut min_degree = usize::MAX;
+        let mut max_degree = 0;
+        let mut isolated_node_count = 0;
+        for key in graph.get_node_keys() {
+            let degree = graph.in_degree(key) + graph.out_degree(key);
+            min_degree = min_degree.min(degree);
+            max_degree = max_degree.max(degree);
+            if degree == 0 {
+                isolated_node_count += 1;
+            }
+        }
+
+        let n = node_count as f64;
+        let density = if node_count < 2 {
+            0.0
+        } else {
+            (edge_count - self_loop_count) as f64 / (n * (n - 1.0))
+        };
+        let mean_degree = if node_count == 0 {
+            0.0
+        } else {
+            2.0 * edge_count as f64 / n
+        };
+
+        GraphStats {
+            node_count,
+            edge_count,
+            density,
+            min_degree: if node_count == 0 { 0 } else { min_degree },
+            max_degree,
+            mean_degree,
+            self_loop_count,
+            isolated_node_count,
+            strongly_connected_components: scc_iterative(graph).len(),
+            weakly_connected_components: weakly_connected_components(graph).len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_small_graphs() {
+        let empty = GraphStats::compute(&build_graph::<Node, Edge>(vec![], vec![]));
+        assert_eq!(
+            (empty.node_count, empty.min_degree, empty.max_degree),
+            (0, 0, 0)
+        );
+        assert_eq!((empty.density, empty.mean_degree), (0.0, 0.0));
+        assert_eq!(empty.strongly_connected_components, 0);
+
+        let single = GraphStats::compute(&build_graph::<Node, Edge>(vec![7], vec![(7, 7)]));
+        assert_eq!((single.density, single.mean_degree), (0.0, 2.0));
+        assert_eq!((single.min_degree, single.isolated_node_count), (2, 0));
+        assert_eq!(single.weakly_connected_components, 1);
+    }
+
+    #[test]
+    fn test_complete_graph() {
+        let keys: Vec<u32> = vec![10, 20, 30, 40];
+        let edges = keys
+            .iter()
+            .flat_map(|&u| keys.iter().filter(move |&&v| v != u).map(move |&v| (u, v)))
+            .collect();
+        let stats = GraphStats::compute(&build_graph::<Node, Edge>(keys, edges));
+
+        assert_eq!(stats.edge_count, 12);
+        assert_eq!(stats.density, 1.0);
+        assert_eq!((stats.min_degree, stats.max_degree), (6, 6));
+        assert_eq!(stats.mean_degree, 6.0);
+        assert_eq!(stats.strongly_connected_components, 1);
+        assert_eq!(stats.weakly_connected_components, 1);
+    }
+}
@@ -0,0 +1,235 @@
+use ade_dominators::immediate_dominators;
+use ade_graph::implementations::{Edge, Node};
+use ade_graph::utils::build::build_graph;
+use ade_strongly_connected_components::scc_iterative;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashMap;
+
+/// Finds the strong articulation points of a directed graph.
+///
+/// A strong articulation point is a node whose removal breaks its strongly connected
+/// component into several ones: some pair of the remaining nodes of the component can no
+/// longer reach each other. Components of one or two nodes have none.
+///
+/// Following Italiano, Laura and Santaroni, the strong articulation points of a strongly
+/// connected component are the nodes, other than the root, that dominate some node in the
+/// flow graph of the component from a root, or in the flow graph of its reversal. Two roots
+/// are used, so that each covers the other. The computation runs
+/// [`immediate_dominators`] four times per component, in `O(m log n)` time overall. Keys
+/// need not be sequential.
+///
+/// # Returns
+///
+/// The keys of the strong articulation points, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_connectivity::strong_articulation_points;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Two cycles 0 → 1 → 2 → 0 and 2 → 3 → 2 sharing node 2; removing 3 leaves the first
+/// // cycle whole, removing any other node breaks it
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 2)],
+/// );
+/// assert_eq!(strong_articulation_points(&graph), vec![0, 1, 2]);
+/// ```
+pub fn strong_articulation_points<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<u32> {
+    let mut points = Vec::new();
+    for (members, edges) in nontrivial_components(graph) {
+        let k = members.len() as u32;
+        let reversed: Vec<(u32, u32)> = edges.iter().map(|&(u, v)| (v, u)).collect();
+        let forward = build_graph::<Node, Edge>((0..k).collect(), edges);
+        let backward = build_graph::<Node, Edge>((0..k).collect(), reversed);
+
+        let mut is_point = vec![false; k as usize];
+        for root in [0, 1] {
+            for dominators in [
+                immediate_dominators(&forward, root),
+                immediate_dominators(&backward, root),
+            ] {
+                for dominator in dominators.into_iter().flatten() {
+                    if dominator != root {
+                        is_point[dominator as usize] = true;
+                    }
+                }
+            }
+        }
+        points.extend((0..k as usize).filter(|&i| is_point[i]).map(|i| members[i]));
+    }
+    points.sort_unstable();
+    points
+}
+
+/// Finds the strong bridges of a directed graph.
+///
+/// A strong bridge is an edge whose removal breaks its strongly connected component into
+/// several ones. Edges between components and self-loops are never strong bridges.
+///
+/// Following Italiano, Laura and Santaroni, an edge of a strongly connected component is a
+/// strong bridge if every path from a root to its target goes through it, or every path from
+/// its source to the root does. Each edge is split in two by a new node, which dominates the
+/// target exactly when the edge does, and [`immediate_dominators`] runs twice per component,
+/// in `O(m log m)` time overall. Keys need not be sequential.
+///
+/// # Returns
+///
+/// The strong bridges as `(source, target)` pairs, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_connectivity::strong_bridges;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // A cycle 0 → 1 → 2 → 0 with the chord 0 → 2, and 3 outside of it
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1), (1, 2), (2, 0), (0, 2), (2, 3)],
+/// );
+/// assert_eq!(strong_bridges(&graph), vec![(0, 1), (1, 2), (2, 0)]);
+/// ```
+pub fn strong_bridges<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<(u32, u32)> {
+    let mut bridges = Vec::new();
+    for (members, edges) in nontrivial_components(graph) {
+        // Edge i becomes u → k + i → v
+        let k = members.len() as u32;
+        let nodes: Vec<u32> = (0..k + edges.len() as u32).collect();
+        let split = |forward: bool| {
+            let halves = edges.iter().enumerate().flat_map(|(i, &(u, v))| {
+                let middle = k + i as u32;
+                let (from, to) = if forward { (u, v) } else { (v, u) };
+                [(from, middle), (middle, to)]
+            });
+            build_graph::<Node, Edge>(nodes.clone(), halves.collect())
+        };
+
+        let to_target = immediate_dominators(&split(true), 0);
+        let from_source = immediate_dominators(&split(false), 0);
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            let middle = Some(k + i as u32);
+            if to_target[v as usize] == middle || from_source[u as usize] == middle {
+                bridges.push((members[u as usize], members[v as usize]));
+            }
+        }
+    }
+    bridges.sort_unstable();
+    bridges
+}
+
+// The sorted keys of a strongly connected component and its inner edges, without
+// self-loops, with nodes numbered by position among the keys
+type Component = (Vec<u32>, Vec<(u32, u32)>);
+
+// The strongly connected components with at least two nodes
+fn nontrivial_components<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<Component> {
+    let mut components: Vec<Vec<u32>> = scc_iterative(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .collect();
+    let mut position: HashMap<u32, (usize, u32)> = HashMap::new();
+    for (c, component) in components.iter_mut().enumerate() {
+        component.sort_unstable();
+        for (i, &key) in component.iter().enumerate() {
+            position.insert(key, (c, i as u32));
+        }
+    }
+
+    let mut edges = vec![Vec::new(); components.len()];
+    for edge in graph.get_edges() {
+        if let (Some(&(c, u)), Some(&(d, v))) =
+            (position.get(&edge.source()), position.get(&edge.target()))
+        {
+            if c == d && u != v {
+                edges[c].push((u, v));
+            }
+        }
+    }
+    components.into_iter().zip(edges).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::Graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    // Pairs of distinct nodes of `keys` that lie in the same strongly connected component
+    fn connected_pairs(keys: &[u32], edges: &[(u32, u32)]) -> usize {
+        let graph = build_graph::<Node, Edge>(keys.to_vec(), edges.to_vec());
+        scc_iterative(&graph)
+            .iter()
+            .map(|component| component.len() * (component.len() - 1))
+            .sum()
+    }
+
+    fn graph_edges(graph: &Graph<Node, Edge>) -> Vec<(u32, u32)> {
+        graph.get_edges().map(|edge| edge.key()).collect()
+    }
+
+    #[test]
+    fn test_cycles_and_non_sequential_keys() {
+        // A single cycle: every node and every edge is critical, except in a 2-cycle, where
+        // the node left alone is still strongly connected
+        let cycle = build_graph::<Node, Edge>(vec![5, 7, 9], vec![(5, 7), (7, 9), (9, 5)]);
+        assert_eq!(strong_articulation_points(&cycle), vec![5, 7, 9]);
+        assert_eq!(strong_bridges(&cycle), vec![(5, 7), (7, 9), (9, 5)]);
+
+        let two_cycle = build_graph::<Node, Edge>(vec![3, 8], vec![(3, 8), (8, 3), (8, 8)]);
+        assert!(strong_articulation_points(&two_cycle).is_empty());
+        assert_eq!(strong_bridges(&two_cycle), vec![(3, 8), (8, 3)]);
+    }
+
+    #[test]
+    fn test_acyclic_graph() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (0, 2)]);
+        assert!(strong_articulation_points(&graph).is_empty());
+        assert!(strong_bridges(&graph).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_removal(graph in arb_graph::<Node, Edge>(10, 30)) {
+            let keys: Vec<u32> = graph.get_node_keys().collect();
+            let edges = graph_edges(&graph);
+
+            // A node is critical if some pair of the other nodes is no longer connected
+            let points = strong_articulation_points(&graph);
+            for &key in &keys {
+                let component_pairs = |keys: &[u32], edges: &[(u32, u32)]| {
+                    let graph = build_graph::<Node, Edge>(keys.to_vec(), edges.to_vec());
+                    let mut pairs = 0;
+                    for component in scc_iterative(&graph) {
+                        let others = component.iter().filter(|&&k| k != key).count();
+                        pairs += others * others.saturating_sub(1);
+                    }
+                    pairs
+                };
+                let others: Vec<u32> = keys.iter().copied().filter(|&k| k != key).collect();
+                let kept: Vec<(u32, u32)> =
+                    edges.iter().copied().filter(|&(u, v)| u != key && v != key).collect();
+                let expected = component_pairs(&others, &kept) < component_pairs(&keys, &edges);
+                prop_assert_eq!(points.contains(&key), expected);
+            }
+
+            let pairs = connected_pairs(&keys, &edges);
+            let found = strong_bridges(&graph);
+            for &edge in &edges {
+                let kept: Vec<(u32, u32)> = edges.iter().copied().filter(|&e| e != edge).collect();
+                let expected = connected_pairs(&keys, &kept) < pairs;
+                prop_assert_eq!(found.contains(&edge), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashMap;
+
+// Marks a node not yet visited by the depth-first search
+const UNVISITED: u32 = u32::MAX;
+
+/// Finds the articulation points of a graph with edge directions ignored.
+///
+/// An articulation point, or cut vertex, is a node whose removal disconnects its connected
+/// component, as seen through [`UndirectedView`](ade_graph::implementations::UndirectedView):
+/// two nodes are neighbors if an edge joins them in either direction, and self-loops are
+/// ignored. The articulation points are the single points of failure of a network.
+///
+/// Uses Tarjan's low-link values along an iterative depth-first search, in `O(n + m)` time.
+/// Keys need not be sequential.
+///
+/// # Returns
+///
+/// The keys of the articulation points, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_connectivity::articulation_points;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Two triangles sharing node 2, and a tail 4 - 5
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4, 5],
+///     vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (5, 4)],
+/// );
+/// assert_eq!(articulation_points(&graph), vec![2, 4]);
+/// ```
+pub fn articulation_points<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<u32> {
+    let (keys, neighbors) = undirected_adjacency(graph);
+    let mut points: Vec<u32> = low_link_search(&neighbors)
+        .points
+        .into_iter()
+        .map(|index| keys[index])
+        .collect();
+    points.sort_unstable();
+    points
+}
+
+/// Finds the bridges of a graph with edge directions ignored.
+///
+/// A bridge is an edge whose removal disconnects its connected component, as seen through
+/// [`UndirectedView`](ade_graph::implementations::UndirectedView). Two opposite edges
+/// `(u, v)` and `(v, u)` join the same two neighbors, so they are never bridges: removing
+/// one leaves the other. Self-loops are ignored.
+///
+/// Uses Tarjan's low-link values along an iterative depth-first search, in `O(n + m)` time.
+/// Keys need not be sequential.
+///
+/// # Returns
+///
+/// The bridges as `(u, v)` pairs with `u < v`, in ascending order, whatever the direction
+/// of the edge in the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_connectivity::bridges;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // A triangle 0 - 1 - 2 and the tail 3 → 2, 3 → 4
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4],
+///     vec![(0, 1), (1, 2), (2, 0), (3, 2), (3, 4)],
+/// );
+/// assert_eq!(bridges(&graph), vec![(2, 3), (3, 4)]);
+/// ```
+pub fn bridges<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Vec<(u32, u32)> {
+    let (keys, neighbors) = undirected_adjacency(graph);
+    let mut bridges: Vec<(u32, u32)> = low_link_search(&neighbors)
+        .bridges
+        .into_iter()
+        .map(|(u, v)| {
+            let (u, v) = (keys[u], keys[v]);
+            (u.min(v), u.max(v))
+        })
+        .collect();
+    bridges.sort_unstable();
+    bridges
+}
+
+// The sorted keys of the graph and, by index into them, the neighbors of every node in
+// either direction, without self-loops; a neighbor joined by two opposite edges is listed
+// twice
+fn undirected_adjacency<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> (Vec<u32>, Vec<Vec<usize>>) {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let index: HashMap<u32, usize> = keys.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+
+    let mut neighbors = vec![Vec::new(); keys.len()];
+    for edge in graph.get_edges() {
+        let (source, target) = (index[&edge.source()], index[&edge.target()]);
+        if source != target {
+            neighbors[source].push(target);
+            neighbors[target].push(source);
+        }
+    }
+    for list in &mut neighbors {
+        list.sort_unstable();
+    }
+    (keys, neighbors)
+}
+
+struct LowLink {
+    points: Vec<usize>,
+    bridges: Vec<(usize, usize)>,
+}
+
+// Depth-first search computing, for every node, the smallest discovery time reachable
+// from its subtree through at most one back edge
+fn low_link_search(neighbors: &[Vec<usize>]) -> LowLink {
+    let n = neighbors.len();
+    let mut discovery = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut is_point = vec![false; n];
+    let mut bridges = Vec::new();
+    let mut time = 0;
+
+    // (node, parent, index of the next neighbor to visit, whether the edge to the parent
+    // was skipped); only one edge to the parent is the tree edge, a second one is a cycle
+    let mut stack: Vec<(usize, usize, usize, bool)> = Vec::new();
+    for root in 0..n {
+        if discovery[root] != UNVISITED {
+            continue;
+        }
+        discovery[root] = time;
+        low[root] = time;
+        time += 1;
+        stack.push((root, usize::MAX, 0, false));
+        let mut root_children = 0;
+
+        while let Some((node, parent, next, skipped)) = stack.last_mut() {
+            let (node, parent) = (*node, *parent);
+            if let Some(&neighbor) = neighbors[node].get(*next) {
+                *next += 1;
+                if neighbor == parent && !*skipped {
+                    *skipped = true;
+                } else if discovery[neighbor] == UNVISITED {
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    time += 1;
+                    stack.push((neighbor, node, 0, false));
+                } else {
+                    low[node] = low[node].min(discovery[neighbor]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if parent == usize::MAX {
+                continue;
+            }
+            low[parent] = low[parent].min(low[node]);
+            if low[node] > discovery[parent] {
+                bridges.push((parent, node));
+            }
+            if parent == root {
+                root_children += 1;
+            } else if low[node] >= discovery[parent] {
+                is_point[parent] = true;
+            }
+        }
+        if root_children > 1 {
+            is_point[root] = true;
+        }
+    }
+
+    LowLink {
+        points: (0..n).filter(|&i| is_point[i]).collect(),
+        bridges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Graph, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    // Connected components of the undirected view, by repeated traversal
+    fn component_count(keys: &[u32], edges: &[(u32, u32)]) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let mut count = 0;
+        for &start in keys {
+            if !seen.insert(start) {
+                continue;
+            }
+            count += 1;
+            let mut stack = vec![start];
+            while let Some(key) = stack.pop() {
+                for &(u, v) in edges {
+                    let other = if u == key {
+                        v
+                    } else if v == key {
+                        u
+                    } else {
+                        continue;
+                    };
+                    if seen.insert(other) {
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    fn graph_edges(graph: &Graph<Node, Edge>) -> Vec<(u32, u32)> {
+        graph.get_edges().map(|edge| edge.key()).collect()
+    }
+
+    #[test]
+    fn test_non_sequential_keys_and_opposite_edges() {
+        let graph = build_graph::<Node, Edge>(
+            vec![10, 20, 30, 40],
+            vec![(10, 20), (20, 10), (20, 30), (30, 40), (40, 40)],
+        );
+        assert_eq!(articulation_points(&graph), vec![20, 30]);
+        assert_eq!(bridges(&graph), vec![(20, 30), (30, 40)]);
+    }
+
+    #[test]
+    fn test_cycles_and_isolated_nodes() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0)]);
+        assert!(articulation_points(&graph).is_empty());
+        assert!(bridges(&graph).is_empty());
+
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+        assert!(articulation_points(&empty).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_removal(graph in arb_graph::<Node, Edge>(12, 20)) {
+            let keys: Vec<u32> = graph.get_node_keys().collect();
+            let edges = graph_edges(&graph);
+            let components = component_count(&keys, &edges);
+
+            let points = articulation_points(&graph);
+            for &key in &keys {
+                let others: Vec<u32> = keys.iter().copied().filter(|&k| k != key).collect();
+                let kept: Vec<(u32, u32)> =
+                    edges.iter().copied().filter(|&(u, v)| u != key && v != key).collect();
+                // Removing an isolated node removes one component
+                let isolated = !edges.iter().any(|&(u, v)| u != v && (u == key || v == key));
+                let expected = component_count(&others, &kept) + usize::from(isolated) > components;
+                prop_assert_eq!(points.contains(&key), expected);
+            }
+
+            let found = bridges(&graph);
+            for &(u, v) in &edges {
+                let kept: Vec<(u32, u32)> =
+                    edges.iter().copied().filter(|&edge| edge != (u, v)).collect();
+                let expected = u != v && component_count(&keys, &kept) > components;
+                prop_assert_eq!(found.contains(&(u.min(v), u.max(v))), expected);
+            }
+        }
+    }
+}
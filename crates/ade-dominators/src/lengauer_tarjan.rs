@@ -0,0 +1,297 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+// Marks a node not reached by the depth-first search, or a tree root without ancestor
+const NONE: u32 = u32::MAX;
+
+/// Computes the immediate dominators of the nodes reachable from `root`, with the
+/// Lengauer–Tarjan algorithm.
+///
+/// A node `d` dominates a node `v` if every path from `root` to `v` goes through `d`. The
+/// immediate dominator of `v` is its closest strict dominator, the one dominated by all the
+/// others; linking every node to its immediate dominator gives the dominator tree, rooted
+/// at `root`. In a control flow graph, for example, the blocks that dominate a block are
+/// those that always run before it.
+///
+/// The algorithm numbers the nodes along a depth-first search from `root`, computes their
+/// semidominators from the last to the first, and derives the immediate dominators from
+/// them, with a path-compressed forest. It runs in `O(m log n)` time and never recurses.
+///
+/// # Returns
+///
+/// A vector indexed by node key, holding the immediate dominator of every node. It is
+/// `None` for `root` and for the nodes that cannot be reached from `root`.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and
+/// panics if `root` is not a node of the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_dominators::immediate_dominators;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // An if-else, 0 → {1, 2} → 3, followed by a loop 3 → 4 → 3; 5 is unreachable
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4, 5],
+///     vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 3), (5, 4)],
+/// );
+///
+/// assert_eq!(
+///     immediate_dominators(&graph, 0),
+///     vec![None, Some(0), Some(0), Some(0), Some(3), None]
+/// );
+/// ```
+pub fn immediate_dominators<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    root: u32,
+) -> Vec<Option<u32>> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    if !graph.has_node(root) {
+        panic!("Node {} not found", root);
+    }
+
+    // From here on nodes are identified by their depth-first number
+    let (number, vertex, parent) = depth_first_numbering(graph, root);
+    let count = vertex.len();
+
+    let mut semi: Vec<u32> = (0..count as u32).collect();
+    let mut label: Vec<u32> = (0..count as u32).collect();
+    let mut ancestor = vec![NONE; count];
+    let mut idom = vec![0; count];
+    let mut bucket: Vec<Vec<u32>> = vec![Vec::new(); count];
+
+    for w in (1..count).rev() {
+        for predecessor in graph.get_predecessors_keys(vertex[w]) {
+            let v = number[predecessor as usize];
+            if v == NONE {
+                continue;
+            }
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            semi[w] = semi[w].min(semi[u as usize]);
+        }
+        bucket[semi[w] as usize].push(w as u32);
+
+        let p = parent[w];
+        ancestor[w] = p;
+        for v in std::mem::take(&mut bucket[p as usize]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v as usize] = if semi[u as usize] < semi[v as usize] {
+                u
+            } else {
+                p
+            };
+        }
+    }
+
+    // Nodes in increasing order, so the immediate dominator of `idom[w]` is already final
+    for w in 1..count {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w] as usize];
+        }
+    }
+
+    let mut dominators = vec![None; graph.node_count()];
+    for w in 1..count {
+        dominators[vertex[w] as usize] = Some(vertex[idom[w] as usize]);
+    }
+    dominators
+}
+
+// Numbers the nodes reachable from the root in depth-first preorder; returns the number of
+// every key, NONE if unreachable, the key of every number, and the number of every parent
+fn depth_first_numbering<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    root: u32,
+) -> (Vec<u32>, Vec<u32>, Vec<u32>) {
+    let mut number = vec![NONE; graph.node_count()];
+    let mut vertex = Vec::new();
+    let mut parent = Vec::new();
+
+    let mut stack = vec![(root, NONE)];
+    while let Some((key, from)) = stack.pop() {
+        if number[key as usize] != NONE {
+            continue;
+        }
+        number[key as usize] = vertex.len() as u32;
+        vertex.push(key);
+        parent.push(from);
+
+        let current = number[key as usize];
+        stack.extend(
+            graph
+                .get_successors_keys(key)
+                .filter(|&successor| number[successor as usize] == NONE)
+                .map(|successor| (successor, current)),
+        );
+    }
+    (number, vertex, parent)
+}
+
+// Returns the node with the smallest semidominator on the forest path above `v`, excluding
+// the root of its tree, and compresses that path
+fn eval(v: u32, ancestor: &mut [u32], label: &mut [u32], semi: &[u32]) -> u32 {
+    if ancestor[v as usize] == NONE {
+        return v;
+    }
+
+    let mut path = Vec::new();
+    let mut x = v;
+    while ancestor[ancestor[x as usize] as usize] != NONE {
+        path.push(x);
+        x = ancestor[x as usize];
+    }
+    for &y in path.iter().rev() {
+        let a = ancestor[y as usize] as usize;
+        if semi[label[a] as usize] < semi[label[y as usize] as usize] {
+            label[y as usize] = label[a];
+        }
+        ancestor[y as usize] = ancestor[a];
+    }
+    label[v as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    // Dominator sets by fixpoint iteration, then the strict dominator dominated by all the
+    // others, which is the one with the largest dominator set
+    fn naive_immediate_dominators<N: NodeTrait, E: EdgeTrait>(
+        graph: &impl GraphViewTrait<N, E>,
+        root: u32,
+    ) -> Vec<Option<u32>> {
+        let n = graph.node_count() as u32;
+        let (number, _, _) = depth_first_numbering(graph, root);
+        let reachable: BTreeSet<u32> = (0..n).filter(|&k| number[k as usize] != NONE).collect();
+
+        let mut dom: Vec<BTreeSet<u32>> = (0..n)
+            .map(|k| {
+                if k == root {
+                    BTreeSet::from([root])
+                } else {
+                    reachable.clone()
+                }
+            })
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &v in reachable.iter().filter(|&&v| v != root) {
+                let mut new = reachable.clone();
+                for p in graph
+                    .get_predecessors_keys(v)
+                    .filter(|p| reachable.contains(p))
+                {
+                    new = new.intersection(&dom[p as usize]).copied().collect();
+                }
+                new.insert(v);
+                if new != dom[v as usize] {
+                    dom[v as usize] = new;
+                    changed = true;
+                }
+            }
+        }
+
+        (0..n)
+            .map(|v| {
+                if v == root || !reachable.contains(&v) {
+                    return None;
+                }
+                dom[v as usize]
+                    .iter()
+                    .filter(|&&d| d != v)
+                    .max_by_key(|&&d| dom[d as usize].len())
+                    .copied()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_textbook_graph() {
+        // The example of Lengauer and Tarjan's paper, with R, A, B, ..., L numbered 0 to 12
+        let successors: [&[u32]; 13] = [
+            &[1, 2, 3],
+            &[4],
+            &[1, 4, 5],
+            &[6, 7],
+            &[12],
+            &[8],
+            &[9],
+            &[9, 10],
+            &[5, 11],
+            &[11],
+            &[9],
+            &[0, 9],
+            &[8],
+        ];
+        let edges = successors
+            .iter()
+            .enumerate()
+            .flat_map(|(source, targets)| targets.iter().map(move |&t| (source as u32, t)))
+            .collect();
+        let graph = build_graph::<Node, Edge>((0..13).collect(), edges);
+
+        let expected = vec![
+            None,
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(3),
+            Some(3),
+            Some(0),
+            Some(0),
+            Some(7),
+            Some(0),
+            Some(4),
+        ];
+        assert_eq!(immediate_dominators(&graph, 0), expected);
+    }
+
+    #[test]
+    fn test_other_root_and_unreachable_nodes() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3), (3, 1)]);
+        assert_eq!(
+            immediate_dominators(&graph, 2),
+            vec![None, Some(3), None, Some(2)]
+        );
+
+        let single = build_graph::<Node, Edge>(vec![0], vec![(0, 0)]);
+        assert_eq!(immediate_dominators(&single, 0), vec![None]);
+    }
+
+    #[test]
+    fn test_panics() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(immediate_dominators(&graph, 1), INVALID_KEY_SEQUENCE);
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_panics_with!(immediate_dominators(&graph, 2), "Node 2 not found");
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive_dominators(graph in arb_graph::<Node, Edge>(20, 50)) {
+            if !graph.is_empty() {
+                prop_assert_eq!(
+                    immediate_dominators(&graph, 0),
+                    naive_immediate_dominators(&graph, 0)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod lengauer_tarjan;
+
+pub use lengauer_tarjan::immediate_dominators;
@@ -1,4 +1,5 @@
 use ade_elementary_circuits::*;
+use ade_graph::implementations::{Edge, MatrixGraph, Node};
 use ade_graph::utils::build::build_graph;
 use ade_graph_generators::complete_graph_data;
 use ade_graph_generators::generate_random_graph_data;
@@ -12,7 +13,7 @@ fn benchmark_elementary_circuits_single(c: &mut Criterion) {
     // Pre-generate the complete graph outside the benchmark
     let n: usize = 4;
     let (nodes, edges) = complete_graph_data(n);
-    let graph = build_graph(nodes, edges);
+    let graph = build_graph::<Node, Edge>(nodes, edges);
 
     c.bench_function("elementary_circuits_n4", |b| {
         b.iter(|| {
@@ -22,9 +23,23 @@ fn benchmark_elementary_circuits_single(c: &mut Criterion) {
     });
 }
 
+fn benchmark_elementary_circuits_matrix(c: &mut Criterion) {
+    // Complete graphs are as dense as possible, the best case for an adjacency matrix
+    let n: usize = 4;
+    let (nodes, edges) = complete_graph_data(n);
+    let graph = MatrixGraph::from_view(&build_graph::<Node, Edge>(nodes, edges));
+
+    c.bench_function("elementary_circuits_n4_matrix", |b| {
+        b.iter(|| {
+            let circuits = elementary_circuits(black_box(&graph));
+            black_box(circuits.len())
+        })
+    });
+}
+
 fn benchmark_elementary_random_graph(c: &mut Criterion) {
     let (nodes, edges) = generate_random_graph_data(11, 44, 3);
-    let graph = build_graph(nodes, edges);
+    let graph = build_graph::<Node, Edge>(nodes, edges);
 
     c.bench_function("elementary_circuits_random_graph", |b| {
         b.iter(|| {
@@ -37,6 +52,7 @@ fn benchmark_elementary_random_graph(c: &mut Criterion) {
 criterion_group!(
     benches,
     benchmark_elementary_circuits_single,
+    benchmark_elementary_circuits_matrix,
     benchmark_elementary_random_graph,
 );
 criterion_main!(benches);
@@ -0,0 +1,150 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+use std::collections::HashMap;
+
+// The state of a node in the depth-first search
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    OnStack,
+    Done,
+}
+
+/// Tests whether a directed graph has a cycle, a self-loop included.
+///
+/// Unlike [`elementary_circuits`](crate::elementary_circuits), which enumerates every circuit,
+/// this stops at the first back edge of a depth-first search, in `O(n + m)` time. Keys need
+/// not be sequential.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::has_cycle;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let dag = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (1, 2)]);
+/// assert!(!has_cycle(&dag));
+///
+/// let cyclic = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 1)]);
+/// assert!(has_cycle(&cyclic));
+/// ```
+pub fn has_cycle<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> bool {
+    find_any_cycle(graph).is_some()
+}
+
+/// Finds one cycle of a directed graph, if it has any.
+///
+/// The search is the one of [`has_cycle`], and the cycle returned is the one closed by the
+/// first back edge found. Roots are tried in ascending key order, but which cycle is found
+/// also depends on the order in which the graph lists successors. Keys need not be
+/// sequential.
+///
+/// # Returns
+///
+/// The cycle as a closed [`Path`], starting and ending with the same node as the circuits of
+/// [`elementary_circuits`](crate::elementary_circuits), or `None` if the graph is acyclic.
+/// No node appears twice except for the first and last.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::find_any_cycle;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 5, 7], vec![(0, 5), (5, 7), (7, 5)]);
+/// assert_eq!(find_any_cycle(&graph).unwrap(), vec![5, 7, 5]);
+///
+/// let self_loop = build_graph::<Node, Edge>(vec![3], vec![(3, 3)]);
+/// assert_eq!(find_any_cycle(&self_loop).unwrap(), vec![3, 3]);
+///
+/// let dag = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+/// assert_eq!(find_any_cycle(&dag), None);
+/// ```
+pub fn find_any_cycle<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Option<Path> {
+    let mut roots: Vec<u32> = graph.get_node_keys().collect();
+    roots.sort_unstable();
+
+    let mut visits: HashMap<u32, Visit> = HashMap::with_capacity(roots.len());
+    let mut stack = Vec::new();
+    for root in roots {
+        if visits.contains_key(&root) {
+            continue;
+        }
+        visits.insert(root, Visit::OnStack);
+        stack.push((root, graph.get_successors_keys(root)));
+
+        while let Some((key, successors)) = stack.last_mut() {
+            let key = *key;
+            match successors.next() {
+                Some(successor) => match visits.get(&successor) {
+                    None => {
+                        visits.insert(successor, Visit::OnStack);
+                        stack.push((successor, graph.get_successors_keys(successor)));
+                    }
+                    Some(Visit::OnStack) => {
+                        // A back edge: the cycle is the stack from the successor up to key
+                        let start = stack
+                            .iter()
+                            .position(|&(k, _)| k == successor)
+                            .expect("Nodes on the stack are in the stack");
+                        let mut cycle: Vec<u32> = stack[start..].iter().map(|&(k, _)| k).collect();
+                        cycle.push(successor);
+                        return Some(Path::new(cycle));
+                    }
+                    Some(Visit::Done) => {}
+                },
+                None => {
+                    visits.insert(key, Visit::Done);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elementary_circuits;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_cycle_behind_finished_nodes() {
+        // 0 → 1 → 2 is finished before the cycle 3 → 4 → 5 → 3 is reached from 0 → 3
+        let graph = build_graph::<Node, Edge>(
+            (0..6).collect(),
+            vec![(0, 1), (1, 2), (0, 3), (3, 4), (4, 5), (5, 3), (3, 2)],
+        );
+        assert_eq!(find_any_cycle(&graph).unwrap(), vec![3, 4, 5, 3]);
+
+        // A diamond reaches 3 twice without a cycle
+        let diamond =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert!(!has_cycle(&diamond));
+        assert!(!has_cycle(&build_graph::<Node, Edge>(vec![], vec![])));
+    }
+
+    proptest! {
+        #[test]
+        fn test_agrees_with_elementary_circuits(graph in arb_graph::<Node, Edge>(12, 30)) {
+            let circuits = elementary_circuits(&graph);
+            match find_any_cycle(&graph) {
+                None => prop_assert!(circuits.is_empty()),
+                Some(cycle) => {
+                    prop_assert!(!circuits.is_empty());
+                    prop_assert!(!cycle.is_empty());
+                    prop_assert!(cycle.is_closed() && cycle.is_simple());
+                    for (source, target) in cycle.edges() {
+                        prop_assert!(graph.has_edge(source, target));
+                    }
+                }
+            }
+        }
+    }
+}
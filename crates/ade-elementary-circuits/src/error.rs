@@ -0,0 +1,24 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use std::fmt;
+
+/// Errors returned by [`try_elementary_circuits`](crate::try_elementary_circuits).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// The graph does not have sequential keys `0..node_count`: `key` is one of its keys
+    /// outside that range.
+    NonSequentialKeys { key: u32, node_count: usize },
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::NonSequentialKeys { key, node_count } => write!(
+                f,
+                "{}, found key {} in a graph of {} nodes",
+                INVALID_KEY_SEQUENCE, key, node_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
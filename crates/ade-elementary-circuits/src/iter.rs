@@ -0,0 +1,170 @@
+use crate::{resume_circuit, JohnsonState, Resume};
+use ade_common::{BudgetTracker, ExecutionBudget, NoProgress, INVALID_KEY_SEQUENCE};
+use ade_strongly_connected_components::scc_iterative;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+use std::marker::PhantomData;
+
+/// An iterator over the elementary circuits of a graph, created by
+/// [`elementary_circuits_iter`](crate::elementary_circuits_iter).
+///
+/// Circuits are found lazily: each call to `next` resumes Johnson's algorithm where the
+/// previous one stopped, and runs it only until the next circuit. Nothing is computed for
+/// the circuits that are never requested, so the iterator can be stopped early with
+/// adapters such as `take` or `find`. The strongly connected components of the graph are
+/// computed when the iterator is created.
+///
+/// Circuits are yielded in the same order as [`elementary_circuits`](crate::elementary_circuits)
+/// returns them.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::ElementaryCircuitsIter;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Complete graph on 8 nodes, with 16064 circuits
+/// let nodes: Vec<u32> = (0..8).collect();
+/// let edges = nodes
+///     .iter()
+///     .flat_map(|&i| nodes.iter().filter(move |&&j| i != j).map(move |&j| (i, j)))
+///     .collect();
+/// let graph = build_graph::<Node, Edge>(nodes.clone(), edges);
+///
+/// // Only the circuits before the first one of length 5 are computed
+/// let long = ElementaryCircuitsIter::new(&graph).find(|circuit| circuit.len() == 5);
+/// assert!(long.is_some_and(|circuit| circuit.is_closed()));
+/// ```
+pub struct ElementaryCircuitsIter<'a, N, E, G> {
+    graph: &'a G,
+    state: JohnsonState,
+    // Never exhausted, the CIRCUIT procedure requires one
+    tracker: BudgetTracker,
+    // Components not processed yet
+    components: std::vec::IntoIter<Vec<u32>>,
+    // Nodes of the current component not yet used as least vertex, in ascending order
+    nodes: Vec<u32>,
+    // Least vertex of the running search, if any
+    current: Option<u32>,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> ElementaryCircuitsIter<'a, N, E, G> {
+    /// Creates an iterator over the elementary circuits of `graph`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys starting from 0.
+    pub fn new(graph: &'a G) -> Self {
+        if !graph.has_sequential_keys() {
+            panic!("{}", INVALID_KEY_SEQUENCE);
+        }
+
+        Self {
+            graph,
+            state: JohnsonState::new(graph.node_count()),
+            tracker: ExecutionBudget::unlimited().start(),
+            components: scc_iterative(graph).into_iter(),
+            nodes: Vec::new(),
+            current: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Iterator
+    for ElementaryCircuitsIter<'_, N, E, G>
+{
+    type Item = Path;
+
+    fn next(&mut self) -> Option<Path> {
+        let graph = self.graph;
+        let state = &mut self.state;
+        loop {
+            if let Some(s) = self.current {
+                match resume_circuit(s, state, graph, &NoProgress, &mut self.tracker) {
+                    Ok(Resume::Found) => {
+                        let mut keys = Vec::with_capacity(state.stack.len() + 1);
+                        keys.extend_from_slice(&state.stack);
+                        keys.push(s);
+                        return Some(Path::new(keys));
+                    }
+                    Ok(Resume::Finished) => {
+                        state.finish(s);
+                        self.current = None;
+                    }
+                    Err(_) => unreachable!("NoProgress is never cancelled"),
+                }
+            }
+
+            // Start the search from the next least vertex, as find_component_circuits does
+            if let Some(s) = self.nodes.pop() {
+                state.start(s, graph);
+                state.enter(s, graph);
+                self.current = Some(s);
+                continue;
+            }
+
+            // Move to the next non-trivial component
+            let mut component = self.components.next()?;
+            if component.len() == 1 && !graph.has_edge(component[0], component[0]) {
+                continue;
+            }
+            component.sort_unstable();
+            for &key in &component {
+                state.active[key as usize] = true;
+            }
+            self.nodes = component;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elementary_circuits;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_iter_matches_collected() {
+        for i in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(12, 30, i);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let collected = elementary_circuits(&graph);
+            let iterated: Vec<Path> = ElementaryCircuitsIter::new(&graph).collect();
+            assert_eq!(iterated, collected);
+        }
+    }
+
+    #[test]
+    fn test_iter_stops_early() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 0), (2, 2), (3, 3)]);
+
+        let mut circuits = ElementaryCircuitsIter::new(&graph);
+        assert!(circuits.next().is_some());
+        assert_eq!(circuits.count(), 2);
+    }
+
+    #[test]
+    fn test_iter_long_cycle() {
+        let n: u32 = 100_000;
+        let nodes: Vec<u32> = (0..n).collect();
+        let edges: Vec<(u32, u32)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let circuits: Vec<Path> = ElementaryCircuitsIter::new(&graph).collect();
+        assert_eq!(circuits.len(), 1);
+        assert_eq!(circuits[0].len(), n as usize);
+    }
+
+    #[test]
+    fn test_iter_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(ElementaryCircuitsIter::new(&graph), INVALID_KEY_SEQUENCE);
+    }
+}
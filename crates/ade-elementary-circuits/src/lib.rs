@@ -1,9 +1,25 @@
+pub mod cycle;
+pub mod error;
+pub mod iter;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod sink;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod utils;
 
-use ade_common::INVALID_KEY_SEQUENCE;
-use ade_strongly_connected_components::scc_iterative;
-use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+pub use cycle::{find_any_cycle, has_cycle};
+pub use error::CircuitError;
+pub use iter::ElementaryCircuitsIter;
+#[cfg(feature = "parallel")]
+pub use parallel::{par_elementary_circuits, ParallelConfig};
+pub use sink::{CircuitCounter, CircuitSink};
+
+use ade_common::{
+    BudgetTracker, Budgeted, Cancelled, ExecutionBudget, NoProgress, PhaseProgress, Progress,
+    INVALID_KEY_SEQUENCE,
+};
+use ade_strongly_connected_components::{scc_iterative_with_progress, SccWorkspace};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
 use smallvec::SmallVec;
 
 /// Finds all elementary circuits in a directed graph.
@@ -16,6 +32,9 @@ use smallvec::SmallVec;
 /// that the first and last vertices are the same. For example, in the path `[0, 1, 2, 0]`,
 /// the circuit visits nodes 0 → 1 → 2 → 0.
 ///
+/// As in the original paper, the graph is first decomposed into strongly connected components,
+/// and each non-trivial component is searched independently.
+///
 /// # Type Parameters
 ///
 /// * `N` - The node type, which must implement [`NodeTrait`]
@@ -27,8 +46,8 @@ use smallvec::SmallVec;
 ///
 /// # Returns
 ///
-/// A vector of elementary circuits, where each circuit is represented as a vector of node keys
-/// (`u32`). Each circuit starts and ends with the same node. The order of circuits is not specified.
+/// A vector of elementary circuits, where each circuit is a closed [`Path`]: it starts and ends
+/// with the same node. The order of circuits is not specified.
 ///
 /// Returns an empty vector if the graph has no circuits.
 ///
@@ -38,7 +57,8 @@ use smallvec::SmallVec;
 /// If the graph has non-sequential keys, the function will panic with [`INVALID_KEY_SEQUENCE`].
 ///
 /// Use [`has_sequential_keys`](GraphViewTrait::has_sequential_keys) to check if a graph
-/// meets this requirement before calling this function.
+/// meets this requirement before calling this function, or call [`try_elementary_circuits`]
+/// to get an error instead of a panic.
 ///
 /// # Panics
 ///
@@ -59,9 +79,9 @@ use smallvec::SmallVec;
 ///
 /// let circuits = elementary_circuits(&graph);
 /// assert_eq!(circuits.len(), 1);
-/// // The circuit is [0, 1, 2, 0] (or a rotation of it)
-/// assert_eq!(circuits[0].len(), 4);
-/// assert_eq!(circuits[0][0], circuits[0][3]); // First and last are the same
+/// // The circuit is [0, 1, 2, 0] (or a rotation of it), it follows 3 edges
+/// assert_eq!(circuits[0].len(), 3);
+/// assert!(circuits[0].is_closed()); // First and last are the same
 /// ```
 ///
 /// ```
@@ -108,8 +128,8 @@ use smallvec::SmallVec;
 ///
 /// let circuits = elementary_circuits(&graph);
 /// assert_eq!(circuits.len(), 2);
-/// assert!(circuits.iter().any(|c| c.len() == 3)); // (0→1→0)
-/// assert!(circuits.iter().any(|c| c.len() == 4)); // (0→1→2→0)
+/// assert!(circuits.iter().any(|c| c.len() == 2)); // (0→1→0)
+/// assert!(circuits.iter().any(|c| c.len() == 3)); // (0→1→2→0)
 /// ```
 ///
 /// # Example: Non-sequential keys will panic
@@ -130,123 +150,689 @@ use smallvec::SmallVec;
 /// ```
 pub fn elementary_circuits<N: NodeTrait, E: EdgeTrait>(
     graph: &impl GraphViewTrait<N, E>,
-) -> Vec<Vec<u32>> {
-    // Panic if the graph does not have sequential keys
+) -> Vec<Path> {
+    match try_elementary_circuits(graph) {
+        Ok(circuits) => circuits,
+        Err(CircuitError::NonSequentialKeys { .. }) => panic!("{}", INVALID_KEY_SEQUENCE),
+    }
+}
+
+/// Finds all elementary circuits in a directed graph, returning an error instead of
+/// panicking when the graph does not have sequential keys.
+///
+/// Use this variant when the graph comes from user input. A graph with arbitrary keys can
+/// be renumbered first, for instance with `ade_graph::utils::renumber::renumber_bfs`, and
+/// the circuits mapped back to the original keys through the returned mapping.
+///
+/// # Errors
+///
+/// Returns [`CircuitError::NonSequentialKeys`] if the keys of the graph are not
+/// `0..node_count`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{try_elementary_circuits, CircuitError};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+/// assert_eq!(try_elementary_circuits(&graph).unwrap().len(), 1);
+///
+/// let graph = build_graph::<Node, Edge>(vec![1, 3], vec![(1, 3), (3, 1)]);
+/// assert_eq!(
+///     try_elementary_circuits(&graph),
+///     Err(CircuitError::NonSequentialKeys { key: 3, node_count: 2 })
+/// );
+/// ```
+pub fn try_elementary_circuits<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<Vec<Path>, CircuitError> {
     if !graph.has_sequential_keys() {
-        panic!("{}", INVALID_KEY_SEQUENCE);
+        let node_count = graph.node_count();
+        let key = graph
+            .get_node_keys()
+            .filter(|&key| key as usize >= node_count)
+            .min()
+            .expect("Non-sequential keys include one outside 0..n");
+        return Err(CircuitError::NonSequentialKeys { key, node_count });
+    }
+
+    let mut circuits: Vec<Path> = Vec::new();
+    elementary_circuits_into(graph, &mut circuits);
+    Ok(circuits)
+}
+
+/// Returns an iterator that finds the elementary circuits of a directed graph lazily, one
+/// at a time.
+///
+/// Use it instead of [`elementary_circuits`] to stop as soon as a circuit of interest is
+/// found, without paying for the others. See [`ElementaryCircuitsIter`].
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::elementary_circuits_iter;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2],
+///     vec![(0, 1), (1, 2), (2, 0), (1, 0)],
+/// );
+///
+/// let first_two: Vec<_> = elementary_circuits_iter(&graph).take(2).collect();
+/// assert_eq!(first_two.len(), 2);
+/// ```
+pub fn elementary_circuits_iter<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>>(
+    graph: &'a G,
+) -> ElementaryCircuitsIter<'a, N, E, G> {
+    ElementaryCircuitsIter::new(graph)
+}
+
+/// Finds all elementary circuits in a directed graph and passes them to a [`CircuitSink`].
+///
+/// This is the streaming counterpart of [`elementary_circuits`]: circuits are handed to
+/// `sink` as soon as they are found, and the algorithm itself never stores them. Use it
+/// when the number of circuits is too large to collect, for instance to count them with
+/// [`CircuitCounter`] or to write them to a file.
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{elementary_circuits_into, CircuitCounter};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2],
+///     vec![(0, 1), (1, 2), (2, 0), (1, 0)],
+/// );
+///
+/// let mut counter = CircuitCounter::default();
+/// elementary_circuits_into(&graph, &mut counter);
+/// assert_eq!(counter.count(), 2);
+/// ```
+pub fn elementary_circuits_into<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    sink: impl CircuitSink,
+) {
+    CircuitWorkspace::new().run_into(graph, sink);
+}
+
+/// Finds elementary circuits until `budget` is exhausted.
+///
+/// The number of elementary circuits can grow exponentially with the size of the graph, so
+/// enumerating all of them may take forever on adversarial inputs. This function stops
+/// Johnson's search once the wall-clock time or the number of node expansions allowed by
+/// `budget` is spent, and returns the circuits found so far with
+/// [`truncated`](Budgeted::truncated) set. Use it when a predictable latency matters more than
+/// a complete result. The initial decomposition into strongly connected components runs in
+/// linear time and is not counted against the budget.
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::elementary_circuits_with_budget;
+/// use ade_common::ExecutionBudget;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use std::time::Duration;
+///
+/// // A complete graph on 12 nodes has more than a billion circuits
+/// let nodes: Vec<u32> = (0..12).collect();
+/// let edges = nodes
+///     .iter()
+///     .flat_map(|&i| nodes.iter().filter(move |&&j| i != j).map(move |&j| (i, j)))
+///     .collect();
+/// let graph = build_graph::<Node, Edge>(nodes, edges);
+///
+/// let budget = ExecutionBudget::unlimited()
+///     .with_time_limit(Duration::from_millis(100))
+///     .with_max_expansions(10_000);
+/// let result = elementary_circuits_with_budget(&graph, &budget);
+///
+/// assert!(result.truncated);
+/// assert!(!result.value.is_empty());
+/// ```
+pub fn elementary_circuits_with_budget<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    budget: &ExecutionBudget,
+) -> Budgeted<Vec<Path>> {
+    CircuitWorkspace::new().run_into_with_budget(graph, Vec::new(), budget)
+}
+
+/// Name of the phase in which [`elementary_circuits_with_progress`] searches for circuits.
+///
+/// It follows the [`SCC_PHASE`](ade_strongly_connected_components::SCC_PHASE) of the
+/// initial decomposition into strongly connected components.
+pub const CIRCUITS_PHASE: &str = "elementary circuits";
+
+// Number of search steps between two cancellation checks
+const CHECK_INTERVAL: usize = 1024;
+
+/// Finds all elementary circuits, reporting progress and allowing cancellation.
+///
+/// Behaves like [`elementary_circuits_into`], and reports to `progress` two phases: the
+/// decomposition into strongly connected components, then [`CIRCUITS_PHASE`], whose fraction
+/// is the share of nodes already used as the starting node of Johnson's search. The number of
+/// circuits through each node varies wildly, so the fraction is only a rough estimate of the
+/// remaining time. The search polls [`is_cancelled`](Progress::is_cancelled) while it runs,
+/// and stops as soon as it returns `true`; the circuits already passed to `sink` are kept.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if `progress` was cancelled before the search completed.
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{elementary_circuits_with_progress, CircuitCounter, CIRCUITS_PHASE};
+/// use ade_graph::Path;
+/// use ade_common::{CancellationToken, Cancelled, Progress};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0), (1, 0)]);
+///
+/// let mut phases = Vec::new();
+/// let mut progress = |phase: &str, fraction: f64| {
+///     if fraction == 1.0 {
+///         phases.push(phase.to_string());
+///     }
+/// };
+/// let mut circuits: Vec<Path> = Vec::new();
+/// elementary_circuits_with_progress(&graph, &mut circuits, &mut progress)?;
+/// assert_eq!(circuits.len(), 2);
+/// assert_eq!(phases.last().map(String::as_str), Some(CIRCUITS_PHASE));
+///
+/// let token = CancellationToken::new();
+/// let mut progress = (|_: &str, _: f64| {}).with_cancellation(token.clone());
+/// token.cancel();
+/// let result = elementary_circuits_with_progress(&graph, CircuitCounter::default(), &mut progress);
+/// assert_eq!(result, Err(Cancelled));
+/// # Ok::<(), Cancelled>(())
+/// ```
+pub fn elementary_circuits_with_progress<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    sink: impl CircuitSink,
+    progress: &mut impl Progress,
+) -> Result<(), Cancelled> {
+    CircuitWorkspace::new().run_with_progress(graph, sink, progress)
+}
+
+/// Reusable state for enumerating elementary circuits.
+///
+/// Johnson's algorithm needs blocked sets, blocked maps, stacks and node masks sized to the
+/// graph, plus the buffers of the initial SCC decomposition. A workspace keeps all of them
+/// between calls, so that enumerating circuits on many graphs does not allocate them again.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{CircuitCounter, CircuitWorkspace};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let mut workspace = CircuitWorkspace::new();
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+/// assert_eq!(workspace.run(&graph).len(), 1);
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 0), (1, 2), (2, 1)]);
+/// let mut counter = CircuitCounter::default();
+/// workspace.run_into(&graph, &mut counter);
+/// assert_eq!(counter.count(), 2);
+/// ```
+pub struct CircuitWorkspace {
+    state: JohnsonState,
+    scc: SccWorkspace,
+}
+
+impl CircuitWorkspace {
+    /// Creates an empty workspace. Buffers grow on first use.
+    pub fn new() -> Self {
+        Self {
+            state: JohnsonState::new(0),
+            scc: SccWorkspace::new(),
+        }
     }
 
-    // Here the algorithm starts
-    let mut circuits: Vec<Vec<u32>> = Vec::new();
-    let mut stack: Vec<u32> = Vec::new();
+    /// Finds all elementary circuits, as [`elementary_circuits`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys starting from 0.
+    pub fn run<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> Vec<Path> {
+        let mut circuits: Vec<Path> = Vec::new();
+        self.run_into(graph, &mut circuits);
+        circuits
+    }
 
-    let n = match graph.get_nodes().count() {
-        0 => return circuits, // Return empty circuits if no nodes
-        len => (len - 1) as u32,
-    };
+    /// Finds all elementary circuits and passes them to `sink`, as [`elementary_circuits_into`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys starting from 0.
+    pub fn run_into<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+        sink: impl CircuitSink,
+    ) {
+        match self.run_with_progress(graph, sink, &mut NoProgress) {
+            Ok(()) => (),
+            Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+        }
+    }
 
-    let mut s: u32 = n; // Start with the maximum node and decrease it, so that graph nodes are always {0, 1, ..., s}
-    let size = (n + 1) as usize;
-    let mut blocked_set: Vec<bool> = vec![false; size];
-    let mut blocked_map: Vec<SmallVec<[u32; 4]>> = vec![SmallVec::new(); size];
+    /// Finds all elementary circuits and passes them to `sink`, reporting progress and
+    /// allowing cancellation, as [`elementary_circuits_with_progress`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cancelled`] if `progress` was cancelled before the search completed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys starting from 0.
+    pub fn run_with_progress<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+        mut sink: impl CircuitSink,
+        progress: &mut impl Progress,
+    ) -> Result<(), Cancelled> {
+        let mut tracker = ExecutionBudget::unlimited().start();
+        self.search(graph, &mut sink, progress, &mut tracker)
+    }
 
-    loop {
-        // Create the subgraph induced by {0, 1, ..., s}
-        let remaining_nodes: Vec<u32> = (0..=s).collect();
-        let subgraph = graph.filter(&remaining_nodes);
+    /// Finds elementary circuits and passes them to `sink` until `budget` is exhausted, as
+    /// [`elementary_circuits_with_budget`] does. The sink is handed back in the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys starting from 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_elementary_circuits::{CircuitCounter, CircuitWorkspace};
+    /// use ade_common::ExecutionBudget;
+    /// use ade_graph::implementations::{Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0), (1, 1)]);
+    /// let budget = ExecutionBudget::unlimited().with_max_expansions(100);
+    ///
+    /// let result = CircuitWorkspace::new().run_into_with_budget(&graph, CircuitCounter::default(), &budget);
+    /// assert_eq!(result.value.count(), 2);
+    /// assert!(!result.truncated);
+    /// ```
+    pub fn run_into_with_budget<N: NodeTrait, E: EdgeTrait, S: CircuitSink>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+        mut sink: S,
+        budget: &ExecutionBudget,
+    ) -> Budgeted<S> {
+        let mut tracker = budget.start();
+        match self.search(graph, &mut sink, &mut NoProgress, &mut tracker) {
+            Ok(()) => Budgeted {
+                value: sink,
+                truncated: tracker.is_exhausted(),
+            },
+            Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+        }
+    }
 
-        // Find the strongly connected components of the subgraph
-        let components = scc_iterative(&subgraph);
+    // Runs the whole search, stopping early if progress is cancelled or the budget of
+    // the tracker is exhausted
+    fn search<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+        sink: &mut impl CircuitSink,
+        progress: &mut impl Progress,
+        tracker: &mut BudgetTracker,
+    ) -> Result<(), Cancelled> {
+        // Panic if the graph does not have sequential keys
+        if !graph.has_sequential_keys() {
+            panic!("{}", INVALID_KEY_SEQUENCE);
+        }
 
-        // Choose the component containing the max vertex
-        let component = components.iter().find(|c| c.contains(&s)).unwrap();
+        let state = &mut self.state;
+        state.reset(graph.node_count());
 
-        // Create the subgraph induced by the component containing the max vertex
-        let adj = subgraph.filter(component);
+        let components = scc_iterative_with_progress(graph, &mut self.scc, progress)?;
+        let mut phase = PhaseProgress::new(CIRCUITS_PHASE, graph.node_count());
 
-        // Find the elementary circuits in the subgraph adj
-        if adj.get_nodes().next().is_some() {
-            for key in adj.get_node_keys() {
-                let k = key as usize;
-                blocked_set[k] = false;
-                blocked_map[k].clear();
+        // Circuits never leave a strongly connected component, so each component is processed
+        // independently, and nodes in trivial components are never visited again
+        for mut component in components {
+            if component.len() == 1 && !graph.has_edge(component[0], component[0]) {
+                phase.advance(1, progress)?;
+                continue;
             }
-
-            find_circuit(
-                s,
-                s,
-                &mut circuits,
-                &mut stack,
-                &mut blocked_set,
-                &mut blocked_map,
-                &adj,
-            );
-            if s == 0 {
-                break;
+            component.sort_unstable();
+            find_component_circuits(
+                &component, sink, state, graph, &mut phase, progress, tracker,
+            )?;
+            if tracker.is_exhausted() {
+                return Ok(());
             }
-            s -= 1;
-        } else {
-            s = 0;
         }
+        phase.finish(progress);
+        Ok(())
     }
+}
 
-    circuits
+impl Default for CircuitWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-fn find_circuit<N: NodeTrait, E: EdgeTrait>(
-    s: u32,
-    v: u32,
-    circuits: &mut Vec<Vec<u32>>,
-    stack: &mut Vec<u32>,
-    blocked_set: &mut [bool],
-    blocked_map: &mut [SmallVec<[u32; 4]>],
-    adj: &impl GraphViewTrait<N, E>,
-) -> bool {
-    let mut f: bool = false;
-    let v_us = v as usize;
-
-    stack.push(v);
-    blocked_set[v_us] = true;
-
-    for w_key in adj.get_successors_keys(v) {
-        if w_key == s {
-            let mut circuit = Vec::with_capacity(stack.len() + 1);
-            circuit.extend_from_slice(stack);
-            circuit.push(s);
-            circuits.push(circuit);
-            f = true;
-        } else if !blocked_set[w_key as usize]
-            && find_circuit(s, w_key, circuits, stack, blocked_set, blocked_map, adj)
-        {
-            f = true;
+// Runs Johnson's least vertex loop on a strongly connected component of the graph
+fn find_component_circuits<N: NodeTrait, E: EdgeTrait>(
+    nodes: &[u32],
+    sink: &mut impl CircuitSink,
+    state: &mut JohnsonState,
+    graph: &impl GraphViewTrait<N, E>,
+    phase: &mut PhaseProgress,
+    progress: &mut impl Progress,
+    tracker: &mut BudgetTracker,
+) -> Result<(), Cancelled> {
+    for &key in nodes {
+        state.active[key as usize] = true;
+    }
+
+    // Start with the maximum node and decrease it, so that active nodes are always
+    // the component nodes lower than or equal to s
+    for &s in nodes.iter().rev() {
+        state.start(s, graph);
+
+        // A cancelled search leaves the state dirty, the next run resets it
+        find_circuit(s, sink, state, graph, progress, tracker)?;
+        if tracker.is_exhausted() {
+            return Ok(());
+        }
+
+        state.finish(s);
+        phase.advance(1, progress)?;
+    }
+    Ok(())
+}
+
+// Struct containing mutable state of Johnson's algorithm
+struct JohnsonState {
+    // Nodes of the component being processed not yet used as the least vertex s
+    active: Vec<bool>,
+    // Component containing the current least vertex s, as a list and as a mask
+    component: Vec<u32>,
+    in_component: Vec<bool>,
+    // Scratch space for the reachability searches and for unblocking
+    reached: Vec<bool>,
+    visited: Vec<u32>,
+    pending: Vec<u32>,
+    // Current path, and for each node on it whether a circuit was found through it
+    // together with the start of its unvisited successors in `successors`
+    stack: Vec<u32>,
+    frames: Vec<(u32, bool, usize)>,
+    successors: Vec<u32>,
+    blocked_set: Vec<bool>,
+    blocked_map: Vec<SmallVec<[u32; 4]>>,
+    // Steps since cancellation was last checked, kept across resumed searches
+    steps: usize,
+}
+
+// Where a resumed CIRCUIT procedure stopped
+enum Resume {
+    // A circuit was found: the stack holds it, without the closing s
+    Found,
+    // The search from s is over, or the budget is exhausted
+    Finished,
+}
+
+impl JohnsonState {
+    fn new(n: usize) -> Self {
+        Self {
+            active: vec![false; n],
+            component: Vec::new(),
+            in_component: vec![false; n],
+            reached: vec![false; n],
+            visited: Vec::new(),
+            pending: Vec::new(),
+            stack: Vec::new(),
+            frames: Vec::new(),
+            successors: Vec::new(),
+            blocked_set: vec![false; n],
+            blocked_map: vec![SmallVec::new(); n],
+            steps: 0,
         }
     }
 
-    if f {
-        unblock(v, blocked_set, blocked_map);
-    } else {
-        for w_key in adj.get_successors_keys(v) {
-            let list = &mut blocked_map[w_key as usize];
-            if !list.contains(&v) {
-                list.push(v);
+    // Prepares the state for a graph with n nodes, keeping the allocated buffers
+    fn reset(&mut self, n: usize) {
+        for mask in [
+            &mut self.active,
+            &mut self.in_component,
+            &mut self.reached,
+            &mut self.blocked_set,
+        ] {
+            mask.clear();
+            mask.resize(n, false);
+        }
+        for list in &mut self.blocked_map {
+            list.clear();
+        }
+        self.blocked_map.resize(n, SmallVec::new());
+        self.component.clear();
+        self.visited.clear();
+        self.pending.clear();
+        self.stack.clear();
+        self.frames.clear();
+        self.successors.clear();
+        self.steps = 0;
+    }
+
+    // Prepares the search for circuits whose least vertex is s
+    fn start<N: NodeTrait, E: EdgeTrait>(&mut self, s: u32, graph: &impl GraphViewTrait<N, E>) {
+        // Restrict the search to the strongly connected component containing s
+        self.find_component(s, graph);
+
+        for &key in &self.component {
+            let k = key as usize;
+            self.blocked_set[k] = false;
+            self.blocked_map[k].clear();
+        }
+    }
+
+    // Cleans up after the search from s, which is never used again
+    fn finish(&mut self, s: u32) {
+        for &key in &self.component {
+            self.in_component[key as usize] = false;
+        }
+        self.active[s as usize] = false;
+    }
+
+    // Pushes v on the current path, blocks it, and schedules its successors in the component
+    fn enter<N: NodeTrait, E: EdgeTrait>(&mut self, v: u32, graph: &impl GraphViewTrait<N, E>) {
+        self.stack.push(v);
+        self.blocked_set[v as usize] = true;
+
+        let start = self.successors.len();
+        self.frames.push((v, false, start));
+        let in_component = &self.in_component;
+        self.successors.extend(
+            graph
+                .get_successors_keys(v)
+                .filter(|&w| in_component[w as usize]),
+        );
+        // Successors are popped from the end, reverse them to visit them in graph order
+        self.successors[start..].reverse();
+    }
+
+    // Unblocks u and, transitively, the nodes waiting on it in the blocked map
+    fn unblock(&mut self, u: u32) {
+        self.blocked_set[u as usize] = false;
+        self.pending.push(u);
+
+        while let Some(v) = self.pending.pop() {
+            while let Some(w) = self.blocked_map[v as usize].pop() {
+                let w_us = w as usize;
+                if self.blocked_set[w_us] {
+                    self.blocked_set[w_us] = false;
+                    self.pending.push(w);
+                }
             }
         }
     }
 
-    stack.pop();
-    f
+    // Collects the strongly connected component of s in the subgraph induced by the active
+    // nodes: the nodes that are reachable from s and from which s is reachable. Only the
+    // region reachable from s is visited, instead of running SCC on the whole subgraph.
+    fn find_component<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        s: u32,
+        graph: &impl GraphViewTrait<N, E>,
+    ) {
+        // Forward search: mark the active nodes reachable from s
+        self.reached[s as usize] = true;
+        self.visited.push(s);
+        self.pending.push(s);
+        while let Some(v) = self.pending.pop() {
+            for w in graph.get_successors_keys(v) {
+                let w_us = w as usize;
+                if self.active[w_us] && !self.reached[w_us] {
+                    self.reached[w_us] = true;
+                    self.visited.push(w);
+                    self.pending.push(w);
+                }
+            }
+        }
+
+        // Backward search among the reached nodes
+        self.component.clear();
+        self.in_component[s as usize] = true;
+        self.component.push(s);
+        self.pending.push(s);
+        while let Some(v) = self.pending.pop() {
+            for w in graph.get_predecessors_keys(v) {
+                let w_us = w as usize;
+                if self.reached[w_us] && !self.in_component[w_us] {
+                    self.in_component[w_us] = true;
+                    self.component.push(w);
+                    self.pending.push(w);
+                }
+            }
+        }
+
+        for &key in &self.visited {
+            self.reached[key as usize] = false;
+        }
+        self.visited.clear();
+    }
+}
+
+// Johnson's CIRCUIT procedure, with explicit stacks instead of recursion so that long
+// paths cannot overflow the call stack. Stops early if progress is cancelled or the
+// tracker denies an expansion.
+fn find_circuit<N: NodeTrait, E: EdgeTrait>(
+    s: u32,
+    sink: &mut impl CircuitSink,
+    state: &mut JohnsonState,
+    graph: &impl GraphViewTrait<N, E>,
+    progress: &impl Progress,
+    tracker: &mut BudgetTracker,
+) -> Result<(), Cancelled> {
+    if !tracker.expand() {
+        return Ok(());
+    }
+    state.enter(s, graph);
+
+    while let Resume::Found = resume_circuit(s, state, graph, progress, tracker)? {
+        // Close the circuit on the stack itself, so that no allocation is needed
+        state.stack.push(s);
+        sink.push(&state.stack);
+        state.stack.pop();
+    }
+    Ok(())
 }
 
-fn unblock(u: u32, blocked_set: &mut [bool], blocked_map: &mut [SmallVec<[u32; 4]>]) {
-    let u_us = u as usize;
-    blocked_set[u_us] = false;
+// Runs the CIRCUIT procedure from s, entered with state.enter, until it finds the next
+// circuit or ends. All of its state lives in JohnsonState, so it can be resumed after
+// each circuit.
+fn resume_circuit<N: NodeTrait, E: EdgeTrait>(
+    s: u32,
+    state: &mut JohnsonState,
+    graph: &impl GraphViewTrait<N, E>,
+    progress: &impl Progress,
+    tracker: &mut BudgetTracker,
+) -> Result<Resume, Cancelled> {
+    while let Some(&(v, f, start)) = state.frames.last() {
+        state.steps += 1;
+        if state.steps == CHECK_INTERVAL {
+            state.steps = 0;
+            if progress.is_cancelled() {
+                return Err(Cancelled);
+            }
+        }
+
+        // Visit the next successor of v, if any
+        if state.successors.len() > start {
+            let w = state.successors.pop().unwrap();
+            if w == s {
+                state.frames.last_mut().unwrap().1 = true;
+                return Ok(Resume::Found);
+            } else if !state.blocked_set[w as usize] {
+                if !tracker.expand() {
+                    return Ok(Resume::Finished);
+                }
+                state.enter(w, graph);
+            }
+            continue;
+        }
+
+        // All successors of v have been visited
+        state.frames.pop();
+        if f {
+            state.unblock(v);
+        } else {
+            for w_key in graph.get_successors_keys(v) {
+                if !state.in_component[w_key as usize] {
+                    continue;
+                }
+                let list = &mut state.blocked_map[w_key as usize];
+                if !list.contains(&v) {
+                    list.push(v);
+                }
+            }
+        }
+        state.stack.pop();
 
-    while let Some(w) = blocked_map[u_us].pop() {
-        let w_us = w as usize;
-        if blocked_set[w_us] {
-            unblock(w, blocked_set, blocked_map);
+        if f {
+            if let Some(parent) = state.frames.last_mut() {
+                parent.1 = true;
+            }
         }
     }
+    Ok(Resume::Finished)
 }
 
 #[cfg(test)]
@@ -255,12 +841,13 @@ mod tests {
     use crate::utils::circuits_equal;
     use crate::utils::number_circuits;
     use ade_common::{self, assert_panics_with};
-    use ade_graph::implementations::{Node, Edge};
+    use ade_graph::implementations::{Edge, Node};
     use ade_graph::utils::build::build_graph;
     use ade_graph_generators::complete_graph_data;
     use ade_graph_generators::generate_random_graph_data;
     use graph_cycles::Cycles;
     use petgraph::graph::Graph as PetGraph;
+    use proptest::prelude::*;
     use rand::Rng;
     use std::collections::HashSet;
 
@@ -282,11 +869,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_elementary_circuits() {
+        let graph = build_graph::<Node, Edge>(vec![1, 3, 5], vec![(1, 3), (3, 5), (5, 1)]);
+        let error = try_elementary_circuits(&graph).unwrap_err();
+        assert_eq!(
+            error,
+            CircuitError::NonSequentialKeys {
+                key: 3,
+                node_count: 3
+            }
+        );
+        assert!(error
+            .to_string()
+            .starts_with(ade_common::INVALID_KEY_SEQUENCE));
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 1)]);
+        let circuits = try_elementary_circuits(&graph).unwrap();
+        assert!(circuits_equal(&circuits, &[vec![1, 2, 1]]));
+    }
+
     #[test]
     fn test_elementary_circuits_3() {
         let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
         let circuits = elementary_circuits(&graph);
-        let expected = vec![];
+        let expected: Vec<Vec<u32>> = vec![];
 
         assert!(circuits_equal(&circuits, &expected));
     }
@@ -295,7 +902,7 @@ mod tests {
     fn test_elementary_circuits_4() {
         let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![]);
         let circuits = elementary_circuits(&graph);
-        let expected = vec![];
+        let expected: Vec<Vec<u32>> = vec![];
 
         assert!(circuits_equal(&circuits, &expected));
     }
@@ -304,7 +911,7 @@ mod tests {
     fn test_elementary_circuits_5() {
         let graph = build_graph::<Node, Edge>(vec![], vec![]);
         let circuits = elementary_circuits(&graph);
-        let expected = vec![];
+        let expected: Vec<Vec<u32>> = vec![];
 
         assert!(circuits_equal(&circuits, &expected));
     }
@@ -372,6 +979,180 @@ mod tests {
         assert_eq!(circuits.len(), number_circuits(n))
     }
 
+    #[test]
+    fn test_elementary_circuits_long_cycle() {
+        let n: u32 = 200_000;
+        let nodes: Vec<u32> = (0..n).collect();
+        let edges: Vec<(u32, u32)> = (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let mut counter = CircuitCounter::default();
+        elementary_circuits_into(&graph, &mut counter);
+        assert_eq!(counter.count(), 1);
+    }
+
+    #[test]
+    fn test_elementary_circuits_cancelled_while_running() {
+        use ade_common::{CancellationToken, Cancelled};
+
+        // Cancels the search once enough circuits have been found
+        struct CancelAfter {
+            count: usize,
+            limit: usize,
+            token: CancellationToken,
+        }
+
+        impl CircuitSink for CancelAfter {
+            fn push(&mut self, _circuit: &[u32]) {
+                self.count += 1;
+                if self.count == self.limit {
+                    self.token.cancel();
+                }
+            }
+        }
+
+        let n: usize = 8;
+        let (nodes, edges) = complete_graph_data(n);
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let mut workspace = CircuitWorkspace::new();
+
+        let token = CancellationToken::new();
+        let mut sink = CancelAfter {
+            count: 0,
+            limit: 100,
+            token: token.clone(),
+        };
+        let mut progress = token.clone();
+        let result = workspace.run_with_progress(&graph, &mut sink, &mut progress);
+
+        assert_eq!(result, Err(Cancelled));
+        assert!(sink.count >= 100 && sink.count < number_circuits(n));
+
+        // The workspace can be reused after a cancelled search
+        assert_eq!(workspace.run(&graph).len(), number_circuits(n));
+    }
+
+    #[test]
+    fn test_elementary_circuits_with_budget() {
+        use ade_common::ExecutionBudget;
+        use std::collections::HashSet;
+
+        let n: usize = 7;
+        let (nodes, edges) = complete_graph_data(n);
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let all: HashSet<Path> = elementary_circuits(&graph).into_iter().collect();
+
+        let unlimited = elementary_circuits_with_budget(&graph, &ExecutionBudget::unlimited());
+        assert!(!unlimited.truncated);
+        assert_eq!(unlimited.value.len(), number_circuits(n));
+
+        let budget = ExecutionBudget::unlimited().with_max_expansions(500);
+        let partial = elementary_circuits_with_budget(&graph, &budget);
+        assert!(partial.truncated);
+        assert!(!partial.value.is_empty() && partial.value.len() < all.len());
+
+        // Partial results are genuine circuits, each found once
+        let found: HashSet<Path> = partial.value.iter().cloned().collect();
+        assert_eq!(found.len(), partial.value.len());
+        assert!(found.is_subset(&all));
+    }
+
+    #[test]
+    fn test_elementary_circuits_budget_exactly_enough() {
+        use ade_common::ExecutionBudget;
+
+        // Starting from node 9, the search expands the whole cycle, then each lower start
+        // node is expanded alone: 10 + 9 expansions in total
+        let nodes: Vec<u32> = (0..10).collect();
+        let edges = (0..10).map(|i| (i, (i + 1) % 10)).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let enough = ExecutionBudget::unlimited().with_max_expansions(19);
+        let result = elementary_circuits_with_budget(&graph, &enough);
+        assert!(!result.truncated);
+        assert_eq!(result.value.len(), 1);
+
+        let short = ExecutionBudget::unlimited().with_max_expansions(9);
+        let result = elementary_circuits_with_budget(&graph, &short);
+        assert!(result.truncated);
+        assert!(result.value.is_empty());
+    }
+
+    #[test]
+    fn test_elementary_circuits_with_progress_reports_phases() {
+        use ade_strongly_connected_components::SCC_PHASE;
+
+        // A chain of 100 two-node cycles
+        let nodes: Vec<u32> = (0..200).collect();
+        let edges = (0..100)
+            .flat_map(|k| {
+                [
+                    (2 * k, 2 * k + 1),
+                    (2 * k + 1, 2 * k),
+                    (2 * k + 1, 2 * k + 2),
+                ]
+            })
+            .filter(|&(_, target)| target < 200)
+            .collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let mut reports: Vec<(String, f64)> = Vec::new();
+        let mut progress = |phase: &str, fraction: f64| reports.push((phase.to_string(), fraction));
+        let mut circuits: Vec<Vec<u32>> = Vec::new();
+        elementary_circuits_with_progress(&graph, &mut circuits, &mut progress).unwrap();
+
+        assert_eq!(circuits.len(), 100);
+        assert_eq!(reports.first().map(|r| r.0.as_str()), Some(SCC_PHASE));
+        assert_eq!(reports.last(), Some(&(CIRCUITS_PHASE.to_string(), 1.0)));
+
+        let fractions: Vec<f64> = reports
+            .iter()
+            .filter(|r| r.0 == CIRCUITS_PHASE)
+            .map(|r| r.1)
+            .collect();
+        assert!(fractions.len() > 1);
+        assert!(fractions.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_circuit_workspace_reuse() {
+        let mut workspace = CircuitWorkspace::new();
+
+        for n in [6, 3, 5] {
+            let (nodes, edges) = complete_graph_data(n);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            assert_eq!(workspace.run(&graph).len(), number_circuits(n));
+        }
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 1)]);
+        assert!(circuits_equal(&workspace.run(&graph), &[vec![1, 2, 1]]));
+    }
+
+    #[test]
+    fn test_elementary_circuits_into_counter() {
+        let n: usize = 6;
+        let (nodes, edges) = complete_graph_data(n);
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let mut counter = CircuitCounter::default();
+        elementary_circuits_into(&graph, &mut counter);
+
+        assert_eq!(counter.count(), number_circuits(n));
+    }
+
+    #[test]
+    fn test_elementary_circuits_into_matches_collected() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 2), (3, 3)],
+        );
+
+        let mut streamed: Vec<Vec<u32>> = Vec::new();
+        elementary_circuits_into(&graph, &mut streamed);
+
+        assert!(circuits_equal(&streamed, &elementary_circuits(&graph)));
+    }
+
     // #[test]
     // fn test_elementary_circuits_random_graph() {
     //     let (nodes, edges) = generate_random_graph_data(25, 125, 3);
@@ -421,8 +1202,7 @@ mod tests {
             let graph = build_graph::<Node, Edge>(nodes.clone(), unique_edges.clone());
             let circuits = elementary_circuits(&graph);
 
-            let g = PetGraph::<(), ()>::from_edges(unique_edges.clone());
-            let cycles = g.cycles();
+            let cycles = PetGraph::from(&graph).cycles();
 
             assert_eq!(
                 circuits.len(),
@@ -434,4 +1214,16 @@ mod tests {
             );
         }
     }
+
+    proptest! {
+        #[test]
+        fn test_elementary_circuits_match_reference(
+            graph in ade_graph::utils::strategies::arb_graph::<Node, Edge>(8, 20)
+        ) {
+            let circuits = elementary_circuits(&graph);
+            let expected = ade_reference::elementary_circuits(&graph);
+            prop_assert_eq!(circuits.len(), expected.len());
+            prop_assert!(circuits_equal(&circuits, &expected));
+        }
+    }
 }
@@ -0,0 +1,169 @@
+use crate::{find_circuit, JohnsonState};
+use ade_common::{Cancelled, ExecutionBudget, NoProgress, INVALID_KEY_SEQUENCE};
+use ade_strongly_connected_components::scc_iterative;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+use rayon::prelude::*;
+use std::sync::Mutex;
+
+/// Options for [`par_elementary_circuits`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParallelConfig {
+    /// Whether to return the circuits in the order of
+    /// [`elementary_circuits`](crate::elementary_circuits), whatever the number of threads.
+    ///
+    /// When `false`, the circuits of each start vertex are appended as soon as its search
+    /// completes, so their order changes from run to run. Ordering them keeps the circuits
+    /// of every start vertex until all searches end, which is convenient for tests and for
+    /// reproducible output.
+    pub deterministic: bool,
+}
+
+/// Finds all elementary circuits of a directed graph on the rayon thread pool.
+///
+/// Johnson's algorithm looks for the circuits whose least vertex is `s` in the component of
+/// `s` restricted to the vertices not greater than `s`, so the searches from different start
+/// vertices are independent. After the decomposition into strongly connected components,
+/// every start vertex of every nontrivial component becomes a task, and each thread of the
+/// pool runs its tasks with its own Johnson state. The circuits found are those of
+/// [`elementary_circuits`](crate::elementary_circuits), in the order chosen by
+/// [`ParallelConfig::deterministic`].
+///
+/// Runs on the current rayon pool: use `rayon::ThreadPool::install` to choose the number of
+/// threads. Requires the `parallel` feature.
+///
+/// # Panics
+///
+/// Panics if the graph does not have sequential keys starting from 0.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{elementary_circuits, par_elementary_circuits, ParallelConfig};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1), (1, 2), (2, 0), (1, 0), (2, 3), (3, 3)],
+/// );
+///
+/// let config = ParallelConfig { deterministic: true };
+/// assert_eq!(par_elementary_circuits(&graph, &config), elementary_circuits(&graph));
+/// ```
+pub fn par_elementary_circuits<N, E>(
+    graph: &(impl GraphViewTrait<N, E> + Sync),
+    config: &ParallelConfig,
+) -> Vec<Path>
+where
+    N: NodeTrait,
+    E: EdgeTrait,
+{
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let n = graph.node_count();
+
+    // Tasks in the order of the sequential search: components in turn, and the start
+    // vertices of each from the largest down
+    let components: Vec<Vec<u32>> = scc_iterative(graph)
+        .into_iter()
+        .filter(|component| component.len() > 1 || graph.has_edge(component[0], component[0]))
+        .map(|mut component| {
+            component.sort_unstable();
+            component
+        })
+        .collect();
+    let tasks: Vec<(&[u32], usize)> = components
+        .iter()
+        .flat_map(|component| (0..component.len()).rev().map(move |i| (&component[..], i)))
+        .collect();
+
+    if config.deterministic {
+        let circuits: Vec<Vec<Path>> = tasks
+            .par_iter()
+            .map_init(
+                || JohnsonState::new(n),
+                |state, &(component, i)| {
+                    let mut circuits = Vec::new();
+                    search_from(component, i, graph, state, &mut circuits);
+                    circuits
+                },
+            )
+            .collect();
+        circuits.concat()
+    } else {
+        let found = Mutex::new(Vec::new());
+        tasks.par_iter().for_each_init(
+            || (JohnsonState::new(n), Vec::new()),
+            |(state, circuits), &(component, i)| {
+                search_from(component, i, graph, state, circuits);
+                found
+                    .lock()
+                    .expect("No thread panics while holding the lock")
+                    .append(circuits);
+            },
+        );
+        found
+            .into_inner()
+            .expect("No thread panics while holding the lock")
+    }
+}
+
+// Runs Johnson's search from the start vertex component[i], with the active vertices
+// component[..=i], and leaves the state clean for the next task
+fn search_from<N: NodeTrait, E: EdgeTrait>(
+    component: &[u32],
+    i: usize,
+    graph: &impl GraphViewTrait<N, E>,
+    state: &mut JohnsonState,
+    circuits: &mut Vec<Path>,
+) {
+    for &key in &component[..=i] {
+        state.active[key as usize] = true;
+    }
+    let s = component[i];
+    state.start(s, graph);
+    let mut tracker = ExecutionBudget::unlimited().start();
+    match find_circuit(s, circuits, state, graph, &NoProgress, &mut tracker) {
+        Ok(()) => (),
+        Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+    }
+    state.finish(s);
+    for &key in &component[..i] {
+        state.active[key as usize] = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elementary_circuits;
+    use crate::utils::circuits_equal;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_panics() {
+        let graph = build_graph::<Node, Edge>(vec![1, 3], vec![(1, 3), (3, 1)]);
+        assert_panics_with!(
+            par_elementary_circuits(&graph, &ParallelConfig::default()),
+            INVALID_KEY_SEQUENCE
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_agrees_with_sequential_search(
+            graph in ade_graph::utils::strategies::arb_graph::<Node, Edge>(9, 24)
+        ) {
+            let expected = elementary_circuits(&graph);
+            let ordered = par_elementary_circuits(&graph, &ParallelConfig { deterministic: true });
+            prop_assert_eq!(&ordered, &expected);
+
+            let unordered = par_elementary_circuits(&graph, &ParallelConfig::default());
+            prop_assert!(circuits_equal(&unordered, &expected));
+        }
+    }
+}
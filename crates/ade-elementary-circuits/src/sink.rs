@@ -0,0 +1,86 @@
+use ade_traits::Path;
+
+/// A destination for the circuits found by [`elementary_circuits_into`](crate::elementary_circuits_into).
+///
+/// Each circuit is passed as a borrowed slice of node keys that starts and ends with the
+/// same node. The slice is only valid for the duration of the call, so implementations
+/// that need to keep it must copy it.
+///
+/// Implement this trait to stream circuits somewhere other than memory, for example to a
+/// file, or to aggregate them without storing them at all.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{elementary_circuits_into, CircuitSink};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Keep track of the longest circuit only
+/// struct Longest(usize);
+///
+/// impl CircuitSink for Longest {
+///     fn push(&mut self, circuit: &[u32]) {
+///         self.0 = self.0.max(circuit.len() - 1);
+///     }
+/// }
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 0), (1, 2), (2, 0)]);
+/// let mut longest = Longest(0);
+/// elementary_circuits_into(&graph, &mut longest);
+/// assert_eq!(longest.0, 3);
+/// ```
+pub trait CircuitSink {
+    /// Receives one elementary circuit.
+    fn push(&mut self, circuit: &[u32]);
+}
+
+impl CircuitSink for Vec<Vec<u32>> {
+    fn push(&mut self, circuit: &[u32]) {
+        Vec::push(self, circuit.to_vec());
+    }
+}
+
+impl CircuitSink for Vec<Path> {
+    fn push(&mut self, circuit: &[u32]) {
+        Vec::push(self, Path::from(circuit));
+    }
+}
+
+impl<S: CircuitSink + ?Sized> CircuitSink for &mut S {
+    fn push(&mut self, circuit: &[u32]) {
+        (**self).push(circuit);
+    }
+}
+
+/// A [`CircuitSink`] that only counts circuits.
+///
+/// # Examples
+///
+/// ```
+/// use ade_elementary_circuits::{elementary_circuits_into, CircuitCounter};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0), (1, 1)]);
+/// let mut counter = CircuitCounter::default();
+/// elementary_circuits_into(&graph, &mut counter);
+/// assert_eq!(counter.count(), 2);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitCounter {
+    count: usize,
+}
+
+impl CircuitCounter {
+    /// Returns the number of circuits received so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl CircuitSink for CircuitCounter {
+    fn push(&mut self, _circuit: &[u32]) {
+        self.count += 1;
+    }
+}
@@ -1,15 +1,15 @@
 // Helper function to compare two sets of circuits
-pub fn circuits_equal(circuits1: &Vec<Vec<u32>>, circuits2: &Vec<Vec<u32>>) -> bool {
+pub fn circuits_equal<A: AsRef<[u32]>, B: AsRef<[u32]>>(circuits1: &[A], circuits2: &[B]) -> bool {
     let norm1 = normalize_circuits(circuits1);
     let norm2 = normalize_circuits(circuits2);
     norm1 == norm2
 }
 
 // Function to normalize a set of circuits
-fn normalize_circuits(circuits: &Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+fn normalize_circuits<C: AsRef<[u32]>>(circuits: &[C]) -> Vec<Vec<u32>> {
     let mut normalized_circuits: Vec<Vec<u32>> = circuits
         .iter()
-        .map(|circuit| normalize_circuit(circuit))
+        .map(|circuit| normalize_circuit(circuit.as_ref()))
         .collect();
 
     // Sort circuits for deterministic comparison
@@ -0,0 +1,355 @@
+use ade_graph::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphMutTrait, GraphViewTrait, NodeTrait};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Finds a small set of edges whose reversal makes a graph acyclic, with the greedy
+/// heuristic of Eades, Lin and Smyth.
+///
+/// The heuristic builds a sequence of the nodes by repeatedly removing sinks, which go to
+/// the end of the sequence, then sources, which go to the start, and otherwise the node
+/// with the largest difference between its out-degree and its in-degree, which goes to the
+/// start. The edges pointing backwards in the sequence form the feedback arc set. It runs
+/// in `O((n + m) log n)` time and never returns more than half of the edges; on connected
+/// graphs without 2-cycles it returns at most `m / 2 - n / 6` of the `m` edges. Finding a
+/// minimum feedback arc set is NP-hard.
+///
+/// Self-loops are never returned, since reversing them does not break them. Ties are
+/// broken by the smallest key, so the result is deterministic. Keys need not be
+/// sequential.
+///
+/// # Returns
+///
+/// The feedback arc set, as `(source, target)` pairs in ascending order. It is empty if the
+/// graph is acyclic, apart from self-loops.
+///
+/// # Examples
+///
+/// ```
+/// use ade_feedback_arc_set::feedback_arc_set;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Two cycles, 0 → 1 → 2 → 0 and 2 → 3 → 2
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 2)],
+/// );
+/// assert_eq!(feedback_arc_set(&graph), vec![(2, 0), (3, 2)]);
+/// ```
+pub fn feedback_arc_set<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<(u32, u32)> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let index: HashMap<u32, usize> = keys.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+
+    let mut successors = vec![Vec::new(); keys.len()];
+    let mut predecessors = vec![Vec::new(); keys.len()];
+    for edge in graph
+        .get_edges()
+        .filter(|edge| edge.source() != edge.target())
+    {
+        let (source, target) = (index[&edge.source()], index[&edge.target()]);
+        successors[source].push(target);
+        predecessors[target].push(source);
+    }
+
+    let position = greedy_sequence(&successors, &predecessors);
+    let mut edges: Vec<(u32, u32)> = successors
+        .iter()
+        .enumerate()
+        .flat_map(|(source, targets)| targets.iter().map(move |&target| (source, target)))
+        .filter(|&(source, target)| position[source] > position[target])
+        .map(|(source, target)| (keys[source], keys[target]))
+        .collect();
+    edges.sort_unstable();
+    edges
+}
+
+/// Makes a graph acyclic by reversing the edges of its [`feedback_arc_set`] and removing
+/// its self-loops.
+///
+/// Reversed edges are rebuilt with [`EdgeTrait::with_endpoints`] and nodes with
+/// [`NodeTrait::with_key`], so both keep their data. If the reversal of an edge is already
+/// an edge of the graph, the two merge into the existing one, since a graph has at most one
+/// edge per pair of nodes.
+///
+/// # Returns
+///
+/// The acyclic graph and the reversed edges, with their original `(source, target)`
+/// orientation, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_feedback_arc_set::make_acyclic;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0), (2, 2)]);
+/// let (acyclic, reversed) = make_acyclic(&graph);
+///
+/// assert_eq!(reversed, vec![(2, 0)]);
+/// assert!(acyclic.has_edge(0, 2));
+/// assert!(!acyclic.has_edge(2, 0));
+/// assert!(!acyclic.has_edge(2, 2));
+/// ```
+pub fn make_acyclic<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> (Graph<N, E>, Vec<(u32, u32)>) {
+    let reversed = feedback_arc_set(graph);
+    let to_reverse: HashSet<(u32, u32)> = reversed.iter().copied().collect();
+
+    let nodes = graph
+        .get_nodes()
+        .map(|node| node.with_key(node.key()))
+        .collect();
+    let mut edges: Vec<E> = Vec::new();
+    let mut flipped: Vec<E> = Vec::new();
+    for edge in graph
+        .get_edges()
+        .filter(|edge| edge.source() != edge.target())
+    {
+        if to_reverse.contains(&edge.key()) {
+            flipped.push(edge.with_endpoints(edge.target(), edge.source()));
+        } else {
+            edges.push(edge.clone());
+        }
+    }
+    // Edges already in that direction take precedence over reversed ones
+    let existing: HashSet<(u32, u32)> = edges.iter().map(|edge| edge.key()).collect();
+    edges.extend(
+        flipped
+            .into_iter()
+            .filter(|edge| !existing.contains(&edge.key())),
+    );
+
+    (Graph::new(nodes, edges), reversed)
+}
+
+/// Makes a graph acyclic in place, as [`make_acyclic`] does, for any implementation of
+/// [`GraphMutTrait`].
+///
+/// The edges of the [`feedback_arc_set`] are removed and added back reversed with
+/// [`EdgeTrait::with_endpoints`], unless the reversal is already an edge of the graph, and
+/// self-loops are removed. Nodes are left untouched.
+///
+/// # Returns
+///
+/// The reversed edges, with their original `(source, target)` orientation, in ascending
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_feedback_arc_set::make_acyclic_in_place;
+/// use ade_graph::implementations::{DenseGraph, Edge, Node};
+/// use ade_graph::{EdgeTrait, GraphViewTrait, NodeTrait};
+///
+/// let mut graph = DenseGraph::<Node, Edge>::new(
+///     (0..3).map(Node::new).collect(),
+///     vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 0), Edge::new(2, 2)],
+/// );
+/// assert_eq!(make_acyclic_in_place(&mut graph), vec![(2, 0)]);
+/// assert!(graph.has_edge(0, 2));
+/// assert!(!graph.has_edge(2, 0));
+/// assert!(!graph.has_edge(2, 2));
+/// ```
+pub fn make_acyclic_in_place<N: NodeTrait, E: EdgeTrait, G: GraphMutTrait<N, E>>(
+    graph: &mut G,
+) -> Vec<(u32, u32)> {
+    let reversed = feedback_arc_set(graph);
+    let self_loops: Vec<u32> = graph
+        .get_edges()
+        .filter(|edge| edge.source() == edge.target())
+        .map(|edge| edge.source())
+        .collect();
+    for key in self_loops {
+        graph.remove_edge(key, key);
+    }
+    // The feedback arc set never holds both directions of a pair, so an edge already in
+    // the reversed direction is kept and takes precedence
+    for &(source, target) in &reversed {
+        let edge = graph.remove_edge(source, target).unwrap();
+        if !graph.has_edge(target, source) {
+            graph.add_edge(edge.with_endpoints(target, source));
+        }
+    }
+    reversed
+}
+
+// Position of each node in the sequence of the greedy heuristic
+fn greedy_sequence(successors: &[Vec<usize>], predecessors: &[Vec<usize>]) -> Vec<usize> {
+    let n = successors.len();
+    let mut out_degrees: Vec<usize> = successors.iter().map(Vec::len).collect();
+    let mut in_degrees: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+    let delta = |out_degree: usize, in_degree: usize| out_degree as i64 - in_degree as i64;
+
+    // Candidates are checked when popped, since their degrees may have changed since
+    let mut sinks: Vec<usize> = (0..n).rev().filter(|&i| out_degrees[i] == 0).collect();
+    let mut sources: Vec<usize> = (0..n).rev().filter(|&i| in_degrees[i] == 0).collect();
+    // Largest delta last, then smallest index
+    let mut deltas: BTreeSet<(i64, Reverse<usize>)> = (0..n)
+        .map(|i| (delta(out_degrees[i], in_degrees[i]), Reverse(i)))
+        .collect();
+
+    let mut removed = vec![false; n];
+    let mut start = Vec::with_capacity(n);
+    let mut end = Vec::new();
+    while !deltas.is_empty() {
+        let (key, at_end) = if let Some(sink) = pop_valid(&mut sinks, &removed, &out_degrees) {
+            (sink, true)
+        } else if let Some(source) = pop_valid(&mut sources, &removed, &in_degrees) {
+            (source, false)
+        } else {
+            let (_, Reverse(key)) = *deltas.last().unwrap();
+            (key, false)
+        };
+
+        removed[key] = true;
+        deltas.remove(&(delta(out_degrees[key], in_degrees[key]), Reverse(key)));
+        if at_end {
+            end.push(key);
+        } else {
+            start.push(key);
+        }
+
+        for &target in successors[key].iter().filter(|&&target| !removed[target]) {
+            deltas.remove(&(
+                delta(out_degrees[target], in_degrees[target]),
+                Reverse(target),
+            ));
+            in_degrees[target] -= 1;
+            deltas.insert((
+                delta(out_degrees[target], in_degrees[target]),
+                Reverse(target),
+            ));
+            if in_degrees[target] == 0 {
+                sources.push(target);
+            }
+        }
+        for &source in predecessors[key].iter().filter(|&&source| !removed[source]) {
+            deltas.remove(&(
+                delta(out_degrees[source], in_degrees[source]),
+                Reverse(source),
+            ));
+            out_degrees[source] -= 1;
+            deltas.insert((
+                delta(out_degrees[source], in_degrees[source]),
+                Reverse(source),
+            ));
+            if out_degrees[source] == 0 {
+                sinks.push(source);
+            }
+        }
+    }
+
+    // Sinks were collected from the end of the sequence backwards
+    let mut position = vec![0; n];
+    for (i, key) in start.into_iter().chain(end.into_iter().rev()).enumerate() {
+        position[key] = i;
+    }
+    position
+}
+
+// Pops the last candidate that is still in the graph with a zero degree
+fn pop_valid(candidates: &mut Vec<usize>, removed: &[bool], degrees: &[usize]) -> Option<usize> {
+    while let Some(key) = candidates.pop() {
+        if !removed[key] && degrees[key] == 0 {
+            return Some(key);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{DataEdge, Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+    use ade_topological_sort::topological_sort_kahn;
+    use ade_traits::DataEdgeTrait;
+
+    #[test]
+    fn test_random_graphs_become_acyclic() {
+        for seed in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(40, 200, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let (acyclic, reversed) = make_acyclic(&graph);
+
+            assert!(topological_sort_kahn(&acyclic).is_ok());
+            assert!(reversed
+                .iter()
+                .all(|&(source, target)| graph.has_edge(source, target)));
+            let m = graph
+                .get_edges()
+                .filter(|e| e.source() != e.target())
+                .count();
+            assert!(2 * reversed.len() <= m);
+        }
+    }
+
+    #[test]
+    fn test_in_place_matches_copy() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(30, 120, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let (acyclic, reversed) = make_acyclic(&graph);
+
+            let mut in_place = graph.clone();
+            assert_eq!(make_acyclic_in_place(&mut in_place), reversed);
+            let sorted_edges = |graph: &Graph<Node, Edge>| {
+                let mut edges: Vec<(u32, u32)> = graph.get_edges().map(|e| e.key()).collect();
+                edges.sort_unstable();
+                edges
+            };
+            assert_eq!(sorted_edges(&in_place), sorted_edges(&acyclic));
+        }
+    }
+
+    #[test]
+    fn test_acyclic_graph_is_unchanged() {
+        let (nodes, edges) = generate_random_graph_data(30, 100, 7);
+        let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        assert_eq!(feedback_arc_set(&graph), vec![]);
+    }
+
+    #[test]
+    fn test_opposite_edges_merge() {
+        let graph = Graph::<Node, DataEdge<&str>>::new(
+            vec![Node::new(10), Node::new(20)],
+            vec![
+                DataEdge::with_data(10, 20, "a"),
+                DataEdge::with_data(20, 10, "b"),
+            ],
+        );
+        let (acyclic, reversed) = make_acyclic(&graph);
+
+        assert_eq!(reversed.len(), 1);
+        assert_eq!(acyclic.get_edges().count(), 1);
+        let kept = if reversed == vec![(20, 10)] { "a" } else { "b" };
+        assert_eq!(*acyclic.get_edges().next().unwrap().data(), kept);
+    }
+
+    #[test]
+    fn test_reversed_edges_keep_payload() {
+        let graph = Graph::<Node, DataEdge<u8>>::new(
+            (0..3).map(Node::new).collect(),
+            vec![
+                DataEdge::with_data(0, 1, 1),
+                DataEdge::with_data(1, 2, 2),
+                DataEdge::with_data(2, 0, 3),
+            ],
+        );
+        let (acyclic, reversed) = make_acyclic(&graph);
+        let (source, target) = reversed[0];
+        assert_eq!(
+            *acyclic.get_edge(target, source).data(),
+            *graph.get_edge(source, target).data()
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod eades_lin_smyth;
+
+pub use eades_lin_smyth::{feedback_arc_set, make_acyclic, make_acyclic_in_place};
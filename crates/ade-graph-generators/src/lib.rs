@@ -1,6 +1,7 @@
 pub mod complete_graph;
+pub mod random_connected_graph;
 pub mod random_graph;
-//pub mod random_connected_graph;
 
 pub use complete_graph::complete_graph_data;
+pub use random_connected_graph::generate_random_connected_graph_data;
 pub use random_graph::generate_random_graph_data;
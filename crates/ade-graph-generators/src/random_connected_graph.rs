@@ -1,3 +1,4 @@
+use ade_common::SeededRng;
 use std::collections::HashSet;
 
 /// Generate a random connected graph
@@ -42,83 +43,48 @@ pub fn generate_random_connected_graph_data(
     }
 
     let node_keys: Vec<u32> = (0..n as u32).collect();
-    let mut rng_state = seed;
-    let mut edge_set = HashSet::new();
-
-    // First, create a spanning tree to ensure connectivity
-    let mut nodes_in_tree = HashSet::new();
-    nodes_in_tree.insert(0u32); // Start with node 0
+    let mut rng = SeededRng::new(seed);
+    let mut edge_set = HashSet::with_capacity(m);
+    let mut edge_pairs = Vec::with_capacity(m);
 
+    // First, create a spanning tree to ensure connectivity: nodes 0..i are already in it
     for i in 1..n as u32 {
-        // Connect node i to a random node already in the tree
-        rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-        let tree_nodes: Vec<_> = nodes_in_tree.iter().copied().collect();
-        let random_tree_node = tree_nodes[(rng_state as usize) % tree_nodes.len()];
-
-        // Randomly choose direction of the edge
-        rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-        if rng_state % 2 == 0 {
-            edge_set.insert((random_tree_node, i));
+        // Connect node i to a random node already in the tree, in a random direction
+        let random_tree_node = rng.below(i as u64) as u32;
+        let edge = if rng.below(2) == 0 {
+            (random_tree_node, i)
         } else {
-            edge_set.insert((i, random_tree_node));
-        }
-
-        nodes_in_tree.insert(i);
+            (i, random_tree_node)
+        };
+        edge_set.insert(edge);
+        edge_pairs.push(edge);
     }
 
     // Add remaining random edges
-    while edge_set.len() < m {
-        rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-        let source = (rng_state as u32) % (n as u32);
-
-        rng_state = rng_state.wrapping_mul(1664525).wrapping_add(1013904223);
-        let target = (rng_state as u32) % (n as u32);
+    while edge_pairs.len() < m {
+        let source = rng.below(n as u64) as u32;
+        let target = rng.below(n as u64) as u32;
 
         // No self-loops
-        if source != target {
-            edge_set.insert((source, target));
+        if source != target && edge_set.insert((source, target)) {
+            edge_pairs.push((source, target));
         }
     }
 
-    let edge_pairs: Vec<(u32, u32)> = edge_set.into_iter().collect();
-
     (node_keys, edge_pairs)
 }
 
 #[cfg(test)]
 mod connected_graph_tests {
     use super::*;
-    use graph::core::Graph;
-    use graph::edge::Edge;
-    use graph::node::Node;
-    use graph::utils::build_graph;
-    use std::collections::HashSet;
-
-    /// Helper function to check if a graph is connected using strongly connected components
-    /// We create an undirected version of the graph and check if there's only one SCC
-    fn is_connected(nodes: &[u32], edges: &[(u32, u32)]) -> bool {
-        use strongly_connected_components::scc;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::GraphViewTrait;
+    use ade_strongly_connected_components::weakly_connected_components;
 
-        if nodes.is_empty() {
-            return true;
-        }
-
-        // Create nodes for the undirected graph
-        let graph_nodes: Vec<Node> = nodes.iter().map(|&key| Node::new(key)).collect();
-
-        // Create edges for the undirected graph (add both directions)
-        let mut graph_edges = Vec::new();
-        for &(source, target) in edges {
-            graph_edges.push(Edge::new(source, target));
-            graph_edges.push(Edge::new(target, source)); // Add reverse edge for undirected behavior
-        }
-
-        // Build the undirected graph
-        let undirected_graph = Graph::new(graph_nodes, graph_edges);
-
-        // Check if there's exactly one strongly connected component
-        let components = scc(&undirected_graph);
-        components.len() == 1
+    fn is_connected(nodes: &[u32], edges: &[(u32, u32)]) -> bool {
+        let graph = build_graph::<Node, Edge>(nodes.to_vec(), edges.to_vec());
+        weakly_connected_components(&graph).len() <= 1
     }
 
     #[test]
@@ -289,11 +255,11 @@ mod connected_graph_tests {
     fn test_generate_random_connected_graph_data_with_build_graph() {
         // Integration test: ensure the generated data works with build_graph
         let (nodes, edges) = generate_random_connected_graph_data(5, 10, 42);
-        let graph = build_graph(nodes.clone(), edges.clone());
+        let graph = build_graph::<Node, Edge>(nodes.clone(), edges.clone());
 
         // Basic sanity checks on the built graph
-        assert_eq!(graph.get_node_keys().len(), 5);
-        assert_eq!(graph.get_edges().len(), 10);
+        assert_eq!(graph.get_node_keys().count(), 5);
+        assert_eq!(graph.get_edges().count(), 10);
 
         // Check that all nodes exist
         for i in 0..5 {
@@ -1,13 +1,4 @@
-fn lcg_next(state: &mut u64) -> u64 {
-    *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
-    *state
-}
-
-fn mix64(mut z: u64) -> u64 {
-    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
-    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
-    z ^ (z >> 31)
-}
+use ade_common::SeededRng;
 
 pub fn generate_random_graph_data(n: usize, m: usize, seed: u64) -> (Vec<u32>, Vec<(u32, u32)>) {
     if n == 0 {
@@ -15,12 +6,12 @@ pub fn generate_random_graph_data(n: usize, m: usize, seed: u64) -> (Vec<u32>, V
     }
 
     let node_keys: Vec<u32> = (0..n as u32).collect();
-    let mut rng_state = seed;
+    let mut rng = SeededRng::new(seed);
     let mut edges = Vec::with_capacity(m);
 
     for _ in 0..m {
-        let from = (mix64(lcg_next(&mut rng_state)) % (n as u64)) as u32;
-        let mut to = (mix64(lcg_next(&mut rng_state)) % (n as u64)) as u32;
+        let from = rng.below(n as u64) as u32;
+        let mut to = rng.below(n as u64) as u32;
         if to == from {
             to = ((to as usize + 1) % n) as u32;
         }
@@ -0,0 +1,639 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphMutTrait, NodeTrait};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The key given to each label by [`GraphBuilder::labeled_node`].
+pub type Labels = HashMap<String, u32>;
+
+// An edge waiting for build to resolve its endpoints
+#[derive(Debug, Clone)]
+enum PendingEdge<E> {
+    Keyed(E),
+    Labeled(String, String),
+}
+
+/// Builds a [`Graph`] from nodes and edges given in any order, reporting every problem at
+/// once.
+///
+/// Unlike [`Graph::new`], which panics on the first edge referencing a missing node, the
+/// builder accepts edges whose nodes are added later, and checks everything in
+/// [`build`](GraphBuilder::build). Nodes can be given with explicit keys, or with string
+/// labels: each new label gets the next free key, starting from 0, so that graphs built
+/// only from labels have sequential keys.
+///
+/// By default duplicate edges and missing endpoints are problems, while self-loops are
+/// accepted. [`allow_duplicate_edges`](GraphBuilder::allow_duplicate_edges),
+/// [`allow_self_loops`](GraphBuilder::allow_self_loops) and
+/// [`auto_create_missing_nodes`](GraphBuilder::auto_create_missing_nodes) change these
+/// rules, and [`validate`](GraphBuilder::validate) lists the problems without building.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::builder::GraphBuilder;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// // Edges can come before their nodes
+/// let graph = GraphBuilder::<Node, Edge>::new()
+///     .edge(Edge::new(1, 2))
+///     .node(Node::new(1))
+///     .node(Node::new(2))
+///     .build()
+///     .unwrap();
+/// assert!(graph.has_edge(1, 2));
+///
+/// // Labels get sequential keys
+/// let (graph, labels) = GraphBuilder::<Node, Edge>::new()
+///     .labeled_edge("parse", "check")
+///     .labeled_node("parse")
+///     .labeled_node("check")
+///     .build_with_labels()
+///     .unwrap();
+/// assert!(graph.has_sequential_keys());
+/// assert!(graph.has_edge(labels["parse"], labels["check"]));
+/// ```
+///
+/// ```
+/// use ade_graph::builder::{BuildProblem, GraphBuilder};
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let error = GraphBuilder::<Node, Edge>::new()
+///     .node(Node::new(1))
+///     .edge(Edge::new(1, 2))
+///     .edge(Edge::new(3, 1))
+///     .build()
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     error.problems(),
+///     &[
+///         BuildProblem::MissingNode { edge: (1, 2), key: 2 },
+///         BuildProblem::MissingNode { edge: (3, 1), key: 3 },
+///     ]
+/// );
+/// ```
+///
+/// ```
+/// use ade_graph::builder::{BuildProblem, GraphBuilder};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let builder = GraphBuilder::<Node, Edge>::new()
+///     .auto_create_missing_nodes(true)
+///     .allow_self_loops(false)
+///     .edges(vec![Edge::new(0, 1), Edge::new(1, 1)]);
+/// assert_eq!(builder.validate(), vec![BuildProblem::SelfLoop(1)]);
+///
+/// let graph = builder.allow_self_loops(true).build().unwrap();
+/// assert_eq!(graph.node_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<N, E> {
+    nodes: Vec<N>,
+    keys: HashSet<u32>,
+    labels: HashMap<String, u32>,
+    next_label_key: u32,
+    edges: Vec<PendingEdge<E>>,
+    problems: Vec<BuildProblem>,
+    allow_duplicate_edges: bool,
+    allow_self_loops: bool,
+    auto_create_missing_nodes: bool,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphBuilder<N, E> {
+    /// Creates a builder with no nodes and no edges.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            keys: HashSet::new(),
+            labels: HashMap::new(),
+            next_label_key: 0,
+            edges: Vec::new(),
+            problems: Vec::new(),
+            allow_duplicate_edges: false,
+            allow_self_loops: true,
+            auto_create_missing_nodes: false,
+        }
+    }
+
+    /// Sets whether two edges with the same source and target are accepted, in which case
+    /// the last one added is kept. Defaults to `false`: duplicates are reported.
+    pub fn allow_duplicate_edges(mut self, allow: bool) -> Self {
+        self.allow_duplicate_edges = allow;
+        self
+    }
+
+    /// Sets whether edges from a node to itself are accepted. Defaults to `true`; when
+    /// `false`, self-loops are reported.
+    pub fn allow_self_loops(mut self, allow: bool) -> Self {
+        self.allow_self_loops = allow;
+        self
+    }
+
+    /// Sets whether the endpoints of edges that were never added are created, instead of
+    /// being reported. Defaults to `false`.
+    ///
+    /// Missing keys get a node created with [`NodeTrait::new`], and missing labels get a
+    /// labeled node, as [`labeled_node`](GraphBuilder::labeled_node) does, once all missing
+    /// keys are created.
+    pub fn auto_create_missing_nodes(mut self, create: bool) -> Self {
+        self.auto_create_missing_nodes = create;
+        self
+    }
+
+    /// Adds a node. Adding a second node with the same key is reported by
+    /// [`build`](GraphBuilder::build).
+    pub fn node(mut self, node: N) -> Self {
+        if self.keys.insert(node.key()) {
+            self.nodes.push(node);
+        } else {
+            self.problems.push(BuildProblem::DuplicateNode(node.key()));
+        }
+        self
+    }
+
+    /// Adds several nodes, as [`node`](GraphBuilder::node) does.
+    pub fn nodes(self, nodes: impl IntoIterator<Item = N>) -> Self {
+        nodes.into_iter().fold(self, Self::node)
+    }
+
+    /// Adds an edge. Its nodes can be added before or after it.
+    pub fn edge(mut self, edge: E) -> Self {
+        self.edges.push(PendingEdge::Keyed(edge));
+        self
+    }
+
+    /// Adds several edges, as [`edge`](GraphBuilder::edge) does.
+    pub fn edges(self, edges: impl IntoIterator<Item = E>) -> Self {
+        edges.into_iter().fold(self, Self::edge)
+    }
+
+    /// Adds a node identified by `label`, with the smallest free key not yet given to
+    /// another label.
+    ///
+    /// Adding the same label twice is reported by [`build`](GraphBuilder::build). Nodes
+    /// added later with [`node`](GraphBuilder::node) must not reuse the key.
+    pub fn labeled_node(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if self.labels.contains_key(&label) {
+            self.problems.push(BuildProblem::DuplicateLabel(label));
+            return self;
+        }
+        while self.keys.contains(&self.next_label_key) {
+            self.next_label_key += 1;
+        }
+        let key = self.next_label_key;
+        self.labels.insert(label, key);
+        self.node(N::new(key))
+    }
+
+    /// Adds an edge between the nodes identified by two labels. The labeled nodes can be
+    /// added before or after it.
+    pub fn labeled_edge(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.edges
+            .push(PendingEdge::Labeled(source.into(), target.into()));
+        self
+    }
+
+    /// Returns the key given to `label`, if it was added with
+    /// [`labeled_node`](GraphBuilder::labeled_node).
+    pub fn key_of(&self, label: &str) -> Option<u32> {
+        self.labels.get(label).copied()
+    }
+
+    /// Returns the problems that [`build`](GraphBuilder::build) would report, in the same
+    /// order, or an empty list if the graph can be built.
+    ///
+    /// The builder is kept, so it can be fixed and built afterwards. The check runs on a
+    /// copy of the nodes and edges.
+    pub fn validate(&self) -> Vec<BuildProblem> {
+        self.clone()
+            .build_with_labels()
+            .err()
+            .map_or_else(Vec::new, BuildError::into_problems)
+    }
+
+    /// Checks the nodes and edges and builds the graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BuildError`] listing every problem found: problems with nodes first, then
+    /// problems with edges, each in the order they were added to the builder.
+    pub fn build(self) -> Result<Graph<N, E>, BuildError> {
+        self.build_with_labels().map(|(graph, _)| graph)
+    }
+
+    /// Builds the graph, as [`build`](GraphBuilder::build) does, and also returns the key
+    /// given to each label.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BuildError`] listing every problem found.
+    pub fn build_with_labels(self) -> Result<(Graph<N, E>, Labels), BuildError> {
+        self.checked_parts()
+            .map(|(nodes, edges, labels)| (Graph::new(nodes, edges), labels))
+    }
+
+    /// Checks the nodes and edges, as [`build`](GraphBuilder::build) does, and adds them to
+    /// `graph`, which can be of any implementation of [`GraphMutTrait`].
+    ///
+    /// Nodes are added before edges. Edges are only checked against the nodes of the
+    /// builder, so `graph` is usually empty; a node of `graph` with the same key as a node
+    /// of the builder is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BuildError`] listing every problem found, in which case `graph` is left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::builder::GraphBuilder;
+    /// use ade_graph::implementations::{DenseGraph, Edge, Node};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+    /// GraphBuilder::new()
+    ///     .edge(Edge::new(0, 1))
+    ///     .auto_create_missing_nodes(true)
+    ///     .build_into(&mut graph)
+    ///     .unwrap();
+    /// assert!(graph.has_edge(0, 1));
+    /// ```
+    pub fn build_into<G: GraphMutTrait<N, E>>(self, graph: &mut G) -> Result<(), BuildError> {
+        let (nodes, edges, _) = self.checked_parts()?;
+        for node in nodes {
+            graph.add_node(node);
+        }
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        Ok(())
+    }
+
+    // Returns the nodes, the edges and the labels once every check has passed
+    fn checked_parts(mut self) -> Result<(Vec<N>, Vec<E>, Labels), BuildError> {
+        if self.auto_create_missing_nodes {
+            self = self.create_missing_nodes();
+        }
+        let mut problems = self.problems;
+        let mut edges: Vec<E> = Vec::with_capacity(self.edges.len());
+        let mut edge_index = HashMap::with_capacity(self.edges.len());
+
+        for pending in self.edges {
+            let edge = match pending {
+                PendingEdge::Keyed(edge) => {
+                    let found = problems.len();
+                    for key in endpoints(edge.source(), edge.target()) {
+                        if !self.keys.contains(&key) {
+                            problems.push(BuildProblem::MissingNode {
+                                edge: edge.key(),
+                                key,
+                            });
+                        }
+                    }
+                    if problems.len() > found {
+                        continue;
+                    }
+                    edge
+                }
+                PendingEdge::Labeled(source, target) => {
+                    match (self.labels.get(&source), self.labels.get(&target)) {
+                        (Some(&source), Some(&target)) => E::new(source, target),
+                        (source_key, target_key) => {
+                            let mut missing = Vec::with_capacity(2);
+                            if source_key.is_none() {
+                                missing.push(source.clone());
+                            }
+                            if target_key.is_none() && source != target {
+                                missing.push(target.clone());
+                            }
+                            for label in missing {
+                                problems.push(BuildProblem::MissingLabel {
+                                    edge: (source.clone(), target.clone()),
+                                    label,
+                                });
+                            }
+                            continue;
+                        }
+                    }
+                }
+            };
+            if !self.allow_self_loops && edge.source() == edge.target() {
+                problems.push(BuildProblem::SelfLoop(edge.source()));
+                continue;
+            }
+            match edge_index.get(&edge.key()) {
+                None => {
+                    edge_index.insert(edge.key(), edges.len());
+                    edges.push(edge);
+                }
+                Some(&index) if self.allow_duplicate_edges => edges[index] = edge,
+                Some(_) => problems.push(BuildProblem::DuplicateEdge(edge.source(), edge.target())),
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(BuildError { problems });
+        }
+        Ok((self.nodes, edges, self.labels))
+    }
+
+    // Adds the endpoints of edges that were never added, keys first so that the labels
+    // created afterwards skip them
+    fn create_missing_nodes(mut self) -> Self {
+        let mut labels = Vec::new();
+        for pending in &self.edges {
+            match pending {
+                PendingEdge::Keyed(edge) => {
+                    for key in endpoints(edge.source(), edge.target()) {
+                        if self.keys.insert(key) {
+                            self.nodes.push(N::new(key));
+                        }
+                    }
+                }
+                PendingEdge::Labeled(source, target) => {
+                    labels.extend([source.clone(), target.clone()]);
+                }
+            }
+        }
+        for label in labels {
+            if !self.labels.contains_key(&label) {
+                self = self.labeled_node(label);
+            }
+        }
+        self
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> Default for GraphBuilder<N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The distinct endpoints of an edge, a self-loop has only one
+fn endpoints(source: u32, target: u32) -> impl Iterator<Item = u32> {
+    std::iter::once(source).chain((source != target).then_some(target))
+}
+
+/// A problem found by [`GraphBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildProblem {
+    /// Two nodes have the same key.
+    DuplicateNode(u32),
+    /// Two labeled nodes have the same label.
+    DuplicateLabel(String),
+    /// Two edges have the same source and target.
+    DuplicateEdge(u32, u32),
+    /// An edge goes from the node with this key to itself, and self-loops are not allowed.
+    SelfLoop(u32),
+    /// An edge, given as `(source, target)`, references a node key that was never added.
+    MissingNode { edge: (u32, u32), key: u32 },
+    /// A labeled edge, given as `(source, target)`, references a label that was never added.
+    MissingLabel {
+        edge: (String, String),
+        label: String,
+    },
+}
+
+impl fmt::Display for BuildProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildProblem::DuplicateNode(key) => write!(f, "Duplicate node {}", key),
+            BuildProblem::DuplicateLabel(label) => write!(f, "Duplicate label {:?}", label),
+            BuildProblem::DuplicateEdge(source, target) => {
+                write!(f, "Duplicate edge {}→{}", source, target)
+            }
+            BuildProblem::SelfLoop(key) => write!(f, "Self-loop on node {}", key),
+            BuildProblem::MissingNode { edge, key } => {
+                write!(f, "Node {} not found for edge {}→{}", key, edge.0, edge.1)
+            }
+            BuildProblem::MissingLabel { edge, label } => {
+                write!(
+                    f,
+                    "Label {:?} not found for edge {}→{}",
+                    label, edge.0, edge.1
+                )
+            }
+        }
+    }
+}
+
+/// The error returned by [`GraphBuilder::build`], listing every problem found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    problems: Vec<BuildProblem>,
+}
+
+impl BuildError {
+    /// Returns the problems found, never empty.
+    pub fn problems(&self) -> &[BuildProblem] {
+        &self.problems
+    }
+
+    /// Consumes the error and returns the problems found.
+    pub fn into_problems(self) -> Vec<BuildProblem> {
+        self.problems
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid graph: ")?;
+        for (i, problem) in self.problems.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DenseGraph, Edge, Node, WeightedEdge};
+    use ade_traits::{GraphViewTrait, WeightedEdgeTrait};
+
+    #[test]
+    fn test_build_into() {
+        let mut graph = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+        GraphBuilder::new()
+            .node(Node::new(0))
+            .edges(vec![Edge::new(0, 1), Edge::new(1, 0)])
+            .node(Node::new(1))
+            .build_into(&mut graph)
+            .unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.has_edge(0, 1) && graph.has_edge(1, 0));
+
+        let mut graph = DenseGraph::<Node, Edge>::new(vec![Node::new(0)], vec![]);
+        let error = GraphBuilder::new()
+            .node(Node::new(1))
+            .edge(Edge::new(1, 2))
+            .build_into(&mut graph)
+            .unwrap_err();
+        assert_eq!(
+            error.problems(),
+            &[BuildProblem::MissingNode {
+                edge: (1, 2),
+                key: 2
+            }]
+        );
+        assert_eq!(graph.node_count(), 1);
+    }
+
+    #[test]
+    fn test_deferred_edges() {
+        let graph = GraphBuilder::<Node, Edge>::new()
+            .edges(vec![Edge::new(0, 1), Edge::new(1, 1)])
+            .nodes(vec![Node::new(1), Node::new(0)])
+            .build()
+            .unwrap();
+
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.has_edge(0, 1));
+        assert!(graph.has_edge(1, 1));
+    }
+
+    #[test]
+    fn test_all_problems_are_reported() {
+        let error = GraphBuilder::<Node, Edge>::new()
+            .node(Node::new(0))
+            .node(Node::new(0))
+            .edge(Edge::new(0, 0))
+            .edge(Edge::new(0, 0))
+            .edge(Edge::new(5, 5))
+            .edge(Edge::new(6, 7))
+            .labeled_node("a")
+            .labeled_node("a")
+            .labeled_edge("a", "b")
+            .labeled_edge("c", "c")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error.into_problems(),
+            vec![
+                BuildProblem::DuplicateNode(0),
+                BuildProblem::DuplicateLabel("a".to_string()),
+                BuildProblem::DuplicateEdge(0, 0),
+                BuildProblem::MissingNode {
+                    edge: (5, 5),
+                    key: 5
+                },
+                BuildProblem::MissingNode {
+                    edge: (6, 7),
+                    key: 6
+                },
+                BuildProblem::MissingNode {
+                    edge: (6, 7),
+                    key: 7
+                },
+                BuildProblem::MissingLabel {
+                    edge: ("a".to_string(), "b".to_string()),
+                    label: "b".to_string(),
+                },
+                BuildProblem::MissingLabel {
+                    edge: ("c".to_string(), "c".to_string()),
+                    label: "c".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labels_skip_explicit_keys() {
+        let builder = GraphBuilder::<Node, Edge>::new()
+            .node(Node::new(0))
+            .node(Node::new(2))
+            .labeled_node("x")
+            .labeled_node("y")
+            .labeled_edge("x", "y")
+            .edge(Edge::new(0, 1));
+        assert_eq!(builder.key_of("x"), Some(1));
+        assert_eq!(builder.key_of("y"), Some(3));
+        assert_eq!(builder.key_of("z"), None);
+
+        let (graph, labels) = builder.build_with_labels().unwrap();
+        assert!(graph.has_sequential_keys());
+        assert!(graph.has_edge(1, 3));
+        assert_eq!(labels.len(), 2);
+    }
+
+    #[test]
+    fn test_options() {
+        let builder = GraphBuilder::<Node, WeightedEdge<u32>>::new()
+            .node(Node::new(0))
+            .edge(WeightedEdge::with_weight(0, 1, 1))
+            .edge(WeightedEdge::with_weight(0, 1, 2))
+            .edge(WeightedEdge::with_weight(0, 0, 3));
+        assert_eq!(
+            builder.validate(),
+            vec![
+                BuildProblem::MissingNode {
+                    edge: (0, 1),
+                    key: 1
+                },
+                BuildProblem::MissingNode {
+                    edge: (0, 1),
+                    key: 1
+                },
+            ]
+        );
+
+        let builder = builder
+            .auto_create_missing_nodes(true)
+            .allow_self_loops(false);
+        assert_eq!(
+            builder.validate(),
+            vec![BuildProblem::DuplicateEdge(0, 1), BuildProblem::SelfLoop(0)]
+        );
+
+        // The last duplicate is kept
+        let graph = builder
+            .allow_duplicate_edges(true)
+            .allow_self_loops(true)
+            .build()
+            .unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.get_edge(0, 1).weight(), 2);
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_created_labels_skip_created_keys() {
+        let (graph, labels) = GraphBuilder::<Node, Edge>::new()
+            .auto_create_missing_nodes(true)
+            .labeled_edge("a", "b")
+            .edge(Edge::new(0, 2))
+            .build_with_labels()
+            .unwrap();
+
+        assert_eq!((labels["a"], labels["b"]), (1, 3));
+        assert!(graph.has_sequential_keys());
+        assert!(graph.has_edge(1, 3) && graph.has_edge(0, 2));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = GraphBuilder::<Node, Edge>::new()
+            .labeled_edge("a", "b")
+            .edge(Edge::new(1, 2))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid graph: Label \"a\" not found for edge a→b; Label \"b\" not found for edge a→b; \
+             Node 1 not found for edge 1→2; Node 2 not found for edge 1→2"
+        );
+    }
+}
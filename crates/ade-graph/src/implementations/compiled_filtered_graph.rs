@@ -0,0 +1,317 @@
+use crate::implementations::filtered_graph::bitset;
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use fixedbitset::FixedBitSet;
+use std::marker::PhantomData;
+use std::mem::{size_of, size_of_val};
+
+/// A filtered view with precomputed adjacency lists.
+///
+/// [`FilteredGraph`] checks every neighbor against its bitset each time successors or
+/// predecessors are requested. `CompiledFilteredGraph` does that work once, when it is
+/// built, and stores the active neighbors of every active node in flat vectors. Neighbor
+/// queries then return plain slices, which pays off for algorithms that traverse the
+/// same view many times.
+///
+/// The view is a snapshot: it is built from a [`FilteredGraph`] with
+/// [`FilteredGraph::compile`] and borrows the same base graph.
+///
+/// # Type Parameters
+///
+/// * `'a` - Lifetime of the borrowed base graph
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+/// * `G` - Base graph type implementing [`GraphViewTrait`], [`Graph`] by default
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge, FilteredGraph};
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = Graph::<Node, Edge>::new(
+///     vec![Node::new(0), Node::new(1), Node::new(2)],
+///     vec![Edge::new(0, 1), Edge::new(0, 2), Edge::new(1, 0)],
+/// );
+///
+/// let compiled = FilteredGraph::new(&graph, vec![0, 1]).compile();
+///
+/// assert_eq!(compiled.successors_slice(0), &[1]);
+/// assert_eq!(compiled.predecessors_slice(0), &[1]);
+/// assert!(!compiled.has_node(2));
+/// ```
+pub struct CompiledFilteredGraph<
+    'a,
+    N: NodeTrait,
+    E: EdgeTrait,
+    G: GraphViewTrait<N, E> = Graph<N, E>,
+> {
+    base: &'a G,
+    active: FixedBitSet,
+    node_count: usize,
+    successor_offsets: Vec<usize>,
+    successors: Vec<u32>,
+    predecessor_offsets: Vec<usize>,
+    predecessors: Vec<u32>,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> CompiledFilteredGraph<'a, N, E, G> {
+    pub(crate) fn new(base: &'a G, active: FixedBitSet) -> Self {
+        let size = active.len();
+        let mut successor_offsets = Vec::with_capacity(size + 1);
+        let mut successors = Vec::new();
+        let mut predecessor_offsets = Vec::with_capacity(size + 1);
+        let mut predecessors = Vec::new();
+
+        successor_offsets.push(0);
+        predecessor_offsets.push(0);
+        for key in 0..size {
+            if active.contains(key) {
+                let key = key as u32;
+                successors.extend(
+                    base.get_successors_keys(key)
+                        .filter(|&succ| active.contains(succ as usize)),
+                );
+                predecessors.extend(
+                    base.get_predecessors_keys(key)
+                        .filter(|&pred| active.contains(pred as usize)),
+                );
+            }
+            successor_offsets.push(successors.len());
+            predecessor_offsets.push(predecessors.len());
+        }
+
+        Self {
+            base,
+            node_count: active.count_ones(..),
+            active,
+            successor_offsets,
+            successors,
+            predecessor_offsets,
+            predecessors,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the keys of the active successors of a node as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not active in the view.
+    pub fn successors_slice(&self, node_key: u32) -> &[u32] {
+        let k = self.check_active(node_key);
+        &self.successors[self.successor_offsets[k]..self.successor_offsets[k + 1]]
+    }
+
+    /// Returns the keys of the active predecessors of a node as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not active in the view.
+    pub fn predecessors_slice(&self, node_key: u32) -> &[u32] {
+        let k = self.check_active(node_key);
+        &self.predecessors[self.predecessor_offsets[k]..self.predecessor_offsets[k + 1]]
+    }
+
+    /// Estimates the heap memory used by the view itself, in bytes.
+    ///
+    /// Counts the bitset of active nodes and the precomputed adjacency vectors; the base
+    /// graph is borrowed.
+    pub fn estimated_heap_size(&self) -> usize {
+        size_of_val(self.active.as_slice())
+            + (self.successor_offsets.capacity() + self.predecessor_offsets.capacity())
+                * size_of::<usize>()
+            + (self.successors.capacity() + self.predecessors.capacity()) * size_of::<u32>()
+    }
+
+    fn is_active(&self, key: u32) -> bool {
+        self.active.contains(key as usize)
+    }
+
+    fn check_active(&self, key: u32) -> usize {
+        if !self.is_active(key) {
+            panic!("Node {} not active in filtered graph", key);
+        }
+        key as usize
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphViewTrait<N, E>
+    for CompiledFilteredGraph<'_, N, E, G>
+{
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> usize {
+        self.successors.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.check_active(key);
+        self.base.get_node(key)
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.is_active(key)
+    }
+
+    fn get_nodes<'b>(&'b self) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.get_node_keys().map(move |key| self.base.get_node(key))
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.active.ones().map(|key| key as u32)
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        if !self.is_active(source) {
+            panic!("Source node {} not active in filtered graph", source);
+        }
+        if !self.is_active(target) {
+            panic!("Target node {} not active in filtered graph", target);
+        }
+        self.base.get_edge(source, target)
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.is_active(source) && self.is_active(target) && self.base.has_edge(source, target)
+    }
+
+    fn get_edges<'b>(&'b self) -> impl Iterator<Item = &'b E>
+    where
+        E: 'b,
+    {
+        self.get_node_keys().flat_map(move |source| {
+            self.successors_slice(source)
+                .iter()
+                .map(move |&target| self.base.get_edge(source, target))
+        })
+    }
+
+    fn get_predecessors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.predecessors_slice(node_key)
+            .iter()
+            .map(move |&pred| self.base.get_node(pred))
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.predecessors_slice(node_key).iter().copied()
+    }
+
+    fn get_successors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.successors_slice(node_key)
+            .iter()
+            .map(move |&succ| self.base.get_node(succ))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.successors_slice(node_key).iter().copied()
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.predecessors_slice(node_key).len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.successors_slice(node_key).len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        // Intersect the requested nodes with the currently active ones
+        let mut active = bitset(self.active.len(), node_keys.iter().copied());
+        active.intersect_with(&self.active);
+        FilteredGraph::from_active(self.base, active)
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        self.active.count_ones(..self.node_count) == self.node_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+
+    fn sorted(keys: impl Iterator<Item = u32>) -> Vec<u32> {
+        let mut keys: Vec<u32> = keys.collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_matches_filtered_graph() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 4), (4, 0)],
+        );
+        let filtered = FilteredGraph::new(&graph, vec![0, 2, 3, 4]);
+        let compiled = filtered.compile();
+
+        assert_eq!(compiled.node_count(), filtered.node_count());
+        assert_eq!(
+            sorted(compiled.get_node_keys()),
+            sorted(filtered.get_node_keys())
+        );
+        for key in filtered.get_node_keys() {
+            assert_eq!(
+                sorted(compiled.get_successors_keys(key)),
+                sorted(filtered.get_successors_keys(key))
+            );
+            assert_eq!(
+                sorted(compiled.get_predecessors_keys(key)),
+                sorted(filtered.get_predecessors_keys(key))
+            );
+        }
+        assert_eq!(compiled.get_edges().count(), filtered.get_edges().count());
+        assert!(compiled.has_edge(4, 4));
+        assert!(!compiled.has_edge(0, 1));
+        assert!(!compiled.has_sequential_keys());
+    }
+
+    #[test]
+    fn test_filter() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+        let compiled = FilteredGraph::new(&graph, vec![0, 1]).compile();
+        assert!(compiled.has_sequential_keys());
+
+        let subgraph = compiled.filter(&[1, 2]);
+        assert_eq!(sorted(subgraph.get_node_keys()), vec![1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 2 not active in filtered graph")]
+    fn test_successors_inactive_node() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+        let compiled = FilteredGraph::new(&graph, vec![0, 1]).compile();
+        compiled.successors_slice(2);
+    }
+
+    #[test]
+    fn test_consistency() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 1), (1, 2), (2, 0), (3, 3), (4, 0)],
+        );
+        for active in [vec![], vec![0, 1, 2], vec![1, 3, 4], vec![0, 1, 2, 3, 4]] {
+            let compiled = FilteredGraph::new(&graph, active).compile();
+            assert_eq!(ade_traits::check_graph_consistency(&compiled), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,483 @@
+use crate::implementations::FilteredGraph;
+use crate::utils::heap_size::node_adjacency_bytes;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::mem::size_of;
+use std::ops::Range;
+
+/// An immutable directed graph stored in compressed sparse row (CSR) form.
+///
+/// The successors of all the nodes are stored one after the other in a single vector, sorted
+/// by source and then by target, and an offset vector gives the range of each node; the
+/// predecessors are stored the same way. Iterating the neighbors of a node reads a
+/// contiguous slice instead of walking a hash set, which suits algorithms that traverse the
+/// graph many times, such as strongly connected components or elementary circuits. Edges
+/// are stored next to their targets, and `has_edge` and `get_edge` use a binary search
+/// among the successors of the source.
+///
+/// The graph cannot be modified: build it once with [`CsrGraph::from_view`], after all the
+/// changes have been made to the original graph. Keys are always `0..n`: graphs with other
+/// keys are renumbered in ascending order of key, and
+/// [`from_view_with_keys`](CsrGraph::from_view_with_keys) returns the original keys.
+///
+/// Nodes are rebuilt with [`NodeTrait::with_key`], so they keep their data but not their
+/// own predecessor and successor sets, which are empty: neighbors are only available through
+/// the [`GraphViewTrait`] methods.
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{CsrGraph, Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 2), (0, 1), (2, 0)]);
+/// let csr = CsrGraph::from_view(&graph);
+///
+/// assert_eq!(csr.node_count(), 3);
+/// assert_eq!(csr.successors(0), &[1, 2]);
+/// assert_eq!(csr.get_predecessors_keys(0).collect::<Vec<_>>(), vec![2]);
+/// assert!(csr.has_edge(2, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsrGraph<N, E> {
+    nodes: Vec<N>,
+    successor_offsets: Vec<usize>,
+    successors: Vec<u32>,
+    // The edge to `successors[i]` is `edges[i]`
+    edges: Vec<E>,
+    predecessor_offsets: Vec<usize>,
+    predecessors: Vec<u32>,
+    // The edge from `predecessors[i]` is `edges[predecessor_edges[i]]`
+    predecessor_edges: Vec<usize>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> CsrGraph<N, E> {
+    /// Builds a compressed sparse row copy of a graph.
+    ///
+    /// Any implementation of [`GraphViewTrait`] can be copied, including other views; nodes
+    /// and edges are cloned. Building takes `O(n + m log m)` time for `n` nodes and `m`
+    /// edges, to sort the neighbors of every node.
+    ///
+    /// A graph with sequential keys keeps its keys. Other graphs are renumbered in ascending
+    /// order of key, the smallest key becoming 0; use
+    /// [`from_view_with_keys`](Self::from_view_with_keys) to map the new keys back, or
+    /// renumber the graph first, for example with
+    /// [`renumber_bfs`](crate::utils::renumber::renumber_bfs).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{CsrGraph, DataNode, Edge, Graph};
+    /// use ade_graph::{DataNodeTrait, GraphViewTrait};
+    ///
+    /// let graph = Graph::<DataNode<&str>, Edge>::new(
+    ///     vec![DataNode::with_data(0, "a"), DataNode::with_data(1, "b")],
+    ///     vec![Edge::new(0, 1)],
+    /// );
+    /// let csr = CsrGraph::from_view(&graph);
+    ///
+    /// assert_eq!(*csr.get_node(1).data(), "b");
+    /// assert_eq!(csr.get_successors_keys(0).collect::<Vec<_>>(), vec![1]);
+    /// ```
+    pub fn from_view<G: GraphViewTrait<N, E>>(graph: &G) -> Self {
+        Self::from_view_with_keys(graph).0
+    }
+
+    /// Builds a compressed sparse row copy of a graph, and returns the original key of each
+    /// node.
+    ///
+    /// The graph is copied as by [`from_view`](Self::from_view), and `keys[k]` is the key in
+    /// `graph` of the node with key `k` in the copy. Keys are in ascending order, so they are
+    /// `0..n` for a graph with sequential keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{CsrGraph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![10, 20, 30], vec![(30, 10), (10, 20)]);
+    /// let (csr, keys) = CsrGraph::from_view_with_keys(&graph);
+    ///
+    /// assert_eq!(keys, vec![10, 20, 30]);
+    /// assert_eq!(csr.successors(2), &[0]);
+    /// ```
+    pub fn from_view_with_keys<G: GraphViewTrait<N, E>>(graph: &G) -> (Self, Vec<u32>) {
+        let sequential = graph.has_sequential_keys();
+        let n = graph.node_count() as u32;
+        let keys: Vec<u32> = if sequential {
+            (0..n).collect()
+        } else {
+            let mut keys: Vec<u32> = graph.get_node_keys().collect();
+            keys.sort_unstable();
+            keys
+        };
+        let index = |key: u32| {
+            if sequential {
+                key
+            } else {
+                keys.binary_search(&key).unwrap() as u32
+            }
+        };
+        let nodes = (0..n)
+            .map(|i| graph.get_node(keys[i as usize]).with_key(i))
+            .collect();
+
+        let mut successor_offsets = Vec::with_capacity(n as usize + 1);
+        let mut successors = Vec::new();
+        let mut edges = Vec::new();
+        successor_offsets.push(0);
+        for &key in &keys {
+            let start = successors.len();
+            successors.extend(graph.get_successors_keys(key).map(index));
+            successors[start..].sort_unstable();
+            edges.extend(successors[start..].iter().map(|&target| {
+                let target = keys[target as usize];
+                let edge = graph.get_edge(key, target);
+                if sequential {
+                    edge.clone()
+                } else {
+                    edge.with_endpoints(index(key), index(target))
+                }
+            }));
+            successor_offsets.push(successors.len());
+        }
+
+        let mut predecessor_offsets = Vec::with_capacity(n as usize + 1);
+        let mut predecessors = Vec::with_capacity(successors.len());
+        predecessor_offsets.push(0);
+        for &key in &keys {
+            let start = predecessors.len();
+            predecessors.extend(graph.get_predecessors_keys(key).map(index));
+            predecessors[start..].sort_unstable();
+            predecessor_offsets.push(predecessors.len());
+        }
+
+        let mut predecessor_edges = Vec::with_capacity(predecessors.len());
+        for key in 0..n {
+            let range = predecessor_offsets[key as usize]..predecessor_offsets[key as usize + 1];
+            predecessor_edges.extend(predecessors[range].iter().map(|&source| {
+                let start = successor_offsets[source as usize];
+                let end = successor_offsets[source as usize + 1];
+                let offset = successors[start..end]
+                    .binary_search(&key)
+                    .expect("Every predecessor has the node among its successors");
+                start + offset
+            }));
+        }
+
+        let csr = CsrGraph {
+            nodes,
+            successor_offsets,
+            successors,
+            edges,
+            predecessor_offsets,
+            predecessors,
+            predecessor_edges,
+        };
+        (csr, keys)
+    }
+
+    /// Returns the keys of the successors of a node, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn successors(&self, key: u32) -> &[u32] {
+        &self.successors[self.successor_range(key)]
+    }
+
+    /// Returns the keys of the predecessors of a node, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn predecessors(&self, key: u32) -> &[u32] {
+        &self.predecessors[self.predecessor_range(key)]
+    }
+
+    /// Estimates the heap memory used by the graph, in bytes.
+    ///
+    /// Sums the node, offset, neighbor and edge vectors, and the adjacency sets of the nodes,
+    /// which are empty unless the node type allocates them eagerly. See
+    /// [`Graph::estimated_heap_size`](crate::implementations::Graph::estimated_heap_size) for
+    /// the limits of the estimate.
+    pub fn estimated_heap_size(&self) -> usize {
+        self.nodes.capacity() * size_of::<N>()
+            + (self.successor_offsets.capacity() + self.predecessor_offsets.capacity())
+                * size_of::<usize>()
+            + (self.successors.capacity() + self.predecessors.capacity()) * size_of::<u32>()
+            + self.predecessor_edges.capacity() * size_of::<usize>()
+            + self.edges.capacity() * size_of::<E>()
+            + self.nodes.iter().map(node_adjacency_bytes).sum::<usize>()
+    }
+
+    fn successor_range(&self, key: u32) -> Range<usize> {
+        if !self.has_node(key) {
+            panic!("Node {} not found", key);
+        }
+        let key = key as usize;
+        self.successor_offsets[key]..self.successor_offsets[key + 1]
+    }
+
+    fn predecessor_range(&self, key: u32) -> Range<usize> {
+        if !self.has_node(key) {
+            panic!("Node {} not found", key);
+        }
+        let key = key as usize;
+        self.predecessor_offsets[key]..self.predecessor_offsets[key + 1]
+    }
+
+    // Index of the edge from source to target in the successor and edge vectors
+    fn edge_index(&self, source: u32, target: u32) -> Option<usize> {
+        if !self.has_node(source) {
+            return None;
+        }
+        let range = self.successor_range(source);
+        let start = range.start;
+        self.successors[range]
+            .binary_search(&target)
+            .ok()
+            .map(|offset| start + offset)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for CsrGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        true
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.nodes
+            .get(key as usize)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        (key as usize) < self.nodes.len()
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        match self.edge_index(source, target) {
+            Some(index) => &self.edges[index],
+            None => panic!("Edge {}→{} not found", source, target),
+        }
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.edge_index(source, target).is_some()
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.iter()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        0..self.nodes.len() as u32
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.edges.iter()
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.predecessors(node_key)
+            .iter()
+            .map(|&pred_key| &self.nodes[pred_key as usize])
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.predecessors(node_key).iter().copied()
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.successors(node_key)
+            .iter()
+            .map(|&succ_key| &self.nodes[succ_key as usize])
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.successors(node_key).iter().copied()
+    }
+
+    fn get_out_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        let range = self.successor_range(node_key);
+        self.edges[range.clone()]
+            .iter()
+            .zip(&self.successors[range])
+            .map(|(edge, &target)| (edge, &self.nodes[target as usize]))
+    }
+
+    fn get_in_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        let range = self.predecessor_range(node_key);
+        self.predecessor_edges[range.clone()]
+            .iter()
+            .zip(&self.predecessors[range])
+            .map(|(&edge, &source)| (&self.edges[edge], &self.nodes[source as usize]))
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.predecessors(node_key).len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.successors(node_key).len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DataEdge, Edge, Graph, Node};
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+    use ade_traits::DataEdgeTrait;
+
+    #[test]
+    fn test_neighbors_are_sorted_slices() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 3), (0, 1), (2, 0), (3, 0), (1, 1), (1, 2)],
+        );
+        let csr = CsrGraph::from_view(&graph);
+
+        assert_eq!(csr.node_count(), 4);
+        assert_eq!(csr.edge_count(), 6);
+        assert_eq!(csr.successors(0), &[1, 3]);
+        assert_eq!(csr.successors(1), &[1, 2]);
+        assert_eq!(csr.predecessors(0), &[2, 3]);
+        assert_eq!(csr.predecessors(1), &[0, 1]);
+        assert_eq!(
+            (csr.in_degree(1), csr.out_degree(1), csr.degree(1)),
+            (2, 2, 4)
+        );
+        assert_eq!(
+            csr.get_successors(1)
+                .map(|node| node.key())
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(csr.has_edge(1, 1) && !csr.has_edge(1, 0) && !csr.has_edge(7, 0));
+        assert_eq!(ade_traits::check_graph_consistency(&csr), Ok(()));
+    }
+
+    #[test]
+    fn test_edges_keep_their_data() {
+        let graph = Graph::<Node, DataEdge<u8>>::new(
+            (0..3).map(Node::new).collect(),
+            vec![
+                DataEdge::with_data(2, 1, 21),
+                DataEdge::with_data(2, 0, 20),
+                DataEdge::with_data(0, 2, 2),
+            ],
+        );
+        let csr = CsrGraph::from_view(&graph);
+
+        assert_eq!(*csr.get_edge(2, 0).data(), 20);
+        assert_eq!(*csr.get_edge(2, 1).data(), 21);
+        assert_eq!(*csr.get_edge(0, 2).data(), 2);
+        let keys: Vec<(u32, u32)> = csr.get_edges().map(|edge| edge.key()).collect();
+        assert_eq!(keys, vec![(0, 2), (2, 0), (2, 1)]);
+
+        let out: Vec<(u8, u32)> = csr
+            .get_out_edges(2)
+            .map(|(edge, target)| (*edge.data(), target.key()))
+            .collect();
+        assert_eq!(out, vec![(20, 0), (21, 1)]);
+        let into: Vec<(u8, u32)> = csr
+            .get_in_edges(2)
+            .map(|(edge, source)| (*edge.data(), source.key()))
+            .collect();
+        assert_eq!(into, vec![(2, 0)]);
+    }
+
+    #[test]
+    fn test_from_csr_view_and_filter() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        let csr = CsrGraph::from_view(&CsrGraph::from_view(&graph));
+        assert_eq!(ade_traits::check_graph_consistency(&csr), Ok(()));
+
+        let subgraph = csr.filter(&[0, 1]);
+        assert_eq!(subgraph.node_count(), 2);
+        assert!(subgraph.has_edge(0, 1));
+        assert_eq!(subgraph.get_predecessors_keys(0).count(), 0);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let csr = CsrGraph::from_view(&Graph::<Node, Edge>::new(vec![], vec![]));
+        assert!(csr.is_empty());
+        assert!(csr.has_sequential_keys());
+        assert_eq!(csr.get_edges().count(), 0);
+    }
+
+    #[test]
+    fn test_non_sequential_keys() {
+        let graph = Graph::<Node, DataEdge<u8>>::new(
+            vec![Node::new(7), Node::new(3), Node::new(5)],
+            vec![
+                DataEdge::with_data(7, 3, 73),
+                DataEdge::with_data(3, 5, 35),
+                DataEdge::with_data(5, 5, 55),
+            ],
+        );
+        let (csr, keys) = CsrGraph::from_view_with_keys(&graph);
+
+        assert_eq!(keys, vec![3, 5, 7]);
+        assert!(csr.has_sequential_keys());
+        assert_eq!(csr.successors(2), &[0]);
+        assert_eq!(csr.predecessors(1), &[0, 1]);
+        assert_eq!(*csr.get_edge(2, 0).data(), 73);
+        assert_eq!(csr.get_edge(1, 1).key(), (1, 1));
+        assert_eq!(ade_traits::check_graph_consistency(&csr), Ok(()));
+        assert_eq!(CsrGraph::from_view(&graph).edge_count(), 3);
+    }
+
+    #[test]
+    fn test_panics() {
+        let csr = CsrGraph::from_view(&build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]));
+        assert_panics_with!(csr.successors(2), "Node 2 not found");
+        assert_panics_with!(csr.get_edge(1, 0), "Edge 1→0 not found");
+    }
+}
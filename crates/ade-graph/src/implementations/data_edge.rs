@@ -0,0 +1,98 @@
+use ade_traits::{DataEdgeTrait, EdgeTrait};
+use std::fmt::Debug;
+
+/// A directed edge carrying a payload of type `T`, such as a label or a metadata struct.
+///
+/// `DataEdge` implements [`DataEdgeTrait`] to give access to its payload. The payload is
+/// kept by filtered views and by transformations that change keys, which rebuild edges
+/// with [`EdgeTrait::with_endpoints`]. Edges created through [`EdgeTrait::new`] get the
+/// default payload.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{DataEdge, Graph, Node};
+/// use ade_graph::{DataEdgeTrait, GraphViewTrait};
+///
+/// let graph = Graph::<Node, DataEdge<&str>>::new(
+///     vec![Node::new(0), Node::new(1)],
+///     vec![DataEdge::with_data(0, 1, "imports")],
+/// );
+/// assert_eq!(*graph.get_edge(0, 1).data(), "imports");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataEdge<T> {
+    source: u32,
+    target: u32,
+    data: T,
+}
+
+impl<T> DataEdge<T> {
+    /// Creates a new directed edge from a source node to a target node with the given payload.
+    pub fn with_data(source: u32, target: u32, data: T) -> Self {
+        Self {
+            source,
+            target,
+            data,
+        }
+    }
+
+    /// Consumes the edge and returns its payload.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<T: Debug + Clone + Default> EdgeTrait for DataEdge<T> {
+    fn new(source: u32, target: u32) -> Self {
+        Self::with_data(source, target, T::default())
+    }
+
+    fn source(&self) -> u32 {
+        self.source
+    }
+
+    fn target(&self) -> u32 {
+        self.target
+    }
+
+    fn key(&self) -> (u32, u32) {
+        (self.source, self.target)
+    }
+
+    fn with_endpoints(&self, source: u32, target: u32) -> Self {
+        Self::with_data(source, target, self.data.clone())
+    }
+}
+
+impl<T: Debug + Clone + Default> DataEdgeTrait for DataEdge<T> {
+    type Data = T;
+
+    fn with_data(source: u32, target: u32, data: T) -> Self {
+        DataEdge::with_data(source, target, data)
+    }
+
+    fn data(&self) -> &T {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_edge() {
+        let mut edge = DataEdge::with_data(1, 2, 10u8);
+        *edge.data_mut() += 1;
+
+        let moved = edge.with_endpoints(2, 1);
+        assert_eq!(moved.key(), (2, 1));
+        assert_eq!(moved.into_data(), 11);
+    }
+}
@@ -0,0 +1,131 @@
+use ade_traits::{DataNodeTrait, NodeTrait};
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+/// A node carrying a payload of type `T`, such as a label or a metadata struct.
+///
+/// `DataNode` tracks its predecessors and successors like [`Node`](crate::implementations::Node),
+/// and implements [`DataNodeTrait`] to give access to its payload. The payload is kept by
+/// filtered views, which borrow the nodes of the underlying graph, and by transformations
+/// that change keys, which rebuild nodes with [`NodeTrait::with_key`]. Nodes created
+/// through [`NodeTrait::new`] get the default payload.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{DataNode, Edge, Graph};
+/// use ade_graph::{DataNodeTrait, GraphViewTrait};
+///
+/// let graph = Graph::<DataNode<&str>, Edge>::new(
+///     vec![DataNode::with_data(0, "lexer"), DataNode::with_data(1, "parser")],
+///     vec![Edge::new(0, 1)],
+/// );
+///
+/// let view = graph.filter(&[1]);
+/// assert_eq!(*view.get_node(1).data(), "parser");
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataNode<T> {
+    key: u32,
+    // Neighbors are rebuilt from the edges when a graph is deserialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    predecessors: HashSet<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    successors: HashSet<u32>,
+    data: T,
+}
+
+impl<T> DataNode<T> {
+    /// Creates a new node with the given key and payload, and no connections.
+    pub fn with_data(key: u32, data: T) -> Self {
+        Self {
+            key,
+            predecessors: HashSet::new(),
+            successors: HashSet::new(),
+            data,
+        }
+    }
+
+    /// Consumes the node and returns its payload.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<T: Debug + Clone + Default> NodeTrait for DataNode<T> {
+    fn new(key: u32) -> Self {
+        Self::with_data(key, T::default())
+    }
+
+    fn key(&self) -> u32 {
+        self.key
+    }
+
+    fn predecessors(&self) -> &HashSet<u32> {
+        &self.predecessors
+    }
+
+    fn successors(&self) -> &HashSet<u32> {
+        &self.successors
+    }
+
+    fn add_predecessor(&mut self, key: u32) {
+        self.predecessors.insert(key);
+    }
+
+    fn add_successor(&mut self, key: u32) {
+        self.successors.insert(key);
+    }
+
+    fn remove_predecessor(&mut self, key: u32) {
+        self.predecessors.remove(&key);
+    }
+
+    fn remove_successor(&mut self, key: u32) {
+        self.successors.remove(&key);
+    }
+
+    fn with_key(&self, key: u32) -> Self {
+        Self::with_data(key, self.data.clone())
+    }
+}
+
+impl<T: Debug + Clone + Default> DataNodeTrait for DataNode<T> {
+    type Data = T;
+
+    fn with_data(key: u32, data: T) -> Self {
+        DataNode::with_data(key, data)
+    }
+
+    fn data(&self) -> &T {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_node() {
+        let mut node = DataNode::with_data(1, vec!["a"]);
+        node.add_successor(2);
+        node.data_mut().push("b");
+
+        let moved = node.with_key(3);
+        assert_eq!(moved.key(), 3);
+        assert!(moved.successors().is_empty());
+        assert_eq!(moved.into_data(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_new_uses_default_data() {
+        let node = <DataNode<String> as NodeTrait>::new(0);
+        assert!(node.data().is_empty());
+    }
+}
@@ -0,0 +1,562 @@
+use crate::implementations::{FilteredGraph, Graph};
+use crate::utils::heap_size::{hash_map_bytes, node_adjacency_bytes};
+use ade_traits::{EdgeTrait, GraphMutTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::mem::size_of;
+
+/// A directed graph that stores its nodes in a vector indexed by key.
+///
+/// `DenseGraph` exposes the same API as [`Graph`], but node lookups are plain vector
+/// indexing instead of hash map lookups. This makes `get_node`, `has_node` and the
+/// predecessor/successor iterators noticeably cheaper in hot loops, at the cost of
+/// memory proportional to the largest node key rather than to the number of nodes.
+///
+/// Use it when keys are dense, typically `0..n` as required by the algorithms that
+/// call [`GraphViewTrait::filter`].
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{DenseGraph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let nodes = vec![Node::new(0), Node::new(1), Node::new(2)];
+/// let edges = vec![Edge::new(0, 1), Edge::new(1, 2)];
+/// let graph = DenseGraph::new(nodes, edges);
+///
+/// assert_eq!(graph.node_count(), 3);
+/// assert!(graph.has_sequential_keys());
+/// assert!(graph.has_edge(1, 2));
+/// ```
+///
+/// Converting an existing graph:
+///
+/// ```
+/// use ade_graph::implementations::{DenseGraph, Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (2, 0)]);
+/// let dense = DenseGraph::from(graph);
+///
+/// assert_eq!(dense.get_successors_keys(2).collect::<Vec<_>>(), vec![0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DenseGraph<N, E> {
+    nodes: Vec<Option<N>>,
+    node_count: usize,
+    edges: HashMap<(u32, u32), E>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> DenseGraph<N, E> {
+    /// Creates a new graph from vectors of nodes and edges.
+    ///
+    /// Duplicate nodes and edges replace earlier ones, as in [`Graph::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any edge references a node that is not in the nodes vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{DenseGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+    /// assert!(graph.is_empty());
+    /// ```
+    pub fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        let mut graph = DenseGraph {
+            nodes: Vec::with_capacity(nodes.len()),
+            node_count: 0,
+            edges: HashMap::with_capacity(edges.len()),
+        };
+
+        for node in nodes {
+            graph.add_node(node);
+        }
+
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+
+    /// Adds a node to the graph.
+    ///
+    /// The storage grows up to the node key if needed. If a node with the same key
+    /// already exists, it is replaced and returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{DenseGraph, Node, Edge};
+    ///
+    /// let mut graph = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+    ///
+    /// assert!(graph.add_node(Node::new(0)).is_none());
+    /// assert!(graph.add_node(Node::new(0)).is_some());
+    /// ```
+    pub fn add_node(&mut self, node: N) -> Option<N> {
+        let index = node.key() as usize;
+        if index >= self.nodes.len() {
+            self.nodes.resize_with(index + 1, || None);
+        }
+
+        let old = self.nodes[index].replace(node);
+        if old.is_none() {
+            self.node_count += 1;
+        }
+        old
+    }
+
+    /// Removes a node from the graph, together with all its incoming and outgoing edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{DenseGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = DenseGraph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1)],
+    ///     vec![Edge::new(0, 1)],
+    /// );
+    ///
+    /// assert!(graph.remove_node(0).is_some());
+    /// assert!(!graph.has_edge(0, 1));
+    /// assert!(graph.remove_node(0).is_none());
+    /// ```
+    pub fn remove_node(&mut self, key: u32) -> Option<N> {
+        let node = self.slot(key)?;
+
+        let mut edges_to_remove = Vec::new();
+        for &predecessor in node.predecessors() {
+            edges_to_remove.push((predecessor, key));
+        }
+        for &successor in node.successors() {
+            edges_to_remove.push((key, successor));
+        }
+        for (source, target) in edges_to_remove {
+            self.remove_edge(source, target);
+        }
+
+        let node = self.nodes[key as usize].take();
+        self.node_count -= 1;
+
+        // Keep the storage as short as possible so that sequential keys stay cheap to detect
+        while matches!(self.nodes.last(), Some(None)) {
+            self.nodes.pop();
+        }
+
+        node
+    }
+
+    /// Adds an edge to the graph, updating the successors and predecessors of its endpoints.
+    ///
+    /// If an edge with the same key already exists, it is replaced and returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{DenseGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = DenseGraph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![]);
+    ///
+    /// assert!(graph.add_edge(Edge::new(0, 1)).is_none());
+    /// assert!(graph.add_edge(Edge::new(0, 1)).is_some());
+    /// assert!(graph.has_edge(0, 1));
+    /// ```
+    pub fn add_edge(&mut self, edge: E) -> Option<E> {
+        let (source, target) = edge.key();
+
+        if source == target {
+            match self.slot_mut(source) {
+                Some(node) => {
+                    node.add_successor(target);
+                    node.add_predecessor(source);
+                    return self.edges.insert((source, target), edge);
+                }
+                None => panic!("Node {} not found", source),
+            }
+        }
+
+        if !self.has_node(source) || !self.has_node(target) {
+            panic!("Node {} or {} not found", source, target);
+        }
+
+        match self
+            .nodes
+            .get_disjoint_mut([source as usize, target as usize])
+        {
+            Ok([Some(source_node), Some(target_node)]) => {
+                source_node.add_successor(target);
+                target_node.add_predecessor(source);
+                self.edges.insert((source, target), edge)
+            }
+            _ => panic!("Node {} or {} not found", source, target),
+        }
+    }
+
+    /// Removes an edge from the graph, updating the successors and predecessors of its endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{DenseGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = DenseGraph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1)],
+    ///     vec![Edge::new(0, 1)],
+    /// );
+    ///
+    /// assert!(graph.remove_edge(0, 1).is_some());
+    /// assert!(graph.remove_edge(0, 1).is_none());
+    /// ```
+    pub fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        if !self.has_node(source) || !self.has_node(target) {
+            panic!("Node {} or {} not found", source, target);
+        }
+
+        let edge = self.edges.remove(&(source, target))?;
+        if let Some(source_node) = self.slot_mut(source) {
+            source_node.remove_successor(target);
+        }
+        if let Some(target_node) = self.slot_mut(target) {
+            target_node.remove_predecessor(source);
+        }
+        Some(edge)
+    }
+
+    /// Estimates the heap memory used by the graph, in bytes.
+    ///
+    /// Sums the node vector, the edge map and the predecessor and successor sets of every
+    /// node. See [`Graph::estimated_heap_size`] for the limits of the estimate.
+    pub fn estimated_heap_size(&self) -> usize {
+        self.nodes.capacity() * size_of::<Option<N>>()
+            + hash_map_bytes(&self.edges)
+            + self.get_nodes().map(node_adjacency_bytes).sum::<usize>()
+    }
+
+    fn slot(&self, key: u32) -> Option<&N> {
+        self.nodes.get(key as usize).and_then(Option::as_ref)
+    }
+
+    fn slot_mut(&mut self, key: u32) -> Option<&mut N> {
+        self.nodes.get_mut(key as usize).and_then(Option::as_mut)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> From<Graph<N, E>> for DenseGraph<N, E> {
+    fn from(graph: Graph<N, E>) -> Self {
+        let (nodes, edges) = graph.into_maps();
+
+        let mut dense = DenseGraph {
+            nodes: Vec::new(),
+            node_count: 0,
+            edges,
+        };
+        for node in nodes.into_values() {
+            dense.add_node(node);
+        }
+        dense
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for DenseGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        self.node_count == self.nodes.len()
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.slot(key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.slot(key).is_some()
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.edges
+            .get(&(source, target))
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.edges.contains_key(&(source, target))
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.iter().flatten()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.get_nodes().map(|node| node.key())
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.edges.values()
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .predecessors()
+            .iter()
+            .map(|pred_key| self.get_node(*pred_key))
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).predecessors().iter().copied()
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .successors()
+            .iter()
+            .map(|succ_key| self.get_node(*succ_key))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).successors().iter().copied()
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).predecessors().len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).successors().len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphMutTrait<N, E> for DenseGraph<N, E> {
+    fn add_node(&mut self, node: N) -> Option<N> {
+        DenseGraph::add_node(self, node)
+    }
+
+    fn remove_node(&mut self, key: u32) -> Option<N> {
+        DenseGraph::remove_node(self, key)
+    }
+
+    fn add_edge(&mut self, edge: E) -> Option<E> {
+        DenseGraph::add_edge(self, edge)
+    }
+
+    fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        DenseGraph::remove_edge(self, source, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::edge::Edge;
+    use crate::implementations::node::Node;
+    use crate::utils::build::build_graph;
+    use ade_traits::GraphError;
+
+    fn sorted(keys: impl Iterator<Item = u32>) -> Vec<u32> {
+        let mut keys: Vec<u32> = keys.collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_add_and_remove_nodes() {
+        let mut graph = DenseGraph::<Node, Edge>::new(Vec::new(), Vec::new());
+
+        assert!(graph.add_node(Node::new(2)).is_none());
+        assert!(graph.add_node(Node::new(2)).is_some());
+        assert_eq!(graph.node_count(), 1);
+        assert!(!graph.has_node(0));
+        assert!(!graph.has_node(7));
+        assert!(!graph.has_sequential_keys());
+
+        graph.add_node(Node::new(0));
+        graph.add_node(Node::new(1));
+        assert!(graph.has_sequential_keys());
+        assert_eq!(sorted(graph.get_node_keys()), vec![0, 1, 2]);
+
+        assert!(graph.remove_node(2).is_some());
+        assert!(graph.remove_node(2).is_none());
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.has_sequential_keys());
+
+        graph.remove_node(0);
+        assert!(!graph.has_sequential_keys());
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut graph = DenseGraph::<Node, Edge>::new(
+            vec![Node::new(0), Node::new(1), Node::new(2)],
+            vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 2)],
+        );
+
+        assert!(graph.has_edge(2, 2));
+        assert_eq!(sorted(graph.get_successors_keys(2)), vec![2]);
+        assert_eq!(sorted(graph.get_predecessors_keys(2)), vec![1, 2]);
+        assert_eq!(graph.get_successors(0).next().unwrap().key(), 1);
+        assert_eq!(graph.get_edge(0, 1).key(), (0, 1));
+
+        assert!(graph.remove_edge(1, 2).is_some());
+        assert!(!graph.has_edge(1, 2));
+        assert_eq!(sorted(graph.get_predecessors_keys(2)), vec![2]);
+
+        graph.remove_node(1);
+        assert!(!graph.has_edge(0, 1));
+        assert_eq!(graph.get_successors_keys(0).count(), 0);
+        assert_eq!(graph.get_edges().count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 0 or 5 not found")]
+    fn test_add_edge_panic() {
+        let mut graph = DenseGraph::<Node, Edge>::new(vec![Node::new(0)], Vec::new());
+        graph.add_edge(Edge::new(0, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 3 not found")]
+    fn test_get_node_panic() {
+        let graph = DenseGraph::<Node, Edge>::new(vec![Node::new(0)], Vec::new());
+        graph.get_node(3);
+    }
+
+    #[test]
+    fn test_from_graph() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        let dense = DenseGraph::from(graph);
+
+        assert_eq!(dense.node_count(), 3);
+        assert!(dense.has_sequential_keys());
+        assert_eq!(sorted(dense.get_successors_keys(2)), vec![0]);
+        assert_eq!(sorted(dense.get_predecessors_keys(0)), vec![2]);
+    }
+
+    #[test]
+    fn test_filter() {
+        let graph = DenseGraph::<Node, Edge>::new(
+            vec![Node::new(0), Node::new(1), Node::new(2)],
+            vec![Edge::new(0, 1), Edge::new(0, 2)],
+        );
+
+        let subgraph = graph.filter(&[0, 1]);
+        assert_eq!(subgraph.node_count(), 2);
+        assert!(subgraph.has_edge(0, 1));
+        assert!(!subgraph.has_edge(0, 2));
+        assert_eq!(sorted(subgraph.get_successors_keys(0)), vec![1]);
+    }
+
+    #[test]
+    fn test_filter_non_sequential_keys() {
+        let graph = DenseGraph::<Node, Edge>::new(vec![Node::new(1), Node::new(3)], Vec::new());
+        let filtered = graph.filter(&[1]);
+        assert_eq!(filtered.get_node_keys().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_consistency() {
+        let mut graph = DenseGraph::<Node, Edge>::new(
+            (0..5).map(Node::new).collect(),
+            vec![
+                Edge::new(0, 1),
+                Edge::new(1, 2),
+                Edge::new(2, 0),
+                Edge::new(3, 3),
+                Edge::new(4, 0),
+            ],
+        );
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+
+        graph.remove_node(4);
+        graph.remove_node(1);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    // Builds and shrinks a graph through the generic modification methods only
+    fn build_and_shrink<G: GraphMutTrait<Node, Edge>>(graph: &mut G) {
+        for key in 0..4 {
+            assert!(graph.add_node(Node::new(key)).is_none());
+        }
+        for (source, target) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 3)] {
+            assert!(graph.add_edge(Edge::new(source, target)).is_none());
+        }
+        assert!(graph.add_edge(Edge::new(0, 1)).is_some());
+        assert_eq!(
+            graph.try_add_edge(Edge::new(4, 0)).unwrap_err(),
+            GraphError::MissingNode(4)
+        );
+        assert_eq!(
+            graph.try_remove_edge(0, 9).unwrap_err(),
+            GraphError::MissingNode(9)
+        );
+        assert_eq!(graph.edge_count(), 5);
+
+        assert!(graph.remove_node(2).is_some());
+        assert!(graph.remove_node(2).is_none());
+        assert!(graph.remove_edge(3, 3).is_some());
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 1));
+        assert_eq!(ade_traits::check_graph_consistency(graph), Ok(()));
+
+        graph.clear();
+        assert!(graph.is_empty());
+        assert_eq!(graph.edge_count(), 0);
+        graph.add_node(Node::new(0));
+        assert!(graph.try_add_edge(Edge::new(0, 0)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_graph_mut_trait() {
+        // DenseGraph relies on the default clear, Graph overrides it
+        build_and_shrink(&mut DenseGraph::new(Vec::new(), Vec::new()));
+        build_and_shrink(&mut Graph::new(Vec::new(), Vec::new()));
+    }
+}
@@ -113,6 +113,22 @@ impl EdgeTrait for Edge {
     }
 }
 
+// With the `serde` feature, an edge is serialized as its `[source, target]` pair
+#[cfg(feature = "serde")]
+impl serde::Serialize for Edge {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(self.source, self.target), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Edge {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <(u32, u32) as serde::Deserialize>::deserialize(deserializer)
+            .map(|(source, target)| Edge::new(source, target))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,395 @@
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+/// A filtered view of a graph that selects edges rather than nodes.
+///
+/// [`FilteredGraph`] keeps the edges induced by a set of nodes, so it cannot drop a single
+/// edge between two kept nodes. `EdgeFilteredGraph` borrows a base graph and either keeps
+/// only a set of listed edges, or hides them and keeps all the others, as needed to remove
+/// the feedback edges of a graph before a layered layout. It can also restrict the nodes at
+/// the same time; an edge is then visible only if both its endpoints are. Listed edges that
+/// are not in the base graph are ignored.
+///
+/// Edges are looked up in a hash set, so keys need not be sequential. Create the view with
+/// the methods of [`FilterEdges`], or with the constructors below.
+///
+/// # Type Parameters
+///
+/// * `'a` - Lifetime of the borrowed base graph
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+/// * `G` - Base graph type implementing [`GraphViewTrait`], [`Graph`] by default
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{EdgeFilteredGraph, Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+///
+/// // Hide the edge closing the cycle
+/// let acyclic = EdgeFilteredGraph::without(&graph, &[(2, 0)]);
+/// assert_eq!(acyclic.node_count(), 3);
+/// assert_eq!(acyclic.edge_count(), 2);
+/// assert!(!acyclic.has_edge(2, 0));
+/// assert_eq!(acyclic.get_successors_keys(2).count(), 0);
+/// ```
+pub struct EdgeFilteredGraph<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E> = Graph<N, E>>
+{
+    base: &'a G,
+    // Active nodes, all the nodes of the base graph when None
+    nodes: Option<HashSet<u32>>,
+    edges: HashSet<(u32, u32)>,
+    // Whether the listed edges are hidden rather than kept
+    hide_listed: bool,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> EdgeFilteredGraph<'a, N, E, G> {
+    /// Creates a view of `base` with all its nodes and only the listed edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{EdgeFilteredGraph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+    /// let view = EdgeFilteredGraph::new(&graph, &[(0, 1), (0, 2)]);
+    ///
+    /// assert_eq!(view.node_count(), 3);
+    /// assert_eq!(view.get_edges().count(), 1);
+    /// assert!(view.has_edge(0, 1));
+    /// ```
+    pub fn new(base: &'a G, edge_keys: &[(u32, u32)]) -> Self {
+        Self::build(base, None, edge_keys, false)
+    }
+
+    /// Creates a view of `base` with all its nodes and all its edges except the listed ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{EdgeFilteredGraph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0), (1, 1)]);
+    /// let view = EdgeFilteredGraph::without(&graph, &[(1, 0), (1, 1)]);
+    ///
+    /// assert_eq!(view.edge_count(), 1);
+    /// assert_eq!(view.get_predecessors_keys(1).collect::<Vec<_>>(), vec![0]);
+    /// ```
+    pub fn without(base: &'a G, edge_keys: &[(u32, u32)]) -> Self {
+        Self::build(base, None, edge_keys, true)
+    }
+
+    /// Creates a view of `base` with only the listed nodes, and only the listed edges
+    /// between them.
+    ///
+    /// Node keys that are not in the base graph are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{EdgeFilteredGraph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+    /// let view = EdgeFilteredGraph::with_nodes(&graph, &[1, 2], &[(0, 1), (1, 2)]);
+    ///
+    /// assert_eq!(view.node_count(), 2);
+    /// assert_eq!(view.edge_count(), 1);
+    /// assert!(view.has_edge(1, 2));
+    /// ```
+    pub fn with_nodes(base: &'a G, node_keys: &[u32], edge_keys: &[(u32, u32)]) -> Self {
+        let nodes = node_keys
+            .iter()
+            .copied()
+            .filter(|&key| base.has_node(key))
+            .collect();
+        Self::build(base, Some(nodes), edge_keys, false)
+    }
+
+    fn build(
+        base: &'a G,
+        nodes: Option<HashSet<u32>>,
+        edge_keys: &[(u32, u32)],
+        hide_listed: bool,
+    ) -> Self {
+        Self {
+            base,
+            nodes,
+            edges: edge_keys.iter().copied().collect(),
+            hide_listed,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the base graph.
+    pub fn base(&self) -> &'a G {
+        self.base
+    }
+
+    fn is_active(&self, key: u32) -> bool {
+        match &self.nodes {
+            Some(nodes) => nodes.contains(&key),
+            None => self.base.has_node(key),
+        }
+    }
+
+    // Whether an edge of the base graph between two active nodes is visible
+    fn is_selected(&self, source: u32, target: u32) -> bool {
+        self.edges.contains(&(source, target)) != self.hide_listed
+    }
+
+    // Listed edges that are in the base graph, between active nodes
+    fn listed_edges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.edges.iter().copied().filter(|&(source, target)| {
+            self.is_active(source) && self.is_active(target) && self.base.has_edge(source, target)
+        })
+    }
+
+    fn check_active(&self, key: u32) {
+        if !self.is_active(key) {
+            panic!("Node {} not found", key);
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphViewTrait<N, E>
+    for EdgeFilteredGraph<'_, N, E, G>
+{
+    fn node_count(&self) -> usize {
+        match &self.nodes {
+            Some(nodes) => nodes.len(),
+            None => self.base.node_count(),
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        // Listed edges are only hidden in views with all the nodes of the base graph
+        if self.hide_listed {
+            self.base.edge_count() - self.listed_edges().count()
+        } else {
+            self.listed_edges().count()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node_count() == 0
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.check_active(key);
+        self.base.get_node(key)
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.is_active(key)
+    }
+
+    fn get_nodes<'b>(&'b self) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.get_node_keys().map(move |key| self.base.get_node(key))
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.base
+            .get_node_keys()
+            .filter(move |&key| self.is_active(key))
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        if !self.has_edge(source, target) {
+            panic!("Edge {}→{} not found", source, target);
+        }
+        self.base.get_edge(source, target)
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.is_active(source)
+            && self.is_active(target)
+            && self.is_selected(source, target)
+            && self.base.has_edge(source, target)
+    }
+
+    fn get_edges<'b>(&'b self) -> impl Iterator<Item = &'b E>
+    where
+        E: 'b,
+    {
+        self.get_node_keys().flat_map(move |source| {
+            self.get_successors_keys(source)
+                .map(move |target| self.base.get_edge(source, target))
+        })
+    }
+
+    fn get_predecessors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.get_predecessors_keys(node_key)
+            .map(move |key| self.base.get_node(key))
+    }
+
+    fn get_successors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.get_successors_keys(node_key)
+            .map(move |key| self.base.get_node(key))
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.check_active(node_key);
+        self.base
+            .get_predecessors_keys(node_key)
+            .filter(move |&source| self.is_active(source) && self.is_selected(source, node_key))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.check_active(node_key);
+        self.base
+            .get_successors_keys(node_key)
+            .filter(move |&target| self.is_active(target) && self.is_selected(node_key, target))
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        match &self.nodes {
+            Some(nodes) => nodes.iter().all(|&key| (key as usize) < nodes.len()),
+            None => self.base.has_sequential_keys(),
+        }
+    }
+}
+
+/// Edge filtering for every graph view.
+///
+/// Implemented for all [`GraphViewTrait`] types, so that `graph.filter_edges(...)` is
+/// available wherever `graph.filter(...)` is. Each method returns an [`EdgeFilteredGraph`]
+/// borrowing the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{FilterEdges, Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+///
+/// assert_eq!(graph.filter_edges(&[(1, 2)]).edge_count(), 1);
+/// assert_eq!(graph.without_edges(&[(1, 2)]).edge_count(), 2);
+/// assert_eq!(graph.filter_nodes_and_edges(&[0, 1], &[(0, 1), (1, 2)]).edge_count(), 1);
+/// ```
+pub trait FilterEdges<N: NodeTrait, E: EdgeTrait>: GraphViewTrait<N, E> + Sized {
+    /// Returns a view with all the nodes and only the listed edges, see
+    /// [`EdgeFilteredGraph::new`].
+    fn filter_edges(&self, edge_keys: &[(u32, u32)]) -> EdgeFilteredGraph<'_, N, E, Self> {
+        EdgeFilteredGraph::new(self, edge_keys)
+    }
+
+    /// Returns a view with all the nodes and all the edges except the listed ones, see
+    /// [`EdgeFilteredGraph::without`].
+    fn without_edges(&self, edge_keys: &[(u32, u32)]) -> EdgeFilteredGraph<'_, N, E, Self> {
+        EdgeFilteredGraph::without(self, edge_keys)
+    }
+
+    /// Returns a view with only the listed nodes and the listed edges between them, see
+    /// [`EdgeFilteredGraph::with_nodes`].
+    fn filter_nodes_and_edges(
+        &self,
+        node_keys: &[u32],
+        edge_keys: &[(u32, u32)],
+    ) -> EdgeFilteredGraph<'_, N, E, Self> {
+        EdgeFilteredGraph::with_nodes(self, node_keys, edge_keys)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> FilterEdges<N, E> for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+
+    fn sorted_edges<N: NodeTrait, E: EdgeTrait>(
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> Vec<(u32, u32)> {
+        let mut edges: Vec<(u32, u32)> = graph.get_edges().map(|edge| edge.key()).collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    fn base() -> Graph<Node, Edge> {
+        build_graph(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 3)],
+        )
+    }
+
+    #[test]
+    fn test_keep_and_hide() {
+        let graph = base();
+
+        // (1, 0) is not in the base graph and is ignored
+        let kept = graph.filter_edges(&[(0, 1), (3, 3), (1, 0)]);
+        assert_eq!(sorted_edges(&kept), vec![(0, 1), (3, 3)]);
+        assert_eq!(kept.edge_count(), 2);
+        assert_eq!(kept.node_count(), 4);
+        assert_eq!(kept.get_successors_keys(2).count(), 0);
+
+        let hidden = graph.without_edges(&[(0, 1), (3, 3), (1, 0)]);
+        assert_eq!(sorted_edges(&hidden), vec![(1, 2), (2, 0), (2, 3)]);
+        assert_eq!(hidden.edge_count(), 3);
+        assert_eq!(hidden.get_predecessors_keys(1).count(), 0);
+        assert!(hidden.has_sequential_keys());
+
+        for view in [kept, hidden] {
+            assert_eq!(ade_traits::check_graph_consistency(&view), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_with_nodes() {
+        let graph = base();
+        let view = graph.filter_nodes_and_edges(&[2, 3, 9], &[(1, 2), (2, 3), (3, 3)]);
+
+        assert_eq!(view.node_count(), 2);
+        assert!(view.has_node(2) && view.has_node(3) && !view.has_node(9));
+        assert_eq!(sorted_edges(&view), vec![(2, 3), (3, 3)]);
+        assert_eq!(view.edge_count(), 2);
+        assert!(!view.has_sequential_keys());
+        assert_eq!(ade_traits::check_graph_consistency(&view), Ok(()));
+
+        assert_panics_with!(view.get_node(0), "Node 0 not found");
+        assert_panics_with!(view.get_edge(2, 0), "Edge 2→0 not found");
+    }
+
+    #[test]
+    fn test_filter_composes() {
+        let graph = base();
+        let view = graph.without_edges(&[(2, 0)]);
+        let filtered = view.filter(&[0, 1, 2]);
+        assert_eq!(sorted_edges(&filtered), vec![(0, 1), (1, 2)]);
+
+        // Hiding edges of a node-filtered view
+        let subgraph = graph.filter(&[0, 1, 2]);
+        let acyclic = subgraph.without_edges(&[(2, 0)]);
+        assert_eq!(sorted_edges(&acyclic), vec![(0, 1), (1, 2)]);
+        assert_eq!(acyclic.edge_count(), 2);
+    }
+}
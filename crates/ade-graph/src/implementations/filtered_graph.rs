@@ -1,32 +1,42 @@
-use crate::implementations::Graph;
+use crate::implementations::{CompiledFilteredGraph, Graph};
+use crate::utils::heap_size::hash_set_bytes;
+use ade_common::INVALID_KEY_SEQUENCE;
 use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
 use fixedbitset::FixedBitSet;
-use ade_common::INVALID_KEY_SEQUENCE;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::mem::{size_of, size_of_val};
 
 /// A filtered view of a graph that only exposes a subset of nodes and their edges.
 ///
 /// `FilteredGraph` provides a memory-efficient way to work with subsets of a graph
-/// without copying the underlying data. It maintains a bitset to track which nodes
-/// are "active" (visible) in the filtered view, while borrowing the original graph data.
+/// without copying the underlying data. It tracks which nodes are "active" (visible) in
+/// the filtered view, while borrowing the original graph data.
 ///
 /// # Features
 ///
 /// - **Zero-copy filtering** - references the base graph without duplicating data
-/// - **O(1) membership checks** using a bitset for active nodes
+/// - **O(1) membership checks** using a bitset for active nodes, or a hash set when the
+///   keys of the base graph are not sequential
 /// - **Automatic edge filtering** - only edges between active nodes are visible
-/// - **Composable filtering** - can create filtered views of filtered views
+/// - **Any base graph** - filters any [`GraphViewTrait`] implementation, such as a
+///   [`CsrGraph`](crate::implementations::CsrGraph)
+/// - **Composable filtering** - filtering a filtered view intersects the bitsets and returns
+///   a view of the same base graph, so nested filters do not stack
 ///
-/// # Requirements
+/// # Key spaces
 ///
-/// The base graph **must have sequential keys** (0, 1, 2, ..., n-1) to use filtering.
-/// This requirement allows efficient bitset-based lookups. Attempting to filter a graph
-/// with non-sequential keys will panic.
+/// When the base graph has sequential keys (0, 1, 2, ..., n-1), the active nodes are
+/// stored in a bitset indexed by key, which is compact and fast. Otherwise they are stored
+/// in a hash set, along with their sorted keys, so graphs with arbitrary keys, such as
+/// identifiers from external data, can be filtered too.
 ///
 /// # Type Parameters
 ///
 /// * `'a` - Lifetime of the borrowed base graph
 /// * `N` - Node type implementing [`NodeTrait`]
 /// * `E` - Edge type implementing [`EdgeTrait`]
+/// * `G` - Base graph type implementing [`GraphViewTrait`], [`Graph`] by default
 ///
 /// # Examples
 ///
@@ -101,20 +111,30 @@ use ade_common::INVALID_KEY_SEQUENCE;
 /// assert!(second_filter.has_node(2));
 /// assert!(!second_filter.has_node(0));
 /// ```
-pub struct FilteredGraph<'a, N: NodeTrait, E: EdgeTrait> {
-    base: &'a Graph<N, E>,
-    active: FixedBitSet,
+pub struct FilteredGraph<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E> = Graph<N, E>> {
+    base: &'a G,
+    active: Active,
+    // Number of active nodes, so that node_count does not scan the bitset
+    node_count: usize,
+    _marker: PhantomData<fn() -> (N, E)>,
 }
 
-impl<'a, N: NodeTrait, E: EdgeTrait> FilteredGraph<'a, N, E> {
+// Active nodes: a bitset indexed by key when the base graph has sequential keys, a hash set
+// otherwise, with the keys also kept sorted to iterate them in order
+enum Active {
+    Bits(FixedBitSet),
+    Keys { set: HashSet<u32>, sorted: Vec<u32> },
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> FilteredGraph<'a, N, E, G> {
     /// Creates a new filtered view of a graph with only the specified nodes active.
     ///
     /// This method creates a lightweight view over the base graph that only exposes
     /// the nodes specified in `active_nodes` and edges between those nodes. The base
     /// graph data is borrowed, not copied, making this operation very efficient.
     ///
-    /// Node keys that don't exist in the base graph or are outside the valid range
-    /// are silently ignored.
+    /// Node keys that don't exist in the base graph are silently ignored. The base graph
+    /// may have any keys, see [Key spaces](FilteredGraph#key-spaces).
     ///
     /// # Arguments
     ///
@@ -125,11 +145,6 @@ impl<'a, N: NodeTrait, E: EdgeTrait> FilteredGraph<'a, N, E> {
     ///
     /// A new `FilteredGraph` instance that provides a filtered view of the base graph.
     ///
-    /// # Panics
-    ///
-    /// Panics if the base graph does not have sequential keys (0, 1, 2, ..., n-1).
-    /// Sequential keys are required for the efficient bitset-based filtering mechanism.
-    ///
     /// # Examples
     ///
     /// Basic filtering:
@@ -192,29 +207,46 @@ impl<'a, N: NodeTrait, E: EdgeTrait> FilteredGraph<'a, N, E> {
     /// assert!(!filtered.has_node(1));
     /// assert!(!filtered.has_node(3));
     /// ```
-    pub fn new(base: &'a Graph<N, E>, active_nodes: impl IntoIterator<Item = u32>) -> Self {
-        // Panic if the graph does not have sequential keys
-        if !base.has_sequential_keys() {
-            panic!("{}", INVALID_KEY_SEQUENCE);
+    pub fn new(base: &'a G, active_nodes: impl IntoIterator<Item = u32>) -> Self {
+        if base.has_sequential_keys() {
+            return Self::from_active(base, bitset(base.node_count(), active_nodes));
         }
 
-        let node_count = base.get_node_keys().count();
+        let set = active_nodes
+            .into_iter()
+            .filter(|&key| base.has_node(key))
+            .collect();
+        Self::from_keys(base, set)
+    }
 
-        // Assume normalized keys: 0, 1, 2, ..., n-1
-        let mut active = FixedBitSet::with_capacity(node_count);
-        for key in active_nodes {
-            if (key as usize) < node_count {
-                active.insert(key as usize);
-            }
+    /// Creates a view of a base graph with sequential keys from the bitset of its active
+    /// nodes, without checking the keys again.
+    pub(crate) fn from_active(base: &'a G, active: FixedBitSet) -> Self {
+        Self {
+            base,
+            node_count: active.count_ones(..),
+            active: Active::Bits(active),
+            _marker: PhantomData,
         }
+    }
 
-        Self { base, active }
+    // Creates a view of a base graph with any keys from the set of its active nodes
+    fn from_keys(base: &'a G, set: HashSet<u32>) -> Self {
+        let mut sorted: Vec<u32> = set.iter().copied().collect();
+        sorted.sort_unstable();
+        Self {
+            base,
+            node_count: set.len(),
+            active: Active::Keys { set, sorted },
+            _marker: PhantomData,
+        }
     }
 
     /// Checks if a node is active (visible) in the filtered view.
     ///
-    /// This is an internal helper method that performs an O(1) lookup in the bitset
-    /// to determine if a node with the given key is included in the filtered view.
+    /// This is an internal helper method that performs an O(1) lookup in the bitset, or in
+    /// the hash set, to determine if a node with the given key is included in the filtered
+    /// view.
     ///
     /// # Arguments
     ///
@@ -244,17 +276,107 @@ impl<'a, N: NodeTrait, E: EdgeTrait> FilteredGraph<'a, N, E> {
     /// assert!(filtered.has_node(2));  // active
     /// ```
     fn is_active(&self, key: u32) -> bool {
-        self.active.contains(key as usize)
+        match &self.active {
+            Active::Bits(bits) => bits.contains(key as usize),
+            Active::Keys { set, .. } => set.contains(&key),
+        }
+    }
+
+    /// Returns the keys of the active nodes in ascending order.
+    ///
+    /// The keys are read directly from the bitset of active nodes, or from their sorted
+    /// keys, so iterating them costs time proportional to the active nodes rather than to
+    /// the size of the base graph.
+    /// [`GraphViewTrait::get_node_keys`], `get_nodes` and `get_edges` are built on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge, FilteredGraph};
+    ///
+    /// let graph = Graph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![],
+    /// );
+    ///
+    /// let filtered = FilteredGraph::new(&graph, vec![3, 0, 2]);
+    /// assert_eq!(filtered.active_keys().collect::<Vec<_>>(), vec![0, 2, 3]);
+    /// ```
+    pub fn active_keys(&self) -> impl Iterator<Item = u32> + '_ {
+        let (bits, keys) = match &self.active {
+            Active::Bits(bits) => (Some(bits.ones().map(|key| key as u32)), None),
+            Active::Keys { sorted, .. } => (None, Some(sorted.iter().copied())),
+        };
+        bits.into_iter().flatten().chain(keys.into_iter().flatten())
+    }
+
+    /// Estimates the heap memory used by the view itself, in bytes.
+    ///
+    /// Only the bitset, or the hash set and sorted keys, of active nodes is counted, the
+    /// base graph is borrowed.
+    pub fn estimated_heap_size(&self) -> usize {
+        match &self.active {
+            Active::Bits(bits) => size_of_val(bits.as_slice()),
+            Active::Keys { set, sorted } => {
+                hash_set_bytes(set) + sorted.capacity() * size_of::<u32>()
+            }
+        }
+    }
+
+    /// Precomputes the active neighbors of every active node.
+    ///
+    /// The returned [`CompiledFilteredGraph`] exposes the same nodes and edges as this view,
+    /// but answers successor and predecessor queries from flat vectors instead of filtering
+    /// the base graph at each call. Compile a view when it is going to be traversed many times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base graph does not have sequential keys, since the compiled view
+    /// indexes its adjacency by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge, FilteredGraph};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = Graph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1), Node::new(2)],
+    ///     vec![Edge::new(0, 1), Edge::new(1, 2)],
+    /// );
+    ///
+    /// let compiled = FilteredGraph::new(&graph, vec![1, 2]).compile();
+    /// assert_eq!(compiled.get_successors_keys(1).collect::<Vec<_>>(), vec![2]);
+    /// ```
+    pub fn compile(&self) -> CompiledFilteredGraph<'a, N, E, G> {
+        match &self.active {
+            Active::Bits(bits) => CompiledFilteredGraph::new(self.base, bits.clone()),
+            Active::Keys { .. } => panic!("{}", INVALID_KEY_SEQUENCE),
+        }
     }
 }
 
-impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E> {
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphViewTrait<N, E>
+    for FilteredGraph<'_, N, E, G>
+{
     fn node_count(&self) -> usize {
-        self.active.count_ones(..)
+        self.node_count
+    }
+
+    // Counts the successors of active nodes instead of building every edge
+    fn edge_count(&self) -> usize {
+        self.active_keys()
+            .map(|source| {
+                self.base
+                    .get_successors_keys(source)
+                    .filter(|&target| self.is_active(target))
+                    .count()
+            })
+            .sum()
     }
 
     fn is_empty(&self) -> bool {
-        self.active.count_ones(..) == 0
+        self.node_count == 0
     }
 
     fn get_node(&self, key: u32) -> &N {
@@ -272,15 +394,11 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E
     where
         N: 'b,
     {
-        self.base
-            .get_nodes()
-            .filter(move |n| self.is_active(n.key()))
+        self.active_keys().map(move |key| self.base.get_node(key))
     }
 
     fn get_node_keys(&self) -> impl Iterator<Item = u32> {
-        self.base
-            .get_node_keys()
-            .filter(move |&k| self.is_active(k))
+        self.active_keys()
     }
 
     fn get_edge(&self, source: u32, target: u32) -> &E {
@@ -301,9 +419,12 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E
     where
         E: 'b,
     {
-        self.base
-            .get_edges()
-            .filter(move |e| self.is_active(e.source()) && self.is_active(e.target()))
+        self.active_keys().flat_map(move |source| {
+            self.base
+                .get_successors_keys(source)
+                .filter(move |&target| self.is_active(target))
+                .map(move |target| self.base.get_edge(source, target))
+        })
     }
 
     fn get_predecessors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
@@ -314,11 +435,9 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E
             panic!("Node {} not active in filtered graph", node_key);
         }
         self.base
-            .get_node(node_key)
-            .predecessors()
-            .iter()
-            .filter(move |&&pred| self.is_active(pred))
-            .map(move |&pred| self.base.get_node(pred))
+            .get_predecessors_keys(node_key)
+            .filter(move |&pred| self.is_active(pred))
+            .map(move |pred| self.base.get_node(pred))
     }
 
     fn get_successors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
@@ -329,11 +448,9 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E
             panic!("Node {} not active in filtered graph", node_key);
         }
         self.base
-            .get_node(node_key)
-            .successors()
-            .iter()
-            .filter(move |&&succ| self.is_active(succ))
-            .map(move |&succ| self.base.get_node(succ))
+            .get_successors_keys(node_key)
+            .filter(move |&succ| self.is_active(succ))
+            .map(move |succ| self.base.get_node(succ))
     }
 
     fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
@@ -355,39 +472,82 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for FilteredGraph<'_, N, E
     }
 
     fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
-        // Panic if the base graph does not have sequential keys
-        if !self.base.has_sequential_keys() {
-            panic!("{}", ade_common::INVALID_KEY_SEQUENCE);
+        // The base graph was checked when this view was created: intersect the requested
+        // nodes with the active ones and filter the base directly
+        match &self.active {
+            Active::Bits(bits) => {
+                let mut active = bitset(bits.len(), node_keys.iter().copied());
+                active.intersect_with(bits);
+                FilteredGraph::from_active(self.base, active)
+            }
+            Active::Keys { set, .. } => {
+                let active = node_keys
+                    .iter()
+                    .copied()
+                    .filter(|key| set.contains(key))
+                    .collect();
+                FilteredGraph::from_keys(self.base, active)
+            }
         }
-        
-        // Intersect the requested nodes with the currently active ones
-        let filtered_keys = node_keys.iter().copied().filter(|&key| self.is_active(key));
-
-        FilteredGraph::new(self.base, filtered_keys)
     }
 
     fn has_sequential_keys(&self) -> bool {
-        let size = self.active.count_ones(..);
-        if size == 0 {
-            return true;
+        match &self.active {
+            // Keys are sequential if all of the first `size` bits are set
+            Active::Bits(bits) => bits.count_ones(..self.node_count) == self.node_count,
+            // Sorted distinct keys are sequential if the last one is `size - 1`
+            Active::Keys { sorted, .. } => sorted
+                .last()
+                .is_none_or(|&last| last as usize + 1 == sorted.len()),
         }
+    }
+}
 
-        // Quick checks first - check if we have nodes 0 and size-1
-        if !self.is_active(0) || !self.is_active(size as u32 - 1) {
-            return false;
+// Bitset of the keys lower than `size`, ignoring the others
+pub(crate) fn bitset(size: usize, keys: impl IntoIterator<Item = u32>) -> FixedBitSet {
+    let mut active = FixedBitSet::with_capacity(size);
+    for key in keys {
+        if (key as usize) < size {
+            active.insert(key as usize);
         }
-
-        // Check if all keys from 0 to size-1 are active
-        (0..size as u32).all(|i| self.is_active(i))
     }
+    active
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::implementations::{Edge, Node};
+    use crate::implementations::{CsrGraph, Edge, Node};
+    use ade_common::assert_panics_with;
     use ade_traits::GraphViewTrait;
 
+    #[test]
+    fn test_keys_nodes_and_edges_follow_active_bits() {
+        let base_graph = Graph::<Node, Edge>::new(
+            (0..6).map(Node::new).collect(),
+            vec![
+                Edge::new(5, 0),
+                Edge::new(0, 2),
+                Edge::new(2, 5),
+                Edge::new(1, 2),
+                Edge::new(5, 5),
+            ],
+        );
+        let filtered = FilteredGraph::new(&base_graph, vec![5, 2, 0]);
+
+        assert_eq!(filtered.get_node_keys().collect::<Vec<_>>(), vec![0, 2, 5]);
+        assert_eq!(
+            filtered.get_nodes().map(|n| n.key()).collect::<Vec<_>>(),
+            vec![0, 2, 5]
+        );
+
+        let mut edges: Vec<(u32, u32)> = filtered.get_edges().map(|e| e.key()).collect();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(0, 2), (2, 5), (5, 0), (5, 5)]);
+        assert_eq!(filtered.edge_count(), 4);
+        assert_eq!(filtered.compile().edge_count(), 4);
+    }
+
     #[test]
     fn test_node_count() {
         let mut base_graph = Graph::<Node, Edge>::new(Vec::new(), Vec::new());
@@ -431,4 +591,75 @@ mod tests {
         let filtered = FilteredGraph::new(&base_graph, vec![2]);
         assert!(!filtered.has_sequential_keys());
     }
+
+    #[test]
+    fn test_filter_any_base_and_nested_filters() {
+        let graph = Graph::<Node, Edge>::new(
+            (0..6).map(Node::new).collect(),
+            vec![
+                Edge::new(0, 1),
+                Edge::new(1, 2),
+                Edge::new(2, 3),
+                Edge::new(3, 4),
+                Edge::new(4, 5),
+            ],
+        );
+        let csr = CsrGraph::from_view(&graph);
+        let filtered = FilteredGraph::new(&csr, vec![0, 1, 2, 3, 4]);
+        assert_eq!(filtered.edge_count(), 4);
+
+        // Keys outside the outer view, or outside the base graph, are dropped
+        let nested = filtered.filter(&[1, 2, 3, 5, 9]);
+        assert_eq!(nested.get_node_keys().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(nested.edge_count(), 2);
+
+        let twice = nested.filter(&[0, 2, 3]);
+        assert_eq!(twice.get_node_keys().collect::<Vec<_>>(), vec![2, 3]);
+        assert!(twice.has_edge(2, 3));
+        assert_eq!(filtered.compile().filter(&[3, 4, 5]).edge_count(), 1);
+    }
+
+    #[test]
+    fn test_non_sequential_keys() {
+        let base_graph = Graph::<Node, Edge>::new(
+            [7, 30, 12, 100].into_iter().map(Node::new).collect(),
+            vec![
+                Edge::new(7, 30),
+                Edge::new(30, 12),
+                Edge::new(12, 100),
+                Edge::new(100, 7),
+            ],
+        );
+        let filtered = FilteredGraph::new(&base_graph, vec![100, 12, 7, 5]);
+
+        assert_eq!(filtered.active_keys().collect::<Vec<_>>(), vec![7, 12, 100]);
+        assert_eq!(filtered.node_count(), 3);
+        assert_eq!(filtered.edge_count(), 2);
+        assert!(filtered.has_edge(100, 7) && !filtered.has_edge(7, 30));
+        assert!(filtered.estimated_heap_size() > 0);
+        assert_eq!(ade_traits::check_graph_consistency(&filtered), Ok(()));
+
+        let nested = filtered.filter(&[12, 30, 100]);
+        assert_eq!(nested.get_node_keys().collect::<Vec<_>>(), vec![12, 100]);
+        assert!(nested.has_edge(12, 100));
+        assert_panics_with!(filtered.compile(), INVALID_KEY_SEQUENCE);
+    }
+
+    #[test]
+    fn test_consistency() {
+        let base_graph = Graph::<Node, Edge>::new(
+            (0..5).map(Node::new).collect(),
+            vec![
+                Edge::new(0, 1),
+                Edge::new(1, 2),
+                Edge::new(2, 0),
+                Edge::new(3, 3),
+                Edge::new(4, 0),
+            ],
+        );
+        for active in [vec![], vec![0, 1, 2], vec![1, 3, 4], vec![0, 1, 2, 3, 4]] {
+            let filtered = FilteredGraph::new(&base_graph, active);
+            assert_eq!(ade_traits::check_graph_consistency(&filtered), Ok(()));
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use crate::implementations::FilteredGraph;
-use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
-use std::collections::HashMap;
+use crate::ops::{GraphOp, RecordingGraph};
+use crate::utils::heap_size::{hash_map_bytes, node_adjacency_bytes};
+use crate::utils::set_ops::MergePolicy;
+use ade_traits::{EdgeTrait, GraphError, GraphMutTrait, GraphViewTrait, NodeTrait};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
 /// A directed graph data structure with nodes and edges.
@@ -20,6 +23,22 @@ use std::fmt::Debug;
 /// * `N` - Node type implementing [`NodeTrait`]
 /// * `E` - Edge type implementing [`EdgeTrait`]
 ///
+/// # Serialization
+///
+/// With the `serde` feature, a graph serializes as an object with its `nodes` and its
+/// `edges`, each sorted by key. [`Node`](crate::implementations::Node) is written as its key
+/// and [`Edge`](crate::implementations::Edge) as its `[source, target]` pair, so a
+/// `Graph<Node, Edge>` in JSON looks like:
+///
+/// ```json
+/// {"nodes": [0, 1, 2], "edges": [[0, 1], [1, 2], [2, 0]]}
+/// ```
+///
+/// Other node and edge types are written with their own `Serialize` implementation; the
+/// neighbors of a node are never stored, since they follow from the edges. Deserialization
+/// goes through [`GraphBuilder`](crate::builder::GraphBuilder), so a duplicate node or edge,
+/// or an edge referencing a missing node, is reported as an error instead of a panic.
+///
 /// # Examples
 ///
 /// Creating a graph from vectors:
@@ -77,7 +96,7 @@ use std::fmt::Debug;
 /// assert!(!graph.has_edge(1, 2));
 /// assert!(!graph.has_edge(2, 3));
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Graph<N, E> {
     nodes: HashMap<u32, N>,
     edges: HashMap<(u32, u32), E>,
@@ -172,7 +191,7 @@ impl<N: NodeTrait, E: EdgeTrait> Graph<N, E> {
     /// // Adding a node with the same key returns the old node
     /// assert!(graph.add_node(Node::new(1)).is_some());
     /// ```
-    pub fn add_node(&mut self, node: N) ->  Option<N> {
+    pub fn add_node(&mut self, node: N) -> Option<N> {
         self.nodes.insert(node.key(), node)
     }
 
@@ -212,20 +231,20 @@ impl<N: NodeTrait, E: EdgeTrait> Graph<N, E> {
     pub fn remove_node(&mut self, key: u32) -> Option<N> {
         if let Some(node) = self.nodes.get(&key) {
             let mut edges_to_remove = Vec::new();
-    
+
             for &predecessor in node.predecessors() {
                 edges_to_remove.push((predecessor, key));
             }
-    
+
             for &successor in node.successors() {
                 edges_to_remove.push((key, successor));
             }
-    
+
             for (source, target) in edges_to_remove {
                 self.remove_edge(source, target);
             }
 
-            return self.nodes.remove(&key)
+            return self.nodes.remove(&key);
         }
         None
     }
@@ -271,25 +290,59 @@ impl<N: NodeTrait, E: EdgeTrait> Graph<N, E> {
     /// ```
     pub fn add_edge(&mut self, edge: E) -> Option<E> {
         let (source, target) = edge.key();
-        
+        self.try_add_edge(edge)
+            .unwrap_or_else(|_| missing_endpoint(source, target))
+    }
+
+    /// Adds an edge to the graph, or returns an error if one of its endpoints is missing.
+    ///
+    /// This is the fallible version of [`add_edge`](Graph::add_edge), for code loading
+    /// edges from untrusted data. The graph is unchanged when an error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(old_edge))` - If an edge with the same key already existed
+    /// * `Ok(None)` - If no edge with this key existed previously
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] with the key of the source, or else of the
+    /// target, if it does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::{GraphError, GraphViewTrait};
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![Node::new(1), Node::new(2)], vec![]);
+    ///
+    /// assert!(graph.try_add_edge(Edge::new(1, 2)).unwrap().is_none());
+    /// assert_eq!(graph.try_add_edge(Edge::new(2, 3)).unwrap_err(), GraphError::MissingNode(3));
+    /// assert!(!graph.has_edge(2, 3));
+    /// ```
+    pub fn try_add_edge(&mut self, edge: E) -> Result<Option<E>, GraphError> {
+        let (source, target) = edge.key();
+
         if source == target {
-            match self.nodes.get_mut(&source) {
+            return match self.nodes.get_mut(&source) {
                 Some(node) => {
                     node.add_successor(target);
                     node.add_predecessor(source);
-                    return self.edges.insert((source, target), edge)
+                    Ok(self.edges.insert((source, target), edge))
                 }
-                None => panic!("Node {} not found", source)
-            }
+                None => Err(GraphError::MissingNode(source)),
+            };
         }
-        
+
         match self.nodes.get_disjoint_mut([&source, &target]) {
             [Some(source_node), Some(target_node)] => {
                 source_node.add_successor(target);
                 target_node.add_predecessor(source);
-                self.edges.insert((source, target), edge)
+                Ok(self.edges.insert((source, target), edge))
             }
-            _ => panic!("Node {} or {} not found", source, target)
+            [None, _] => Err(GraphError::MissingNode(source)),
+            [_, None] => Err(GraphError::MissingNode(target)),
         }
     }
 
@@ -330,23 +383,515 @@ impl<N: NodeTrait, E: EdgeTrait> Graph<N, E> {
     /// // Trying to remove it again returns None
     /// assert!(graph.remove_edge(1, 2).is_none());
     /// ```
-    pub fn remove_edge(&mut self, source:u32, target:u32) -> Option<E> {
-        if !self.nodes.contains_key(&source) || !self.nodes.contains_key(&target) {
-            panic!("Node {} or {} not found", source, target);
+    pub fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        match self.try_remove_edge(source, target) {
+            Ok(edge) => Some(edge),
+            Err(GraphError::MissingEdge(..)) => None,
+            Err(GraphError::MissingNode(_)) => missing_endpoint(source, target),
+        }
+    }
+
+    /// Removes an edge from the graph, or returns an error if it does not exist.
+    ///
+    /// This is the fallible version of [`remove_edge`](Graph::remove_edge).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] with the key of the source, or else of the
+    /// target, if it does not exist in the graph, and [`GraphError::MissingEdge`] if both
+    /// nodes exist but the edge does not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::GraphError;
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(
+    ///     vec![Node::new(1), Node::new(2)],
+    ///     vec![Edge::new(1, 2)],
+    /// );
+    ///
+    /// assert!(graph.try_remove_edge(1, 2).is_ok());
+    /// assert_eq!(graph.try_remove_edge(1, 2).unwrap_err(), GraphError::MissingEdge(1, 2));
+    /// assert_eq!(graph.try_remove_edge(1, 3).unwrap_err(), GraphError::MissingNode(3));
+    /// ```
+    pub fn try_remove_edge(&mut self, source: u32, target: u32) -> Result<E, GraphError> {
+        if let Some(&key) = [source, target]
+            .iter()
+            .find(|key| !self.nodes.contains_key(key))
+        {
+            return Err(GraphError::MissingNode(key));
+        }
+
+        let edge = self
+            .edges
+            .remove(&(source, target))
+            .ok_or(GraphError::MissingEdge(source, target))?;
+        if let Some(source_node) = self.nodes.get_mut(&source) {
+            source_node.remove_successor(target);
+        }
+        if let Some(target_node) = self.nodes.get_mut(&target) {
+            target_node.remove_predecessor(source);
+        }
+        Ok(edge)
+    }
+
+    /// Adds the nodes and edges of `other` to the graph, resolving the nodes and edges
+    /// present in both with `policy`.
+    ///
+    /// The nodes and edges of `other` are moved, not cloned. A node taken from `other`
+    /// keeps the predecessors and successors it has in this graph, plus those given by the
+    /// edges of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, WeightedEdge};
+    /// use ade_graph::utils::set_ops::MergePolicy;
+    /// use ade_graph::{GraphViewTrait, WeightedEdgeTrait};
+    ///
+    /// let mut graph = Graph::new(
+    ///     vec![Node::new(0), Node::new(1)],
+    ///     vec![WeightedEdge::with_weight(0, 1, 1)],
+    /// );
+    /// let other = Graph::new(
+    ///     vec![Node::new(1), Node::new(2)],
+    ///     vec![WeightedEdge::with_weight(1, 2, 5)],
+    /// );
+    ///
+    /// graph.merge(other, MergePolicy::KeepExisting);
+    /// assert_eq!(graph.node_count(), 3);
+    /// assert!(graph.has_edge(0, 1) && graph.has_edge(1, 2));
+    /// ```
+    pub fn merge(&mut self, other: Graph<N, E>, policy: MergePolicy) {
+        let (nodes, edges) = other.into_parts();
+
+        for mut node in nodes {
+            clear_neighbors(&mut node);
+            match self.nodes.get(&node.key()) {
+                None => {
+                    self.nodes.insert(node.key(), node);
+                }
+                Some(existing) if policy == MergePolicy::Replace => {
+                    for &predecessor in existing.predecessors() {
+                        node.add_predecessor(predecessor);
+                    }
+                    for &successor in existing.successors() {
+                        node.add_successor(successor);
+                    }
+                    self.nodes.insert(node.key(), node);
+                }
+                Some(_) => {}
+            }
+        }
+
+        for edge in edges {
+            if policy == MergePolicy::Replace || !self.edges.contains_key(&edge.key()) {
+                self.add_edge(edge);
+            }
+        }
+    }
+
+    /// Contracts the edge from `source` to `target`, merging `target` into `source`.
+    ///
+    /// The edge is removed, and the other edges of `target` are redirected to `source` as in
+    /// [`merge_nodes`](Graph::merge_nodes), which keeps the payload of `source`. An edge from
+    /// `target` back to `source` becomes a self-loop, kept only if `keep_self_loops` is
+    /// `true`. Contracting a self-loop removes it and leaves the nodes unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The contracted edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node or the edge does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (1, 0)]);
+    /// graph.contract_edge(0, 1, false);
+    ///
+    /// assert_eq!(graph.node_count(), 2);
+    /// assert!(graph.has_edge(0, 2));
+    /// assert!(!graph.has_edge(0, 0));
+    /// ```
+    pub fn contract_edge(&mut self, source: u32, target: u32, keep_self_loops: bool) -> E {
+        let edge = self
+            .remove_edge(source, target)
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target));
+        if source != target {
+            self.merge_nodes(&[source, target], source, keep_self_loops);
+        }
+        edge
+    }
+
+    /// Replaces the nodes in `keys` with a single node `new_key`, redirecting their edges.
+    ///
+    /// The new node is the one already keyed `new_key` if it is among `keys`, and otherwise
+    /// a copy of the node `keys[0]` made with [`NodeTrait::with_key`]. Each edge to or from
+    /// a merged node is moved to `new_key` with [`EdgeTrait::with_endpoints`]. Edges between
+    /// two different merged nodes become self-loops, kept only if `keep_self_loops` is
+    /// `true`; self-loops already on a merged node are always kept. When several edges end
+    /// up between the same nodes, the one with the smallest original `(source, target)` key
+    /// is kept.
+    ///
+    /// Nothing happens if `keys` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node in `keys` does not exist, or if `new_key` is the key of a node that
+    /// is not in `keys`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = build_graph::<Node, Edge>(
+    ///     vec![0, 1, 2, 3],
+    ///     vec![(0, 1), (0, 2), (1, 3), (2, 3)],
+    /// );
+    /// graph.merge_nodes(&[1, 2], 10, false);
+    ///
+    /// assert_eq!(graph.node_count(), 3);
+    /// assert!(graph.has_edge(0, 10) && graph.has_edge(10, 3));
+    /// assert_eq!(graph.edge_count(), 2);
+    /// ```
+    pub fn merge_nodes(&mut self, keys: &[u32], new_key: u32, keep_self_loops: bool) {
+        for &key in keys {
+            if !self.nodes.contains_key(&key) {
+                panic!("Node {} not found", key);
+            }
+        }
+        let merged: HashSet<u32> = keys.iter().copied().collect();
+        if merged.is_empty() {
+            return;
+        }
+        if !merged.contains(&new_key) && self.nodes.contains_key(&new_key) {
+            panic!("Node {} already exists", new_key);
+        }
+
+        // Sorted, so that the smallest original key wins among edges that collapse
+        let mut incident = Vec::new();
+        for key in &merged {
+            let node = &self.nodes[key];
+            incident.extend(
+                node.predecessors()
+                    .iter()
+                    .map(|&predecessor| (predecessor, *key)),
+            );
+            incident.extend(node.successors().iter().map(|&successor| (*key, successor)));
+        }
+        incident.sort_unstable();
+        incident.dedup();
+        let edges: Vec<E> = incident
+            .into_iter()
+            .filter_map(|(source, target)| self.remove_edge(source, target))
+            .collect();
+
+        let node = if merged.contains(&new_key) {
+            self.nodes.remove(&new_key).expect("merged node exists")
+        } else {
+            self.nodes[&keys[0]].with_key(new_key)
+        };
+        self.nodes.retain(|key, _| !merged.contains(key));
+        self.add_node(node);
+
+        let redirect = |key: u32| if merged.contains(&key) { new_key } else { key };
+        for edge in edges {
+            let (source, target) = (redirect(edge.source()), redirect(edge.target()));
+            let introduced_loop = source == target && edge.source() != edge.target();
+            if introduced_loop && !keep_self_loops {
+                continue;
+            }
+            if self.edges.contains_key(&(source, target)) {
+                continue;
+            }
+            if edge.key() == (source, target) {
+                self.add_edge(edge);
+            } else {
+                self.add_edge(edge.with_endpoints(source, target));
+            }
+        }
+    }
+
+    /// Keeps only the nodes for which `keep` returns `true`, removing the others with their
+    /// edges.
+    ///
+    /// `keep` is called once per node. The removed nodes are unlinked from their remaining
+    /// neighbors in a single pass, in time proportional to the number of nodes plus the
+    /// degrees of the removed nodes, instead of calling [`remove_node`](Graph::remove_node)
+    /// for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::{GraphViewTrait, NodeTrait};
+    ///
+    /// let mut graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3)]);
+    /// graph.retain_nodes(|node| node.key() != 3);
+    ///
+    /// assert_eq!(graph.node_count(), 3);
+    /// assert!(graph.has_edge(1, 2));
+    /// assert_eq!(graph.get_successors_keys(2).count(), 0);
+    /// ```
+    pub fn retain_nodes(&mut self, mut keep: impl FnMut(&N) -> bool) {
+        let removed: HashSet<u32> = self
+            .nodes
+            .values()
+            .filter(|node| !keep(node))
+            .map(|node| node.key())
+            .collect();
+        if removed.is_empty() {
+            return;
         }
 
-        match self.edges.remove(&(source, target)) {
-            Some(edge) => {
-                if let Some(source_node) = self.nodes.get_mut(&source) {
-                    source_node.remove_successor(target);
+        // Edges between a removed node and a kept one, to unlink the kept endpoint
+        let mut cut = Vec::new();
+        for key in &removed {
+            let node = &self.nodes[key];
+            for &predecessor in node.predecessors() {
+                self.edges.remove(&(predecessor, *key));
+                cut.push((predecessor, *key));
+            }
+            for &successor in node.successors() {
+                self.edges.remove(&(*key, successor));
+                cut.push((*key, successor));
+            }
+        }
+        for (source, target) in cut {
+            if !removed.contains(&source) {
+                if let Some(node) = self.nodes.get_mut(&source) {
+                    node.remove_successor(target);
                 }
-                if let Some(target_node) = self.nodes.get_mut(&target) {
-                    target_node.remove_predecessor(source);
+            }
+            if !removed.contains(&target) {
+                if let Some(node) = self.nodes.get_mut(&target) {
+                    node.remove_predecessor(source);
                 }
-                Some(edge)
             }
-            None => None,
         }
+
+        self.nodes.retain(|key, _| !removed.contains(key));
+    }
+
+    /// Keeps only the edges for which `keep` returns `true`.
+    ///
+    /// `keep` is called once per edge, and the endpoints of each removed edge are updated
+    /// in the same pass. All nodes are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::{EdgeTrait, GraphViewTrait};
+    ///
+    /// let mut graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0), (1, 1)]);
+    /// graph.retain_edges(|edge| edge.source() != edge.target());
+    ///
+    /// assert_eq!(graph.edge_count(), 2);
+    /// assert_eq!(graph.get_successors_keys(1).collect::<Vec<_>>(), vec![0]);
+    /// ```
+    pub fn retain_edges(&mut self, mut keep: impl FnMut(&E) -> bool) {
+        let nodes = &mut self.nodes;
+        self.edges.retain(|&(source, target), edge| {
+            if keep(edge) {
+                return true;
+            }
+            if let Some(node) = nodes.get_mut(&source) {
+                node.remove_successor(target);
+            }
+            if let Some(node) = nodes.get_mut(&target) {
+                node.remove_predecessor(source);
+            }
+            false
+        });
+    }
+
+    /// Estimates the heap memory used by the graph, in bytes.
+    ///
+    /// Sums the allocations of the node and edge maps and of the predecessor and successor
+    /// sets of every node. The result is an estimate based on the capacity of each hash
+    /// table, and does not include heap data owned by custom node or edge types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    ///
+    /// let empty = Graph::<Node, Edge>::new(vec![], vec![]);
+    /// assert_eq!(empty.estimated_heap_size(), 0);
+    ///
+    /// let graph = Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![Edge::new(0, 1)]);
+    /// assert!(graph.estimated_heap_size() > 0);
+    /// ```
+    pub fn estimated_heap_size(&self) -> usize {
+        hash_map_bytes(&self.nodes)
+            + hash_map_bytes(&self.edges)
+            + self.nodes.values().map(node_adjacency_bytes).sum::<usize>()
+    }
+
+    /// Applies a single operation to the graph.
+    ///
+    /// Calls the modification method matching `op`, and discards the value it returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation adds or removes an edge whose source or target node does not
+    /// exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::ops::GraphOp;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+    /// graph.apply(GraphOp::AddEdge(Edge::new(1, 1)));
+    /// assert!(graph.has_edge(1, 1));
+    /// ```
+    pub fn apply(&mut self, op: GraphOp<N, E>) {
+        match op {
+            GraphOp::AddNode(node) => {
+                self.add_node(node);
+            }
+            GraphOp::RemoveNode(key) => {
+                self.remove_node(key);
+            }
+            GraphOp::AddEdge(edge) => {
+                self.add_edge(edge);
+            }
+            GraphOp::RemoveEdge(source, target) => {
+                self.remove_edge(source, target);
+            }
+        }
+    }
+
+    /// Applies a sequence of operations to the graph, in order.
+    ///
+    /// Replaying the operations recorded on a graph onto a copy of its initial state gives
+    /// the same graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an operation adds or removes an edge whose source or target node does not
+    /// exist in the graph. The operations before it have been applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::ops::GraphOp;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+    /// graph.replay([GraphOp::AddNode(Node::new(1)), GraphOp::RemoveNode(1)]);
+    /// assert!(graph.is_empty());
+    /// ```
+    pub fn replay(&mut self, ops: impl IntoIterator<Item = GraphOp<N, E>>) {
+        for op in ops {
+            self.apply(op);
+        }
+    }
+
+    /// Starts recording the modifications of the graph.
+    ///
+    /// The returned [`RecordingGraph`] borrows the graph mutably and logs each modification
+    /// made through it as a [`GraphOp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+    /// let mut recorder = graph.record();
+    /// recorder.add_node(Node::new(1));
+    /// recorder.remove_node(1);
+    /// assert_eq!(recorder.finish().len(), 2);
+    /// ```
+    pub fn record(&mut self) -> RecordingGraph<'_, N, E> {
+        RecordingGraph::new(self)
+    }
+
+    /// Consumes the graph and returns its nodes and edges, without cloning them.
+    ///
+    /// Nodes are sorted by key and edges by `(source, target)`, so the result does not
+    /// depend on the internal hash order. The nodes keep their predecessors and successors;
+    /// [`from_parts`](Graph::from_parts) rebuilds them from the edges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_graph::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::<Node, Edge>::new(
+    ///     vec![Node::new(2), Node::new(1)],
+    ///     vec![Edge::new(2, 1), Edge::new(1, 2)],
+    /// );
+    /// let (nodes, mut edges) = graph.into_parts();
+    /// assert_eq!(nodes.iter().map(|node| node.key()).collect::<Vec<_>>(), vec![1, 2]);
+    ///
+    /// // Drop an edge and rebuild the graph
+    /// edges.retain(|edge| edge.source() == 1);
+    /// let graph = Graph::from_parts(nodes, edges).unwrap();
+    /// assert_eq!(graph.get_successors_keys(2).count(), 0);
+    /// ```
+    pub fn into_parts(self) -> (Vec<N>, Vec<E>) {
+        let mut nodes: Vec<N> = self.nodes.into_values().collect();
+        nodes.sort_unstable_by_key(|node| node.key());
+        let mut edges: Vec<E> = self.edges.into_values().collect();
+        edges.sort_unstable_by_key(|edge| edge.key());
+        (nodes, edges)
+    }
+
+    /// Builds a graph from nodes and edges, such as those returned by
+    /// [`into_parts`](Graph::into_parts), without cloning them.
+    ///
+    /// Unlike [`new`](Graph::new), the predecessors and successors already stored in the
+    /// nodes are discarded and rebuilt from `edges`, so nodes taken from another graph can
+    /// be reused with a different set of edges. Duplicate nodes or edges replace earlier
+    /// ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] for the first edge whose source, or else target,
+    /// is not in `nodes`.
+    pub fn from_parts(nodes: Vec<N>, edges: Vec<E>) -> Result<Self, GraphError> {
+        let mut graph = Graph {
+            nodes: HashMap::with_capacity(nodes.len()),
+            edges: HashMap::with_capacity(edges.len()),
+        };
+
+        for mut node in nodes {
+            clear_neighbors(&mut node);
+            graph.add_node(node);
+        }
+
+        for edge in edges {
+            graph.try_add_edge(edge)?;
+        }
+
+        Ok(graph)
+    }
+
+    /// Consumes the graph and returns its node and edge maps.
+    pub(crate) fn into_maps(self) -> (HashMap<u32, N>, HashMap<(u32, u32), E>) {
+        (self.nodes, self.edges)
     }
 }
 
@@ -355,6 +900,10 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for Graph<N, E> {
         self.nodes.len()
     }
 
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
     fn is_empty(&self) -> bool {
         self.nodes.is_empty()
     }
@@ -442,15 +991,137 @@ impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for Graph<N, E> {
         self.get_node(node_key).successors().iter().copied()
     }
 
+    fn get_out_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        // Both endpoints of every edge exist, so the maps are indexed without checks
+        self.get_node(node_key)
+            .successors()
+            .iter()
+            .map(move |target| (&self.edges[&(node_key, *target)], &self.nodes[target]))
+    }
+
+    fn get_in_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        self.get_node(node_key)
+            .predecessors()
+            .iter()
+            .map(move |source| (&self.edges[&(*source, node_key)], &self.nodes[source]))
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).predecessors().len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).successors().len()
+    }
+
     fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
-        // Panic if the graph does not have sequential keys
-        if !self.has_sequential_keys() {
-            panic!("{}", ade_common::INVALID_KEY_SEQUENCE);
-        }
         FilteredGraph::new(self, node_keys.iter().copied())
     }
 }
 
+impl<N: NodeTrait, E: EdgeTrait> GraphMutTrait<N, E> for Graph<N, E> {
+    fn add_node(&mut self, node: N) -> Option<N> {
+        Graph::add_node(self, node)
+    }
+
+    fn remove_node(&mut self, key: u32) -> Option<N> {
+        Graph::remove_node(self, key)
+    }
+
+    fn add_edge(&mut self, edge: E) -> Option<E> {
+        Graph::add_edge(self, edge)
+    }
+
+    fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        Graph::remove_edge(self, source, target)
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+    }
+
+    fn try_add_edge(&mut self, edge: E) -> Result<Option<E>, GraphError> {
+        Graph::try_add_edge(self, edge)
+    }
+
+    fn try_remove_edge(&mut self, source: u32, target: u32) -> Result<E, GraphError> {
+        Graph::try_remove_edge(self, source, target)
+    }
+}
+
+// Removes the predecessors and successors stored in a node
+fn clear_neighbors<N: NodeTrait>(node: &mut N) {
+    let predecessors: Vec<u32> = node.predecessors().iter().copied().collect();
+    let successors: Vec<u32> = node.successors().iter().copied().collect();
+    for key in predecessors {
+        node.remove_predecessor(key);
+    }
+    for key in successors {
+        node.remove_successor(key);
+    }
+}
+
+// Panics for an edge whose source or target is missing, naming both endpoints
+pub(crate) fn missing_endpoint(source: u32, target: u32) -> ! {
+    if source == target {
+        panic!("Node {} not found", source);
+    }
+    panic!("Node {} or {} not found", source, target)
+}
+
+#[cfg(feature = "serde")]
+impl<N: NodeTrait + serde::Serialize, E: EdgeTrait + serde::Serialize> serde::Serialize
+    for Graph<N, E>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        // Sorted, so that equal graphs serialize identically
+        let mut nodes: Vec<&N> = self.nodes.values().collect();
+        nodes.sort_unstable_by_key(|node| node.key());
+        let mut edges: Vec<&E> = self.edges.values().collect();
+        edges.sort_unstable_by_key(|edge| edge.key());
+
+        let mut state = serializer.serialize_struct("Graph", 2)?;
+        state.serialize_field("nodes", &nodes)?;
+        state.serialize_field("edges", &edges)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N, E> serde::Deserialize<'de> for Graph<N, E>
+where
+    N: NodeTrait + serde::Deserialize<'de>,
+    E: EdgeTrait + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(bound(deserialize = "N: serde::Deserialize<'de>, E: serde::Deserialize<'de>"))]
+        struct GraphData<N, E> {
+            nodes: Vec<N>,
+            edges: Vec<E>,
+        }
+
+        let data = GraphData::<N, E>::deserialize(deserializer)?;
+        // Nodes start without neighbors, whatever their serialized form holds
+        crate::builder::GraphBuilder::new()
+            .nodes(data.nodes.iter().map(|node| node.with_key(node.key())))
+            .edges(data.edges)
+            .build()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 use std::fmt;
 
 impl<N: NodeTrait, E: EdgeTrait> fmt::Display for Graph<N, E> {
@@ -480,7 +1151,26 @@ mod tests {
     use super::*;
     use crate::implementations::edge::Edge;
     use crate::implementations::node::Node;
+    use crate::implementations::{DataNode, WeightedEdge};
+    use crate::utils::compare::structural_eq;
     use ade_common::assert_panics_with;
+    use ade_traits::{DataNodeTrait, WeightedEdgeTrait};
+
+    #[test]
+    fn test_estimated_heap_size() {
+        let small =
+            Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![Edge::new(0, 1)]);
+        let nodes = (0..100).map(Node::new).collect();
+        let edges = (0..99).map(|i| Edge::new(i, i + 1)).collect();
+        let large = Graph::<Node, Edge>::new(nodes, edges);
+
+        assert!(large.estimated_heap_size() > small.estimated_heap_size());
+        assert!(
+            large.estimated_heap_size()
+                >= 100 * std::mem::size_of::<(u32, Node)>()
+                    + 99 * std::mem::size_of::<((u32, u32), Edge)>()
+        );
+    }
 
     #[test]
     fn test_node_count() {
@@ -602,7 +1292,7 @@ mod tests {
 
         assert!(graph.add_edge(Edge::new(1, 1)).is_none()); // Adding a new edge should return None
         assert!(graph.add_edge(Edge::new(1, 1)).is_some()); // Adding the same edge again should return Some(Edge)
-        
+
         assert!(graph.has_edge(1, 1));
 
         // Check predecessors and successors
@@ -717,6 +1407,212 @@ mod tests {
         graph.remove_edge(1, 3);
     }
 
+    #[test]
+    fn test_try_add_and_remove_edge() {
+        let mut graph = Graph::<Node, Edge>::new(vec![Node::new(1), Node::new(2)], Vec::new());
+
+        assert_eq!(
+            graph.try_add_edge(Edge::new(3, 1)).unwrap_err(),
+            GraphError::MissingNode(3)
+        );
+        assert_eq!(
+            graph.try_add_edge(Edge::new(1, 4)).unwrap_err(),
+            GraphError::MissingNode(4)
+        );
+        assert_eq!(
+            graph.try_add_edge(Edge::new(5, 5)).unwrap_err(),
+            GraphError::MissingNode(5)
+        );
+        assert_eq!(graph.edge_count(), 0);
+
+        assert!(graph.try_add_edge(Edge::new(1, 2)).unwrap().is_none());
+        assert!(graph.try_add_edge(Edge::new(1, 2)).unwrap().is_some());
+        assert!(graph.try_add_edge(Edge::new(2, 2)).unwrap().is_none());
+
+        assert_eq!(
+            graph.try_remove_edge(2, 1).unwrap_err(),
+            GraphError::MissingEdge(2, 1)
+        );
+        assert_eq!(
+            graph.try_remove_edge(2, 6).unwrap_err(),
+            GraphError::MissingNode(6)
+        );
+        assert_eq!(graph.try_remove_edge(1, 2).unwrap().key(), (1, 2));
+        assert_eq!(graph.try_remove_edge(2, 2).unwrap().key(), (2, 2));
+        assert_eq!(graph.edge_count(), 0);
+        assert!(graph.get_node(1).successors().is_empty());
+        assert!(graph.get_node(2).predecessors().is_empty());
+    }
+
+    #[test]
+    fn test_into_and_from_parts() {
+        let graph = Graph::<Node, Edge>::new(
+            vec![Node::new(3), Node::new(1), Node::new(2)],
+            vec![Edge::new(3, 1), Edge::new(1, 2), Edge::new(2, 2)],
+        );
+        let copy = graph.clone();
+        let (nodes, edges) = graph.into_parts();
+        assert_eq!(
+            nodes.iter().map(|node| node.key()).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            edges.iter().map(|edge| edge.key()).collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 1)]
+        );
+
+        let rebuilt = Graph::from_parts(nodes.clone(), edges).unwrap();
+        assert!(structural_eq(&rebuilt, &copy));
+
+        // Stale neighbors are dropped
+        let rebuilt = Graph::from_parts(nodes.clone(), vec![Edge::new(1, 2)]).unwrap();
+        assert_eq!(ade_traits::check_graph_consistency(&rebuilt), Ok(()));
+        assert!(rebuilt.get_node(3).successors().is_empty());
+        assert_eq!(
+            Graph::from_parts(nodes, vec![Edge::new(1, 9)]).unwrap_err(),
+            GraphError::MissingNode(9)
+        );
+    }
+
+    #[test]
+    fn test_retain_nodes_and_edges() {
+        let mut graph = Graph::<Node, Edge>::new(
+            (0..6).map(Node::new).collect(),
+            vec![
+                Edge::new(0, 1),
+                Edge::new(1, 2),
+                Edge::new(2, 0),
+                Edge::new(2, 3),
+                Edge::new(3, 3),
+                Edge::new(3, 4),
+                Edge::new(4, 5),
+                Edge::new(5, 3),
+            ],
+        );
+
+        let mut calls = 0;
+        graph.retain_nodes(|node| {
+            calls += 1;
+            node.key() != 3 && node.key() != 4
+        });
+        assert_eq!(calls, 6);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 3);
+        assert!(graph.get_node(5).successors().is_empty());
+        assert_eq!(graph.get_successors_keys(2).collect::<Vec<_>>(), vec![0]);
+
+        graph.retain_edges(|edge| edge.source() != 2);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.get_node(0).predecessors().is_empty());
+
+        graph.retain_nodes(|_| true);
+        graph.retain_edges(|_| false);
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 0));
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    #[test]
+    fn test_merge() {
+        let build = || {
+            Graph::new(
+                vec![DataNode::with_data(0, "a"), DataNode::with_data(1, "b")],
+                vec![
+                    WeightedEdge::with_weight(0, 1, 1),
+                    WeightedEdge::with_weight(1, 1, 2),
+                ],
+            )
+        };
+        let other = Graph::new(
+            vec![DataNode::with_data(1, "B"), DataNode::with_data(2, "C")],
+            vec![
+                WeightedEdge::with_weight(1, 1, 20),
+                WeightedEdge::with_weight(2, 1, 30),
+            ],
+        );
+
+        let mut kept = build();
+        kept.merge(other.clone(), MergePolicy::KeepExisting);
+        assert_eq!(ade_traits::check_graph_consistency(&kept), Ok(()));
+        assert_eq!((kept.node_count(), kept.edge_count()), (3, 3));
+        assert_eq!(*kept.get_node(1).data(), "b");
+        assert_eq!(kept.get_edge(1, 1).weight(), 2);
+
+        let mut replaced = build();
+        replaced.merge(other, MergePolicy::Replace);
+        assert_eq!(ade_traits::check_graph_consistency(&replaced), Ok(()));
+        assert_eq!(*replaced.get_node(1).data(), "B");
+        assert_eq!(replaced.get_edge(1, 1).weight(), 20);
+        // Node 1 keeps its edge from node 0
+        assert!(replaced.get_node(1).predecessors().contains(&0));
+    }
+
+    #[test]
+    fn test_merge_nodes() {
+        let build = || {
+            Graph::new(
+                (0..4)
+                    .map(|key| DataNode::with_data(key, key * 10))
+                    .collect(),
+                vec![
+                    WeightedEdge::with_weight(0, 1, 1),
+                    WeightedEdge::with_weight(0, 2, 2),
+                    WeightedEdge::with_weight(1, 2, 3),
+                    WeightedEdge::with_weight(2, 2, 4),
+                    WeightedEdge::with_weight(2, 3, 5),
+                ],
+            )
+        };
+
+        let mut graph = build();
+        graph.merge_nodes(&[2, 1], 7, false);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+        assert_eq!(graph.get_node_keys().count(), 3);
+        assert_eq!(*graph.get_node(7).data(), 20);
+        // 0→1 and 0→2 collapse, keeping the smallest original key
+        assert_eq!(graph.get_edge(0, 7).weight(), 1);
+        assert_eq!(graph.get_edge(7, 3).weight(), 5);
+        // The existing self-loop on 2 is kept, the one introduced by 1→2 is not
+        assert_eq!(graph.get_edge(7, 7).weight(), 4);
+        assert_eq!(graph.edge_count(), 3);
+
+        let mut graph = build();
+        graph.merge_nodes(&[1, 2, 1], 1, true);
+        assert_eq!(*graph.get_node(1).data(), 10);
+        assert_eq!(graph.get_edge(1, 1).weight(), 3);
+        assert!(!graph.has_node(2));
+
+        graph.merge_nodes(&[], 9, true);
+        assert!(!graph.has_node(9));
+        assert_panics_with!(build().merge_nodes(&[1, 5], 1, true), "Node 5 not found");
+        assert_panics_with!(
+            build().merge_nodes(&[1, 2], 0, true),
+            "Node 0 already exists"
+        );
+    }
+
+    #[test]
+    fn test_contract_edge() {
+        let mut graph = Graph::<Node, Edge>::new(
+            (0..3).map(Node::new).collect(),
+            vec![
+                Edge::new(0, 1),
+                Edge::new(1, 0),
+                Edge::new(1, 2),
+                Edge::new(2, 2),
+            ],
+        );
+        assert_eq!(graph.contract_edge(2, 2, false).key(), (2, 2));
+        assert_eq!(graph.node_count(), 3);
+
+        assert_eq!(graph.contract_edge(0, 1, true).key(), (0, 1));
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+        assert!(graph.has_edge(0, 0) && graph.has_edge(0, 2));
+        assert!(!graph.has_node(1));
+        assert_panics_with!(graph.contract_edge(2, 0, true), "Edge 2→0 not found");
+    }
+
     #[test]
     fn test_remove_node() {
         let mut graph = Graph::<Node, Edge>::new(Vec::new(), Vec::new());
@@ -754,8 +1650,6 @@ mod tests {
         let node_c = graph.get_node(3);
         assert!(node_c.predecessors().contains(&2));
         assert!(!node_c.successors().contains(&1));
-
-
     }
 
     #[test]
@@ -790,6 +1684,31 @@ mod tests {
         assert!(graph.get_successors(1).any(|n| n.key() == 3));
     }
 
+    #[test]
+    fn test_get_out_and_in_edges() {
+        let graph = Graph::<Node, Edge>::new(
+            vec![Node::new(1), Node::new(2), Node::new(3)],
+            vec![Edge::new(1, 2), Edge::new(1, 3), Edge::new(3, 2)],
+        );
+
+        let mut out: Vec<((u32, u32), u32)> = graph
+            .get_out_edges(1)
+            .map(|(edge, target)| (edge.key(), target.key()))
+            .collect();
+        out.sort_unstable();
+        assert_eq!(out, vec![((1, 2), 2), ((1, 3), 3)]);
+
+        let mut into: Vec<((u32, u32), u32)> = graph
+            .get_in_edges(2)
+            .map(|(edge, source)| (edge.key(), source.key()))
+            .collect();
+        into.sort_unstable();
+        assert_eq!(into, vec![((1, 2), 1), ((3, 2), 3)]);
+
+        assert_eq!(graph.get_out_edges(2).count(), 0);
+        assert_panics_with!(graph.get_out_edges(4).count(), "Node 4 not found");
+    }
+
     #[test]
     fn test_get_successors_keys() {
         let mut graph = Graph::<Node, Edge>::new(Vec::new(), Vec::new());
@@ -862,11 +1781,16 @@ mod tests {
         // Verify graph does NOT have sequential keys
         assert!(!graph.has_sequential_keys());
 
-        // Attempting to filter should panic
-        assert_panics_with!(
-            graph.filter(&[1, 3]),
-            ade_common::INVALID_KEY_SEQUENCE
-        );
+        // The filtered view falls back to a hash set of keys
+        let filtered = graph.filter(&[1, 3, 4]);
+        assert_eq!(filtered.node_count(), 2);
+        assert!(filtered.has_node(1) && filtered.has_node(3) && !filtered.has_node(5));
+        assert!(filtered.has_edge(1, 3));
+        assert!(!filtered.has_edge(3, 5));
+        assert_eq!(filtered.get_successors_keys(3).count(), 0);
+        assert!(!filtered.has_sequential_keys());
+        assert!(filtered.filter(&[3, 5]).has_node(3));
+        assert_eq!(filtered.filter(&[3, 5]).node_count(), 1);
     }
 
     #[test]
@@ -921,4 +1845,189 @@ mod tests {
 
         assert!(graph.has_sequential_keys());
     }
+
+    #[test]
+    fn test_consistency() {
+        let mut graph = Graph::<Node, Edge>::new(
+            vec![Node::new(2), Node::new(5), Node::new(7)],
+            vec![
+                Edge::new(2, 5),
+                Edge::new(5, 7),
+                Edge::new(7, 2),
+                Edge::new(5, 5),
+            ],
+        );
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+
+        graph.remove_node(5);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    // Delegates to a graph but forgets the predecessors of one node
+    struct MissingPredecessors {
+        graph: Graph<Node, Edge>,
+        node: u32,
+    }
+
+    impl GraphViewTrait<Node, Edge> for MissingPredecessors {
+        fn node_count(&self) -> usize {
+            self.graph.node_count()
+        }
+        fn is_empty(&self) -> bool {
+            self.graph.is_empty()
+        }
+        fn get_node(&self, key: u32) -> &Node {
+            self.graph.get_node(key)
+        }
+        fn get_edge(&self, source: u32, target: u32) -> &Edge {
+            self.graph.get_edge(source, target)
+        }
+        fn has_node(&self, key: u32) -> bool {
+            self.graph.has_node(key)
+        }
+        fn has_edge(&self, source: u32, target: u32) -> bool {
+            self.graph.has_edge(source, target)
+        }
+        fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a Node>
+        where
+            Node: 'a,
+        {
+            self.graph.get_nodes()
+        }
+        fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a Edge>
+        where
+            Edge: 'a,
+        {
+            self.graph.get_edges()
+        }
+        fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a Node>
+        where
+            Node: 'a,
+        {
+            let node = self.node;
+            self.graph
+                .get_predecessors(node_key)
+                .filter(move |_| node_key != node)
+        }
+        fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a Node>
+        where
+            Node: 'a,
+        {
+            self.graph.get_successors(node_key)
+        }
+        fn get_node_keys(&self) -> impl Iterator<Item = u32> + '_ {
+            self.graph.get_node_keys()
+        }
+        fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> + '_ {
+            let node = self.node;
+            self.graph
+                .get_predecessors_keys(node_key)
+                .filter(move |_| node_key != node)
+        }
+        fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> + '_ {
+            self.graph.get_successors_keys(node_key)
+        }
+        fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<Node, Edge> {
+            self.graph.filter(node_keys)
+        }
+        fn has_sequential_keys(&self) -> bool {
+            self.graph.has_sequential_keys()
+        }
+    }
+
+    #[test]
+    fn test_consistency_detects_missing_predecessors() {
+        let graph = Graph::<Node, Edge>::new(
+            vec![Node::new(0), Node::new(1), Node::new(2)],
+            vec![Edge::new(0, 1), Edge::new(1, 2)],
+        );
+        let broken = MissingPredecessors { graph, node: 2 };
+
+        assert_eq!(
+            ade_traits::check_graph_consistency(&broken),
+            Err(ade_traits::ConsistencyError::PredecessorMismatch {
+                source: 1,
+                target: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_path_against_graph() {
+        use ade_traits::{Path, PathError};
+
+        let graph = Graph::<Node, Edge>::new(
+            vec![Node::new(0), Node::new(1), Node::new(2)],
+            vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 0)],
+        );
+
+        let circuit = Path::new(vec![0, 1, 2, 0]);
+        assert_eq!(circuit.validate(&graph), Ok(()));
+        assert_eq!(circuit.weight(&graph, |e| e.source() + e.target()), 6);
+
+        assert_eq!(Path::new(vec![]).validate(&graph), Err(PathError::Empty));
+        assert_eq!(
+            Path::new(vec![0, 3]).validate(&graph),
+            Err(PathError::NodeNotFound(3))
+        );
+        assert_eq!(
+            Path::new(vec![0, 2]).validate(&graph),
+            Err(PathError::EdgeNotFound(0, 2))
+        );
+        assert_panics_with!(
+            Path::new(vec![0, 2]).weight(&graph, |_| 1),
+            "Edge 0→2 not found"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_schema() {
+        use crate::implementations::{DataEdge, DataNode};
+        use ade_traits::DataNodeTrait;
+
+        let graph = Graph::<Node, Edge>::new(
+            vec![Node::new(2), Node::new(0), Node::new(1)],
+            vec![
+                Edge::new(2, 0),
+                Edge::new(0, 1),
+                Edge::new(1, 2),
+                Edge::new(1, 1),
+            ],
+        );
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(
+            json,
+            r#"{"nodes":[0,1,2],"edges":[[0,1],[1,1],[1,2],[2,0]]}"#
+        );
+
+        let decoded: Graph<Node, Edge> = serde_json::from_str(&json).unwrap();
+        assert_eq!(ade_traits::check_graph_consistency(&decoded), Ok(()));
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+        let labeled = Graph::<DataNode<String>, DataEdge<u8>>::new(
+            vec![
+                DataNode::with_data(0, "a".to_string()),
+                DataNode::with_data(1, "b".to_string()),
+            ],
+            vec![DataEdge::with_data(0, 1, 7)],
+        );
+        let json = serde_json::to_string(&labeled).unwrap();
+        let decoded: Graph<DataNode<String>, DataEdge<u8>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.get_node(1).data(), "b");
+        assert!(decoded.get_node(0).successors().contains(&1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_graph_is_an_error() {
+        let missing = serde_json::from_str::<Graph<Node, Edge>>(r#"{"nodes":[0],"edges":[[0,1]]}"#);
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "Invalid graph: Node 1 not found for edge 0→1"
+        );
+
+        let duplicate = serde_json::from_str::<Graph<Node, Edge>>(r#"{"nodes":[0,0],"edges":[]}"#);
+        assert!(duplicate.is_err());
+    }
 }
@@ -0,0 +1,464 @@
+use crate::implementations::FilteredGraph;
+use crate::utils::heap_size::{hash_map_bytes, node_adjacency_bytes};
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use fixedbitset::FixedBitSet;
+use std::collections::HashMap;
+use std::mem::size_of;
+
+// Bits in a block of a FixedBitSet
+const BLOCK_BITS: usize = 32;
+
+/// A directed graph stored as an adjacency matrix, for dense graphs.
+///
+/// The edges are the bits of an `n × n` [`FixedBitSet`], one row per source, so `has_edge`
+/// is a single bit test and the successors of a node are found by scanning its row a block
+/// of bits at a time. A second matrix holds the transposed rows, for predecessors. Memory
+/// grows with the square of the number of nodes, whatever the number of edges, so
+/// `MatrixGraph` pays off for dense graphs, such as complete graphs, where
+/// [`Graph`](crate::implementations::Graph) spends more on its hash sets.
+///
+/// Keys are always `0..n` and the set of nodes is fixed; edges can be added and removed.
+/// Edge values are kept in a hash map and only read by `get_edge` and `get_edges`.
+///
+/// Nodes are rebuilt with [`NodeTrait::with_key`], so they keep their data but not their
+/// own predecessor and successor sets, which are empty: neighbors are only available through
+/// the [`GraphViewTrait`] methods.
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{MatrixGraph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut graph = MatrixGraph::<Node, Edge>::new(
+///     vec![Node::new(0), Node::new(1), Node::new(2)],
+///     vec![Edge::new(0, 1), Edge::new(1, 2)],
+/// );
+/// graph.add_edge(Edge::new(2, 0));
+///
+/// assert!(graph.has_edge(2, 0));
+/// assert_eq!(graph.get_successors_keys(0).collect::<Vec<_>>(), vec![1]);
+///
+/// let closure = graph.transitive_closure();
+/// assert_eq!(closure.edge_count(), 9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MatrixGraph<N, E> {
+    nodes: Vec<N>,
+    // Bits per row, a multiple of the block size so that every row starts a new block
+    stride: usize,
+    rows: FixedBitSet,
+    columns: FixedBitSet,
+    edges: HashMap<(u32, u32), E>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> MatrixGraph<N, E> {
+    /// Creates a new graph from vectors of nodes and edges.
+    ///
+    /// Nodes may be given in any order, but their keys must be `0..n`. Duplicate edges
+    /// replace earlier ones, as in [`Graph::new`](crate::implementations::Graph::new).
+    ///
+    /// # Panics
+    ///
+    /// Panics with [`INVALID_KEY_SEQUENCE`] if the node keys are not sequential, and panics
+    /// if any edge references a node that is not in the nodes vector.
+    pub fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        let mut slots: Vec<Option<N>> = vec![None; nodes.len()];
+        for node in nodes {
+            let key = node.key();
+            match slots.get_mut(key as usize) {
+                Some(slot @ None) => *slot = Some(node.with_key(key)),
+                _ => panic!("{}", INVALID_KEY_SEQUENCE),
+            }
+        }
+
+        let mut graph = Self::with_nodes(slots.into_iter().flatten().collect());
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        graph
+    }
+
+    /// Builds an adjacency matrix copy of a graph.
+    ///
+    /// Any implementation of [`GraphViewTrait`] can be copied, including other views; nodes
+    /// and edges are cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{MatrixGraph, Node, Edge};
+    /// use ade_graph::utils::build::build_graph;
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (2, 1)]);
+    /// let matrix = MatrixGraph::from_view(&graph);
+    ///
+    /// assert_eq!(matrix.get_predecessors_keys(1).collect::<Vec<_>>(), vec![0, 2]);
+    /// ```
+    pub fn from_view<G: GraphViewTrait<N, E>>(graph: &G) -> Self {
+        if !graph.has_sequential_keys() {
+            panic!("{}", INVALID_KEY_SEQUENCE);
+        }
+
+        let n = graph.node_count() as u32;
+        let mut matrix = Self::with_nodes(
+            (0..n)
+                .map(|key| graph.get_node(key).with_key(key))
+                .collect(),
+        );
+        for edge in graph.get_edges() {
+            matrix.add_edge(edge.clone());
+        }
+        matrix
+    }
+
+    /// Adds an edge to the graph.
+    ///
+    /// If an edge with the same key already exists, it is replaced and returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    pub fn add_edge(&mut self, edge: E) -> Option<E> {
+        let (source, target) = edge.key();
+        self.check_endpoints(source, target);
+
+        self.rows.insert(self.bit(source, target));
+        self.columns.insert(self.bit(target, source));
+        self.edges.insert((source, target), edge)
+    }
+
+    /// Removes an edge from the graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{MatrixGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let mut graph = MatrixGraph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1)],
+    ///     vec![Edge::new(0, 1)],
+    /// );
+    ///
+    /// assert!(graph.remove_edge(0, 1).is_some());
+    /// assert!(graph.remove_edge(0, 1).is_none());
+    /// assert_eq!(graph.get_predecessors_keys(1).count(), 0);
+    /// ```
+    pub fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        self.check_endpoints(source, target);
+
+        let edge = self.edges.remove(&(source, target))?;
+        self.rows.set(self.bit(source, target), false);
+        self.columns.set(self.bit(target, source), false);
+        Some(edge)
+    }
+
+    /// Returns the transitive closure of the graph.
+    ///
+    /// The closure has an edge from `u` to `v` whenever `v` can be reached from `u` through
+    /// a path of at least one edge, so a node only has a self-loop if it lies on a cycle.
+    /// Edges of the graph keep their values and the other edges are created with
+    /// [`EdgeTrait::new`].
+    ///
+    /// Uses Warshall's algorithm, merging whole rows with bitwise OR: `O(n³ / 32)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{MatrixGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = MatrixGraph::<Node, Edge>::new(
+    ///     vec![Node::new(0), Node::new(1), Node::new(2)],
+    ///     vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 2)],
+    /// );
+    /// let closure = graph.transitive_closure();
+    ///
+    /// assert!(closure.has_edge(0, 2));
+    /// assert!(closure.has_edge(2, 2));
+    /// assert!(!closure.has_edge(0, 0));
+    /// ```
+    pub fn transitive_closure(&self) -> Self {
+        let n = self.nodes.len();
+        let width = self.stride / BLOCK_BITS;
+        let mut reach = self.rows.clone();
+
+        let blocks = reach.as_mut_slice();
+        let mut through = vec![0; width];
+        for k in 0..n {
+            through.copy_from_slice(&blocks[k * width..(k + 1) * width]);
+            let (block, mask) = (k / BLOCK_BITS, 1 << (k % BLOCK_BITS));
+            for row in blocks.chunks_exact_mut(width) {
+                if row[block] & mask != 0 {
+                    for (cell, &next) in row.iter_mut().zip(&through) {
+                        *cell |= next;
+                    }
+                }
+            }
+        }
+
+        let mut closure = Self::with_nodes(self.nodes.clone());
+        for source in 0..n as u32 {
+            for target in row_ones(&reach, self.stride, source) {
+                let edge = match self.edges.get(&(source, target)) {
+                    Some(edge) => edge.clone(),
+                    None => E::new(source, target),
+                };
+                closure.add_edge(edge);
+            }
+        }
+        closure
+    }
+
+    /// Estimates the heap memory used by the graph, in bytes.
+    ///
+    /// Sums the node vector, the two bit matrices, the edge map and the adjacency sets of the
+    /// nodes. See [`Graph::estimated_heap_size`](crate::implementations::Graph::estimated_heap_size)
+    /// for the limits of the estimate.
+    pub fn estimated_heap_size(&self) -> usize {
+        self.nodes.capacity() * size_of::<N>()
+            + (self.rows.as_slice().len() + self.columns.as_slice().len()) * size_of::<u32>()
+            + hash_map_bytes(&self.edges)
+            + self.nodes.iter().map(node_adjacency_bytes).sum::<usize>()
+    }
+
+    // Creates a graph without edges from nodes with keys 0..n in order
+    fn with_nodes(nodes: Vec<N>) -> Self {
+        let n = nodes.len();
+        let stride = n.div_ceil(BLOCK_BITS) * BLOCK_BITS;
+        MatrixGraph {
+            nodes,
+            stride,
+            rows: FixedBitSet::with_capacity(n * stride),
+            columns: FixedBitSet::with_capacity(n * stride),
+            edges: HashMap::new(),
+        }
+    }
+
+    fn bit(&self, row: u32, column: u32) -> usize {
+        row as usize * self.stride + column as usize
+    }
+
+    fn check_endpoints(&self, source: u32, target: u32) {
+        if !self.has_node(source) || !self.has_node(target) {
+            panic!("Node {} or {} not found", source, target);
+        }
+    }
+}
+
+// Iterates the columns set in a row of a bit matrix, a block at a time
+fn row_ones(matrix: &FixedBitSet, stride: usize, row: u32) -> impl Iterator<Item = u32> + '_ {
+    let width = stride / BLOCK_BITS;
+    let start = row as usize * width;
+    matrix.as_slice()[start..start + width]
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &block)| {
+            let mut bits = block;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let bit = bits.trailing_zeros();
+                bits &= bits - 1;
+                Some((index * BLOCK_BITS) as u32 + bit)
+            })
+        })
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for MatrixGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        true
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.nodes
+            .get(key as usize)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        (key as usize) < self.nodes.len()
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.edges
+            .get(&(source, target))
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.has_node(source)
+            && self.has_node(target)
+            && self.rows.contains(self.bit(source, target))
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.iter()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        0..self.nodes.len() as u32
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.edges.values()
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_predecessors_keys(node_key)
+            .map(|pred_key| &self.nodes[pred_key as usize])
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        if !self.has_node(node_key) {
+            panic!("Node {} not found", node_key);
+        }
+        row_ones(&self.columns, self.stride, node_key)
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_successors_keys(node_key)
+            .map(|succ_key| &self.nodes[succ_key as usize])
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        if !self.has_node(node_key) {
+            panic!("Node {} not found", node_key);
+        }
+        row_ones(&self.rows, self.stride, node_key)
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::traversal::Bfs;
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+
+    #[test]
+    fn test_rows_and_columns() {
+        // More than one block per row
+        let n = 70;
+        let edges: Vec<Edge> = (0..n)
+            .flat_map(|i| [Edge::new(i, (i + 1) % n), Edge::new(i, (i + 35) % n)])
+            .collect();
+        let mut graph = MatrixGraph::new((0..n).rev().map(Node::new).collect(), edges);
+
+        assert_eq!(graph.node_count(), 70);
+        assert_eq!(
+            graph.get_successors_keys(10).collect::<Vec<_>>(),
+            vec![11, 45]
+        );
+        assert_eq!(
+            graph.get_successors_keys(35).collect::<Vec<_>>(),
+            vec![0, 36]
+        );
+        assert_eq!(
+            graph.get_predecessors_keys(69).collect::<Vec<_>>(),
+            vec![34, 68]
+        );
+        assert!(graph.has_edge(69, 0) && !graph.has_edge(0, 69) && !graph.has_edge(0, 70));
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+
+        assert!(graph.remove_edge(10, 45).is_some());
+        assert_eq!(
+            graph.get_predecessors_keys(45).collect::<Vec<_>>(),
+            vec![44]
+        );
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    #[test]
+    fn test_transitive_closure_matches_bfs() {
+        let graph = build_graph::<Node, Edge>(
+            (0..40).collect(),
+            (0..39)
+                .map(|i| (i, i + 1))
+                .chain([(20, 5), (39, 39)])
+                .collect(),
+        );
+        let closure = MatrixGraph::from_view(&graph).transitive_closure();
+
+        for source in 0..40 {
+            let mut expected: Vec<u32> = graph
+                .get_successors_keys(source)
+                .flat_map(|succ| Bfs::new(&graph, succ))
+                .collect();
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(
+                closure.get_successors_keys(source).collect::<Vec<_>>(),
+                expected
+            );
+        }
+        assert_eq!(ade_traits::check_graph_consistency(&closure), Ok(()));
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = MatrixGraph::<Node, Edge>::new(vec![], vec![]);
+        assert!(graph.is_empty());
+        assert_eq!(graph.transitive_closure().edge_count(), 0);
+    }
+
+    #[test]
+    fn test_panics() {
+        assert_panics_with!(
+            MatrixGraph::<Node, Edge>::new(vec![Node::new(0), Node::new(2)], vec![]),
+            INVALID_KEY_SEQUENCE
+        );
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(MatrixGraph::from_view(&graph), INVALID_KEY_SEQUENCE);
+
+        let mut graph = MatrixGraph::<Node, Edge>::new(vec![Node::new(0)], vec![]);
+        assert_panics_with!(graph.add_edge(Edge::new(0, 1)), "Node 0 or 1 not found");
+        assert_panics_with!(graph.get_successors_keys(3).count(), "Node 3 not found");
+    }
+}
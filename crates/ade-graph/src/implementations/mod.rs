@@ -1,9 +1,41 @@
+pub mod compiled_filtered_graph;
+pub mod csr_graph;
+pub mod data_edge;
+pub mod data_node;
+pub mod dense_graph;
 pub mod edge;
+pub mod edge_filtered_graph;
 pub mod filtered_graph;
 pub mod graph;
+pub mod matrix_graph;
+pub mod multi_graph;
 pub mod node;
+#[cfg(feature = "ordered")]
+pub mod ordered_graph;
+#[cfg(feature = "persistent")]
+pub mod persistent_graph;
+pub mod reversed_graph;
+pub mod shared_graph;
+pub mod undirected_view;
+pub mod weighted_edge;
 
+pub use compiled_filtered_graph::CompiledFilteredGraph;
+pub use csr_graph::CsrGraph;
+pub use data_edge::DataEdge;
+pub use data_node::DataNode;
+pub use dense_graph::DenseGraph;
 pub use edge::Edge;
+pub use edge_filtered_graph::{EdgeFilteredGraph, FilterEdges};
 pub use filtered_graph::FilteredGraph;
 pub use graph::Graph;
+pub use matrix_graph::MatrixGraph;
+pub use multi_graph::MultiGraph;
 pub use node::Node;
+#[cfg(feature = "ordered")]
+pub use ordered_graph::OrderedGraph;
+#[cfg(feature = "persistent")]
+pub use persistent_graph::PersistentGraph;
+pub use reversed_graph::ReversedGraph;
+pub use shared_graph::SharedGraph;
+pub use undirected_view::UndirectedView;
+pub use weighted_edge::WeightedEdge;
@@ -0,0 +1,519 @@
+use crate::implementations::{FilteredGraph, Graph};
+use crate::utils::heap_size::{hash_map_bytes, node_adjacency_bytes};
+use ade_traits::{EdgeTrait, GraphError, GraphViewTrait, NodeTrait};
+use std::collections::HashMap;
+use std::mem::size_of;
+
+/// A directed graph that can hold several edges between the same pair of nodes.
+///
+/// [`Graph`] keys its edges by `(source, target)`, so adding a second edge between the same
+/// nodes replaces the first. `MultiGraph` instead gives each edge a `u32` edge id, so
+/// parallel edges, such as the arcs of a flow network or several relationships between two
+/// entities, are all kept. Edge ids are given in increasing order and never reused.
+///
+/// Through [`GraphViewTrait`], a multigraph is seen as the simple graph of its distinct
+/// `(source, target)` pairs: `get_edges`, `get_edge` and `edge_count` report the first edge
+/// added between each pair, and the neighbors of a node are listed once. Every algorithm
+/// accepting a [`GraphViewTrait`] can thus run on it. [`edges_between`](MultiGraph::edges_between)
+/// and [`all_edges`](MultiGraph::all_edges) give the parallel edges, and
+/// [`collapse`](MultiGraph::collapse) merges them into a [`Graph`].
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{MultiGraph, Node, WeightedEdge};
+/// use ade_graph::{EdgeTrait, GraphViewTrait, WeightedEdgeTrait};
+///
+/// let mut graph = MultiGraph::new(vec![Node::new(0), Node::new(1)], vec![]);
+/// let first = graph.add_edge(WeightedEdge::with_weight(0, 1, 3));
+/// let second = graph.add_edge(WeightedEdge::with_weight(0, 1, 4));
+///
+/// assert_ne!(first, second);
+/// assert_eq!(graph.multiplicity(0, 1), 2);
+/// assert_eq!(graph.parallel_edge_count(), 2);
+/// assert_eq!(graph.edge_count(), 1);
+///
+/// // Merge parallel arcs by summing their capacities
+/// let simple = graph.collapse(|edges| {
+///     let weight = edges.iter().map(|edge| edge.weight()).sum();
+///     WeightedEdge::with_weight(edges[0].source(), edges[0].target(), weight)
+/// });
+/// assert_eq!(simple.get_edge(0, 1).weight(), 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiGraph<N, E> {
+    nodes: HashMap<u32, N>,
+    edges: HashMap<u32, E>,
+    // Ids of the edges between each pair of nodes, in increasing order
+    pairs: HashMap<(u32, u32), Vec<u32>>,
+    next_edge_id: u32,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> MultiGraph<N, E> {
+    /// Creates a new multigraph from vectors of nodes and edges.
+    ///
+    /// Duplicate nodes replace earlier ones, as in [`Graph::new`], while every edge is kept:
+    /// the `i`-th edge gets edge id `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any edge references a node that is not in the nodes vector.
+    pub fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        let mut graph = MultiGraph {
+            nodes: HashMap::with_capacity(nodes.len()),
+            edges: HashMap::with_capacity(edges.len()),
+            pairs: HashMap::with_capacity(edges.len()),
+            next_edge_id: 0,
+        };
+
+        for node in nodes {
+            graph.add_node(node);
+        }
+
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+
+    /// Adds a node to the multigraph.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(old_node)` - If a node with the same key already existed
+    /// * `None` - If no node with this key existed
+    pub fn add_node(&mut self, node: N) -> Option<N> {
+        self.nodes.insert(node.key(), node)
+    }
+
+    /// Removes a node from the multigraph, together with all its incoming and outgoing
+    /// edges.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(node)` - If the node existed, returns the removed node
+    /// * `None` - If no node with this key existed
+    pub fn remove_node(&mut self, key: u32) -> Option<N> {
+        let node = self.nodes.get(&key)?;
+
+        let mut pairs_to_remove = Vec::new();
+        for &predecessor in node.predecessors() {
+            pairs_to_remove.push((predecessor, key));
+        }
+        for &successor in node.successors() {
+            pairs_to_remove.push((key, successor));
+        }
+        for (source, target) in pairs_to_remove {
+            self.remove_edges(source, target);
+        }
+
+        self.nodes.remove(&key)
+    }
+
+    /// Adds an edge to the multigraph, next to any edge already between its endpoints.
+    ///
+    /// # Returns
+    ///
+    /// The edge id of the new edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the multigraph. Use
+    /// [`try_add_edge`](MultiGraph::try_add_edge) to get an error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{MultiGraph, Node, Edge};
+    ///
+    /// let mut graph = MultiGraph::new(vec![Node::new(0)], vec![]);
+    /// assert_eq!(graph.add_edge(Edge::new(0, 0)), 0);
+    /// assert_eq!(graph.add_edge(Edge::new(0, 0)), 1);
+    /// ```
+    pub fn add_edge(&mut self, edge: E) -> u32 {
+        self.try_add_edge(edge)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Adds an edge to the multigraph, or returns an error if one of its endpoints is
+    /// missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] with the key of the source, or else of the
+    /// target, if it does not exist in the multigraph.
+    pub fn try_add_edge(&mut self, edge: E) -> Result<u32, GraphError> {
+        let (source, target) = edge.key();
+
+        if source == target {
+            let node = self
+                .nodes
+                .get_mut(&source)
+                .ok_or(GraphError::MissingNode(source))?;
+            node.add_successor(target);
+            node.add_predecessor(source);
+        } else {
+            match self.nodes.get_disjoint_mut([&source, &target]) {
+                [Some(source_node), Some(target_node)] => {
+                    source_node.add_successor(target);
+                    target_node.add_predecessor(source);
+                }
+                [None, _] => return Err(GraphError::MissingNode(source)),
+                [_, None] => return Err(GraphError::MissingNode(target)),
+            }
+        }
+
+        let id = self.next_edge_id;
+        self.next_edge_id += 1;
+        self.edges.insert(id, edge);
+        self.pairs.entry((source, target)).or_default().push(id);
+        Ok(id)
+    }
+
+    /// Removes the edge with the given edge id.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(edge)` - If the edge existed, returns the removed edge
+    /// * `None` - If no edge has this id
+    pub fn remove_edge(&mut self, id: u32) -> Option<E> {
+        let edge = self.edges.remove(&id)?;
+        let (source, target) = edge.key();
+        let ids = self
+            .pairs
+            .get_mut(&(source, target))
+            .expect("Every edge is listed with its endpoints");
+        ids.retain(|&other| other != id);
+        if ids.is_empty() {
+            self.unlink(source, target);
+        }
+        Some(edge)
+    }
+
+    /// Removes all the edges from `source` to `target`.
+    ///
+    /// # Returns
+    ///
+    /// The removed edges, in increasing order of edge id, empty if there were none.
+    pub fn remove_edges(&mut self, source: u32, target: u32) -> Vec<E> {
+        let Some(ids) = self.pairs.get(&(source, target)) else {
+            return Vec::new();
+        };
+        let edges = ids
+            .iter()
+            .map(|id| self.edges.remove(id).unwrap())
+            .collect();
+        self.unlink(source, target);
+        edges
+    }
+
+    /// Returns the edge with the given edge id, if any.
+    pub fn edge(&self, id: u32) -> Option<&E> {
+        self.edges.get(&id)
+    }
+
+    /// Returns the edges from `source` to `target`, with their edge ids, in increasing order
+    /// of edge id.
+    pub fn edges_between(&self, source: u32, target: u32) -> impl Iterator<Item = (u32, &E)> {
+        self.pairs
+            .get(&(source, target))
+            .into_iter()
+            .flatten()
+            .map(|&id| (id, &self.edges[&id]))
+    }
+
+    /// Returns every edge of the multigraph, parallel edges included, with its edge id.
+    pub fn all_edges(&self) -> impl Iterator<Item = (u32, &E)> {
+        self.edges.iter().map(|(&id, edge)| (id, edge))
+    }
+
+    /// Returns the number of edges from `source` to `target`.
+    pub fn multiplicity(&self, source: u32, target: u32) -> usize {
+        self.pairs.get(&(source, target)).map_or(0, Vec::len)
+    }
+
+    /// Returns the number of edges, parallel edges included.
+    ///
+    /// [`edge_count`](GraphViewTrait::edge_count) counts each pair of nodes once.
+    pub fn parallel_edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Collapses the multigraph into a [`Graph`], with one edge for each pair of connected
+    /// nodes.
+    ///
+    /// `merge` receives the parallel edges of each pair, in increasing order of edge id, and
+    /// returns the edge kept for the pair, which must have the same endpoints. Nodes are
+    /// rebuilt with [`NodeTrait::with_key`], so their data is kept.
+    pub fn collapse(&self, mut merge: impl FnMut(&[&E]) -> E) -> Graph<N, E> {
+        let nodes = self
+            .nodes
+            .values()
+            .map(|node| node.with_key(node.key()))
+            .collect();
+        let edges = self
+            .pairs
+            .values()
+            .map(|ids| {
+                let parallel: Vec<&E> = ids.iter().map(|id| &self.edges[id]).collect();
+                merge(&parallel)
+            })
+            .collect();
+        Graph::new(nodes, edges)
+    }
+
+    /// Collapses the multigraph into a [`Graph`], keeping the first edge added between each
+    /// pair of nodes, as [`GraphViewTrait`] reports it.
+    pub fn to_simple_graph(&self) -> Graph<N, E> {
+        self.collapse(|edges| edges[0].clone())
+    }
+
+    /// Estimates the heap memory used by the multigraph, in bytes.
+    ///
+    /// Sums the node and edge maps, the edge ids of each pair and the predecessor and
+    /// successor sets of every node. See [`Graph::estimated_heap_size`] for the limits of the
+    /// estimate.
+    pub fn estimated_heap_size(&self) -> usize {
+        hash_map_bytes(&self.nodes)
+            + hash_map_bytes(&self.edges)
+            + hash_map_bytes(&self.pairs)
+            + self
+                .pairs
+                .values()
+                .map(|ids| ids.capacity() * size_of::<u32>())
+                .sum::<usize>()
+            + self.nodes.values().map(node_adjacency_bytes).sum::<usize>()
+    }
+
+    // Forgets a pair of nodes whose last edge was removed
+    fn unlink(&mut self, source: u32, target: u32) {
+        self.pairs.remove(&(source, target));
+        if let Some(node) = self.nodes.get_mut(&source) {
+            node.remove_successor(target);
+        }
+        if let Some(node) = self.nodes.get_mut(&target) {
+            node.remove_predecessor(source);
+        }
+    }
+
+    // The first edge added between a pair of nodes, which stands for the pair in the view
+    fn first_edge(&self, source: u32, target: u32) -> Option<&E> {
+        let ids = self.pairs.get(&(source, target))?;
+        self.edges.get(&ids[0])
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> From<Graph<N, E>> for MultiGraph<N, E> {
+    // Edge ids follow the order of the edges by key
+    fn from(graph: Graph<N, E>) -> Self {
+        let (nodes, edges) = graph.into_parts();
+        MultiGraph::new(nodes, edges)
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for MultiGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.pairs.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        let n = self.nodes.len() as u32;
+        (0..n).all(|key| self.nodes.contains_key(&key))
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.nodes
+            .get(&key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.nodes.contains_key(&key)
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.first_edge(source, target)
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.pairs.contains_key(&(source, target))
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.values()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.nodes.keys().copied()
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.pairs.values().map(|ids| &self.edges[&ids[0]])
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .predecessors()
+            .iter()
+            .map(|pred_key| self.get_node(*pred_key))
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).predecessors().iter().copied()
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .successors()
+            .iter()
+            .map(|succ_key| self.get_node(*succ_key))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).successors().iter().copied()
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).predecessors().len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).successors().len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node, WeightedEdge};
+    use crate::utils::compare::structural_eq;
+    use ade_common::assert_panics_with;
+    use ade_traits::WeightedEdgeTrait;
+
+    fn weighted(edges: &[(u32, u32, u32)]) -> MultiGraph<Node, WeightedEdge<u32>> {
+        MultiGraph::new(
+            (0..3).map(Node::new).collect(),
+            edges
+                .iter()
+                .map(|&(source, target, weight)| WeightedEdge::with_weight(source, target, weight))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let mut graph = weighted(&[(0, 1, 5), (0, 1, 6), (1, 2, 7), (2, 2, 1), (2, 2, 2)]);
+
+        assert_eq!(graph.parallel_edge_count(), 5);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.get_edge(0, 1).weight(), 5);
+        assert_eq!(graph.out_degree(0), 1);
+        assert_eq!(
+            graph
+                .edges_between(2, 2)
+                .map(|(id, edge)| (id, edge.weight()))
+                .collect::<Vec<_>>(),
+            vec![(3, 1), (4, 2)]
+        );
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+
+        // The pair stays until its last edge is removed
+        assert_eq!(graph.remove_edge(0).unwrap().weight(), 5);
+        assert!(graph.remove_edge(0).is_none());
+        assert_eq!(graph.get_edge(0, 1).weight(), 6);
+        assert_eq!(graph.remove_edge(1).unwrap().weight(), 6);
+        assert!(!graph.has_edge(0, 1));
+        assert_eq!(graph.out_degree(0), 0);
+
+        assert_eq!(graph.remove_edges(2, 2).len(), 2);
+        assert!(graph.remove_edges(2, 2).is_empty());
+        assert_eq!(graph.multiplicity(2, 2), 0);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+
+        // Edge ids are not reused
+        assert_eq!(graph.add_edge(WeightedEdge::with_weight(0, 1, 9)), 5);
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut graph = weighted(&[(0, 1, 1), (1, 0, 1), (1, 0, 2), (1, 1, 3), (2, 0, 4)]);
+        assert!(graph.remove_node(1).is_some());
+        assert!(graph.remove_node(1).is_none());
+
+        assert_eq!(graph.parallel_edge_count(), 1);
+        assert_eq!(
+            graph.all_edges().map(|(id, _)| id).collect::<Vec<_>>(),
+            vec![4]
+        );
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    #[test]
+    fn test_collapse_and_conversions() {
+        let graph = weighted(&[(0, 1, 1), (0, 1, 2), (1, 2, 3)]);
+        let simple = graph.to_simple_graph();
+        assert!(structural_eq(&simple, &graph));
+        assert_eq!(simple.get_edge(0, 1).weight(), 1);
+
+        let heaviest = graph.collapse(|edges| {
+            edges
+                .iter()
+                .max_by_key(|edge| edge.weight())
+                .map(|&&edge| edge)
+                .unwrap()
+        });
+        assert_eq!(heaviest.get_edge(0, 1).weight(), 2);
+
+        let back = MultiGraph::from(simple);
+        assert_eq!(back.parallel_edge_count(), 2);
+        assert!(back.has_sequential_keys());
+        assert_eq!(back.filter(&[0, 1]).edge_count(), 1);
+    }
+
+    #[test]
+    fn test_missing_endpoints() {
+        let mut graph = MultiGraph::<Node, Edge>::new(vec![Node::new(0)], vec![]);
+        assert_eq!(
+            graph.try_add_edge(Edge::new(0, 4)).unwrap_err(),
+            GraphError::MissingNode(4)
+        );
+        assert_eq!(
+            graph.try_add_edge(Edge::new(3, 3)).unwrap_err(),
+            GraphError::MissingNode(3)
+        );
+        assert_panics_with!(graph.add_edge(Edge::new(2, 0)), "Node 2 not found");
+        assert_eq!(graph.parallel_edge_count(), 0);
+        assert!(graph.estimated_heap_size() > 0);
+    }
+}
@@ -159,6 +159,22 @@ impl NodeTrait for Node {
     }
 }
 
+// With the `serde` feature, a node is serialized as its key: its neighbors belong to the
+// edges of the graph, and a deserialized node has none
+#[cfg(feature = "serde")]
+impl serde::Serialize for Node {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.key)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Node {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u32 as serde::Deserialize>::deserialize(deserializer).map(Node::new)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
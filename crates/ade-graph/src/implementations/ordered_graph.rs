@@ -0,0 +1,349 @@
+use crate::implementations::graph::missing_endpoint;
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphMutTrait, GraphViewTrait, NodeTrait};
+use indexmap::{IndexMap, IndexSet};
+
+/// A directed graph that iterates its nodes, edges and neighbors in insertion order.
+///
+/// [`Graph`] stores nodes and edges in hash maps, so the order of `get_nodes`, `get_edges`
+/// and of the neighbor iterators changes from one run to the next, and leaks into the
+/// output of algorithms that visit nodes in that order. `OrderedGraph` exposes the same API
+/// with a deterministic order, for reproducible results and stable snapshots in tests:
+///
+/// - `get_nodes` and `get_node_keys` follow the order in which nodes were first added
+/// - `get_edges` follows the order in which edges were first added
+/// - `get_successors` and `get_predecessors` follow the order in which the edges to or
+///   from a node were added
+///
+/// Replacing a node or an edge keeps its position, and removing one keeps the order of
+/// the others, at the cost of `O(n)` removals. The neighbor sets of [`NodeTrait`] are still
+/// maintained, but they are hash sets: iterate through the graph to get the order.
+///
+/// Available with the `ordered` feature.
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{OrderedGraph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut graph = OrderedGraph::new(
+///     vec![Node::new(5), Node::new(1), Node::new(9)],
+///     vec![Edge::new(5, 9), Edge::new(5, 1)],
+/// );
+/// graph.remove_node(1);
+/// graph.add_node(Node::new(1));
+///
+/// assert_eq!(graph.get_node_keys().collect::<Vec<_>>(), vec![5, 9, 1]);
+/// assert_eq!(graph.get_successors_keys(5).collect::<Vec<_>>(), vec![9]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderedGraph<N, E> {
+    nodes: IndexMap<u32, N>,
+    edges: IndexMap<(u32, u32), E>,
+    neighbors: IndexMap<u32, Neighbors>,
+}
+
+// Neighbors of a node, in the order their edges were added
+#[derive(Debug, Clone, Default)]
+struct Neighbors {
+    predecessors: IndexSet<u32>,
+    successors: IndexSet<u32>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> OrderedGraph<N, E> {
+    /// Creates a new graph from vectors of nodes and edges, in the order they appear.
+    ///
+    /// Duplicate nodes or edges replace earlier ones, as in [`Graph::new`], and keep their
+    /// position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any edge references a node that is not in the nodes vector.
+    pub fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        let mut graph = OrderedGraph {
+            nodes: IndexMap::with_capacity(nodes.len()),
+            edges: IndexMap::with_capacity(edges.len()),
+            neighbors: IndexMap::with_capacity(nodes.len()),
+        };
+
+        for node in nodes {
+            graph.add_node(node);
+        }
+
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+
+    /// Adds a node after the existing ones.
+    ///
+    /// If a node with the same key already exists, it is replaced in place and returned.
+    pub fn add_node(&mut self, node: N) -> Option<N> {
+        let key = node.key();
+        self.neighbors.entry(key).or_default();
+        self.nodes.insert(key, node)
+    }
+
+    /// Removes a node, together with all its incoming and outgoing edges, keeping the order
+    /// of the other nodes and edges.
+    pub fn remove_node(&mut self, key: u32) -> Option<N> {
+        let neighbors = self.neighbors.get(&key)?;
+
+        let mut edges_to_remove = Vec::new();
+        for &predecessor in &neighbors.predecessors {
+            edges_to_remove.push((predecessor, key));
+        }
+        for &successor in &neighbors.successors {
+            edges_to_remove.push((key, successor));
+        }
+        for (source, target) in edges_to_remove {
+            self.remove_edge(source, target);
+        }
+
+        self.neighbors.swap_remove(&key);
+        self.nodes.shift_remove(&key)
+    }
+
+    /// Adds an edge after the existing ones.
+    ///
+    /// If an edge with the same key already exists, it is replaced in place and returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    pub fn add_edge(&mut self, edge: E) -> Option<E> {
+        let (source, target) = edge.key();
+        if !self.has_node(source) || !self.has_node(target) {
+            missing_endpoint(source, target);
+        }
+
+        self.neighbors[&source].successors.insert(target);
+        self.neighbors[&target].predecessors.insert(source);
+        self.nodes[&source].add_successor(target);
+        self.nodes[&target].add_predecessor(source);
+        self.edges.insert((source, target), edge)
+    }
+
+    /// Removes an edge, keeping the order of the other edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    pub fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        if !self.has_node(source) || !self.has_node(target) {
+            missing_endpoint(source, target);
+        }
+
+        let edge = self.edges.shift_remove(&(source, target))?;
+        self.neighbors[&source].successors.shift_remove(&target);
+        self.neighbors[&target].predecessors.shift_remove(&source);
+        self.nodes[&source].remove_successor(target);
+        self.nodes[&target].remove_predecessor(source);
+        Some(edge)
+    }
+
+    /// Converts the graph into a [`Graph`], which loses the order.
+    pub fn into_graph(self) -> Graph<N, E> {
+        let mut graph = Graph::new(Vec::new(), Vec::new());
+        for (_, node) in self.nodes {
+            graph.add_node(node);
+        }
+        for (_, edge) in self.edges {
+            graph.add_edge(edge);
+        }
+        graph
+    }
+
+    // The ordered neighbors of a node, panicking if it does not exist
+    fn neighbors_of(&self, key: u32) -> &Neighbors {
+        self.neighbors
+            .get(&key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for OrderedGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        let n = self.nodes.len() as u32;
+        (0..n).all(|key| self.nodes.contains_key(&key))
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.nodes
+            .get(&key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.nodes.contains_key(&key)
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.edges
+            .get(&(source, target))
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.edges.contains_key(&(source, target))
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.values()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.nodes.keys().copied()
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.edges.values()
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_predecessors_keys(node_key)
+            .map(|pred_key| &self.nodes[&pred_key])
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.neighbors_of(node_key).predecessors.iter().copied()
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_successors_keys(node_key)
+            .map(|succ_key| &self.nodes[&succ_key])
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.neighbors_of(node_key).successors.iter().copied()
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.neighbors_of(node_key).predecessors.len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.neighbors_of(node_key).successors.len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphMutTrait<N, E> for OrderedGraph<N, E> {
+    fn add_node(&mut self, node: N) -> Option<N> {
+        OrderedGraph::add_node(self, node)
+    }
+
+    fn remove_node(&mut self, key: u32) -> Option<N> {
+        OrderedGraph::remove_node(self, key)
+    }
+
+    fn add_edge(&mut self, edge: E) -> Option<E> {
+        OrderedGraph::add_edge(self, edge)
+    }
+
+    fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        OrderedGraph::remove_edge(self, source, target)
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.neighbors.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use ade_common::assert_panics_with;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut graph = OrderedGraph::<Node, Edge>::new(
+            [3, 0, 7, 2].into_iter().map(Node::new).collect(),
+            vec![
+                Edge::new(3, 2),
+                Edge::new(3, 0),
+                Edge::new(7, 0),
+                Edge::new(3, 7),
+                Edge::new(2, 0),
+            ],
+        );
+
+        assert_eq!(graph.get_node_keys().collect::<Vec<_>>(), vec![3, 0, 7, 2]);
+        assert_eq!(
+            graph.get_successors_keys(3).collect::<Vec<_>>(),
+            vec![2, 0, 7]
+        );
+        assert_eq!(
+            graph.get_predecessors_keys(0).collect::<Vec<_>>(),
+            vec![3, 7, 2]
+        );
+
+        // Replacing keeps the position, removing keeps the order of the others
+        assert!(graph.add_edge(Edge::new(3, 2)).is_some());
+        assert!(graph.add_node(Node::new(0)).is_some());
+        graph.remove_edge(3, 0);
+        assert_eq!(
+            graph.get_edges().map(|edge| edge.key()).collect::<Vec<_>>(),
+            vec![(3, 2), (7, 0), (3, 7), (2, 0)]
+        );
+        graph.remove_node(7);
+        assert_eq!(graph.get_node_keys().collect::<Vec<_>>(), vec![3, 0, 2]);
+        assert_eq!(graph.get_successors_keys(3).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(ade_traits::check_graph_consistency(&graph), Ok(()));
+    }
+
+    #[test]
+    fn test_conversion_and_missing_nodes() {
+        let mut graph = OrderedGraph::<Node, Edge>::new(
+            vec![Node::new(1), Node::new(0)],
+            vec![Edge::new(1, 0), Edge::new(0, 0)],
+        );
+        assert!(graph.has_sequential_keys());
+        assert_eq!(graph.filter(&[0]).edge_count(), 1);
+        assert_panics_with!(graph.add_edge(Edge::new(0, 4)), "Node 0 or 4 not found");
+        assert_panics_with!(graph.remove_edge(4, 4), "Node 4 not found");
+
+        let converted = graph.clone().into_graph();
+        assert!(converted.has_edge(1, 0) && converted.has_edge(0, 0));
+        GraphMutTrait::clear(&mut graph);
+        assert!(graph.is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use im::HashMap;
+
+/// An immutable directed graph with structural sharing.
+///
+/// Every modification of a `PersistentGraph` returns a new graph and leaves the original
+/// untouched. Both graphs share the parts of their structure that did not change, so
+/// a modification only copies the nodes it touches and a few internal tree nodes, instead
+/// of the whole graph. Cloning is O(1).
+///
+/// This makes snapshots cheap: an editor can keep every version of a graph for undo and
+/// redo, and readers on other threads can work on a snapshot while a writer derives new
+/// versions from it.
+///
+/// Available with the `persistent` feature.
+///
+/// # Type Parameters
+///
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let empty = PersistentGraph::<Node, Edge>::new(vec![], vec![]);
+/// let v1 = empty.add_node(Node::new(1)).add_node(Node::new(2));
+/// let v2 = v1.add_edge(Edge::new(1, 2));
+/// let v3 = v2.remove_node(1);
+///
+/// // Older versions are still available, for instance to undo changes
+/// assert!(!v1.has_edge(1, 2));
+/// assert!(v2.has_edge(1, 2));
+/// assert!(!v3.has_node(1));
+/// assert_eq!(v2.node_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PersistentGraph<N, E> {
+    nodes: HashMap<u32, N>,
+    edges: HashMap<(u32, u32), E>,
+}
+
+impl<N: NodeTrait, E: EdgeTrait> PersistentGraph<N, E> {
+    /// Creates a new persistent graph from vectors of nodes and edges.
+    ///
+    /// Duplicate nodes or edges replace earlier ones, as in [`Graph::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any edge references a node that is not in the nodes vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = PersistentGraph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![Edge::new(1, 2), Edge::new(2, 3)],
+    /// );
+    ///
+    /// assert_eq!(graph.node_count(), 3);
+    /// assert!(graph.has_edge(2, 3));
+    /// ```
+    pub fn new(nodes: Vec<N>, edges: Vec<E>) -> Self {
+        let mut graph = PersistentGraph {
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+        };
+
+        for node in nodes {
+            graph.insert_node(node);
+        }
+
+        for edge in edges {
+            graph.insert_edge(edge);
+        }
+
+        graph
+    }
+
+    /// Returns a new graph with `node` added.
+    ///
+    /// If a node with the same key already exists, it is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let empty = PersistentGraph::<Node, Edge>::new(vec![], vec![]);
+    /// let graph = empty.add_node(Node::new(1));
+    ///
+    /// assert!(graph.has_node(1));
+    /// assert!(!empty.has_node(1));
+    /// ```
+    #[must_use]
+    pub fn add_node(&self, node: N) -> Self {
+        let mut graph = self.clone();
+        graph.insert_node(node);
+        graph
+    }
+
+    /// Returns a new graph without the node `key` and the edges connected to it.
+    ///
+    /// If the node does not exist, the returned graph is a copy of this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = PersistentGraph::new(vec![Node::new(1), Node::new(2)], vec![Edge::new(1, 2)]);
+    /// let removed = graph.remove_node(1);
+    ///
+    /// assert!(!removed.has_node(1));
+    /// assert!(!removed.has_edge(1, 2));
+    /// assert!(graph.has_edge(1, 2));
+    /// ```
+    #[must_use]
+    pub fn remove_node(&self, key: u32) -> Self {
+        let mut graph = self.clone();
+        if let Some(node) = self.nodes.get(&key) {
+            for &predecessor in node.predecessors() {
+                graph.delete_edge(predecessor, key);
+            }
+            for &successor in node.successors() {
+                graph.delete_edge(key, successor);
+            }
+            graph.nodes.remove(&key);
+        }
+        graph
+    }
+
+    /// Returns a new graph with `edge` added.
+    ///
+    /// If an edge with the same key already exists, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = PersistentGraph::<Node, Edge>::new(vec![Node::new(1), Node::new(2)], vec![]);
+    /// let connected = graph.add_edge(Edge::new(1, 2));
+    ///
+    /// assert!(connected.has_edge(1, 2));
+    /// assert_eq!(connected.get_successors_keys(1).collect::<Vec<_>>(), vec![2]);
+    /// assert!(!graph.has_edge(1, 2));
+    /// ```
+    #[must_use]
+    pub fn add_edge(&self, edge: E) -> Self {
+        let mut graph = self.clone();
+        graph.insert_edge(edge);
+        graph
+    }
+
+    /// Returns a new graph without the edge from `source` to `target`.
+    ///
+    /// If the edge does not exist, the returned graph is a copy of this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    /// use ade_graph::GraphViewTrait;
+    ///
+    /// let graph = PersistentGraph::new(vec![Node::new(1), Node::new(2)], vec![Edge::new(1, 2)]);
+    /// let removed = graph.remove_edge(1, 2);
+    ///
+    /// assert!(!removed.has_edge(1, 2));
+    /// assert!(graph.has_edge(1, 2));
+    /// ```
+    #[must_use]
+    pub fn remove_edge(&self, source: u32, target: u32) -> Self {
+        if !self.nodes.contains_key(&source) || !self.nodes.contains_key(&target) {
+            panic!("Node {} or {} not found", source, target);
+        }
+
+        let mut graph = self.clone();
+        graph.delete_edge(source, target);
+        graph
+    }
+
+    /// Returns `true` if both graphs are the same version, sharing all their structure.
+    ///
+    /// This is a constant-time identity check, not a structural comparison: two graphs built
+    /// separately with the same nodes and edges are not pointer-equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{PersistentGraph, Node, Edge};
+    ///
+    /// let graph = PersistentGraph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+    /// let snapshot = graph.clone();
+    ///
+    /// assert!(graph.ptr_eq(&snapshot));
+    /// assert!(!graph.ptr_eq(&graph.add_node(Node::new(2))));
+    /// ```
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.nodes.ptr_eq(&other.nodes) && self.edges.ptr_eq(&other.edges)
+    }
+
+    // Inserts a node in place, copying only the shared tree nodes on its path
+    fn insert_node(&mut self, node: N) {
+        self.nodes.insert(node.key(), node);
+    }
+
+    // Inserts an edge in place and updates the adjacency of its endpoints
+    fn insert_edge(&mut self, edge: E) {
+        let (source, target) = edge.key();
+        if !self.nodes.contains_key(&source) || !self.nodes.contains_key(&target) {
+            panic!("Node {} or {} not found", source, target);
+        }
+
+        // Only the two endpoints are copied, the other nodes stay shared
+        if let Some(source_node) = self.nodes.get_mut(&source) {
+            source_node.add_successor(target);
+        }
+        if let Some(target_node) = self.nodes.get_mut(&target) {
+            target_node.add_predecessor(source);
+        }
+        self.edges.insert((source, target), edge);
+    }
+
+    // Removes an edge in place and updates the adjacency of its endpoints
+    fn delete_edge(&mut self, source: u32, target: u32) {
+        if self.edges.remove(&(source, target)).is_some() {
+            if let Some(source_node) = self.nodes.get_mut(&source) {
+                source_node.remove_successor(target);
+            }
+            if let Some(target_node) = self.nodes.get_mut(&target) {
+                target_node.remove_predecessor(source);
+            }
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> From<Graph<N, E>> for PersistentGraph<N, E> {
+    fn from(graph: Graph<N, E>) -> Self {
+        let (nodes, edges) = graph.into_maps();
+        PersistentGraph {
+            nodes: nodes.into_iter().collect(),
+            edges: edges.into_iter().collect(),
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait> GraphViewTrait<N, E> for PersistentGraph<N, E> {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        let size = self.nodes.len();
+        if size == 0 {
+            return true;
+        }
+
+        // Quick checks first
+        if !self.nodes.contains_key(&0) || !self.nodes.contains_key(&(size as u32 - 1)) {
+            return false;
+        }
+
+        // Check if all keys are sequential
+        (0..size as u32).all(|i| self.nodes.contains_key(&i))
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.nodes
+            .get(&key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.nodes.contains_key(&key)
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.edges
+            .get(&(source, target))
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.edges.contains_key(&(source, target))
+    }
+
+    fn get_nodes<'a>(&'a self) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.nodes.values()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.nodes.keys().copied()
+    }
+
+    fn get_edges<'a>(&'a self) -> impl Iterator<Item = &'a E>
+    where
+        E: 'a,
+    {
+        self.edges.values()
+    }
+
+    fn get_predecessors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .predecessors()
+            .iter()
+            .map(|pred_key| self.get_node(*pred_key))
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).predecessors().iter().copied()
+    }
+
+    fn get_successors<'a>(&'a self, node_key: u32) -> impl Iterator<Item = &'a N>
+    where
+        N: 'a,
+    {
+        self.get_node(node_key)
+            .successors()
+            .iter()
+            .map(|succ_key| self.get_node(*succ_key))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.get_node(node_key).successors().iter().copied()
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).predecessors().len()
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.get_node(node_key).successors().len()
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+    use ade_traits::check_graph_consistency;
+
+    fn sorted<I: Iterator<Item = u32>>(keys: I) -> Vec<u32> {
+        let mut keys: Vec<u32> = keys.collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_versions_are_independent() {
+        let v0 = PersistentGraph::<Node, Edge>::new(
+            vec![Node::new(0), Node::new(1), Node::new(2)],
+            vec![Edge::new(0, 1), Edge::new(1, 2)],
+        );
+        let v1 = v0.add_edge(Edge::new(2, 0));
+        let v2 = v1.remove_node(1);
+        let v3 = v2.remove_edge(2, 0);
+
+        assert_eq!(v0.get_edges().count(), 2);
+        assert_eq!(v1.get_edges().count(), 3);
+        assert_eq!(v2.get_edges().count(), 1);
+        assert_eq!(v3.get_edges().count(), 0);
+
+        assert_eq!(sorted(v0.get_predecessors_keys(0)), Vec::<u32>::new());
+        assert_eq!(sorted(v1.get_predecessors_keys(0)), vec![2]);
+        assert_eq!(sorted(v1.get_successors_keys(1)), vec![2]);
+        assert_eq!(sorted(v2.get_node_keys()), vec![0, 2]);
+
+        for graph in [&v0, &v1, &v2, &v3] {
+            assert_eq!(check_graph_consistency(graph), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_matches_graph() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0), (3, 3)]);
+        let persistent = PersistentGraph::from(build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (1, 2), (2, 0), (3, 3)],
+        ));
+
+        assert_eq!(
+            sorted(persistent.get_node_keys()),
+            sorted(graph.get_node_keys())
+        );
+        for key in graph.get_node_keys() {
+            assert_eq!(
+                sorted(persistent.get_successors_keys(key)),
+                sorted(graph.get_successors_keys(key))
+            );
+            assert_eq!(
+                sorted(persistent.get_predecessors_keys(key)),
+                sorted(graph.get_predecessors_keys(key))
+            );
+        }
+        assert!(persistent.has_sequential_keys());
+        assert_eq!(persistent.filter(&[0, 1]).get_edges().count(), 1);
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let graph = PersistentGraph::<Node, Edge>::new(vec![Node::new(1)], vec![Edge::new(1, 1)]);
+        assert_eq!(sorted(graph.get_successors_keys(1)), vec![1]);
+        assert_eq!(sorted(graph.get_predecessors_keys(1)), vec![1]);
+
+        let removed = graph.remove_node(1);
+        assert!(removed.is_empty());
+        assert_eq!(removed.get_edges().count(), 0);
+    }
+
+    #[test]
+    fn test_unchanged_versions_share_structure() {
+        let graph = PersistentGraph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+        assert!(graph.ptr_eq(&graph.remove_node(7)));
+        assert!(!graph.ptr_eq(&graph.add_node(Node::new(2))));
+    }
+
+    #[test]
+    fn test_add_edge_panic() {
+        let graph = PersistentGraph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+        assert_panics_with!(graph.add_edge(Edge::new(1, 2)), "Node 1 or 2 not found");
+        assert_panics_with!(graph.remove_edge(2, 1), "Node 2 or 1 not found");
+    }
+}
@@ -0,0 +1,221 @@
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::marker::PhantomData;
+
+/// A view of a graph with every edge reversed.
+///
+/// `ReversedGraph` borrows a base graph and swaps its successors and predecessors, so the
+/// view has an edge from `v` to `u` for every edge from `u` to `v` of the base graph.
+/// Nothing is copied: algorithms that walk edges backwards, such as backward reachability,
+/// Kosaraju's algorithm or layouts built from the sinks, can reuse their forward version on
+/// the view instead of constructing the transposed graph.
+///
+/// Edge values are those of the base graph and keep their original direction: the edge
+/// returned by `get_edge(v, u)` is the base edge with source `u` and target `v`. Code that
+/// reads the endpoints of an edge, rather than the neighbors of a node, must swap them, and
+/// the view does not pass [`check_graph_consistency`](ade_traits::check_graph_consistency).
+///
+/// # Type Parameters
+///
+/// * `'a` - Lifetime of the borrowed base graph
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+/// * `G` - Base graph type implementing [`GraphViewTrait`], [`Graph`] by default
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge, ReversedGraph};
+/// use ade_graph::traversal::Bfs;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::{EdgeTrait, GraphViewTrait};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (3, 2)]);
+/// let reversed = ReversedGraph::new(&graph);
+///
+/// // Nodes that can reach 2 in the base graph
+/// let mut reaching: Vec<u32> = Bfs::new(&reversed, 2).collect();
+/// reaching.sort_unstable();
+/// assert_eq!(reaching, vec![0, 1, 2, 3]);
+///
+/// assert!(reversed.has_edge(2, 1));
+/// assert!(!reversed.has_edge(1, 2));
+/// assert_eq!(reversed.get_edge(2, 1).key(), (1, 2));
+/// ```
+pub struct ReversedGraph<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E> = Graph<N, E>> {
+    base: &'a G,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> ReversedGraph<'a, N, E, G> {
+    /// Creates a reversed view of `base`.
+    ///
+    /// The base graph is borrowed, not copied, so creating the view takes constant time.
+    pub fn new(base: &'a G) -> Self {
+        Self {
+            base,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the base graph.
+    pub fn base(&self) -> &'a G {
+        self.base
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphViewTrait<N, E>
+    for ReversedGraph<'_, N, E, G>
+{
+    fn node_count(&self) -> usize {
+        self.base.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.base.edge_count()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.base.get_node(key)
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.base.has_node(key)
+    }
+
+    fn get_nodes<'b>(&'b self) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.base.get_nodes()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.base.get_node_keys()
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        self.base.get_edge(target, source)
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.base.has_edge(target, source)
+    }
+
+    fn get_edges<'b>(&'b self) -> impl Iterator<Item = &'b E>
+    where
+        E: 'b,
+    {
+        self.base.get_edges()
+    }
+
+    fn get_predecessors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.base.get_successors(node_key)
+    }
+
+    fn get_successors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.base.get_predecessors(node_key)
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.base.get_predecessors_keys(node_key)
+    }
+
+    fn get_out_edges<'b>(&'b self, node_key: u32) -> impl Iterator<Item = (&'b E, &'b N)>
+    where
+        N: 'b,
+        E: 'b,
+    {
+        self.base.get_in_edges(node_key)
+    }
+
+    fn get_in_edges<'b>(&'b self, node_key: u32) -> impl Iterator<Item = (&'b E, &'b N)>
+    where
+        N: 'b,
+        E: 'b,
+    {
+        self.base.get_out_edges(node_key)
+    }
+
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.base.out_degree(node_key)
+    }
+
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.base.in_degree(node_key)
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.base.get_successors_keys(node_key)
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        self.base.has_sequential_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+
+    fn sorted(keys: impl Iterator<Item = u32>) -> Vec<u32> {
+        let mut keys: Vec<u32> = keys.collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_neighbors_are_swapped() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (0, 2), (1, 2), (2, 0), (3, 3)],
+        );
+        let reversed = ReversedGraph::new(&graph);
+
+        for key in 0..4 {
+            assert_eq!(
+                sorted(reversed.get_successors_keys(key)),
+                sorted(graph.get_predecessors_keys(key))
+            );
+            assert_eq!(
+                sorted(reversed.get_predecessors(key).map(|node| node.key())),
+                sorted(graph.get_successors_keys(key))
+            );
+            assert_eq!(reversed.in_degree(key), graph.out_degree(key));
+            assert_eq!(reversed.out_degree(key), graph.in_degree(key));
+        }
+        assert_eq!(reversed.degree_sequence(), vec![3, 3, 2, 2]);
+        assert!(reversed.has_edge(1, 0) && !reversed.has_edge(0, 1));
+        assert!(reversed.has_edge(3, 3));
+        assert_eq!(reversed.get_edge(2, 1).key(), (1, 2));
+        assert_eq!(reversed.get_edges().count(), 5);
+    }
+
+    #[test]
+    fn test_reversing_twice_and_filtering() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+        let reversed = ReversedGraph::new(&graph);
+        let twice = ReversedGraph::new(&reversed);
+        assert_eq!(sorted(twice.get_successors_keys(1)), vec![2]);
+
+        let filtered = reversed.filter(&[1, 2]);
+        assert_eq!(sorted(filtered.get_successors_keys(2)), vec![1]);
+        assert_eq!(filtered.get_successors_keys(1).count(), 0);
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A graph shared between threads, with concurrent reads and exclusive writes.
+///
+/// All the graph types of this crate are `Send` and `Sync` when their nodes and edges are,
+/// so a plain `&Graph` can already be read from many threads. `SharedGraph` adds the
+/// coordination needed when the graph also changes: it wraps any graph `G` in an
+/// [`Arc`]`<`[`RwLock`]`>`, so that many threads can hold a read guard at the same time and
+/// run algorithms such as SCC or reachability on it, while writers wait for exclusive access.
+///
+/// Clones share the same graph. Guards should be held only as long as needed, since a
+/// writer blocks until every read guard is dropped. When reads are long, wrap a
+/// [`PersistentGraph`](crate::implementations::PersistentGraph) instead and take a
+/// [`snapshot`](SharedGraph::snapshot): readers then work on their own version without
+/// holding the lock, and writers are never blocked by them.
+///
+/// # Panics
+///
+/// Every method panics if a thread panicked while holding the write guard, since the graph
+/// may have been left half-modified.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge, SharedGraph};
+/// use ade_graph::GraphViewTrait;
+/// use std::thread;
+///
+/// let shared = SharedGraph::new(Graph::<Node, Edge>::new(
+///     vec![Node::new(0), Node::new(1)],
+///     vec![Edge::new(0, 1)],
+/// ));
+///
+/// // Readers on several threads
+/// let readers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let graph = shared.clone();
+///         thread::spawn(move || graph.read().node_count())
+///     })
+///     .collect();
+/// for reader in readers {
+///     assert_eq!(reader.join().unwrap(), 2);
+/// }
+///
+/// // A background writer
+/// let writer = shared.clone();
+/// thread::spawn(move || {
+///     writer.update(|graph| {
+///         graph.add_node(Node::new(2));
+///         graph.add_edge(Edge::new(1, 2));
+///     })
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert!(shared.read().has_edge(1, 2));
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedGraph<G> {
+    inner: Arc<RwLock<G>>,
+}
+
+impl<G> SharedGraph<G> {
+    /// Wraps a graph so that it can be shared between threads.
+    pub fn new(graph: G) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(graph)),
+        }
+    }
+
+    /// Locks the graph for reading, blocking while a writer holds it.
+    ///
+    /// Any number of threads can read at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a writer panicked while holding the graph.
+    pub fn read(&self) -> RwLockReadGuard<'_, G> {
+        self.inner
+            .read()
+            .unwrap_or_else(|_| panic!("Shared graph poisoned by a panicking writer"))
+    }
+
+    /// Locks the graph for writing, blocking until every reader and writer releases it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a writer panicked while holding the graph.
+    pub fn write(&self) -> RwLockWriteGuard<'_, G> {
+        self.inner
+            .write()
+            .unwrap_or_else(|_| panic!("Shared graph poisoned by a panicking writer"))
+    }
+
+    /// Applies `f` to the graph while holding the write lock, and returns its result.
+    ///
+    /// Several modifications made in one call are seen by readers all at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a writer panicked while holding the graph.
+    pub fn update<R>(&self, f: impl FnOnce(&mut G) -> R) -> R {
+        f(&mut self.write())
+    }
+
+    /// Returns a copy of the current graph, taken under the read lock.
+    ///
+    /// The copy can be read for as long as needed without blocking writers. For a
+    /// [`PersistentGraph`](crate::implementations::PersistentGraph) the copy is O(1); for
+    /// other graphs it copies the whole graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a writer panicked while holding the graph.
+    pub fn snapshot(&self) -> G
+    where
+        G: Clone,
+    {
+        self.read().clone()
+    }
+}
+
+impl<G> Clone for SharedGraph<G> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{
+        CompiledFilteredGraph, DenseGraph, Edge, FilteredGraph, Graph, Node,
+    };
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+    use ade_traits::GraphViewTrait;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_graph_types_are_send_and_sync() {
+        assert_send_sync::<Graph<Node, Edge>>();
+        assert_send_sync::<DenseGraph<Node, Edge>>();
+        assert_send_sync::<FilteredGraph<'static, Node, Edge>>();
+        assert_send_sync::<CompiledFilteredGraph<'static, Node, Edge>>();
+        assert_send_sync::<SharedGraph<Graph<Node, Edge>>>();
+        #[cfg(feature = "persistent")]
+        assert_send_sync::<crate::implementations::PersistentGraph<Node, Edge>>();
+    }
+
+    #[test]
+    fn test_concurrent_reads_and_writes() {
+        let shared = SharedGraph::new(build_graph::<Node, Edge>(vec![0], vec![]));
+
+        let writer = {
+            let graph = shared.clone();
+            thread::spawn(move || {
+                for key in 1..100 {
+                    graph.update(|g| {
+                        g.add_node(Node::new(key));
+                        g.add_edge(Edge::new(key - 1, key));
+                    });
+                }
+            })
+        };
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let graph = shared.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        // Updates are atomic: every node but the first has its incoming edge
+                        let g = graph.read();
+                        assert_eq!(g.get_edges().count(), g.node_count() - 1);
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(shared.read().node_count(), 100);
+    }
+
+    #[test]
+    fn test_snapshot_is_independent() {
+        let shared = SharedGraph::new(build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]));
+        let snapshot = shared.snapshot();
+        shared.update(|g| g.remove_edge(0, 1));
+
+        assert!(snapshot.has_edge(0, 1));
+        assert!(!shared.read().has_edge(0, 1));
+    }
+
+    #[test]
+    fn test_poisoned_graph_panics() {
+        let shared = SharedGraph::new(build_graph::<Node, Edge>(vec![0], vec![]));
+        let graph = shared.clone();
+        let _ = thread::spawn(move || graph.update(|_| panic!("writer failed"))).join();
+
+        assert_panics_with!(
+            shared.read().node_count(),
+            "Shared graph poisoned by a panicking writer"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::implementations::{FilteredGraph, Graph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::marker::PhantomData;
+
+/// A view of a directed graph with edge directions ignored.
+///
+/// `UndirectedView` borrows a base graph and reports, for every node, the nodes joined to it
+/// by an edge in either direction, both as successors and as predecessors. Algorithms that
+/// only follow neighbors, such as traversals, connectivity or bridges, can run on it as on
+/// an undirected graph without building a graph with every edge doubled.
+///
+/// Neighbors are listed once even when both `(u, v)` and `(v, u)` are edges of the base
+/// graph. A self-loop makes a node its own neighbor.
+///
+/// Edges are not duplicated: [`get_edges`](GraphViewTrait::get_edges) yields each edge of
+/// the base graph once, with its original direction, and
+/// [`get_edge`](GraphViewTrait::get_edge) returns the base edge joining two nodes in either
+/// direction, so its source and target may be swapped with respect to the query. The view
+/// therefore does not pass [`check_graph_consistency`](ade_traits::check_graph_consistency),
+/// which expects an edge for every successor.
+///
+/// # Type Parameters
+///
+/// * `'a` - Lifetime of the borrowed base graph
+/// * `N` - Node type implementing [`NodeTrait`]
+/// * `E` - Edge type implementing [`EdgeTrait`]
+/// * `G` - Base graph type implementing [`GraphViewTrait`], [`Graph`] by default
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge, UndirectedView};
+/// use ade_graph::traversal::Bfs;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::{EdgeTrait, GraphViewTrait};
+///
+/// // 0 → 1 ← 2, so 2 cannot be reached from 0 along the edges
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (2, 1)]);
+/// assert_eq!(Bfs::new(&graph, 0).count(), 2);
+///
+/// let undirected = UndirectedView::new(&graph);
+/// assert_eq!(Bfs::new(&undirected, 0).count(), 3);
+/// assert!(undirected.has_edge(1, 0));
+/// assert_eq!(undirected.get_edge(1, 0).key(), (0, 1));
+/// ```
+pub struct UndirectedView<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E> = Graph<N, E>> {
+    base: &'a G,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> UndirectedView<'a, N, E, G> {
+    /// Creates an undirected view of `base`.
+    ///
+    /// The base graph is borrowed, not copied, so creating the view takes constant time.
+    pub fn new(base: &'a G) -> Self {
+        Self {
+            base,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the base graph.
+    pub fn base(&self) -> &'a G {
+        self.base
+    }
+
+    // Successors of the base node, then its predecessors that are not also successors
+    fn neighbor_keys(&self, node_key: u32) -> impl Iterator<Item = u32> + '_ {
+        self.base.get_successors_keys(node_key).chain(
+            self.base
+                .get_predecessors_keys(node_key)
+                .filter(move |&pred| !self.base.has_edge(node_key, pred)),
+        )
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphViewTrait<N, E>
+    for UndirectedView<'_, N, E, G>
+{
+    fn node_count(&self) -> usize {
+        self.base.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.base.edge_count()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
+
+    fn get_node(&self, key: u32) -> &N {
+        self.base.get_node(key)
+    }
+
+    fn has_node(&self, key: u32) -> bool {
+        self.base.has_node(key)
+    }
+
+    fn get_nodes<'b>(&'b self) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.base.get_nodes()
+    }
+
+    fn get_node_keys(&self) -> impl Iterator<Item = u32> {
+        self.base.get_node_keys()
+    }
+
+    fn get_edge(&self, source: u32, target: u32) -> &E {
+        if self.base.has_edge(source, target) {
+            self.base.get_edge(source, target)
+        } else {
+            self.base.get_edge(target, source)
+        }
+    }
+
+    fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.base.has_edge(source, target) || self.base.has_edge(target, source)
+    }
+
+    fn get_edges<'b>(&'b self) -> impl Iterator<Item = &'b E>
+    where
+        E: 'b,
+    {
+        self.base.get_edges()
+    }
+
+    fn get_predecessors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.neighbor_keys(node_key)
+            .map(move |key| self.base.get_node(key))
+    }
+
+    fn get_successors<'b>(&'b self, node_key: u32) -> impl Iterator<Item = &'b N>
+    where
+        N: 'b,
+    {
+        self.neighbor_keys(node_key)
+            .map(move |key| self.base.get_node(key))
+    }
+
+    fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.neighbor_keys(node_key)
+    }
+
+    fn get_predecessors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> {
+        self.neighbor_keys(node_key)
+    }
+
+    fn filter(&self, node_keys: &[u32]) -> impl GraphViewTrait<N, E> {
+        FilteredGraph::new(self, node_keys.iter().copied())
+    }
+
+    fn has_sequential_keys(&self) -> bool {
+        self.base.has_sequential_keys()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+
+    fn sorted(keys: impl Iterator<Item = u32>) -> Vec<u32> {
+        let mut keys: Vec<u32> = keys.collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_neighbors_in_both_directions_once() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1), (1, 0), (2, 1), (1, 3), (3, 3)],
+        );
+        let undirected = UndirectedView::new(&graph);
+
+        assert_eq!(sorted(undirected.get_successors_keys(1)), vec![0, 2, 3]);
+        assert_eq!(sorted(undirected.get_predecessors_keys(1)), vec![0, 2, 3]);
+        assert_eq!(sorted(undirected.get_successors_keys(3)), vec![1, 3]);
+        assert_eq!(
+            sorted(undirected.get_successors(2).map(|node| node.key())),
+            vec![1]
+        );
+
+        assert!(undirected.has_edge(3, 1) && undirected.has_edge(1, 2));
+        assert!(!undirected.has_edge(0, 2));
+        assert_eq!(undirected.get_edge(1, 0).key(), (1, 0));
+        assert_eq!(undirected.get_edge(1, 2).key(), (2, 1));
+        assert_eq!(undirected.get_edges().count(), 5);
+    }
+
+    #[test]
+    fn test_filter_keeps_undirected_neighbors() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (2, 1), (3, 2)]);
+        let undirected = UndirectedView::new(&graph);
+        let filtered = undirected.filter(&[1, 2, 3]);
+
+        assert_eq!(sorted(filtered.get_successors_keys(2)), vec![1, 3]);
+        assert_eq!(sorted(filtered.get_predecessors_keys(1)), vec![2]);
+        assert_eq!(
+            sorted(filtered.get_successors(2).map(|node| node.key())),
+            vec![1, 3]
+        );
+        assert!(!filtered.has_node(0));
+    }
+}
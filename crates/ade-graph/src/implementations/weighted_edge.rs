@@ -0,0 +1,94 @@
+use ade_traits::{EdgeTrait, WeightedEdgeTrait};
+use std::fmt::Debug;
+
+/// A directed edge carrying a weight of type `W`.
+///
+/// `WeightedEdge` implements [`WeightedEdgeTrait`], so it can be used by algorithms that
+/// need edge costs, and [`EdgeTrait`], so it can be used by every other algorithm. Edges
+/// created through [`EdgeTrait::new`], for instance by generic graph transformations, get
+/// the default weight of `W`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, WeightedEdge};
+/// use ade_graph::{GraphViewTrait, WeightedEdgeTrait};
+///
+/// let graph = Graph::<Node, WeightedEdge<u32>>::new(
+///     vec![Node::new(0), Node::new(1)],
+///     vec![WeightedEdge::with_weight(0, 1, 7)],
+/// );
+/// assert_eq!(graph.get_edge(0, 1).weight(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedEdge<W> {
+    source: u32,
+    target: u32,
+    weight: W,
+}
+
+impl<W> WeightedEdge<W> {
+    /// Creates a new directed edge from a source node to a target node with the given weight.
+    pub fn with_weight(source: u32, target: u32, weight: W) -> Self {
+        Self {
+            source,
+            target,
+            weight,
+        }
+    }
+}
+
+impl<W: Copy + Debug + Default> EdgeTrait for WeightedEdge<W> {
+    fn new(source: u32, target: u32) -> Self {
+        Self::with_weight(source, target, W::default())
+    }
+
+    fn source(&self) -> u32 {
+        self.source
+    }
+
+    fn target(&self) -> u32 {
+        self.target
+    }
+
+    fn key(&self) -> (u32, u32) {
+        (self.source, self.target)
+    }
+
+    fn with_endpoints(&self, source: u32, target: u32) -> Self {
+        Self::with_weight(source, target, self.weight)
+    }
+}
+
+impl<W: Copy + Debug + Default> WeightedEdgeTrait for WeightedEdge<W> {
+    type Weight = W;
+
+    fn with_weight(source: u32, target: u32, weight: W) -> Self {
+        WeightedEdge::with_weight(source, target, weight)
+    }
+
+    fn weight(&self) -> W {
+        self.weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_edge() {
+        let edge = WeightedEdge::with_weight(1, 2, -0.5);
+        assert_eq!(edge.key(), (1, 2));
+        assert_eq!(edge.weight(), -0.5);
+        assert_eq!(edge.with_endpoints(4, 5).weight(), -0.5);
+    }
+
+    #[test]
+    fn test_new_uses_default_weight() {
+        let edge = <WeightedEdge<i64> as EdgeTrait>::new(3, 3);
+        assert_eq!(edge.source(), edge.target());
+        assert_eq!(edge.weight(), 0);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Maps user identifiers, such as strings, `u64` or UUIDs, to dense `u32` node keys and back.
+///
+/// Each new identifier gets the next key, starting from 0, so the keys given by an interner
+/// are always sequential and can be used directly by the algorithms that index vectors by
+/// key.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::keyed::KeyInterner;
+///
+/// let mut interner = KeyInterner::new();
+/// assert_eq!(interner.intern("parse".to_string()), 0);
+/// assert_eq!(interner.intern("check".to_string()), 1);
+/// assert_eq!(interner.intern("parse".to_string()), 0);
+///
+/// assert_eq!(interner.key("check"), Some(1));
+/// assert_eq!(interner.id(0).map(String::as_str), Some("parse"));
+/// assert_eq!(interner.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyInterner<K> {
+    keys: HashMap<K, u32>,
+    ids: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> KeyInterner<K> {
+    /// Creates an interner with no identifiers.
+    pub fn new() -> Self {
+        Self {
+            keys: HashMap::new(),
+            ids: Vec::new(),
+        }
+    }
+
+    /// Returns the key of `id`, giving it the next key if it is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all `u32` keys are already in use.
+    pub fn intern(&mut self, id: K) -> u32 {
+        if let Some(&key) = self.keys.get(&id) {
+            return key;
+        }
+        let key = u32::try_from(self.ids.len()).expect("Too many identifiers for u32 keys");
+        self.keys.insert(id.clone(), key);
+        self.ids.push(id);
+        key
+    }
+
+    /// Returns the key of `id`, if it was interned.
+    pub fn key<Q>(&self, id: &Q) -> Option<u32>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.keys.get(id).copied()
+    }
+
+    /// Returns the identifier with the given key, if any.
+    pub fn id(&self, key: u32) -> Option<&K> {
+        self.ids.get(key as usize)
+    }
+
+    /// Returns the number of interned identifiers.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if no identifier was interned.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns the identifiers in key order: the `i`-th one has key `i`.
+    pub fn ids(&self) -> &[K] {
+        &self.ids
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for KeyInterner<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`Graph`] whose nodes are identified by user identifiers instead of `u32` keys.
+///
+/// Nodes and edges are added and queried by identifier, and a [`KeyInterner`] gives each
+/// identifier a node key. Nodes cannot be removed, so the keys of the underlying graph stay
+/// sequential, and [`graph`](KeyedGraph::graph) can be passed to any algorithm; the keys it
+/// returns are turned back into identifiers with [`id`](KeyedGraph::id).
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::keyed::KeyedGraph;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut graph = KeyedGraph::<&str, Node, Edge>::new();
+/// graph.add_edge("parse", "check");
+/// graph.add_edge("check", "emit");
+///
+/// assert!(graph.has_edge("parse", "check"));
+/// assert!(graph.graph().has_sequential_keys());
+///
+/// let check = graph.key("check").unwrap();
+/// let successors: Vec<&str> = graph
+///     .graph()
+///     .get_successors_keys(check)
+///     .map(|key| *graph.id(key))
+///     .collect();
+/// assert_eq!(successors, vec!["emit"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedGraph<K, N: NodeTrait, E: EdgeTrait> {
+    graph: Graph<N, E>,
+    interner: KeyInterner<K>,
+}
+
+impl<K: Hash + Eq + Clone, N: NodeTrait, E: EdgeTrait> KeyedGraph<K, N, E> {
+    /// Creates a graph with no nodes and no edges.
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::new(Vec::new(), Vec::new()),
+            interner: KeyInterner::new(),
+        }
+    }
+
+    /// Adds a node identified by `id`, created with [`NodeTrait::new`], unless it already
+    /// exists.
+    ///
+    /// # Returns
+    ///
+    /// The key of the node.
+    pub fn add_node(&mut self, id: K) -> u32 {
+        self.add_node_with(id, N::new)
+    }
+
+    /// Adds a node identified by `id`, created by `make` from its key, unless it already
+    /// exists, in which case `make` is not called.
+    ///
+    /// # Returns
+    ///
+    /// The key of the node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::keyed::KeyedGraph;
+    /// use ade_graph::implementations::{DataNode, Edge};
+    /// use ade_graph::{DataNodeTrait, GraphViewTrait};
+    ///
+    /// let mut graph = KeyedGraph::<String, DataNode<u8>, Edge>::new();
+    /// let key = graph.add_node_with("a".to_string(), |key| DataNode::with_data(key, 7));
+    /// assert_eq!(*graph.graph().get_node(key).data(), 7);
+    /// ```
+    pub fn add_node_with(&mut self, id: K, make: impl FnOnce(u32) -> N) -> u32 {
+        let key = self.interner.intern(id);
+        if !self.graph.has_node(key) {
+            self.graph.add_node(make(key));
+        }
+        key
+    }
+
+    /// Adds an edge between the nodes identified by `source` and `target`, created with
+    /// [`EdgeTrait::new`]. Missing nodes are added first.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(old_edge)` - If the edge already existed
+    /// * `None` - If the edge is new
+    pub fn add_edge(&mut self, source: K, target: K) -> Option<E> {
+        self.add_edge_with(source, target, E::new)
+    }
+
+    /// Adds an edge between the nodes identified by `source` and `target`, created by `make`
+    /// from their keys. Missing nodes are added first.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(old_edge)` - If the edge already existed
+    /// * `None` - If the edge is new
+    pub fn add_edge_with(
+        &mut self,
+        source: K,
+        target: K,
+        make: impl FnOnce(u32, u32) -> E,
+    ) -> Option<E> {
+        let source = self.add_node(source);
+        let target = self.add_node(target);
+        self.graph.add_edge(make(source, target))
+    }
+
+    /// Removes the edge between the nodes identified by `source` and `target`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(edge)` - If the edge existed, returns the removed edge
+    /// * `None` - If either node or the edge does not exist
+    pub fn remove_edge<Q>(&mut self, source: &Q, target: &Q) -> Option<E>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let source = self.interner.key(source)?;
+        let target = self.interner.key(target)?;
+        self.graph.try_remove_edge(source, target).ok()
+    }
+
+    /// Returns `true` if a node is identified by `id`.
+    pub fn has_node<Q>(&self, id: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.interner.key(id).is_some()
+    }
+
+    /// Returns `true` if there is an edge between the nodes identified by `source` and
+    /// `target`.
+    pub fn has_edge<Q>(&self, source: &Q, target: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.interner.key(source), self.interner.key(target)) {
+            (Some(source), Some(target)) => self.graph.has_edge(source, target),
+            _ => false,
+        }
+    }
+
+    /// Returns the key of the node identified by `id`, if any.
+    pub fn key<Q>(&self, id: &Q) -> Option<u32>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.interner.key(id)
+    }
+
+    /// Returns the identifier of the node with the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node with this key.
+    pub fn id(&self, key: u32) -> &K {
+        self.interner
+            .id(key)
+            .unwrap_or_else(|| panic!("Node {} not found", key))
+    }
+
+    /// Returns the underlying graph, whose keys are sequential.
+    pub fn graph(&self) -> &Graph<N, E> {
+        &self.graph
+    }
+
+    /// Returns the interner mapping identifiers to keys.
+    pub fn interner(&self) -> &KeyInterner<K> {
+        &self.interner
+    }
+
+    /// Consumes the keyed graph and returns the underlying graph and the interner.
+    pub fn into_parts(self) -> (Graph<N, E>, KeyInterner<K>) {
+        (self.graph, self.interner)
+    }
+}
+
+impl<K: Hash + Eq + Clone, N: NodeTrait, E: EdgeTrait> Default for KeyedGraph<K, N, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node, WeightedEdge};
+    use ade_common::assert_panics_with;
+    use ade_traits::WeightedEdgeTrait;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = KeyInterner::<u64>::default();
+        assert!(interner.is_empty());
+        let keys: Vec<u32> = [900, 12, 900, 7]
+            .into_iter()
+            .map(|id| interner.intern(id))
+            .collect();
+        assert_eq!(keys, vec![0, 1, 0, 2]);
+        assert_eq!(interner.ids(), &[900, 12, 7]);
+        assert_eq!(interner.key(&7), Some(2));
+        assert_eq!(interner.key(&8), None);
+        assert_eq!(interner.id(3), None);
+    }
+
+    #[test]
+    fn test_keyed_graph() {
+        let mut graph = KeyedGraph::<String, Node, WeightedEdge<u32>>::new();
+        assert!(graph
+            .add_edge_with("a".into(), "b".into(), |s, t| WeightedEdge::with_weight(
+                s, t, 3
+            ))
+            .is_none());
+        graph.add_edge("b".into(), "b".into());
+        assert_eq!(graph.add_node("c".into()), 2);
+        assert_eq!(graph.add_node("a".into()), 0);
+
+        assert_eq!(graph.graph().node_count(), 3);
+        assert_eq!(graph.graph().get_edge(0, 1).weight(), 3);
+        assert!(graph.has_edge("b", "b"));
+        assert!(!graph.has_edge("a", "z"));
+        assert!(graph.has_node("c") && !graph.has_node("z"));
+        assert_eq!(ade_traits::check_graph_consistency(graph.graph()), Ok(()));
+
+        assert!(graph.remove_edge("a", "b").is_some());
+        assert!(graph.remove_edge("a", "b").is_none());
+        assert!(graph.remove_edge("a", "z").is_none());
+        assert_eq!(graph.id(2), "c");
+        assert_panics_with!(graph.id(5), "Node 5 not found");
+
+        let (graph, interner) = graph.into_parts();
+        assert!(graph.has_sequential_keys());
+        assert_eq!(interner.len(), 3);
+    }
+
+    #[test]
+    fn test_unit_edges() {
+        let mut graph = KeyedGraph::<(u8, u8), Node, Edge>::default();
+        graph.add_edge((0, 1), (1, 0));
+        assert!(graph.has_edge(&(0, 1), &(1, 0)));
+        assert!(!graph.has_edge(&(1, 0), &(0, 1)));
+    }
+}
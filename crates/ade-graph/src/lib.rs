@@ -1,7 +1,14 @@
+pub mod builder;
 pub mod implementations;
+pub mod keyed;
+pub mod ops;
+#[cfg(feature = "ade-petgraph-compat")]
+pub mod petgraph_compat;
+pub mod query;
+pub mod traversal;
 pub mod utils;
 
 pub use utils::build;
-//pub use utils::normalize;
+pub use utils::normalize;
 
 pub use ade_traits::*;
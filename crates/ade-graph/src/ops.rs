@@ -0,0 +1,229 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, NodeTrait};
+use std::ops::Deref;
+
+/// A single modification of a [`Graph`].
+///
+/// A sequence of operations is an event log of a graph: applying it with
+/// [`Graph::replay`] to a copy of the initial graph rebuilds the final graph. Logs are much
+/// smaller than whole graphs, so they can be stored or sent over the network to keep
+/// several copies of a graph in sync. With the `serde` feature, operations implement
+/// `Serialize` and `Deserialize`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge};
+/// use ade_graph::ops::GraphOp;
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+/// graph.replay(vec![
+///     GraphOp::AddNode(Node::new(1)),
+///     GraphOp::AddNode(Node::new(2)),
+///     GraphOp::AddEdge(Edge::new(1, 2)),
+/// ]);
+///
+/// assert!(graph.has_edge(1, 2));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphOp<N, E> {
+    /// Adds a node, replacing the node with the same key if any. See [`Graph::add_node`].
+    AddNode(N),
+    /// Removes a node and its edges. See [`Graph::remove_node`].
+    RemoveNode(u32),
+    /// Adds an edge, replacing the edge with the same key if any. See [`Graph::add_edge`].
+    AddEdge(E),
+    /// Removes the edge from a source to a target. See [`Graph::remove_edge`].
+    RemoveEdge(u32, u32),
+}
+
+/// A [`Graph`] that records every modification made through it, created by
+/// [`Graph::record`].
+///
+/// The recorder has the same modification methods as [`Graph`], and dereferences to the
+/// graph for reading. Each successful modification is appended to the log returned by
+/// [`ops`](RecordingGraph::ops) and [`finish`](RecordingGraph::finish).
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut server = Graph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+/// let mut client = Graph::<Node, Edge>::new(vec![Node::new(1)], vec![]);
+///
+/// let mut recorder = server.record();
+/// recorder.add_node(Node::new(2));
+/// recorder.add_edge(Edge::new(1, 2));
+/// assert!(recorder.has_edge(1, 2));
+/// let ops = recorder.finish();
+///
+/// client.replay(ops);
+/// assert!(client.has_edge(1, 2));
+/// ```
+#[derive(Debug)]
+pub struct RecordingGraph<'a, N, E> {
+    graph: &'a mut Graph<N, E>,
+    ops: Vec<GraphOp<N, E>>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait> RecordingGraph<'a, N, E> {
+    pub(crate) fn new(graph: &'a mut Graph<N, E>) -> Self {
+        Self {
+            graph,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Adds a node to the graph and records it, as [`Graph::add_node`] does.
+    pub fn add_node(&mut self, node: N) -> Option<N> {
+        self.ops.push(GraphOp::AddNode(node.clone()));
+        self.graph.add_node(node)
+    }
+
+    /// Removes a node from the graph and records it, as [`Graph::remove_node`] does.
+    ///
+    /// Nothing is recorded if the node does not exist.
+    pub fn remove_node(&mut self, key: u32) -> Option<N> {
+        let old = self.graph.remove_node(key);
+        if old.is_some() {
+            self.ops.push(GraphOp::RemoveNode(key));
+        }
+        old
+    }
+
+    /// Adds an edge to the graph and records it, as [`Graph::add_edge`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph. Nothing is
+    /// recorded in that case.
+    pub fn add_edge(&mut self, edge: E) -> Option<E> {
+        let recorded = edge.clone();
+        let old = self.graph.add_edge(edge);
+        self.ops.push(GraphOp::AddEdge(recorded));
+        old
+    }
+
+    /// Removes an edge from the graph and records it, as [`Graph::remove_edge`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph. Nothing is
+    /// recorded in that case, nor if the edge does not exist.
+    pub fn remove_edge(&mut self, source: u32, target: u32) -> Option<E> {
+        let old = self.graph.remove_edge(source, target);
+        if old.is_some() {
+            self.ops.push(GraphOp::RemoveEdge(source, target));
+        }
+        old
+    }
+
+    /// Returns the operations recorded so far.
+    pub fn ops(&self) -> &[GraphOp<N, E>] {
+        &self.ops
+    }
+
+    /// Stops recording and returns the recorded operations.
+    pub fn finish(self) -> Vec<GraphOp<N, E>> {
+        self.ops
+    }
+}
+
+impl<N, E> Deref for RecordingGraph<'_, N, E> {
+    type Target = Graph<N, E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::compare::structural_eq;
+    use ade_common::assert_panics_with;
+    use ade_traits::GraphViewTrait;
+
+    fn sorted_edges(graph: &Graph<Node, Edge>) -> Vec<(u32, u32)> {
+        let mut edges: Vec<(u32, u32)> = graph.get_edges().map(|e| e.key()).collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let initial =
+            || Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![Edge::new(0, 1)]);
+        let mut source = initial();
+
+        let mut recorder = source.record();
+        recorder.add_node(Node::new(2));
+        recorder.add_edge(Edge::new(1, 2));
+        recorder.add_edge(Edge::new(2, 0));
+        recorder.remove_edge(0, 1);
+        recorder.remove_node(1);
+        recorder.add_node(Node::new(3));
+        recorder.add_edge(Edge::new(3, 3));
+        assert_eq!(recorder.ops().len(), 7);
+        let ops = recorder.finish();
+
+        let mut replica = initial();
+        replica.replay(ops);
+
+        assert!(structural_eq(&replica, &source));
+        assert_eq!(sorted_edges(&replica), vec![(2, 0), (3, 3)]);
+    }
+
+    #[test]
+    fn test_failed_operation_is_not_recorded() {
+        let mut graph = Graph::<Node, Edge>::new(vec![Node::new(0)], vec![]);
+        let mut recorder = graph.record();
+        assert_panics_with!(recorder.add_edge(Edge::new(0, 1)), "Node 0 or 1 not found");
+        assert!(recorder.ops().is_empty());
+
+        assert!(recorder.remove_node(5).is_none());
+        assert!(recorder.remove_edge(0, 0).is_none());
+        assert!(recorder.ops().is_empty());
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+        graph.apply(GraphOp::AddNode(Node::new(4)));
+        graph.apply(GraphOp::AddEdge(Edge::new(4, 4)));
+        assert!(graph.has_edge(4, 4));
+
+        graph.apply(GraphOp::RemoveEdge(4, 4));
+        assert!(!graph.has_edge(4, 4));
+        graph.apply(GraphOp::RemoveNode(4));
+        assert!(graph.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let ops: Vec<GraphOp<Node, Edge>> = vec![
+            GraphOp::AddNode(Node::new(0)),
+            GraphOp::AddNode(Node::new(1)),
+            GraphOp::AddEdge(Edge::new(0, 1)),
+            GraphOp::RemoveEdge(0, 1),
+            GraphOp::RemoveNode(1),
+        ];
+
+        let json = serde_json::to_string(&ops).unwrap();
+        let decoded: Vec<GraphOp<Node, Edge>> = serde_json::from_str(&json).unwrap();
+
+        let mut expected = Graph::<Node, Edge>::new(vec![], vec![]);
+        expected.replay(ops);
+        let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+        graph.replay(decoded);
+
+        assert_eq!(graph.get_node_keys().collect::<Vec<_>>(), vec![0]);
+        assert!(structural_eq(&graph, &expected));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use petgraph::graph::NodeIndex;
+
+/// Converts a graph into a [`petgraph::Graph`], and returns the key of each node index.
+///
+/// Nodes are added in ascending order of key, so the node with the `i`-th smallest key
+/// becomes the node with index `i`, and `keys[i]` is its key. For a graph with sequential
+/// keys, node indices are the keys themselves. Node and edge values are cloned into the
+/// node and edge weights, and edges are added in ascending order of key.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::petgraph_compat::to_petgraph;
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![10, 20, 30], vec![(10, 20), (20, 30)]);
+/// let (converted, keys) = to_petgraph(&graph);
+///
+/// assert_eq!(keys, vec![10, 20, 30]);
+/// assert!(converted.contains_edge(1.into(), 2.into()));
+///
+/// // Results of petgraph are read back as keys through the mapping
+/// let order = petgraph::algo::toposort(&converted, None).unwrap();
+/// let order: Vec<u32> = order.into_iter().map(|index| keys[index.index()]).collect();
+/// assert_eq!(order, vec![10, 20, 30]);
+/// ```
+pub fn to_petgraph<N: NodeTrait, E: EdgeTrait>(
+    graph: &Graph<N, E>,
+) -> (petgraph::Graph<N, E>, Vec<u32>) {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let mut edges: Vec<&E> = graph.get_edges().collect();
+    edges.sort_unstable_by_key(|edge| edge.key());
+
+    let index = |key: u32| NodeIndex::new(keys.binary_search(&key).unwrap());
+    let mut converted = petgraph::Graph::with_capacity(keys.len(), edges.len());
+    for &key in &keys {
+        converted.add_node(graph.get_node(key).clone());
+    }
+    for edge in edges {
+        converted.add_edge(index(edge.source()), index(edge.target()), edge.clone());
+    }
+    (converted, keys)
+}
+
+/// Converts a graph into a [`petgraph::Graph`], to run petgraph's algorithms on it.
+///
+/// The conversion is the one of [`to_petgraph`], without the mapping: node keys are
+/// preserved as node indices when the graph has sequential keys, so results of petgraph
+/// can be read as ADE keys with [`NodeIndex::index`]. Other graphs are renumbered in
+/// ascending order of key, and [`to_petgraph`] gives the key of each index.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 1)]);
+/// let converted = petgraph::Graph::from(&graph);
+///
+/// let components = petgraph::algo::kosaraju_scc(&converted);
+/// assert_eq!(components.len(), 2);
+/// assert!(converted.contains_edge(2.into(), 1.into()));
+/// ```
+impl<N: NodeTrait, E: EdgeTrait> From<&Graph<N, E>> for petgraph::Graph<N, E> {
+    fn from(graph: &Graph<N, E>) -> Self {
+        to_petgraph(graph).0
+    }
+}
+
+/// Converts a [`petgraph::Graph`] back into a graph.
+///
+/// Node indices become node keys, so the result has sequential keys. Node weights are
+/// rebuilt with [`NodeTrait::with_key`] and edge weights with [`EdgeTrait::with_endpoints`],
+/// so their data is kept whatever key or endpoints they held. A graph has at most one edge
+/// per pair of nodes: of parallel edges, the one added last to the petgraph graph is kept.
+///
+/// Graphs with other weights can be converted with [`petgraph::Graph::map`] first.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge};
+/// use ade_graph::GraphViewTrait;
+/// use ade_traits::{EdgeTrait, NodeTrait};
+///
+/// let mut labeled = petgraph::Graph::<&str, f64>::new();
+/// let a = labeled.add_node("a");
+/// let b = labeled.add_node("b");
+/// labeled.add_edge(a, b, 1.5);
+///
+/// let plain = labeled.map(|i, _| Node::new(i.index() as u32), |_, _| Edge::new(0, 0));
+/// let graph = Graph::from(&plain);
+///
+/// assert_eq!(graph.node_count(), 2);
+/// assert!(graph.has_edge(0, 1));
+/// ```
+impl<N: NodeTrait, E: EdgeTrait> From<&petgraph::Graph<N, E>> for Graph<N, E> {
+    fn from(graph: &petgraph::Graph<N, E>) -> Self {
+        let nodes = graph
+            .node_indices()
+            .map(|index| graph[index].with_key(index.index() as u32))
+            .collect();
+        let edges = graph
+            .edge_indices()
+            .map(|index| {
+                let (source, target) = graph.edge_endpoints(index).unwrap();
+                graph[index].with_endpoints(source.index() as u32, target.index() as u32)
+            })
+            .collect();
+        Graph::new(nodes, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DataEdge, DataNode, Edge, Graph, Node};
+    use crate::utils::build::build_graph;
+    use ade_traits::{DataEdgeTrait, DataNodeTrait, EdgeTrait, GraphViewTrait};
+
+    #[test]
+    fn test_round_trip_keeps_keys_and_data() {
+        let graph = Graph::<DataNode<&str>, DataEdge<u8>>::new(
+            vec![
+                DataNode::with_data(0, "a"),
+                DataNode::with_data(1, "b"),
+                DataNode::with_data(2, "c"),
+            ],
+            vec![
+                DataEdge::with_data(2, 0, 20),
+                DataEdge::with_data(0, 1, 1),
+                DataEdge::with_data(1, 1, 11),
+            ],
+        );
+
+        let converted = petgraph::Graph::from(&graph);
+        assert_eq!(converted.node_count(), 3);
+        assert_eq!(*converted[petgraph::graph::NodeIndex::new(2)].data(), "c");
+        let index = converted.find_edge(2.into(), 0.into()).unwrap();
+        assert_eq!(*converted[index].data(), 20);
+
+        let back = Graph::from(&converted);
+        assert_eq!(ade_traits::check_graph_consistency(&back), Ok(()));
+        assert_eq!(*back.get_node(1).data(), "b");
+        assert_eq!(*back.get_edge(1, 1).data(), 11);
+        let mut edges: Vec<(u32, u32)> = back.get_edges().map(|edge| edge.key()).collect();
+        edges.sort_unstable();
+        assert_eq!(edges, vec![(0, 1), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn test_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![7, 2, 5], vec![(7, 2), (2, 5), (5, 5)]);
+        let (converted, keys) = to_petgraph(&graph);
+        assert_eq!(keys, vec![2, 5, 7]);
+        assert!(converted.contains_edge(2.into(), 0.into()));
+        assert!(converted.contains_edge(1.into(), 1.into()));
+        assert_eq!(converted.edge_count(), 3);
+
+        let back = Graph::from(&petgraph::Graph::<Node, Edge>::from(&graph));
+        assert_eq!(back.get_node_keys().max(), Some(2));
+        assert!(back.has_edge(0, 1) && back.has_edge(2, 0));
+    }
+}
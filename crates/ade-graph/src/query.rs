@@ -0,0 +1,414 @@
+use crate::implementations::FilteredGraph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+// A predicate added with NodeQuery::matching, shared between clones of the query
+type Predicate<N> = Arc<dyn Fn(&N) -> bool + Send + Sync>;
+
+/// A structural condition on the nodes selected by a [`NodeQuery`].
+///
+/// Filters only refer to node keys and to the structure of the graph, so, unlike the
+/// closures passed to [`NodeQuery::matching`], they can be compared, stored and sent
+/// elsewhere. With the `serde` feature, they implement `Serialize` and `Deserialize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeFilter {
+    /// Nodes with exactly this number of predecessors.
+    InDegree(usize),
+    /// Nodes with exactly this number of successors.
+    OutDegree(usize),
+    /// Nodes reachable from this node, the node itself included.
+    ReachableFrom(u32),
+    /// Nodes from which this node is reachable, the node itself included.
+    Reaching(u32),
+    /// Nodes with one of these keys.
+    Keys(Vec<u32>),
+}
+
+/// A reusable selection of nodes, built from chained conditions.
+///
+/// A node is selected when it satisfies every condition. Conditions are either structural
+/// [`NodeFilter`]s or arbitrary predicates on the node added with
+/// [`matching`](NodeQuery::matching). A query is independent of any graph: build it once,
+/// then run it on many graphs with [`keys`](NodeQuery::keys) or [`view`](NodeQuery::view).
+/// To run a query on a single graph, start from [`GraphQuery::query`] instead.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::query::NodeQuery;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Sinks reachable from node 0
+/// let sinks = NodeQuery::<Node>::new().reachable_from(0).with_out_degree(0);
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (3, 2)]);
+/// assert_eq!(sinks.keys(&graph), vec![1, 2]);
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// assert_eq!(sinks.keys(&graph), vec![2]);
+/// ```
+pub struct NodeQuery<N> {
+    filters: Vec<NodeFilter>,
+    predicates: Vec<Predicate<N>>,
+}
+
+impl<N: NodeTrait> NodeQuery<N> {
+    /// Creates a query that selects every node.
+    pub fn new() -> Self {
+        Self {
+            filters: Vec::new(),
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Creates a query from structural filters, for instance deserialized ones.
+    pub fn from_filters(filters: Vec<NodeFilter>) -> Self {
+        Self {
+            filters,
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Adds a structural filter.
+    pub fn filter(mut self, filter: NodeFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Selects nodes with exactly `degree` predecessors.
+    pub fn with_in_degree(self, degree: usize) -> Self {
+        self.filter(NodeFilter::InDegree(degree))
+    }
+
+    /// Selects nodes with exactly `degree` successors.
+    pub fn with_out_degree(self, degree: usize) -> Self {
+        self.filter(NodeFilter::OutDegree(degree))
+    }
+
+    /// Selects nodes reachable from `key`, including `key` itself.
+    pub fn reachable_from(self, key: u32) -> Self {
+        self.filter(NodeFilter::ReachableFrom(key))
+    }
+
+    /// Selects nodes from which `key` is reachable, including `key` itself.
+    pub fn reaching(self, key: u32) -> Self {
+        self.filter(NodeFilter::Reaching(key))
+    }
+
+    /// Selects nodes whose key is in `keys`.
+    pub fn with_keys(self, keys: impl IntoIterator<Item = u32>) -> Self {
+        self.filter(NodeFilter::Keys(keys.into_iter().collect()))
+    }
+
+    /// Selects nodes for which `predicate` returns `true`.
+    ///
+    /// Predicates are not part of [`filters`](NodeQuery::filters), so they are lost when a
+    /// query is stored as its filters.
+    pub fn matching(mut self, predicate: impl Fn(&N) -> bool + Send + Sync + 'static) -> Self {
+        self.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Returns the structural filters of the query.
+    pub fn filters(&self) -> &[NodeFilter] {
+        &self.filters
+    }
+
+    /// Returns the keys of the selected nodes, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a reachability filter refers to a node that is not in the graph.
+    pub fn keys<E: EdgeTrait>(&self, graph: &impl GraphViewTrait<N, E>) -> Vec<u32> {
+        // Reachability sets are computed once per filter, not once per node
+        let sets: Vec<Option<HashSet<u32>>> = self
+            .filters
+            .iter()
+            .map(|filter| match filter {
+                NodeFilter::ReachableFrom(key) => Some(reachable(graph, *key, true)),
+                NodeFilter::Reaching(key) => Some(reachable(graph, *key, false)),
+                NodeFilter::Keys(keys) => Some(keys.iter().copied().collect()),
+                NodeFilter::InDegree(_) | NodeFilter::OutDegree(_) => None,
+            })
+            .collect();
+
+        let mut keys: Vec<u32> = graph
+            .get_node_keys()
+            .filter(|&key| {
+                self.filters
+                    .iter()
+                    .zip(&sets)
+                    .all(|(filter, set)| match (filter, set) {
+                        (NodeFilter::InDegree(degree), _) => graph.in_degree(key) == *degree,
+                        (NodeFilter::OutDegree(degree), _) => graph.out_degree(key) == *degree,
+                        (_, Some(set)) => set.contains(&key),
+                        (_, None) => unreachable!(),
+                    })
+            })
+            .filter(|&key| {
+                let node = graph.get_node(key);
+                self.predicates.iter().all(|predicate| predicate(node))
+            })
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Returns a filtered view of `graph` containing the selected nodes and the edges
+    /// between them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys, as [`FilteredGraph::new`] does,
+    /// or if a reachability filter refers to a node that is not in the graph.
+    pub fn view<'a, E: EdgeTrait, G: GraphViewTrait<N, E>>(
+        &self,
+        graph: &'a G,
+    ) -> FilteredGraph<'a, N, E, G> {
+        FilteredGraph::new(graph, self.keys(graph))
+    }
+}
+
+impl<N: NodeTrait> Default for NodeQuery<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> Clone for NodeQuery<N> {
+    fn clone(&self) -> Self {
+        Self {
+            filters: self.filters.clone(),
+            predicates: self.predicates.clone(),
+        }
+    }
+}
+
+impl<N> fmt::Debug for NodeQuery<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeQuery")
+            .field("filters", &self.filters)
+            .field("predicates", &self.predicates.len())
+            .finish()
+    }
+}
+
+/// A [`NodeQuery`] bound to a graph, created by [`GraphQuery::query`].
+///
+/// It has the same chaining methods as [`NodeQuery`], and runs on its graph with
+/// [`keys`](BoundQuery::keys) or [`view`](BoundQuery::view).
+pub struct BoundQuery<'a, N, E, G> {
+    graph: &'a G,
+    query: NodeQuery<N>,
+    _marker: std::marker::PhantomData<fn() -> E>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> BoundQuery<'a, N, E, G> {
+    // Applies a change to the underlying query
+    fn map(self, f: impl FnOnce(NodeQuery<N>) -> NodeQuery<N>) -> Self {
+        Self {
+            graph: self.graph,
+            query: f(self.query),
+            _marker: self._marker,
+        }
+    }
+
+    /// Selects nodes with exactly `degree` predecessors.
+    pub fn with_in_degree(self, degree: usize) -> Self {
+        self.map(|q| q.with_in_degree(degree))
+    }
+
+    /// Selects nodes with exactly `degree` successors.
+    pub fn with_out_degree(self, degree: usize) -> Self {
+        self.map(|q| q.with_out_degree(degree))
+    }
+
+    /// Selects nodes reachable from `key`, including `key` itself.
+    pub fn reachable_from(self, key: u32) -> Self {
+        self.map(|q| q.reachable_from(key))
+    }
+
+    /// Selects nodes from which `key` is reachable, including `key` itself.
+    pub fn reaching(self, key: u32) -> Self {
+        self.map(|q| q.reaching(key))
+    }
+
+    /// Selects nodes whose key is in `keys`.
+    pub fn with_keys(self, keys: impl IntoIterator<Item = u32>) -> Self {
+        self.map(|q| q.with_keys(keys))
+    }
+
+    /// Selects nodes for which `predicate` returns `true`.
+    pub fn matching(self, predicate: impl Fn(&N) -> bool + Send + Sync + 'static) -> Self {
+        self.map(|q| q.matching(predicate))
+    }
+
+    /// Returns the keys of the selected nodes, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a reachability filter refers to a node that is not in the graph.
+    pub fn keys(&self) -> Vec<u32> {
+        self.query.keys(self.graph)
+    }
+
+    /// Returns a filtered view of the graph containing the selected nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph does not have sequential keys, or if a reachability filter refers
+    /// to a node that is not in the graph.
+    pub fn view(&self) -> FilteredGraph<'a, N, E, G> {
+        self.query.view(self.graph)
+    }
+
+    /// Detaches the query from the graph, to run it again on other graphs.
+    pub fn into_query(self) -> NodeQuery<N> {
+        self.query
+    }
+}
+
+/// Adds [`query`](GraphQuery::query) to every graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::query::GraphQuery;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::{GraphViewTrait, NodeTrait};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (1, 3)]);
+///
+/// let leaves = graph.query().reachable_from(1).with_out_degree(0).matching(|n| n.key() != 3);
+/// assert_eq!(leaves.keys(), vec![2]);
+/// assert_eq!(leaves.view().node_count(), 1);
+/// ```
+pub trait GraphQuery<N: NodeTrait, E: EdgeTrait>: GraphViewTrait<N, E> + Sized {
+    /// Starts a query selecting nodes of this graph.
+    fn query(&self) -> BoundQuery<'_, N, E, Self> {
+        BoundQuery {
+            graph: self,
+            query: NodeQuery::new(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> GraphQuery<N, E> for G {}
+
+// Collects the nodes reachable from key, following edges forward or backward
+fn reachable<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    key: u32,
+    forward: bool,
+) -> HashSet<u32> {
+    if !graph.has_node(key) {
+        panic!("Node {} not found", key);
+    }
+
+    let mut visited = HashSet::from([key]);
+    let mut stack = vec![key];
+    while let Some(v) = stack.pop() {
+        let next: Vec<u32> = if forward {
+            graph.get_successors_keys(v).collect()
+        } else {
+            graph.get_predecessors_keys(v).collect()
+        };
+        for w in next {
+            if visited.insert(w) {
+                stack.push(w);
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+
+    fn sample() -> crate::implementations::Graph<Node, Edge> {
+        // 0 -> 1 -> 2 -> 3, 1 -> 4, 5 -> 3
+        build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (1, 2), (2, 3), (1, 4), (5, 3)],
+        )
+    }
+
+    #[test]
+    fn test_degree_filters() {
+        let graph = sample();
+        assert_eq!(graph.query().with_in_degree(0).keys(), vec![0, 5]);
+        assert_eq!(graph.query().with_out_degree(0).keys(), vec![3, 4]);
+        assert_eq!(graph.query().with_in_degree(2).keys(), vec![3]);
+    }
+
+    #[test]
+    fn test_reachability_filters() {
+        let graph = sample();
+        assert_eq!(graph.query().reachable_from(1).keys(), vec![1, 2, 3, 4]);
+        assert_eq!(graph.query().reaching(3).keys(), vec![0, 1, 2, 3, 5]);
+        assert_eq!(
+            graph.query().reachable_from(0).reaching(3).keys(),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_combined_query_and_view() {
+        let graph = sample();
+        let query = graph
+            .query()
+            .reachable_from(0)
+            .with_keys([1, 2, 3, 5])
+            .matching(|node| node.key() != 3);
+
+        assert_eq!(query.keys(), vec![1, 2]);
+        let view = query.view();
+        assert_eq!(view.node_count(), 2);
+        assert!(view.has_edge(1, 2));
+    }
+
+    #[test]
+    fn test_query_reuse_and_filters() {
+        let query = NodeQuery::<Node>::new()
+            .with_in_degree(0)
+            .with_out_degree(1);
+        assert_eq!(
+            query.filters(),
+            &[NodeFilter::InDegree(0), NodeFilter::OutDegree(1)]
+        );
+
+        let rebuilt = NodeQuery::<Node>::from_filters(query.filters().to_vec());
+        assert_eq!(rebuilt.keys(&sample()), vec![0, 5]);
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_eq!(query.keys(&graph), vec![0]);
+    }
+
+    #[test]
+    fn test_reachable_from_missing_node() {
+        let graph = sample();
+        assert_panics_with!(graph.query().reachable_from(9).keys(), "Node 9 not found");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_filters_serde_round_trip() {
+        let query = NodeQuery::<Node>::new().reachable_from(0).with_keys([1, 2]);
+        let json = serde_json::to_string(query.filters()).unwrap();
+        let filters: Vec<NodeFilter> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            NodeQuery::<Node>::from_filters(filters).keys(&sample()),
+            vec![1, 2]
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{HashSet, VecDeque};
+use std::marker::PhantomData;
+
+/// A breadth-first traversal of the nodes reachable from a start node.
+///
+/// The iterator yields node keys in order of increasing distance from the start, starting
+/// with the start itself. Successors of a node are visited in the order returned by
+/// [`get_successors_keys`](GraphViewTrait::get_successors_keys).
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::traversal::Bfs;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (0, 3)]);
+///
+/// let order: Vec<u32> = Bfs::new(&graph, 0).collect();
+/// assert_eq!(order.len(), 4);
+/// assert_eq!(order[0], 0);
+/// assert_eq!(order[3], 2); // 2 is the only node at distance 2
+/// ```
+pub struct Bfs<'a, N, E, G> {
+    graph: &'a G,
+    visited: HashSet<u32>,
+    queue: VecDeque<u32>,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Bfs<'a, N, E, G> {
+    /// Creates a breadth-first traversal of `graph` from `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is not a node of the graph.
+    pub fn new(graph: &'a G, start: u32) -> Self {
+        if !graph.has_node(start) {
+            panic!("Node {} not found", start);
+        }
+        Self {
+            graph,
+            visited: HashSet::from([start]),
+            queue: VecDeque::from([start]),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Iterator for Bfs<'_, N, E, G> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let key = self.queue.pop_front()?;
+        for successor in self.graph.get_successors_keys(key) {
+            if self.visited.insert(successor) {
+                self.queue.push_back(successor);
+            }
+        }
+        Some(key)
+    }
+}
+
+/// An event of a depth-first traversal, yielded by [`DfsEvents`].
+///
+/// Each node reachable from the start is discovered once and finished once, after all of
+/// its successors. Each edge out of a discovered node is classified once, when it is
+/// followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent {
+    /// The node is visited for the first time.
+    Discover(u32),
+    /// The edge, given as `(source, target)`, leads to a node not yet discovered, which is
+    /// discovered next.
+    TreeEdge(u32, u32),
+    /// The edge leads to a node still being explored, an ancestor of the source in the
+    /// depth-first tree, so it closes a cycle. Self-loops are back edges.
+    BackEdge(u32, u32),
+    /// The edge leads to a node already finished: a descendant of the source reached
+    /// through another path, or a node of another branch.
+    CrossEdge(u32, u32),
+    /// All the successors of the node have been explored.
+    Finish(u32),
+}
+
+/// A depth-first traversal of the nodes reachable from a start node, as a sequence of
+/// [`DfsEvent`]s.
+///
+/// The events are enough to build most depth-first algorithms, such as cycle detection,
+/// topological orders or dominator computations, without managing the frontier. The
+/// traversal uses an explicit stack, so deep graphs cannot overflow the call stack.
+/// Successors of a node are visited in the order returned by
+/// [`get_successors_keys`](GraphViewTrait::get_successors_keys).
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::traversal::{DfsEvent, DfsEvents};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+///
+/// let events: Vec<DfsEvent> = DfsEvents::new(&graph, 0).collect();
+/// assert_eq!(events, vec![
+///     DfsEvent::Discover(0),
+///     DfsEvent::TreeEdge(0, 1),
+///     DfsEvent::Discover(1),
+///     DfsEvent::TreeEdge(1, 2),
+///     DfsEvent::Discover(2),
+///     DfsEvent::BackEdge(2, 0),
+///     DfsEvent::Finish(2),
+///     DfsEvent::Finish(1),
+///     DfsEvent::Finish(0),
+/// ]);
+/// ```
+pub struct DfsEvents<'a, N, E, G> {
+    graph: &'a G,
+    discovered: HashSet<u32>,
+    finished: HashSet<u32>,
+    // Nodes being explored, with the start of their unvisited successors in `successors`
+    frames: Vec<(u32, usize)>,
+    successors: Vec<u32>,
+    // Event to yield before resuming the traversal
+    queued: Option<DfsEvent>,
+    _marker: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> DfsEvents<'a, N, E, G> {
+    /// Creates a depth-first traversal of `graph` from `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is not a node of the graph.
+    pub fn new(graph: &'a G, start: u32) -> Self {
+        if !graph.has_node(start) {
+            panic!("Node {} not found", start);
+        }
+        let mut events = Self {
+            graph,
+            discovered: HashSet::new(),
+            finished: HashSet::new(),
+            frames: Vec::new(),
+            successors: Vec::new(),
+            queued: None,
+            _marker: PhantomData,
+        };
+        events.discover(start);
+        events
+    }
+
+    // Marks key as discovered and schedules the exploration of its successors
+    fn discover(&mut self, key: u32) {
+        self.discovered.insert(key);
+        let start = self.successors.len();
+        self.frames.push((key, start));
+        self.successors.extend(self.graph.get_successors_keys(key));
+        // Successors are popped from the end, reverse them to visit them in graph order
+        self.successors[start..].reverse();
+        self.queued = Some(DfsEvent::Discover(key));
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Iterator for DfsEvents<'_, N, E, G> {
+    type Item = DfsEvent;
+
+    fn next(&mut self) -> Option<DfsEvent> {
+        if let Some(event) = self.queued.take() {
+            return Some(event);
+        }
+
+        let &(source, start) = self.frames.last()?;
+        if self.successors.len() > start {
+            let target = self.successors.pop().unwrap();
+            return Some(if !self.discovered.contains(&target) {
+                self.discover(target);
+                DfsEvent::TreeEdge(source, target)
+            } else if !self.finished.contains(&target) {
+                DfsEvent::BackEdge(source, target)
+            } else {
+                DfsEvent::CrossEdge(source, target)
+            });
+        }
+
+        self.frames.pop();
+        self.finished.insert(source);
+        Some(DfsEvent::Finish(source))
+    }
+}
+
+/// A depth-first traversal of the nodes reachable from a start node.
+///
+/// The iterator yields node keys in preorder, when they are discovered, starting with the
+/// start itself. Use [`DfsEvents`] to also observe edges and finished nodes.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::traversal::Dfs;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (3, 0)]);
+///
+/// let order: Vec<u32> = Dfs::new(&graph, 0).collect();
+/// assert_eq!(order, vec![0, 1, 2]);
+/// ```
+pub struct Dfs<'a, N, E, G> {
+    events: DfsEvents<'a, N, E, G>,
+}
+
+impl<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Dfs<'a, N, E, G> {
+    /// Creates a depth-first traversal of `graph` from `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is not a node of the graph.
+    pub fn new(graph: &'a G, start: u32) -> Self {
+        Self {
+            events: DfsEvents::new(graph, start),
+        }
+    }
+}
+
+impl<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>> Iterator for Dfs<'_, N, E, G> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.events.find_map(|event| match event {
+            DfsEvent::Discover(key) => Some(key),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+    use ade_common::assert_panics_with;
+
+    #[test]
+    fn test_bfs_levels() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 0)],
+        );
+        let order: Vec<u32> = Bfs::new(&graph, 0).collect();
+
+        assert_eq!(order.len(), 5);
+        assert_eq!(order[0], 0);
+        assert_eq!(order[3..], [3, 4]);
+        assert_eq!(Bfs::new(&graph, 5).collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn test_dfs_events_classify_edges() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (1, 2), (2, 2)]);
+        let events: Vec<DfsEvent> = DfsEvents::new(&graph, 0).collect();
+
+        let count = |f: fn(&DfsEvent) -> bool| events.iter().filter(|e| f(e)).count();
+        assert_eq!(count(|e| matches!(e, DfsEvent::Discover(_))), 3);
+        assert_eq!(count(|e| matches!(e, DfsEvent::Finish(_))), 3);
+        assert_eq!(count(|e| matches!(e, DfsEvent::TreeEdge(..))), 2);
+        assert!(events.contains(&DfsEvent::BackEdge(2, 2)));
+        assert_eq!(count(|e| matches!(e, DfsEvent::CrossEdge(..))), 1);
+        assert_eq!(events.last(), Some(&DfsEvent::Finish(0)));
+    }
+
+    #[test]
+    fn test_dfs_deep_path() {
+        let n: u32 = 100_000;
+        let graph =
+            build_graph::<Node, Edge>((0..n).collect(), (1..n).map(|i| (i - 1, i)).collect());
+
+        assert!(Dfs::new(&graph, 0).eq(0..n));
+        let finished: Vec<u32> = DfsEvents::new(&graph, 0)
+            .filter_map(|event| match event {
+                DfsEvent::Finish(key) => Some(key),
+                _ => None,
+            })
+            .collect();
+        assert!(finished.into_iter().eq((0..n).rev()));
+    }
+
+    #[test]
+    fn test_missing_start() {
+        let graph = build_graph::<Node, Edge>(vec![0], vec![]);
+        assert_panics_with!(Bfs::new(&graph, 1).count(), "Node 1 not found");
+        assert_panics_with!(Dfs::new(&graph, 1).count(), "Node 1 not found");
+    }
+}
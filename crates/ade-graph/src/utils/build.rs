@@ -1,6 +1,8 @@
-use ade_traits::NodeTrait;
-use ade_traits::EdgeTrait;
 use crate::implementations::Graph;
+use ade_traits::EdgeTrait;
+use ade_traits::GraphMutTrait;
+use ade_traits::NodeTrait;
+use ade_traits::WeightedEdgeTrait;
 
 /// Build a graph from node keys and edge pairs
 pub fn build_graph<N, E>(node_keys: Vec<u32>, edge_pairs: Vec<(u32, u32)>) -> Graph<N, E>
@@ -17,12 +19,63 @@ where
     Graph::new(nodes, edges)
 }
 
+/// Build a graph from node keys and weighted edge triples `(source, target, weight)`
+pub fn build_graph_weighted<N, E>(
+    node_keys: Vec<u32>,
+    edge_triples: Vec<(u32, u32, E::Weight)>,
+) -> Graph<N, E>
+where
+    N: NodeTrait,
+    E: WeightedEdgeTrait,
+{
+    let nodes: Vec<N> = node_keys.into_iter().map(|key| N::new(key)).collect();
+    let edges: Vec<E> = edge_triples
+        .into_iter()
+        .map(|(source, target, weight)| E::with_weight(source, target, weight))
+        .collect();
+
+    Graph::new(nodes, edges)
+}
+
+/// Add the nodes of `node_keys` and the edges of `edge_pairs` to a graph of any
+/// implementation of [`GraphMutTrait`]
+///
+/// # Panics
+///
+/// Panics if an edge references a node that is neither in `node_keys` nor already in the
+/// graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::build::build_graph_into;
+/// use ade_graph::implementations::{DenseGraph, Edge, Node};
+/// use ade_graph::GraphViewTrait;
+///
+/// let mut graph = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+/// build_graph_into(&mut graph, vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// assert_eq!(graph.edge_count(), 2);
+/// ```
+pub fn build_graph_into<N, E, G>(graph: &mut G, node_keys: Vec<u32>, edge_pairs: Vec<(u32, u32)>)
+where
+    N: NodeTrait,
+    E: EdgeTrait,
+    G: GraphMutTrait<N, E>,
+{
+    for key in node_keys {
+        graph.add_node(N::new(key));
+    }
+    for (source, target) in edge_pairs {
+        graph.add_edge(<E as EdgeTrait>::new(source, target));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ade_traits::{GraphViewTrait, NodeTrait};
     use crate::implementations::Edge;
     use crate::implementations::Node;
+    use ade_traits::{GraphViewTrait, NodeTrait};
 
     #[test]
     fn test_build_empty_graph() {
@@ -162,4 +215,36 @@ mod tests {
             assert_eq!(node.successors().len(), 2);
         }
     }
+
+    #[test]
+    fn test_build_graph_into_other_implementations() {
+        use crate::implementations::DenseGraph;
+
+        let mut dense = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+        build_graph_into(&mut dense, vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+
+        let mut edges: Vec<(u32, u32)> = dense.get_edges().map(|edge| edge.key()).collect();
+        edges.sort_unstable();
+        let mut expected: Vec<(u32, u32)> = graph.get_edges().map(|edge| edge.key()).collect();
+        expected.sort_unstable();
+        assert_eq!(edges, expected);
+        assert_eq!(dense.node_count(), 3);
+    }
+
+    #[test]
+    fn test_build_graph_weighted() {
+        use crate::implementations::WeightedEdge;
+        use ade_traits::Path;
+
+        let graph = build_graph_weighted::<Node, WeightedEdge<f64>>(
+            vec![0, 1, 2],
+            vec![(0, 1, 1.5), (1, 2, 2.0), (0, 2, 4.0)],
+        );
+
+        assert_eq!(graph.get_edges().count(), 3);
+        assert_eq!(graph.get_edge(1, 2).weight(), 2.0);
+        assert_eq!(Path::new(vec![0, 1, 2]).total_weight(&graph), 3.5);
+        assert_eq!(Path::new(vec![0, 2]).total_weight(&graph), 4.0);
+    }
 }
@@ -0,0 +1,195 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Returns `true` if two graphs have the same node keys and the same edge keys.
+///
+/// Only the structure is compared: node and edge payloads, such as weights, are ignored,
+/// and the graphs may be of different implementations or have different node and edge
+/// types. Runs in `O(n + m)` time, looking up each node and edge of `g1` in `g2`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{CsrGraph, Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::compare::structural_eq;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let csr = CsrGraph::from_view(&graph);
+/// assert!(structural_eq(&graph, &csr));
+///
+/// let reversed = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(1, 0), (2, 1)]);
+/// assert!(!structural_eq(&graph, &reversed));
+/// ```
+pub fn structural_eq<N1, E1, N2, E2>(
+    g1: &impl GraphViewTrait<N1, E1>,
+    g2: &impl GraphViewTrait<N2, E2>,
+) -> bool
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    g1.node_count() == g2.node_count()
+        && g1.edge_count() == g2.edge_count()
+        && g1.get_node_keys().all(|key| g2.has_node(key))
+        && g1
+            .get_edges()
+            .all(|edge| g2.has_edge(edge.source(), edge.target()))
+}
+
+/// The nodes and edges found in only one of two graphs, computed by
+/// [`symmetric_difference`].
+///
+/// All lists are sorted in ascending order. An edge between two nodes missing from the
+/// other graph is reported as well as its endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GraphDiff {
+    /// Keys of the nodes of the first graph that are not in the second.
+    pub nodes_only_in_first: Vec<u32>,
+    /// Keys of the nodes of the second graph that are not in the first.
+    pub nodes_only_in_second: Vec<u32>,
+    /// `(source, target)` keys of the edges of the first graph that are not in the second.
+    pub edges_only_in_first: Vec<(u32, u32)>,
+    /// `(source, target)` keys of the edges of the second graph that are not in the first.
+    pub edges_only_in_second: Vec<(u32, u32)>,
+}
+
+impl GraphDiff {
+    /// Returns `true` if both graphs have the same structure, as in [`structural_eq`].
+    pub fn is_empty(&self) -> bool {
+        self.nodes_only_in_first.is_empty()
+            && self.nodes_only_in_second.is_empty()
+            && self.edges_only_in_first.is_empty()
+            && self.edges_only_in_second.is_empty()
+    }
+}
+
+/// Reports the nodes and edges present in only one of two graphs.
+///
+/// As in [`structural_eq`], only node and edge keys are compared and the graphs may be of
+/// different types. This is meant for tests and debugging, to show why two graphs differ.
+///
+/// # Returns
+///
+/// A [`GraphDiff`] with sorted lists, empty if the graphs have the same structure.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::compare::symmetric_difference;
+///
+/// let g1 = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let g2 = build_graph::<Node, Edge>(vec![0, 1, 3], vec![(0, 1), (1, 0), (3, 3)]);
+///
+/// let diff = symmetric_difference(&g1, &g2);
+/// assert_eq!(diff.nodes_only_in_first, vec![2]);
+/// assert_eq!(diff.nodes_only_in_second, vec![3]);
+/// assert_eq!(diff.edges_only_in_first, vec![(1, 2)]);
+/// assert_eq!(diff.edges_only_in_second, vec![(1, 0), (3, 3)]);
+/// ```
+pub fn symmetric_difference<N1, E1, N2, E2>(
+    g1: &impl GraphViewTrait<N1, E1>,
+    g2: &impl GraphViewTrait<N2, E2>,
+) -> GraphDiff
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    GraphDiff {
+        nodes_only_in_first: missing_nodes(g1, g2),
+        nodes_only_in_second: missing_nodes(g2, g1),
+        edges_only_in_first: missing_edges(g1, g2),
+        edges_only_in_second: missing_edges(g2, g1),
+    }
+}
+
+// Sorted keys of the nodes of `from` that are not in `other`
+fn missing_nodes<N1, E1, N2, E2>(
+    from: &impl GraphViewTrait<N1, E1>,
+    other: &impl GraphViewTrait<N2, E2>,
+) -> Vec<u32>
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    let mut keys: Vec<u32> = from
+        .get_node_keys()
+        .filter(|&key| !other.has_node(key))
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+// Sorted keys of the edges of `from` that are not in `other`
+fn missing_edges<N1, E1, N2, E2>(
+    from: &impl GraphViewTrait<N1, E1>,
+    other: &impl GraphViewTrait<N2, E2>,
+) -> Vec<(u32, u32)>
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    let mut keys: Vec<(u32, u32)> = from
+        .get_edges()
+        .map(|edge| edge.key())
+        .filter(|&(source, target)| !other.has_edge(source, target))
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DenseGraph, Edge, Node, WeightedEdge};
+    use crate::utils::build::{build_graph, build_graph_weighted};
+
+    #[test]
+    fn test_payloads_and_implementations_are_ignored() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (2, 2)]);
+        let weighted = build_graph_weighted::<Node, WeightedEdge<u32>>(
+            vec![2, 1, 0],
+            vec![(2, 2, 5), (0, 1, 7)],
+        );
+        let dense = DenseGraph::from(graph.clone());
+
+        assert!(structural_eq(&graph, &weighted));
+        assert!(structural_eq(&weighted, &dense));
+        assert!(symmetric_difference(&graph, &dense).is_empty());
+    }
+
+    #[test]
+    fn test_differences() {
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+        let graph = build_graph::<Node, Edge>(vec![4, 5], vec![(4, 5)]);
+        // Same node and edge counts, different edges
+        let other = build_graph::<Node, Edge>(vec![4, 5], vec![(5, 4)]);
+
+        assert!(structural_eq(&empty, &empty));
+        assert!(!structural_eq(&graph, &other));
+        assert!(!structural_eq(&graph, &empty));
+        assert_eq!(
+            symmetric_difference(&empty, &graph),
+            GraphDiff {
+                nodes_only_in_second: vec![4, 5],
+                edges_only_in_second: vec![(4, 5)],
+                ..GraphDiff::default()
+            }
+        );
+        let diff = symmetric_difference(&graph, &other);
+        assert!(diff.nodes_only_in_first.is_empty() && diff.nodes_only_in_second.is_empty());
+        assert_eq!(
+            (diff.edges_only_in_first, diff.edges_only_in_second),
+            (vec![(4, 5)], vec![(5, 4)])
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use ade_traits::NodeTrait;
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+
+// Control bytes scanned in parallel by the hash table, allocated past the last bucket
+const GROUP_WIDTH: usize = 16;
+
+/// Estimates the bytes allocated by a `HashMap` with the given capacity.
+///
+/// The standard hash table keeps at least one bucket in eight empty and rounds the
+/// number of buckets up to a power of two; each bucket stores one entry and one control
+/// byte. Returns 0 for tables that have not allocated yet.
+pub fn hash_map_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    hash_table_bytes(map.capacity(), size_of::<(K, V)>())
+}
+
+/// Estimates the bytes allocated by a `HashSet`, see [`hash_map_bytes`].
+pub fn hash_set_bytes<T>(set: &HashSet<T>) -> usize {
+    hash_table_bytes(set.capacity(), size_of::<T>())
+}
+
+/// Estimates the bytes allocated by the adjacency sets of a node.
+///
+/// Only the predecessor and successor sets are counted; node types holding other heap
+/// data should add it themselves.
+pub fn node_adjacency_bytes<N: NodeTrait>(node: &N) -> usize {
+    hash_set_bytes(node.predecessors()) + hash_set_bytes(node.successors())
+}
+
+fn hash_table_bytes(capacity: usize, entry_size: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = if capacity < 8 {
+        if capacity < 4 {
+            4
+        } else {
+            8
+        }
+    } else {
+        (capacity * 8 / 7).next_power_of_two()
+    };
+    buckets * (entry_size + 1) + GROUP_WIDTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_set_bytes() {
+        let empty: HashSet<u32> = HashSet::new();
+        assert_eq!(hash_set_bytes(&empty), 0);
+
+        let set: HashSet<u32> = (0..3).collect();
+        assert_eq!(hash_set_bytes(&set), 4 * 5 + GROUP_WIDTH);
+
+        let set: HashSet<u32> = (0..100).collect();
+        assert!(hash_set_bytes(&set) >= 100 * 5);
+    }
+
+    #[test]
+    fn test_hash_map_bytes() {
+        let map: HashMap<(u32, u32), u64> = (0..10).map(|i| ((i, i), 0)).collect();
+        assert!(hash_map_bytes(&map) >= 10 * 17);
+    }
+}
@@ -1,2 +1,9 @@
 pub mod build;
-//pub mod normalize;
+pub mod compare;
+pub mod heap_size;
+pub mod normalize;
+pub mod renumber;
+pub mod set_ops;
+#[cfg(feature = "test-utils")]
+pub mod strategies;
+pub mod subgraph;
@@ -1,6 +1,7 @@
 use crate::implementations::Graph;
 use crate::{EdgeTrait, GraphViewTrait, NodeTrait};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
 
 struct KeyIndexMap {
     key_to_index: HashMap<u32, u32>,
@@ -23,14 +24,14 @@ fn create_key_index_map(keys: impl Iterator<Item = u32>) -> KeyIndexMap {
 }
 
 fn adjacency_structure<N: NodeTrait, E: EdgeTrait>(
-    graph: &Graph<N, E>,
+    graph: &impl GraphViewTrait<N, E>,
     key_to_index: &HashMap<u32, u32>,
 ) -> Graph<N, E> {
     let nodes = graph
         .get_nodes()
         .map(|node| {
             let index = key_to_index[&node.key()];
-            N::new(index)
+            node.with_key(index)
         })
         .collect();
 
@@ -39,20 +40,23 @@ fn adjacency_structure<N: NodeTrait, E: EdgeTrait>(
         .map(|edge| {
             let source = key_to_index[&edge.source()];
             let target = key_to_index[&edge.target()];
-            E::new(source, target)
+            edge.with_endpoints(source, target)
         })
         .collect();
 
     Graph::new(nodes, edges)
 }
 
-pub enum GraphRefOrOwned<'a, N: NodeTrait, E: EdgeTrait> {
-    Borrowed(&'a Graph<N, E>),
+/// A graph returned by [`normalize_graph_keys`]: the original one if its keys were already
+/// sequential, or a copy with renumbered keys.
+pub enum GraphRefOrOwned<'a, N: NodeTrait, E: EdgeTrait, G = Graph<N, E>> {
+    Borrowed(&'a G),
     Owned(Graph<N, E>),
 }
 
 impl<'a, N: NodeTrait, E: EdgeTrait> GraphRefOrOwned<'a, N, E> {
-    pub fn as_ref(&self) -> &Graph<N, E> {
+    /// Returns the graph, borrowed or owned.
+    pub fn graph(&self) -> &Graph<N, E> {
         match self {
             GraphRefOrOwned::Borrowed(g) => g,
             GraphRefOrOwned::Owned(g) => g,
@@ -60,10 +64,43 @@ impl<'a, N: NodeTrait, E: EdgeTrait> GraphRefOrOwned<'a, N, E> {
     }
 }
 
-pub fn normalize_graph_keys<'a, N: NodeTrait, E: EdgeTrait>(
-    graph: &'a Graph<N, E>,
-) -> (GraphRefOrOwned<'a, N, E>, Option<Vec<u32>>) {
-    let n = match graph.get_nodes().count() {
+/// Renumbers the keys of a graph to `0..n`, as required by the algorithms that index
+/// vectors by key.
+///
+/// If the keys are already sequential, the graph is borrowed as is. Otherwise a copy is
+/// built where the `i`-th key returned by
+/// [`get_node_keys`](GraphViewTrait::get_node_keys) becomes `i`, with nodes and edges
+/// rebuilt by [`NodeTrait::with_key`] and [`EdgeTrait::with_endpoints`], so their data is
+/// kept.
+///
+/// # Returns
+///
+/// The graph with sequential keys and, if it was renumbered, the mapping from new keys to
+/// original keys: `mapping[new_key] == old_key`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::normalize::{normalize_graph_keys, GraphRefOrOwned};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![10, 20], vec![(10, 20)]);
+/// let (normalized, mapping) = normalize_graph_keys(&graph);
+///
+/// let mapping = mapping.unwrap();
+/// let GraphRefOrOwned::Owned(normalized) = normalized else { unreachable!() };
+/// assert!(normalized.has_sequential_keys());
+/// assert!(normalized.has_edge(
+///     mapping.iter().position(|&k| k == 10).unwrap() as u32,
+///     mapping.iter().position(|&k| k == 20).unwrap() as u32,
+/// ));
+/// ```
+pub fn normalize_graph_keys<'a, N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E>>(
+    graph: &'a G,
+) -> (GraphRefOrOwned<'a, N, E, G>, Option<Vec<u32>>) {
+    let n = match graph.node_count() {
         0 => return (GraphRefOrOwned::Borrowed(graph), None),
         len => (len - 1) as u32,
     };
@@ -84,9 +121,221 @@ pub fn normalize_graph_keys<'a, N: NodeTrait, E: EdgeTrait>(
     }
 }
 
+/// A value in the result of [`with_normalized_keys`] that is kept as it is.
+///
+/// Every `u32` in a result is taken to be a node key and remapped, so counts, weights or
+/// indices stored as `u32` must be wrapped in `Unmapped` to opt out.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::normalize::{with_normalized_keys, Unmapped};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![10, 20], vec![(10, 20)]);
+/// let (source, edge_count) = with_normalized_keys(&graph, |g| {
+///     let source = g.get_node_keys().find(|&key| g.out_degree(key) == 1).unwrap();
+///     (source, Unmapped(g.edge_count() as u32))
+/// });
+/// assert_eq!(source, 10);
+/// assert_eq!(edge_count, Unmapped(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Unmapped<T>(pub T);
+
+/// Values that can hold node keys, which [`with_normalized_keys`] maps back to the keys of
+/// the original graph.
+///
+/// A `u32` is a node key, as in the results of the algorithms. Collections, options, tuples
+/// and results remap their elements, and other numbers, such as `usize` counts, centrality
+/// scores or layer indices, are kept as they are, as is any value wrapped in [`Unmapped`].
+/// Implement this trait for the result and error types of other algorithms to use them with
+/// [`with_normalized_keys`].
+///
+/// Vectors indexed by key, such as the scores returned by centrality algorithms, are not
+/// reordered: convert them with [`by_key`] first.
+pub trait RemapKeys {
+    /// Replaces every node key `k` held by the value with `mapping[k]`.
+    fn remap_keys(self, mapping: &[u32]) -> Self;
+}
+
+impl RemapKeys for u32 {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        mapping[self as usize]
+    }
+}
+
+impl<T> RemapKeys for Unmapped<T> {
+    fn remap_keys(self, _mapping: &[u32]) -> Self {
+        self
+    }
+}
+
+// Values that never hold node keys
+macro_rules! impl_remap_keys_identity {
+    ($($t:ty),*) => {
+        $(
+            impl RemapKeys for $t {
+                fn remap_keys(self, _mapping: &[u32]) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_remap_keys_identity!((), bool, f32, f64);
+impl_remap_keys_identity!(u8, u16, u64, usize, i8, i16, i32, i64, isize);
+
+impl<T: RemapKeys> RemapKeys for Option<T> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.map(|value| value.remap_keys(mapping))
+    }
+}
+
+impl<T: RemapKeys, Er: RemapKeys> RemapKeys for Result<T, Er> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.map(|value| value.remap_keys(mapping))
+            .map_err(|error| error.remap_keys(mapping))
+    }
+}
+
+impl<A: RemapKeys, B: RemapKeys> RemapKeys for (A, B) {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        (self.0.remap_keys(mapping), self.1.remap_keys(mapping))
+    }
+}
+
+impl<A: RemapKeys, B: RemapKeys, C: RemapKeys> RemapKeys for (A, B, C) {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        (
+            self.0.remap_keys(mapping),
+            self.1.remap_keys(mapping),
+            self.2.remap_keys(mapping),
+        )
+    }
+}
+
+impl<T: RemapKeys> RemapKeys for Vec<T> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.into_iter()
+            .map(|value| value.remap_keys(mapping))
+            .collect()
+    }
+}
+
+impl<T: RemapKeys + Eq + Hash> RemapKeys for HashSet<T> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.into_iter()
+            .map(|value| value.remap_keys(mapping))
+            .collect()
+    }
+}
+
+impl<T: RemapKeys + Ord> RemapKeys for BTreeSet<T> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.into_iter()
+            .map(|value| value.remap_keys(mapping))
+            .collect()
+    }
+}
+
+impl<K: RemapKeys + Eq + Hash, V: RemapKeys> RemapKeys for HashMap<K, V> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.into_iter()
+            .map(|entry| entry.remap_keys(mapping))
+            .collect()
+    }
+}
+
+impl<K: RemapKeys + Ord, V: RemapKeys> RemapKeys for BTreeMap<K, V> {
+    fn remap_keys(self, mapping: &[u32]) -> Self {
+        self.into_iter()
+            .map(|entry| entry.remap_keys(mapping))
+            .collect()
+    }
+}
+
+/// Turns a vector indexed by node key into a map from node key to value, so that
+/// [`with_normalized_keys`] can remap its keys.
+///
+/// The values are remapped too, so values stored as `u32` that are not node keys must be
+/// wrapped in [`Unmapped`].
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::normalize::by_key;
+///
+/// let scores = by_key(vec![0.5, 0.25]);
+/// assert_eq!(scores[&1], 0.25);
+/// ```
+pub fn by_key<T>(values: Vec<T>) -> HashMap<u32, T> {
+    (0..).zip(values).collect()
+}
+
+/// Runs an algorithm that requires sequential keys on a graph with any keys, and maps the
+/// keys in its result back to the keys of the graph.
+///
+/// The graph is normalized with [`normalize_graph_keys`], so it is passed as is to
+/// `algorithm` if its keys are already sequential, and otherwise a renumbered copy with the
+/// same node and edge data is passed. The node keys in the result, every `u32` that is not
+/// wrapped in [`Unmapped`], are then remapped through [`RemapKeys`].
+///
+/// # Panics
+///
+/// Panics if the graph was renumbered and the result holds a `u32` that is not a key of
+/// the renumbered graph, such as an unwrapped count.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::normalize::{by_key, with_normalized_keys};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![10, 20, 30], vec![(10, 20), (10, 30)]);
+///
+/// // Successors as keys, and out-degrees indexed by key
+/// let (successors, degrees) = with_normalized_keys(&graph, |g| {
+///     assert!(g.has_sequential_keys());
+///     let keys: Vec<u32> = g.get_node_keys().collect();
+///     let root = keys.iter().copied().find(|&k| g.out_degree(k) == 2).unwrap();
+///     let mut degrees = vec![0; g.node_count()];
+///     for key in keys {
+///         degrees[key as usize] = g.out_degree(key);
+///     }
+///     let successors: Vec<u32> = g.get_successors_keys(root).collect();
+///     (successors, by_key(degrees))
+/// });
+///
+/// let mut successors = successors;
+/// successors.sort_unstable();
+/// assert_eq!(successors, vec![20, 30]);
+/// assert_eq!(degrees[&10], 2);
+/// assert_eq!(degrees[&30], 0);
+/// ```
+pub fn with_normalized_keys<N: NodeTrait, E: EdgeTrait, R: RemapKeys>(
+    graph: &Graph<N, E>,
+    algorithm: impl FnOnce(&Graph<N, E>) -> R,
+) -> R {
+    let (normalized, mapping) = normalize_graph_keys(graph);
+    let result = algorithm(normalized.graph());
+    match mapping {
+        Some(mapping) => result.remap_keys(&mapping),
+        None => result,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::implementations::{DataNode, Edge, Node, WeightedEdge};
+    use crate::utils::build::build_graph;
+    use ade_traits::{DataNodeTrait, WeightedEdgeTrait};
 
     #[test]
     fn test_create_key_index_map() {
@@ -103,4 +352,79 @@ mod tests {
         assert_eq!(map.index_to_key[2], 3);
         assert_eq!(map.index_to_key[3], 4);
     }
+
+    #[test]
+    fn test_normalize_keeps_payloads() {
+        let graph = Graph::new(
+            vec![DataNode::with_data(10, "a"), DataNode::with_data(20, "b")],
+            vec![WeightedEdge::with_weight(10, 20, 7)],
+        );
+        let (normalized, mapping) = normalize_graph_keys(&graph);
+        let mapping = mapping.unwrap();
+        let normalized = normalized.graph();
+
+        let index = |key: u32| mapping.iter().position(|&k| k == key).unwrap() as u32;
+        assert_eq!(*normalized.get_node(index(20)).data(), "b");
+        assert_eq!(normalized.get_edge(index(10), index(20)).weight(), 7);
+    }
+
+    #[test]
+    fn test_remap_keys() {
+        let mapping = [7, 3, 9];
+        assert_eq!(
+            vec![vec![0u32, 2], vec![1]].remap_keys(&mapping),
+            vec![vec![7, 9], vec![3]]
+        );
+        assert_eq!((Some(1u32), 2.5).remap_keys(&mapping), (Some(3), 2.5));
+        assert_eq!(
+            Err::<Unmapped<u32>, (u32, u32)>((2, 0)).remap_keys(&mapping),
+            Err((9, 7))
+        );
+        assert_eq!(
+            BTreeMap::from([(0u32, 1usize), (2, 5)]).remap_keys(&mapping),
+            BTreeMap::from([(7, 1), (9, 5)])
+        );
+        assert_eq!(
+            HashSet::from([0u32, 1]).remap_keys(&mapping),
+            HashSet::from([7, 3])
+        );
+    }
+
+    #[test]
+    fn test_with_normalized_keys() {
+        let graph = build_graph::<Node, Edge>(vec![5, 40, 12], vec![(5, 40), (40, 12), (12, 5)]);
+        let successors: HashMap<u32, Vec<u32>> = with_normalized_keys(&graph, |g| {
+            assert!(g.has_sequential_keys());
+            g.get_node_keys()
+                .map(|key| (key, g.get_successors_keys(key).collect()))
+                .collect()
+        });
+        assert_eq!(
+            successors,
+            HashMap::from([(5, vec![40]), (40, vec![12]), (12, vec![5])])
+        );
+
+        // Sequential keys are neither copied nor remapped
+        let sequential = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        let root = with_normalized_keys(&sequential, |g| {
+            assert!(std::ptr::eq(g, &sequential));
+            g.get_node_keys().find(|&key| g.in_degree(key) == 0)
+        });
+        assert_eq!(root, Some(0));
+    }
+
+    #[test]
+    fn test_unmapped_values_are_kept() {
+        // Keys 100 and 200 become 0 and 1; the edge count and the index are left alone
+        let graph = build_graph::<Node, Edge>(vec![100, 200], vec![(100, 200)]);
+        let (source, edge_count, index) = with_normalized_keys(&graph, |g| {
+            let source = g
+                .get_node_keys()
+                .find(|&key| g.out_degree(key) == 1)
+                .unwrap();
+            (source, Unmapped(g.edge_count() as u32), 5usize)
+        });
+        assert_eq!(source, 100);
+        assert_eq!((edge_count, index), (Unmapped(1), 5));
+    }
 }
@@ -0,0 +1,248 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Relabels nodes in breadth-first order, to improve memory locality.
+///
+/// Nodes that are close in the graph get close keys, so that algorithms traversing the
+/// renumbered graph (especially from a CSR or dense layout) touch nearby memory. Edges are
+/// followed in both directions; each weakly connected component is numbered contiguously,
+/// starting from its smallest key, and neighbors are visited in ascending key order so
+/// the result is deterministic.
+///
+/// # Returns
+///
+/// The renumbered graph, with sequential keys `0..n`, and the mapping from new keys to
+/// original keys: `mapping[new_key] == old_key`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::renumber::renumber_bfs;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// // A path 10 - 30 - 20 stored with scattered keys
+/// let graph = build_graph::<Node, Edge>(vec![10, 20, 30], vec![(10, 30), (30, 20)]);
+/// let (renumbered, mapping) = renumber_bfs(&graph);
+///
+/// assert_eq!(mapping, vec![10, 30, 20]);
+/// assert!(renumbered.has_edge(0, 1));
+/// assert!(renumbered.has_edge(1, 2));
+/// ```
+pub fn renumber_bfs<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> (Graph<N, E>, Vec<u32>) {
+    let order = breadth_first_order(graph, |keys| keys.sort_unstable(), |starts| starts);
+    relabel(graph, order)
+}
+
+/// Relabels nodes in reverse Cuthill–McKee order, to improve memory locality.
+///
+/// Like [`renumber_bfs`], but each component is started from a node of minimum degree and
+/// neighbors are visited in ascending degree order, and the final order is reversed. This
+/// classic heuristic keeps edges close to the diagonal of the adjacency matrix (small
+/// bandwidth). Degrees count neighbors in both directions.
+///
+/// # Returns
+///
+/// The renumbered graph, with sequential keys `0..n`, and the mapping from new keys to
+/// original keys: `mapping[new_key] == old_key`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::renumber::renumber_rcm;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// // A star centered in 0
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (0, 3)]);
+/// let (renumbered, mapping) = renumber_rcm(&graph);
+///
+/// assert_eq!(mapping, vec![3, 2, 0, 1]);
+/// assert_eq!(renumbered.get_predecessors_keys(2).count(), 0);
+/// ```
+pub fn renumber_rcm<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> (Graph<N, E>, Vec<u32>) {
+    let degrees: HashMap<u32, usize> = graph
+        .get_node_keys()
+        .map(|key| (key, neighbors(graph, key).len()))
+        .collect();
+    let degree = |key: u32| degrees[&key];
+    let mut order = breadth_first_order(
+        graph,
+        |keys| keys.sort_unstable_by_key(|&k| (degree(k), k)),
+        |mut starts| {
+            starts.sort_by_key(|&k| (degree(k), k));
+            starts
+        },
+    );
+    order.reverse();
+    relabel(graph, order)
+}
+
+// Visits every node breadth first, following edges in both directions. `sort_neighbors`
+// orders the unvisited neighbors of a node, and `start_order` the candidate start nodes.
+fn breadth_first_order<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    sort_neighbors: impl Fn(&mut Vec<u32>),
+    start_order: impl Fn(Vec<u32>) -> Vec<u32>,
+) -> Vec<u32> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let starts = start_order(keys);
+
+    let mut order = Vec::with_capacity(starts.len());
+    let mut visited: HashSet<u32> = HashSet::with_capacity(starts.len());
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !visited.insert(start) {
+            continue;
+        }
+        queue.push_back(start);
+
+        while let Some(key) = queue.pop_front() {
+            order.push(key);
+
+            let mut next: Vec<u32> = neighbors(graph, key)
+                .into_iter()
+                .filter(|k| !visited.contains(k))
+                .collect();
+            sort_neighbors(&mut next);
+            for k in next {
+                visited.insert(k);
+                queue.push_back(k);
+            }
+        }
+    }
+
+    order
+}
+
+// Successors and predecessors of a node, without duplicates and without the node itself
+fn neighbors<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>, key: u32) -> Vec<u32> {
+    let mut keys: Vec<u32> = graph
+        .get_successors_keys(key)
+        .chain(graph.get_predecessors_keys(key))
+        .filter(|&k| k != key)
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+// Builds the graph whose key i is the node order[i] of the original graph
+fn relabel<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    order: Vec<u32>,
+) -> (Graph<N, E>, Vec<u32>) {
+    let key_to_index: HashMap<u32, u32> = order
+        .iter()
+        .enumerate()
+        .map(|(index, &key)| (key, index as u32))
+        .collect();
+
+    // Nodes and edges are rebuilt from the originals, so that their data is kept
+    let nodes = order
+        .iter()
+        .enumerate()
+        .map(|(index, &key)| graph.get_node(key).with_key(index as u32))
+        .collect();
+    let edges = graph
+        .get_edges()
+        .map(|edge| edge.with_endpoints(key_to_index[&edge.source()], key_to_index[&edge.target()]))
+        .collect();
+
+    (Graph::new(nodes, edges), order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use crate::utils::build::build_graph;
+
+    // Checks that the renumbered graph is the original one with keys replaced by the mapping
+    fn assert_isomorphic(
+        original: &Graph<Node, Edge>,
+        renumbered: &Graph<Node, Edge>,
+        mapping: &[u32],
+    ) {
+        assert!(renumbered.has_sequential_keys());
+        assert_eq!(renumbered.node_count(), original.node_count());
+        assert_eq!(renumbered.get_edges().count(), original.get_edges().count());
+        for edge in renumbered.get_edges() {
+            assert!(original.has_edge(
+                mapping[edge.source() as usize],
+                mapping[edge.target() as usize]
+            ));
+        }
+    }
+
+    #[test]
+    fn test_renumber_bfs() {
+        let graph =
+            build_graph::<Node, Edge>(vec![5, 1, 9, 7, 3], vec![(9, 1), (1, 5), (5, 5), (3, 7)]);
+        let (renumbered, mapping) = renumber_bfs(&graph);
+
+        // Component {1, 5, 9} from 1, then component {3, 7} is not reachable from it
+        assert_eq!(mapping, vec![1, 5, 9, 3, 7]);
+        assert_isomorphic(&graph, &renumbered, &mapping);
+        assert!(renumbered.has_edge(1, 1));
+    }
+
+    #[test]
+    fn test_renumber_rcm() {
+        // A path 0 - 1 - 2 - 3 - 4 with a chord 1 - 3
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 1), (1, 2), (2, 3), (3, 4), (1, 3)],
+        );
+        let (renumbered, mapping) = renumber_rcm(&graph);
+
+        assert_eq!(mapping, vec![4, 3, 2, 1, 0]);
+        assert_isomorphic(&graph, &renumbered, &mapping);
+
+        // Bandwidth: the largest key difference along an edge
+        let bandwidth = renumbered
+            .get_edges()
+            .map(|e| e.source().abs_diff(e.target()))
+            .max();
+        assert_eq!(bandwidth, Some(2));
+    }
+
+    #[test]
+    fn test_renumber_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        let (renumbered, mapping) = renumber_rcm(&graph);
+        assert!(renumbered.is_empty());
+        assert!(mapping.is_empty());
+    }
+
+    #[test]
+    fn test_renumber_keeps_data() {
+        use crate::implementations::{DataEdge, DataNode};
+        use ade_traits::{DataEdgeTrait, DataNodeTrait};
+
+        let graph = Graph::<DataNode<&str>, DataEdge<u32>>::new(
+            vec![DataNode::with_data(10, "a"), DataNode::with_data(20, "b")],
+            vec![DataEdge::with_data(20, 10, 7)],
+        );
+        let (renumbered, mapping) = renumber_bfs(&graph);
+
+        for (index, &key) in mapping.iter().enumerate() {
+            assert_eq!(
+                renumbered.get_node(index as u32).data(),
+                graph.get_node(key).data()
+            );
+        }
+        let edge = renumbered.get_edges().next().unwrap();
+        assert_eq!(mapping[edge.source() as usize], 20);
+        assert_eq!(*edge.data(), 7);
+    }
+}
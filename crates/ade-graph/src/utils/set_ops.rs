@@ -0,0 +1,144 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// How [`Graph::merge`] resolves a node or an edge present in both graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Keep the node or edge already in the graph.
+    #[default]
+    KeepExisting,
+    /// Replace it with the one from the merged graph.
+    Replace,
+}
+
+/// Returns a graph with the nodes and edges of both graphs.
+///
+/// Nodes and edges present in both graphs are taken from `g1`; use [`Graph::merge`] to
+/// choose otherwise. Nodes are rebuilt with [`NodeTrait::with_key`] and edges are cloned, so
+/// their data is kept. The graphs may be views, such as the results of
+/// [`filter`](GraphViewTrait::filter).
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::set_ops::graph_union;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3)]);
+/// let union = graph_union(&graph.filter(&[0, 1]), &graph.filter(&[2, 3]));
+///
+/// assert_eq!(union.node_count(), 4);
+/// assert!(union.has_edge(0, 1) && union.has_edge(2, 3));
+/// assert!(!union.has_edge(1, 2));
+/// ```
+pub fn graph_union<N: NodeTrait, E: EdgeTrait>(
+    g1: &impl GraphViewTrait<N, E>,
+    g2: &impl GraphViewTrait<N, E>,
+) -> Graph<N, E> {
+    let nodes = g1
+        .get_nodes()
+        .chain(g2.get_nodes().filter(|node| !g1.has_node(node.key())))
+        .map(|node| node.with_key(node.key()))
+        .collect();
+    let edges = g1
+        .get_edges()
+        .chain(
+            g2.get_edges()
+                .filter(|edge| !g1.has_edge(edge.source(), edge.target())),
+        )
+        .cloned()
+        .collect();
+    Graph::new(nodes, edges)
+}
+
+/// Returns a graph with the nodes and edges present in both graphs.
+///
+/// Nodes and edges are taken from `g1`, rebuilt with [`NodeTrait::with_key`] and cloned.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::set_ops::graph_intersection;
+/// use ade_graph::GraphViewTrait;
+///
+/// let g1 = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let g2 = build_graph::<Node, Edge>(vec![1, 2, 3], vec![(1, 2), (2, 1)]);
+/// let common = graph_intersection(&g1, &g2);
+///
+/// assert_eq!(common.node_count(), 2);
+/// assert_eq!(common.edge_count(), 1);
+/// assert!(common.has_edge(1, 2));
+/// ```
+pub fn graph_intersection<N: NodeTrait, E: EdgeTrait>(
+    g1: &impl GraphViewTrait<N, E>,
+    g2: &impl GraphViewTrait<N, E>,
+) -> Graph<N, E> {
+    let nodes = g1
+        .get_nodes()
+        .filter(|node| g2.has_node(node.key()))
+        .map(|node| node.with_key(node.key()))
+        .collect();
+    let edges = g1
+        .get_edges()
+        .filter(|edge| g2.has_edge(edge.source(), edge.target()))
+        .cloned()
+        .collect();
+    Graph::new(nodes, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DataNode, Edge, Node, WeightedEdge};
+    use crate::utils::build::build_graph;
+    use crate::utils::compare::structural_eq;
+    use ade_traits::{DataNodeTrait, WeightedEdgeTrait};
+
+    #[test]
+    fn test_payloads_come_from_the_first_graph() {
+        let g1 = Graph::new(
+            vec![DataNode::with_data(0, 'a'), DataNode::with_data(1, 'b')],
+            vec![WeightedEdge::with_weight(0, 1, 1)],
+        );
+        let g2 = Graph::new(
+            vec![DataNode::with_data(1, 'x'), DataNode::with_data(2, 'y')],
+            vec![
+                WeightedEdge::with_weight(1, 1, 0),
+                WeightedEdge::with_weight(1, 2, 2),
+            ],
+        );
+
+        let union = graph_union(&g1, &g2);
+        assert_eq!(ade_traits::check_graph_consistency(&union), Ok(()));
+        assert_eq!(*union.get_node(1).data(), 'b');
+        assert_eq!(*union.get_node(2).data(), 'y');
+        assert_eq!(union.get_edge(1, 2).weight(), 2);
+
+        let common = graph_intersection(&g1, &g2);
+        assert_eq!(common.node_count(), 1);
+        assert_eq!(*common.get_node(1).data(), 'b');
+    }
+
+    #[test]
+    fn test_set_laws() {
+        let g1 = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 2)]);
+        let g2 = build_graph::<Node, Edge>(vec![1, 2, 3], vec![(2, 2), (2, 3)]);
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+
+        assert!(structural_eq(
+            &graph_union(&g1, &g2),
+            &graph_union(&g2, &g1)
+        ));
+        assert!(structural_eq(
+            &graph_intersection(&g1, &g2),
+            &graph_intersection(&g2, &g1)
+        ));
+        assert!(structural_eq(&graph_union(&g1, &empty), &g1));
+        assert!(graph_intersection(&g1, &empty).is_empty());
+        assert!(structural_eq(&graph_intersection(&g1, &g1), &g1));
+    }
+}
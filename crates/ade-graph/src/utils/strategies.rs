@@ -0,0 +1,183 @@
+use crate::implementations::Graph;
+use crate::utils::build::build_graph;
+use ade_traits::{EdgeTrait, NodeTrait};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// Generates graphs with sequential keys `0..n`, where `n <= max_nodes`.
+///
+/// Edges are drawn uniformly among all ordered pairs of nodes, self-loops included; at most
+/// `max_edges` are generated before duplicates are merged.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::strategies::arb_graph;
+/// use ade_graph::GraphViewTrait;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(graph in arb_graph::<Node, Edge>(20, 40))| {
+///     prop_assert!(graph.has_sequential_keys());
+/// });
+/// ```
+pub fn arb_graph<N: NodeTrait, E: EdgeTrait>(
+    max_nodes: usize,
+    max_edges: usize,
+) -> impl Strategy<Value = Graph<N, E>> {
+    (0..=max_nodes)
+        .prop_flat_map(move |n| {
+            let edges = if n == 0 {
+                Just(Vec::new()).boxed()
+            } else {
+                let key = 0..n as u32;
+                vec((key.clone(), key), 0..=max_edges).boxed()
+            };
+            (Just(n), edges)
+        })
+        .prop_map(|(n, edges)| build_graph((0..n as u32).collect(), edges))
+}
+
+/// Generates directed acyclic graphs with sequential keys and at most `max_nodes` nodes.
+///
+/// Edges follow a random permutation of the keys, so the topological order is not simply
+/// the key order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::strategies::arb_dag;
+/// use ade_graph::{EdgeTrait, GraphViewTrait};
+/// use proptest::prelude::*;
+///
+/// proptest!(|(graph in arb_dag::<Node, Edge>(20, 40))| {
+///     for edge in graph.get_edges() {
+///         prop_assert_ne!(edge.source(), edge.target());
+///     }
+/// });
+/// ```
+pub fn arb_dag<N: NodeTrait, E: EdgeTrait>(
+    max_nodes: usize,
+    max_edges: usize,
+) -> impl Strategy<Value = Graph<N, E>> {
+    (0..=max_nodes)
+        .prop_flat_map(move |n| {
+            let pairs = if n < 2 {
+                Just(Vec::new()).boxed()
+            } else {
+                let position = 0..n;
+                vec((position.clone(), position), 0..=max_edges).boxed()
+            };
+            (Just(n), permutation(n), pairs)
+        })
+        .prop_map(|(n, order, pairs)| {
+            // Edges always go from the earlier to the later position in `order`
+            let edges = pairs
+                .into_iter()
+                .filter(|(i, j)| i != j)
+                .map(|(i, j)| (order[i.min(j)], order[i.max(j)]))
+                .collect();
+            build_graph((0..n as u32).collect(), edges)
+        })
+}
+
+/// Generates strongly connected graphs with sequential keys and `1..=max_nodes` nodes.
+///
+/// Each graph contains a cycle through all its nodes, in random order, plus up to
+/// `max_extra_edges` random edges.
+///
+/// # Panics
+///
+/// Panics if `max_nodes` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::strategies::arb_strongly_connected;
+/// use ade_graph::GraphViewTrait;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(graph in arb_strongly_connected::<Node, Edge>(20, 10))| {
+///     for key in graph.get_node_keys() {
+///         prop_assert!(graph.get_successors_keys(key).count() > 0);
+///     }
+/// });
+/// ```
+pub fn arb_strongly_connected<N: NodeTrait, E: EdgeTrait>(
+    max_nodes: usize,
+    max_extra_edges: usize,
+) -> impl Strategy<Value = Graph<N, E>> {
+    if max_nodes == 0 {
+        panic!("A strongly connected graph needs at least one node");
+    }
+
+    (1..=max_nodes)
+        .prop_flat_map(move |n| {
+            let key = 0..n as u32;
+            (
+                Just(n),
+                permutation(n),
+                vec((key.clone(), key), 0..=max_extra_edges),
+            )
+        })
+        .prop_map(|(n, order, mut edges)| {
+            edges.extend((0..n).map(|i| (order[i], order[(i + 1) % n])));
+            build_graph((0..n as u32).collect(), edges)
+        })
+}
+
+// A random ordering of the keys 0..n
+fn permutation(n: usize) -> impl Strategy<Value = Vec<u32>> {
+    Just((0..n as u32).collect::<Vec<u32>>()).prop_shuffle()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{Edge, Node};
+    use ade_traits::GraphViewTrait;
+    use std::collections::HashSet;
+
+    // Nodes reachable from `start`, start included
+    fn reachable(graph: &Graph<Node, Edge>, start: u32) -> HashSet<u32> {
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(key) = stack.pop() {
+            for succ in graph.get_successors_keys(key) {
+                if seen.insert(succ) {
+                    stack.push(succ);
+                }
+            }
+        }
+        seen
+    }
+
+    proptest! {
+        #[test]
+        fn test_arb_graph_bounds(graph in arb_graph::<Node, Edge>(10, 30)) {
+            prop_assert!(graph.node_count() <= 10);
+            prop_assert!(graph.get_edges().count() <= 30);
+            prop_assert!(graph.has_sequential_keys());
+        }
+
+        #[test]
+        fn test_arb_dag_is_acyclic(graph in arb_dag::<Node, Edge>(12, 40)) {
+            for key in graph.get_node_keys() {
+                let cycle = graph
+                    .get_successors_keys(key)
+                    .any(|succ| reachable(&graph, succ).contains(&key));
+                prop_assert!(!cycle);
+            }
+        }
+
+        #[test]
+        fn test_arb_strongly_connected(graph in arb_strongly_connected::<Node, Edge>(12, 10)) {
+            prop_assert!(graph.node_count() >= 1);
+            for key in graph.get_node_keys() {
+                prop_assert_eq!(reachable(&graph, key).len(), graph.node_count());
+            }
+        }
+    }
+}
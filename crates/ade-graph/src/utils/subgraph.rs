@@ -0,0 +1,188 @@
+use crate::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Copies the subgraph induced by some nodes into an owned [`Graph`].
+///
+/// Unlike [`filter`](GraphViewTrait::filter), which returns a view borrowing the graph, the
+/// result can outlive the original graph and be modified. It holds the selected nodes and
+/// every edge between two of them, with their original keys. Nodes and edges are rebuilt
+/// with [`NodeTrait::with_key`] and cloned, so their data is kept. Keys need not be
+/// sequential, and repeated keys are ignored.
+///
+/// # Panics
+///
+/// Panics if a key is not in the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::subgraph::extract_subgraph;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![1, 2, 3], vec![(1, 2), (2, 3), (3, 1)]);
+/// let mut subgraph = extract_subgraph(&graph, &[1, 2]);
+/// drop(graph);
+///
+/// assert!(subgraph.has_edge(1, 2));
+/// assert_eq!(subgraph.edge_count(), 1);
+/// subgraph.remove_node(2);
+/// assert_eq!(subgraph.node_count(), 1);
+/// ```
+pub fn extract_subgraph<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    keys: &[u32],
+) -> Graph<N, E> {
+    let selected: HashSet<u32> = keys.iter().copied().collect();
+    let nodes = selected
+        .iter()
+        .map(|&key| graph.get_node(key).with_key(key))
+        .collect();
+    let edges = inner_edges(graph, &selected).cloned().collect();
+    Graph::new(nodes, edges)
+}
+
+/// Copies the subgraph induced by some nodes into an owned [`Graph`] with sequential keys.
+///
+/// Like [`extract_subgraph`], but the `i`-th distinct key of `keys` becomes `i`, so the
+/// result can be passed to the algorithms that require sequential keys. Nodes and edges
+/// are rebuilt with [`NodeTrait::with_key`] and [`EdgeTrait::with_endpoints`], so their
+/// data is kept.
+///
+/// # Returns
+///
+/// The subgraph, with keys `0..k`, and the mapping from new keys to original keys:
+/// `mapping[new_key] == old_key`.
+///
+/// # Panics
+///
+/// Panics if a key is not in the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::utils::subgraph::extract_subgraph_renumbered;
+/// use ade_graph::GraphViewTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![10, 20, 30], vec![(10, 20), (20, 30), (30, 10)]);
+/// let (subgraph, mapping) = extract_subgraph_renumbered(&graph, &[30, 10]);
+///
+/// assert_eq!(mapping, vec![30, 10]);
+/// assert!(subgraph.has_sequential_keys());
+/// assert!(subgraph.has_edge(0, 1));
+/// assert_eq!(subgraph.edge_count(), 1);
+/// ```
+pub fn extract_subgraph_renumbered<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    keys: &[u32],
+) -> (Graph<N, E>, Vec<u32>) {
+    let mut key_to_index: HashMap<u32, u32> = HashMap::with_capacity(keys.len());
+    let mut mapping = Vec::with_capacity(keys.len());
+    for &key in keys {
+        if let Entry::Vacant(entry) = key_to_index.entry(key) {
+            entry.insert(mapping.len() as u32);
+            mapping.push(key);
+        }
+    }
+
+    let nodes = mapping
+        .iter()
+        .enumerate()
+        .map(|(index, &key)| graph.get_node(key).with_key(index as u32))
+        .collect();
+    let selected: HashSet<u32> = mapping.iter().copied().collect();
+    let edges = inner_edges(graph, &selected)
+        .map(|edge| edge.with_endpoints(key_to_index[&edge.source()], key_to_index[&edge.target()]))
+        .collect();
+    (Graph::new(nodes, edges), mapping)
+}
+
+// Edges whose source and target are both selected
+fn inner_edges<'a, N: NodeTrait + 'a, E: EdgeTrait + 'a>(
+    graph: &'a impl GraphViewTrait<N, E>,
+    selected: &'a HashSet<u32>,
+) -> impl Iterator<Item = &'a E> {
+    selected.iter().flat_map(move |&key| {
+        graph
+            .get_out_edges(key)
+            .filter(|(_, target)| selected.contains(&target.key()))
+            .map(|(edge, _)| edge)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::{DataNode, Edge, Node, WeightedEdge};
+    use crate::utils::build::build_graph;
+    use crate::utils::compare::structural_eq;
+    use ade_common::assert_panics_with;
+    use ade_traits::{DataNodeTrait, WeightedEdgeTrait};
+
+    #[test]
+    fn test_keeps_data() {
+        let graph = Graph::new(
+            vec![
+                DataNode::with_data(0, "a"),
+                DataNode::with_data(1, "b"),
+                DataNode::with_data(2, "c"),
+            ],
+            vec![
+                WeightedEdge::with_weight(0, 1, 4),
+                WeightedEdge::with_weight(1, 1, 5),
+                WeightedEdge::with_weight(1, 2, 6),
+            ],
+        );
+
+        let subgraph = extract_subgraph(&graph, &[2, 1, 2]);
+        assert_eq!(ade_traits::check_graph_consistency(&subgraph), Ok(()));
+        assert_eq!(*subgraph.get_node(1).data(), "b");
+        assert_eq!(subgraph.get_edge(1, 2).weight(), 6);
+        assert_eq!(subgraph.edge_count(), 2);
+
+        let (renumbered, mapping) = extract_subgraph_renumbered(&graph, &[2, 1, 2]);
+        assert_eq!(mapping, vec![2, 1]);
+        assert_eq!(ade_traits::check_graph_consistency(&renumbered), Ok(()));
+        assert_eq!(*renumbered.get_node(0).data(), "c");
+        assert_eq!(renumbered.get_edge(1, 0).weight(), 6);
+        assert_eq!(renumbered.get_edge(1, 1).weight(), 5);
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let graph = build_graph::<Node, Edge>(
+            (0..6).collect(),
+            vec![
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (5, 5),
+            ],
+        );
+        let keys = [0, 2, 3, 5];
+        assert!(structural_eq(
+            &extract_subgraph(&graph, &keys),
+            &graph.filter(&keys)
+        ));
+        assert!(extract_subgraph(&graph, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_panics_with!(extract_subgraph(&graph, &[0, 7]), "Node 7 not found");
+        assert_panics_with!(
+            extract_subgraph_renumbered(&graph, &[7]),
+            "Node 7 not found"
+        );
+    }
+}
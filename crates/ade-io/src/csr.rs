@@ -0,0 +1,547 @@
+use crate::GraphData;
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// Magic bytes at the start of every CSR file.
+pub const CSR_MAGIC: [u8; 4] = *b"ADEC";
+
+/// Version of the CSR file layout written by [`write_csr`].
+pub const CSR_VERSION: u32 = 1;
+
+// Magic, version, node count and edge count
+const HEADER_WORDS: usize = 4;
+
+/// Errors returned when reading CSR data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsrError {
+    /// The data does not start with [`CSR_MAGIC`].
+    InvalidMagic,
+    /// The data was written with an unsupported layout version.
+    UnsupportedVersion(u32),
+    /// The data is not aligned to 4 bytes, so it cannot be read in place.
+    Misaligned,
+    /// CSR data is little-endian and cannot be read in place on big-endian targets.
+    UnsupportedEndianness,
+    /// The data does not have the length implied by the header, in bytes. `expected` is
+    /// `usize::MAX` if that length does not fit in a `usize`.
+    InvalidLength { expected: usize, found: usize },
+    /// An offset table is not non-decreasing or does not end at the edge count.
+    InvalidOffsets,
+    /// A neighbor list holds a key outside `0..node_count`, or is not strictly increasing.
+    InvalidNeighbors,
+    /// The predecessor lists do not hold the same edges as the successor lists.
+    MismatchedPredecessors,
+}
+
+impl fmt::Display for CsrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsrError::InvalidMagic => write!(f, "Invalid CSR magic bytes"),
+            CsrError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported CSR version {}", version)
+            }
+            CsrError::Misaligned => write!(f, "CSR data is not aligned to 4 bytes"),
+            CsrError::UnsupportedEndianness => {
+                write!(f, "CSR data cannot be read in place on big-endian targets")
+            }
+            CsrError::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid data length: expected {} bytes, found {}",
+                expected, found
+            ),
+            CsrError::InvalidOffsets => write!(f, "Invalid CSR offsets"),
+            CsrError::InvalidNeighbors => write!(f, "Invalid CSR neighbor keys"),
+            CsrError::MismatchedPredecessors => {
+                write!(f, "CSR predecessors do not match the successors")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsrError {}
+
+/// Writes a graph in the binary CSR (compressed sparse row) layout.
+///
+/// The layout is a sequence of little-endian `u32` words:
+///
+/// 1. the header: [`CSR_MAGIC`], [`CSR_VERSION`], the node count `n` and the edge count `m`;
+/// 2. `n + 1` successor offsets, followed by the `m` successor keys;
+/// 3. `n + 1` predecessor offsets, followed by the `m` predecessor keys.
+///
+/// The neighbors of node `k` are the keys between offsets `k` and `k + 1`, sorted in
+/// ascending order. Every section is 4-byte aligned, so the data can be read in place
+/// with [`CsrView`], or memory-mapped with `MmapGraph` when the `mmap` feature is enabled.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Errors
+///
+/// Returns any error reported by `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::csr::write_csr;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let mut bytes = Vec::new();
+/// write_csr(&graph, &mut bytes).unwrap();
+///
+/// // Header, two offset tables of 4 words, two key tables of 2 words
+/// assert_eq!(bytes.len(), (4 + 4 + 2 + 4 + 2) * 4);
+/// ```
+pub fn write_csr<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let n = graph.node_count() as u32;
+    let m = graph.edge_count() as u32;
+
+    let mut words: Vec<u32> = Vec::with_capacity(HEADER_WORDS + 2 * (n as usize + 1 + m as usize));
+    words.push(u32::from_le_bytes(CSR_MAGIC));
+    words.extend([CSR_VERSION, n, m]);
+    push_adjacency(&mut words, n, |key| {
+        graph.get_successors_keys(key).collect()
+    });
+    push_adjacency(&mut words, n, |key| {
+        graph.get_predecessors_keys(key).collect()
+    });
+
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    writer.write_all(&bytes)
+}
+
+// Appends the offset table and the sorted neighbor keys of every node
+fn push_adjacency(words: &mut Vec<u32>, n: u32, neighbors: impl Fn(u32) -> Vec<u32>) {
+    let lists: Vec<Vec<u32>> = (0..n)
+        .map(|key| {
+            let mut list = neighbors(key);
+            list.sort_unstable();
+            list
+        })
+        .collect();
+
+    let mut offset = 0;
+    words.push(offset);
+    for list in &lists {
+        offset += list.len() as u32;
+        words.push(offset);
+    }
+    for list in lists {
+        words.extend(list);
+    }
+}
+
+/// A read-only graph stored in the CSR layout, borrowed without copying.
+///
+/// `CsrView` reads the offset and key tables written by [`write_csr`] directly from a byte
+/// slice, so opening it costs only a validation of the header, offsets and neighbor keys.
+/// Keys are always sequential, from `0` to `node_count() - 1`.
+///
+/// The view does not implement `GraphViewTrait`, which hands out owned node and edge
+/// values; use [`to_graph_data`](Self::to_graph_data) to build a mutable graph instead.
+#[derive(Debug, Clone, Copy)]
+pub struct CsrView<'a> {
+    node_count: usize,
+    successor_offsets: &'a [u32],
+    successors: &'a [u32],
+    predecessor_offsets: &'a [u32],
+    predecessors: &'a [u32],
+}
+
+impl<'a> CsrView<'a> {
+    /// Reads a CSR graph in place from `bytes`.
+    ///
+    /// The slice must be aligned to 4 bytes, which memory-mapped files always are.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CsrError`] if the data is misaligned, has an invalid header, does not
+    /// have the expected length, contains invalid offsets or neighbor lists, or has
+    /// predecessor lists that do not match its successor lists. Every key is checked, in
+    /// `O(n + m)` time, so that corrupt data cannot make later queries return wrong or
+    /// inconsistent answers or index out of bounds.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, CsrError> {
+        let view = Self::parse(bytes)?;
+
+        let n = view.node_count;
+        let m = view.successors.len();
+        // has_edge binary-searches the rows, and neighbors are used as indices
+        let valid_row = |row: &[u32]| {
+            row.windows(2).all(|pair| pair[0] < pair[1])
+                && row.last().is_none_or(|&key| (key as usize) < n)
+        };
+        for (offsets, keys) in [
+            (view.successor_offsets, view.successors),
+            (view.predecessor_offsets, view.predecessors),
+        ] {
+            let increasing = offsets.windows(2).all(|pair| pair[0] <= pair[1]);
+            if offsets[0] != 0 || offsets[n] as usize != m || !increasing {
+                return Err(CsrError::InvalidOffsets);
+            }
+            let mut rows = offsets.windows(2);
+            if !rows.all(|pair| valid_row(&keys[pair[0] as usize..pair[1] as usize])) {
+                return Err(CsrError::InvalidNeighbors);
+            }
+        }
+
+        // Sources are visited in ascending order, so each one must be the next unmatched
+        // predecessor of each of its targets
+        let mut matched: Vec<u32> = view.predecessor_offsets[..n].to_vec();
+        for source in view.node_keys() {
+            for &target in view.successors(source) {
+                let t = target as usize;
+                let slot = matched[t] as usize;
+                if slot == view.predecessor_offsets[t + 1] as usize
+                    || view.predecessors[slot] != source
+                {
+                    return Err(CsrError::MismatchedPredecessors);
+                }
+                matched[t] += 1;
+            }
+        }
+
+        Ok(view)
+    }
+
+    // Splits the data into its tables, checking the header and the total length only
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, CsrError> {
+        if cfg!(target_endian = "big") {
+            return Err(CsrError::UnsupportedEndianness);
+        }
+
+        // SAFETY: every bit pattern is a valid u32, and `align_to` only returns the
+        // correctly aligned middle part of the slice.
+        let (prefix, words, _) = unsafe { bytes.align_to::<u32>() };
+        if !prefix.is_empty() {
+            return Err(CsrError::Misaligned);
+        }
+
+        if words.len() < HEADER_WORDS {
+            return Err(CsrError::InvalidLength {
+                expected: HEADER_WORDS * 4,
+                found: bytes.len(),
+            });
+        }
+        if words[0] != u32::from_le_bytes(CSR_MAGIC) {
+            return Err(CsrError::InvalidMagic);
+        }
+        if words[1] != CSR_VERSION {
+            return Err(CsrError::UnsupportedVersion(words[1]));
+        }
+
+        let n = words[2] as usize;
+        let m = words[3] as usize;
+        // The counts come from the data, so the length may not fit in a usize
+        let expected = n
+            .checked_add(1)
+            .and_then(|words| words.checked_add(m))
+            .and_then(|words| words.checked_mul(2))
+            .and_then(|words| words.checked_add(HEADER_WORDS))
+            .and_then(|words| words.checked_mul(4))
+            .unwrap_or(usize::MAX);
+        if bytes.len() != expected {
+            return Err(CsrError::InvalidLength {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let (successor_offsets, rest) = words[HEADER_WORDS..].split_at(n + 1);
+        let (successors, rest) = rest.split_at(m);
+        let (predecessor_offsets, predecessors) = rest.split_at(n + 1);
+
+        Ok(Self {
+            node_count: n,
+            successor_offsets,
+            successors,
+            predecessor_offsets,
+            predecessors,
+        })
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.successors.len()
+    }
+
+    /// Returns the size in bytes of the CSR data the view reads from.
+    ///
+    /// The view allocates nothing on the heap: this is the memory held by the underlying
+    /// buffer or mapping, to compare with `Graph::estimated_heap_size`.
+    pub fn byte_len(&self) -> usize {
+        4 * (HEADER_WORDS
+            + self.successor_offsets.len()
+            + self.successors.len()
+            + self.predecessor_offsets.len()
+            + self.predecessors.len())
+    }
+
+    /// Returns `true` if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// Returns `true` if `key` is a node of the graph.
+    pub fn has_node(&self, key: u32) -> bool {
+        (key as usize) < self.node_count
+    }
+
+    /// Returns `true` if the graph contains the edge from `source` to `target`.
+    pub fn has_edge(&self, source: u32, target: u32) -> bool {
+        self.has_node(source) && self.successors(source).binary_search(&target).is_ok()
+    }
+
+    /// Returns the node keys, `0..node_count()`.
+    pub fn node_keys(&self) -> Range<u32> {
+        0..self.node_count as u32
+    }
+
+    /// Returns the successors of a node, sorted by key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn successors(&self, key: u32) -> &'a [u32] {
+        let k = self.check_node(key);
+        &self.successors[self.successor_offsets[k] as usize..self.successor_offsets[k + 1] as usize]
+    }
+
+    /// Returns the predecessors of a node, sorted by key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn predecessors(&self, key: u32) -> &'a [u32] {
+        let k = self.check_node(key);
+        &self.predecessors
+            [self.predecessor_offsets[k] as usize..self.predecessor_offsets[k + 1] as usize]
+    }
+
+    /// Returns all edges as `(source, target)` pairs, sorted.
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32)> + 'a {
+        let view = *self;
+        view.node_keys().flat_map(move |source| {
+            view.successors(source)
+                .iter()
+                .map(move |&target| (source, target))
+        })
+    }
+
+    /// Copies node keys and edge pairs out of the view, in the format accepted by `build_graph`.
+    pub fn to_graph_data(&self) -> GraphData {
+        (self.node_keys().collect(), self.edges().collect())
+    }
+
+    fn check_node(&self, key: u32) -> usize {
+        if !self.has_node(key) {
+            panic!("Node {} not found", key);
+        }
+        key as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    // Copies bytes into a u32 buffer, so that they can be viewed with the right alignment
+    fn aligned(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(word)
+            })
+            .collect()
+    }
+
+    fn as_bytes(words: &[u32], len: usize) -> &[u8] {
+        // SAFETY: u8 has no alignment requirement and len never exceeds the buffer size
+        unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, len) }
+    }
+
+    fn encode(nodes: Vec<u32>, edges: Vec<(u32, u32)>) -> Vec<u8> {
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let mut bytes = Vec::new();
+        write_csr(&graph, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = encode(
+            vec![0, 1, 2, 3],
+            vec![(2, 0), (0, 1), (0, 2), (1, 2), (3, 3)],
+        );
+        let words = aligned(&bytes);
+        let view = CsrView::from_bytes(as_bytes(&words, bytes.len())).unwrap();
+
+        assert_eq!(view.node_count(), 4);
+        assert_eq!(view.edge_count(), 5);
+        assert_eq!(view.byte_len(), bytes.len());
+        assert_eq!(view.successors(0), &[1, 2]);
+        assert_eq!(view.predecessors(2), &[0, 1]);
+        assert_eq!(view.successors(3), &[3]);
+        assert!(view.has_edge(2, 0));
+        assert!(!view.has_edge(0, 3));
+        assert!(!view.has_edge(7, 0));
+        assert_eq!(
+            view.to_graph_data(),
+            (
+                vec![0, 1, 2, 3],
+                vec![(0, 1), (0, 2), (1, 2), (2, 0), (3, 3)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let bytes = encode(vec![], vec![]);
+        let words = aligned(&bytes);
+        let view = CsrView::from_bytes(as_bytes(&words, bytes.len())).unwrap();
+
+        assert!(view.is_empty());
+        assert_eq!(view.edges().count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let bytes = encode(vec![0, 1], vec![(0, 1)]);
+        let words = aligned(&bytes);
+
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&words, bytes.len() - 4)).unwrap_err(),
+            CsrError::InvalidLength {
+                expected: bytes.len(),
+                found: bytes.len() - 4
+            }
+        );
+
+        let mut corrupted = words.clone();
+        corrupted[0] = 0;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::InvalidMagic
+        );
+
+        let mut corrupted = words.clone();
+        corrupted[1] = 99;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::UnsupportedVersion(99)
+        );
+
+        // The successor offsets of node 0 are [0, 1], make them decrease
+        let mut corrupted = words.clone();
+        corrupted[HEADER_WORDS + 1] = 5;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::InvalidOffsets
+        );
+
+        // The only successor of node 0 is 1, point it outside the graph
+        let successors = HEADER_WORDS + 3;
+        let mut corrupted = words.clone();
+        corrupted[successors] = 2;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::InvalidNeighbors
+        );
+
+        let mut buffer = words.clone();
+        buffer.push(0);
+        let shifted = &as_bytes(&buffer, bytes.len() + 4)[1..bytes.len() + 1];
+        assert_eq!(
+            CsrView::from_bytes(shifted).unwrap_err(),
+            CsrError::Misaligned
+        );
+    }
+
+    #[test]
+    fn test_unsorted_neighbors() {
+        // Node 0 has successors [1, 2] and node 2 has predecessors [0, 1]
+        let bytes = encode(vec![0, 1, 2], vec![(0, 1), (0, 2), (1, 2)]);
+        let words = aligned(&bytes);
+        let successors = HEADER_WORDS + 4;
+        let predecessors = successors + 3 + 4;
+
+        let mut corrupted = words.clone();
+        corrupted.swap(successors, successors + 1);
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::InvalidNeighbors
+        );
+
+        let mut corrupted = words.clone();
+        corrupted[predecessors + 2] = corrupted[predecessors + 1];
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::InvalidNeighbors
+        );
+    }
+
+    #[test]
+    fn test_mismatched_predecessors() {
+        // Node 2 has predecessors [0, 1]; claim [0, 2] instead, which is still sorted
+        let bytes = encode(vec![0, 1, 2], vec![(0, 1), (0, 2), (1, 2)]);
+        let words = aligned(&bytes);
+        let predecessors = HEADER_WORDS + 4 + 3 + 4;
+
+        let mut corrupted = words.clone();
+        corrupted[predecessors + 2] = 2;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::MismatchedPredecessors
+        );
+
+        // Move the predecessor 0 of node 1 to node 0, keeping the offsets consistent
+        let mut corrupted = words.clone();
+        corrupted[HEADER_WORDS + 4 + 3 + 1] = 1;
+        assert_eq!(
+            CsrView::from_bytes(as_bytes(&corrupted, bytes.len())).unwrap_err(),
+            CsrError::MismatchedPredecessors
+        );
+    }
+
+    #[test]
+    fn test_huge_header() {
+        let mut words = aligned(&encode(vec![0], vec![]));
+        words[2] = u32::MAX;
+        words[3] = u32::MAX;
+        let len = words.len() * 4;
+        assert!(matches!(
+            CsrView::from_bytes(as_bytes(&words, len)).unwrap_err(),
+            CsrError::InvalidLength { found, .. } if found == len
+        ));
+    }
+
+    #[test]
+    fn test_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![1, 3], vec![(1, 3)]);
+        assert_panics_with!(
+            write_csr(&graph, &mut Vec::new()).unwrap(),
+            ade_common::INVALID_KEY_SEQUENCE
+        );
+    }
+}
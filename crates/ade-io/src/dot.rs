@@ -0,0 +1,541 @@
+use crate::GraphData;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fmt::Write;
+
+/// The options of [`to_dot`].
+///
+/// Labels and positions are optional and given per node key; keys that are not nodes of
+/// the graph are ignored. Positions are meant to come from a layout, for example from
+/// `ade_compound_layout::LayoutResult::positions`, and are written so that Graphviz draws
+/// the nodes where the layout put them (`neato -n`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotConfig {
+    /// The name of the digraph.
+    pub name: String,
+    /// Labels shown instead of the node keys.
+    pub labels: BTreeMap<u32, String>,
+    /// Centers of the nodes, with coordinates growing rightwards and downwards.
+    pub positions: BTreeMap<u32, (f64, f64)>,
+}
+
+impl Default for DotConfig {
+    fn default() -> Self {
+        Self {
+            name: "G".to_string(),
+            labels: BTreeMap::new(),
+            positions: BTreeMap::new(),
+        }
+    }
+}
+
+/// Errors returned when parsing DOT text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DotError {
+    /// The text ends before the digraph is closed.
+    UnexpectedEnd,
+    /// A token that the supported subset does not allow at this point, with its line.
+    UnexpectedToken { line: usize, token: String },
+    /// A quoted string or a comment is not closed.
+    Unterminated { line: usize },
+    /// A node identifier is not a `u32` key.
+    InvalidNodeId { line: usize, id: String },
+    /// The text describes an undirected `graph`.
+    Undirected,
+}
+
+impl fmt::Display for DotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DotError::UnexpectedEnd => write!(f, "Unexpected end of DOT text"),
+            DotError::UnexpectedToken { line, token } => {
+                write!(f, "Unexpected token {:?} on line {}", token, line)
+            }
+            DotError::Unterminated { line } => {
+                write!(f, "Unterminated string or comment on line {}", line)
+            }
+            DotError::InvalidNodeId { line, id } => {
+                write!(f, "Invalid node id {:?} on line {}", id, line)
+            }
+            DotError::Undirected => write!(f, "Undirected graphs are not supported"),
+        }
+    }
+}
+
+impl std::error::Error for DotError {}
+
+/// Writes a graph as Graphviz DOT text.
+///
+/// Nodes are identified by their keys and listed in ascending order, followed by the edges
+/// in ascending order, so equal graphs always produce equal text. Nodes with a label or a
+/// position in `config` get `label` and `pos` attributes. Graphviz's y axis points up, so
+/// positions are written with their y coordinate negated, and are pinned with `!`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::dot::{to_dot, DotConfig};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+/// let mut config = DotConfig::default();
+/// config.labels.insert(0, "start".to_string());
+/// config.positions.insert(1, (10.0, 20.0));
+///
+/// assert_eq!(
+///     to_dot(&graph, &config),
+///     "digraph G {\n    0 [label=\"start\"];\n    1 [pos=\"10,-20!\"];\n    0 -> 1;\n}\n"
+/// );
+/// ```
+pub fn to_dot<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    config: &DotConfig,
+) -> String {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let mut edges: Vec<(u32, u32)> = graph.get_edges().map(|edge| edge.key()).collect();
+    edges.sort_unstable();
+
+    let mut out = String::new();
+    writeln!(out, "digraph {} {{", quote_if_needed(&config.name)).unwrap();
+    for key in keys {
+        let mut attributes = Vec::new();
+        if let Some(label) = config.labels.get(&key) {
+            attributes.push(format!("label={}", quote(label)));
+        }
+        if let Some(&(x, y)) = config.positions.get(&key) {
+            attributes.push(format!("pos=\"{},{}!\"", x, 0.0 - y));
+        }
+        if attributes.is_empty() {
+            writeln!(out, "    {};", key).unwrap();
+        } else {
+            writeln!(out, "    {} [{}];", key, attributes.join(", ")).unwrap();
+        }
+    }
+    for (source, target) in edges {
+        writeln!(out, "    {} -> {};", source, target).unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Parses Graphviz DOT text describing a digraph into node keys and edge pairs.
+///
+/// The supported subset covers the graphs written by [`to_dot`] and most hand-written
+/// ones: an optional `strict` keyword, `digraph` with an optional name, node statements,
+/// edge chains such as `0 -> 1 -> 2`, attribute lists, which are ignored, `graph`, `node`
+/// and `edge` default attributes and `name = value` statements, which are ignored too,
+/// and `//`, `/* */` and `#` comments. Node identifiers must be `u32` keys, quoted or not.
+/// Subgraphs are not supported.
+///
+/// # Returns
+///
+/// A tuple `(node_keys, edge_pairs)` in the format accepted by `build_graph`. Keys are in
+/// ascending order and include the nodes only mentioned by edges; edges are in order of
+/// appearance, with repeated edges kept once.
+///
+/// # Errors
+///
+/// Returns a [`DotError`] if the text is not a digraph, a node identifier is not a key, or
+/// the text leaves the supported subset.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::dot::from_dot;
+///
+/// let text = r#"
+///     digraph pipeline {
+///         node [shape=box];
+///         0 [label="lexer"];
+///         0 -> 1 -> 2;
+///         "2" -> 0; // back edge
+///     }
+/// "#;
+///
+/// let (nodes, edges) = from_dot(text).unwrap();
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0)]);
+/// ```
+pub fn from_dot(input: &str) -> Result<GraphData, DotError> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        nodes: BTreeSet::new(),
+        edges: Vec::new(),
+        edge_set: HashSet::new(),
+    };
+    parser.graph()?;
+    Ok((parser.nodes.into_iter().collect(), parser.edges))
+}
+
+// Quotes a name unless it is a plain identifier or a number
+fn quote_if_needed(name: &str) -> String {
+    let plain = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    // An identifier, a number or a quoted string, with quotes removed
+    Id(String),
+    Arrow,
+    UndirectedEdge,
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match &self.kind {
+            TokenKind::Id(id) => id.clone(),
+            TokenKind::Arrow => "->".to_string(),
+            TokenKind::UndirectedEdge => "--".to_string(),
+            TokenKind::Symbol(c) => c.to_string(),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, DotError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '#' | '/' if c == '#' || chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let start = line;
+                i += 2;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(DotError::Unterminated { line: start }),
+                        Some('*') if chars.get(i + 1) == Some(&'/') => break,
+                        Some('\n') => line += 1,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                i += 2;
+            }
+            '"' => {
+                let start = line;
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(DotError::Unterminated { line: start }),
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                            text.push('"');
+                            i += 1;
+                        }
+                        Some(&c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                    }
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token {
+                    kind: TokenKind::Id(text),
+                    line: start,
+                });
+            }
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                tokens.push(Token {
+                    kind: TokenKind::Arrow,
+                    line,
+                });
+                i += 2;
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                tokens.push(Token {
+                    kind: TokenKind::UndirectedEdge,
+                    line,
+                });
+                i += 2;
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Id(chars[start..i].iter().collect()),
+                    line,
+                });
+            }
+            _ => {
+                tokens.push(Token {
+                    kind: TokenKind::Symbol(c),
+                    line,
+                });
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// Recursive descent over the tokens, collecting nodes and edges
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    nodes: BTreeSet<u32>,
+    edges: Vec<(u32, u32)>,
+    edge_set: HashSet<(u32, u32)>,
+}
+
+impl Parser<'_> {
+    fn graph(&mut self) -> Result<(), DotError> {
+        if self.keyword("strict") {
+            self.position += 1;
+        }
+        if self.keyword("graph") {
+            return Err(DotError::Undirected);
+        }
+        if !self.keyword("digraph") {
+            return Err(self.unexpected());
+        }
+        self.position += 1;
+        if let Some(TokenKind::Id(_)) = self.peek() {
+            self.position += 1;
+        }
+        self.expect('{')?;
+
+        while self.peek() != Some(&TokenKind::Symbol('}')) {
+            self.statement()?;
+            if self.peek() == Some(&TokenKind::Symbol(';')) {
+                self.position += 1;
+            }
+        }
+        self.position += 1;
+
+        match self.tokens.get(self.position) {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn statement(&mut self) -> Result<(), DotError> {
+        if self.keyword("subgraph") {
+            return Err(self.unexpected());
+        }
+        if self.keyword("graph") || self.keyword("node") || self.keyword("edge") {
+            self.position += 1;
+            return self.attributes();
+        }
+        let id = match self.peek() {
+            Some(TokenKind::Id(_)) => self.token().text(),
+            None => return Err(DotError::UnexpectedEnd),
+            Some(_) => return Err(self.unexpected()),
+        };
+        let line = self.token().line;
+        self.position += 1;
+
+        // A `name = value` statement sets a graph attribute
+        if self.peek() == Some(&TokenKind::Symbol('=')) {
+            self.position += 1;
+            return self.id().map(|_| ());
+        }
+
+        let mut source = self.node_key(&id, line)?;
+        while self.peek() == Some(&TokenKind::Arrow) {
+            self.position += 1;
+            let line = self
+                .tokens
+                .get(self.position)
+                .map_or(line, |token| token.line);
+            let id = self.id()?;
+            let target = self.node_key(&id, line)?;
+            if self.edge_set.insert((source, target)) {
+                self.edges.push((source, target));
+            }
+            source = target;
+        }
+        if self.peek() == Some(&TokenKind::UndirectedEdge) {
+            return Err(DotError::Undirected);
+        }
+        self.attributes()
+    }
+
+    // Skips an optional sequence of attribute lists
+    fn attributes(&mut self) -> Result<(), DotError> {
+        while self.peek() == Some(&TokenKind::Symbol('[')) {
+            self.position += 1;
+            while self.peek() != Some(&TokenKind::Symbol(']')) {
+                self.id()?;
+                if self.peek() == Some(&TokenKind::Symbol('=')) {
+                    self.position += 1;
+                    self.id()?;
+                }
+                if matches!(self.peek(), Some(TokenKind::Symbol(',' | ';'))) {
+                    self.position += 1;
+                }
+            }
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    fn node_key(&mut self, id: &str, line: usize) -> Result<u32, DotError> {
+        let key = id.parse::<u32>().map_err(|_| DotError::InvalidNodeId {
+            line,
+            id: id.to_string(),
+        })?;
+        self.nodes.insert(key);
+        Ok(key)
+    }
+
+    fn id(&mut self) -> Result<String, DotError> {
+        match self.peek() {
+            Some(TokenKind::Id(id)) => {
+                let id = id.clone();
+                self.position += 1;
+                Ok(id)
+            }
+            None => Err(DotError::UnexpectedEnd),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), DotError> {
+        match self.peek() {
+            Some(TokenKind::Symbol(c)) if *c == symbol => {
+                self.position += 1;
+                Ok(())
+            }
+            None => Err(DotError::UnexpectedEnd),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    // Keywords are case-insensitive in DOT
+    fn keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    fn unexpected(&self) -> DotError {
+        match self.tokens.get(self.position) {
+            None => DotError::UnexpectedEnd,
+            Some(token) => DotError::UnexpectedToken {
+                line: token.line,
+                token: token.text(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_round_trip() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 5], vec![(2, 0), (0, 1), (1, 2), (1, 1)]);
+        let mut config = DotConfig {
+            name: "my graph".to_string(),
+            ..DotConfig::default()
+        };
+        config.labels.insert(2, "say \"hi\"".to_string());
+        config.positions.insert(0, (1.5, 2.0));
+        config.positions.insert(1, (0.0, 0.0));
+
+        let text = to_dot(&graph, &config);
+        assert!(text.starts_with("digraph \"my graph\" {\n"));
+        assert!(text.contains("    1 [pos=\"0,0!\"];\n"));
+        assert!(text.contains("    2 [label=\"say \\\"hi\\\"\"];\n"));
+
+        let (nodes, edges) = from_dot(&text).unwrap();
+        assert_eq!(nodes, vec![0, 1, 2, 5]);
+        assert_eq!(edges, vec![(0, 1), (1, 1), (1, 2), (2, 0)]);
+    }
+
+    #[test]
+    fn test_supported_subset() {
+        let text = "/* header\n comment */\nstrict DiGraph {\n  rankdir = LR\n  edge [color=red; style=dashed] [weight=2]\n  # a comment\n  3 -> 1 -> 3 -> 1\n  4\n}";
+        let (nodes, edges) = from_dot(text).unwrap();
+        assert_eq!(nodes, vec![1, 3, 4]);
+        assert_eq!(edges, vec![(3, 1), (1, 3)]);
+
+        assert_eq!(from_dot("digraph {}").unwrap(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(from_dot("graph { 0 -- 1 }"), Err(DotError::Undirected));
+        assert_eq!(from_dot("digraph { 0 -- 1 }"), Err(DotError::Undirected));
+        assert_eq!(
+            from_dot("digraph {\n a -> 1 }"),
+            Err(DotError::InvalidNodeId {
+                line: 2,
+                id: "a".to_string()
+            })
+        );
+        assert_eq!(
+            from_dot("digraph { 0 -> }"),
+            Err(DotError::UnexpectedToken {
+                line: 1,
+                token: "}".to_string()
+            })
+        );
+        assert_eq!(from_dot("digraph { 0 -> 1"), Err(DotError::UnexpectedEnd));
+        assert_eq!(
+            from_dot("digraph { 0 [label=\"x }"),
+            Err(DotError::Unterminated { line: 1 })
+        );
+        assert_eq!(
+            from_dot("digraph { subgraph { 0 } }"),
+            Err(DotError::UnexpectedToken {
+                line: 1,
+                token: "subgraph".to_string()
+            })
+        );
+    }
+}
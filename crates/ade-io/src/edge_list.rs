@@ -0,0 +1,440 @@
+use crate::GraphData;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, WeightedEdgeTrait};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Node keys and weighted edges, read by [`parse_weighted_edge_list`].
+pub type WeightedGraphData = (Vec<u32>, Vec<(u32, u32, f64)>);
+
+/// Errors returned when parsing edge lists and adjacency lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeListError {
+    /// A field that should be a node key is not a `u32`.
+    InvalidKey { line: usize, field: String },
+    /// A line of an edge list has a source but no target.
+    MissingTarget { line: usize },
+    /// The weight column of an edge list is not a number.
+    InvalidWeight { line: usize, field: String },
+    /// A line of an edge list has more than three columns.
+    TooManyColumns { line: usize },
+}
+
+impl fmt::Display for EdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdgeListError::InvalidKey { line, field } => {
+                write!(f, "Invalid node key {:?} on line {}", field, line)
+            }
+            EdgeListError::MissingTarget { line } => write!(f, "Missing target on line {}", line),
+            EdgeListError::InvalidWeight { line, field } => {
+                write!(f, "Invalid weight {:?} on line {}", field, line)
+            }
+            EdgeListError::TooManyColumns { line } => {
+                write!(f, "Too many columns on line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EdgeListError {}
+
+/// Parses an edge list, one `source target` pair per line.
+///
+/// Columns are separated by whitespace or commas, so SNAP datasets, CSV files and most
+/// text exports can be read as they are. Blank lines and lines starting with `#` or `%`
+/// are skipped, and an optional third column, such as a weight, is ignored; use
+/// [`parse_weighted_edge_list`] to keep it.
+///
+/// # Returns
+///
+/// A tuple `(node_keys, edge_pairs)` in the format accepted by `build_graph`. Node keys are
+/// the endpoints of the edges, in ascending order, and edges are in file order.
+///
+/// # Errors
+///
+/// Returns an [`EdgeListError`] if a line has a single column or more than three, or if a
+/// key is not a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::parse_edge_list;
+///
+/// let text = "# FromNodeId\tToNodeId\n0\t1\n1,2\n\n2 0 0.5\n";
+/// let (nodes, edges) = parse_edge_list(text).unwrap();
+///
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0)]);
+/// ```
+pub fn parse_edge_list(input: &str) -> Result<GraphData, EdgeListError> {
+    let mut nodes = BTreeSet::new();
+    let mut edges = Vec::new();
+    for (line, fields) in data_lines(input) {
+        let (source, target, _) = parse_edge(line, &fields)?;
+        nodes.extend([source, target]);
+        edges.push((source, target));
+    }
+    Ok((nodes.into_iter().collect(), edges))
+}
+
+/// Parses an edge list with an optional weight column, one `source target [weight]` edge
+/// per line.
+///
+/// The format is the one read by [`parse_edge_list`]. Edges without a weight get a weight
+/// of `1.0`.
+///
+/// # Returns
+///
+/// Node keys in ascending order, and `(source, target, weight)` edges in file order.
+///
+/// # Errors
+///
+/// Returns an [`EdgeListError`] if a line has a single column or more than three, if a key
+/// is not a `u32`, or if a weight is not a number.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::parse_weighted_edge_list;
+///
+/// let (nodes, edges) = parse_weighted_edge_list("0 1 2.5\n1 2\n").unwrap();
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1, 2.5), (1, 2, 1.0)]);
+/// ```
+pub fn parse_weighted_edge_list(input: &str) -> Result<WeightedGraphData, EdgeListError> {
+    let mut nodes = BTreeSet::new();
+    let mut edges = Vec::new();
+    for (line, fields) in data_lines(input) {
+        let (source, target, weight) = parse_edge(line, &fields)?;
+        let weight = match weight {
+            Some(field) => field.parse().map_err(|_| EdgeListError::InvalidWeight {
+                line,
+                field: field.to_string(),
+            })?,
+            None => 1.0,
+        };
+        nodes.extend([source, target]);
+        edges.push((source, target, weight));
+    }
+    Ok((nodes.into_iter().collect(), edges))
+}
+
+/// Parses an adjacency list, one `node successor successor ...` line per node.
+///
+/// Columns are separated by whitespace or commas, and blank lines and lines starting with
+/// `#` or `%` are skipped, as in [`parse_edge_list`]. Unlike an edge list, an adjacency list
+/// keeps nodes without edges: a line with a single key adds an isolated node.
+///
+/// # Returns
+///
+/// A tuple `(node_keys, edge_pairs)` in the format accepted by `build_graph`. Node keys are
+/// in ascending order, and include successors without a line of their own.
+///
+/// # Errors
+///
+/// Returns [`EdgeListError::InvalidKey`] if a field is not a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::parse_adjacency_list;
+///
+/// let (nodes, edges) = parse_adjacency_list("0 1 2\n1 2\n3\n").unwrap();
+/// assert_eq!(nodes, vec![0, 1, 2, 3]);
+/// assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2)]);
+/// ```
+pub fn parse_adjacency_list(input: &str) -> Result<GraphData, EdgeListError> {
+    let mut nodes = BTreeSet::new();
+    let mut edges = Vec::new();
+    for (line, fields) in data_lines(input) {
+        let source = parse_key(line, fields[0])?;
+        nodes.insert(source);
+        for field in &fields[1..] {
+            let target = parse_key(line, field)?;
+            nodes.insert(target);
+            edges.push((source, target));
+        }
+    }
+    Ok((nodes.into_iter().collect(), edges))
+}
+
+/// Reads an edge list file, as parsed by [`parse_edge_list`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or an error of kind
+/// [`io::ErrorKind::InvalidData`] wrapping an [`EdgeListError`] if its content is invalid.
+pub fn read_edge_list(path: impl AsRef<Path>) -> io::Result<GraphData> {
+    parse_edge_list(&fs::read_to_string(path)?).map_err(invalid_data)
+}
+
+/// Reads an edge list file with an optional weight column, as parsed by
+/// [`parse_weighted_edge_list`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or an error of kind
+/// [`io::ErrorKind::InvalidData`] wrapping an [`EdgeListError`] if its content is invalid.
+pub fn read_weighted_edge_list(path: impl AsRef<Path>) -> io::Result<WeightedGraphData> {
+    parse_weighted_edge_list(&fs::read_to_string(path)?).map_err(invalid_data)
+}
+
+/// Reads an adjacency list file, as parsed by [`parse_adjacency_list`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or an error of kind
+/// [`io::ErrorKind::InvalidData`] wrapping an [`EdgeListError`] if its content is invalid.
+pub fn read_adjacency_list(path: impl AsRef<Path>) -> io::Result<GraphData> {
+    parse_adjacency_list(&fs::read_to_string(path)?).map_err(invalid_data)
+}
+
+/// Writes the edges of a graph as an edge list, one `source target` line per edge, sorted.
+///
+/// Nodes without edges are not written; use [`write_adjacency_list`] to keep them.
+///
+/// # Errors
+///
+/// Returns any error reported by `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::write_edge_list;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(1, 2), (0, 1)]);
+/// let mut text = Vec::new();
+/// write_edge_list(&graph, &mut text).unwrap();
+///
+/// assert_eq!(String::from_utf8(text).unwrap(), "0 1\n1 2\n");
+/// ```
+pub fn write_edge_list<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let mut edges: Vec<(u32, u32)> = graph.get_edges().map(|edge| edge.key()).collect();
+    edges.sort_unstable();
+    for (source, target) in edges {
+        writeln!(writer, "{} {}", source, target)?;
+    }
+    Ok(())
+}
+
+/// Writes the edges of a graph with their weights, one `source target weight` line per
+/// edge, sorted by `(source, target)`.
+///
+/// # Errors
+///
+/// Returns any error reported by `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::write_weighted_edge_list;
+/// use ade_graph::implementations::{Graph, Node, WeightedEdge};
+/// use ade_traits::{NodeTrait, WeightedEdgeTrait};
+///
+/// let graph = Graph::<Node, WeightedEdge<f64>>::new(
+///     vec![Node::new(0), Node::new(1)],
+///     vec![WeightedEdge::with_weight(0, 1, 2.5)],
+/// );
+/// let mut text = Vec::new();
+/// write_weighted_edge_list(&graph, &mut text).unwrap();
+///
+/// assert_eq!(String::from_utf8(text).unwrap(), "0 1 2.5\n");
+/// ```
+pub fn write_weighted_edge_list<N: NodeTrait, E: WeightedEdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    writer: &mut impl Write,
+) -> io::Result<()>
+where
+    E::Weight: fmt::Display,
+{
+    let mut edges: Vec<&E> = graph.get_edges().collect();
+    edges.sort_unstable_by_key(|edge| edge.key());
+    for edge in edges {
+        writeln!(
+            writer,
+            "{} {} {}",
+            edge.source(),
+            edge.target(),
+            edge.weight()
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes a graph as an adjacency list, one `node successor successor ...` line per node.
+///
+/// Nodes and successors are sorted, and nodes without successors get a line with their
+/// key alone, so isolated nodes are kept.
+///
+/// # Errors
+///
+/// Returns any error reported by `writer`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::edge_list::write_adjacency_list;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 2), (0, 1)]);
+/// let mut text = Vec::new();
+/// write_adjacency_list(&graph, &mut text).unwrap();
+///
+/// assert_eq!(String::from_utf8(text).unwrap(), "0 1 2\n1\n2\n");
+/// ```
+pub fn write_adjacency_list<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    for key in keys {
+        let mut successors: Vec<u32> = graph.get_successors_keys(key).collect();
+        successors.sort_unstable();
+        write!(writer, "{}", key)?;
+        for successor in successors {
+            write!(writer, " {}", successor)?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+// Non-empty, non-comment lines, with their 1-based number and their fields
+fn data_lines(input: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#') && !line.starts_with('%'))
+        .map(|(line, text)| {
+            let fields = text
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|field| !field.is_empty())
+                .collect();
+            (line, fields)
+        })
+}
+
+// Source, target and optional third column of an edge list line
+fn parse_edge<'a>(
+    line: usize,
+    fields: &[&'a str],
+) -> Result<(u32, u32, Option<&'a str>), EdgeListError> {
+    match *fields {
+        [source] => {
+            parse_key(line, source)?;
+            Err(EdgeListError::MissingTarget { line })
+        }
+        [source, target] => Ok((parse_key(line, source)?, parse_key(line, target)?, None)),
+        [source, target, weight] => Ok((
+            parse_key(line, source)?,
+            parse_key(line, target)?,
+            Some(weight),
+        )),
+        _ => Err(EdgeListError::TooManyColumns { line }),
+    }
+}
+
+fn parse_key(line: usize, field: &str) -> Result<u32, EdgeListError> {
+    field.parse().map_err(|_| EdgeListError::InvalidKey {
+        line,
+        field: field.to_string(),
+    })
+}
+
+fn invalid_data(err: EdgeListError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_round_trips() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 7],
+            vec![(2, 0), (0, 1), (1, 2), (1, 1), (0, 2)],
+        );
+
+        let mut text = Vec::new();
+        write_edge_list(&graph, &mut text).unwrap();
+        let (nodes, edges) = parse_edge_list(std::str::from_utf8(&text).unwrap()).unwrap();
+        assert_eq!(nodes, vec![0, 1, 2]);
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 1), (1, 2), (2, 0)]);
+
+        let mut text = Vec::new();
+        write_adjacency_list(&graph, &mut text).unwrap();
+        let (nodes, edges) = parse_adjacency_list(std::str::from_utf8(&text).unwrap()).unwrap();
+        assert_eq!(nodes, vec![0, 1, 2, 7]);
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 1), (1, 2), (2, 0)]);
+    }
+
+    #[test]
+    fn test_read_files() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("ade_edge_list_{}.txt", std::process::id()));
+        fs::write(&path, "% weighted\n3, 4, 0.25\n4 3\n").unwrap();
+
+        assert_eq!(
+            read_edge_list(&path).unwrap(),
+            (vec![3, 4], vec![(3, 4), (4, 3)])
+        );
+        assert_eq!(
+            read_weighted_edge_list(&path).unwrap().1,
+            vec![(3, 4, 0.25), (4, 3, 1.0)]
+        );
+        assert_eq!(
+            read_adjacency_list(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read_edge_list(dir.join("ade_missing_edge_list.txt"))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse_edge_list("0 1\n2\n"),
+            Err(EdgeListError::MissingTarget { line: 2 })
+        );
+        assert_eq!(
+            parse_edge_list("0 1 2 3"),
+            Err(EdgeListError::TooManyColumns { line: 1 })
+        );
+        assert_eq!(
+            parse_edge_list("0 -1"),
+            Err(EdgeListError::InvalidKey {
+                line: 1,
+                field: "-1".to_string()
+            })
+        );
+        assert_eq!(
+            parse_weighted_edge_list("\n0 1 heavy"),
+            Err(EdgeListError::InvalidWeight {
+                line: 2,
+                field: "heavy".to_string()
+            })
+        );
+        // Weights are ignored, not checked, by the unweighted parser
+        assert!(parse_edge_list("0 1 heavy").is_ok());
+    }
+}
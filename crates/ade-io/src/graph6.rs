@@ -0,0 +1,522 @@
+use crate::GraphData;
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::fmt;
+
+/// Optional header that may precede a graph6 string.
+pub const GRAPH6_HEADER: &str = ">>graph6<<";
+
+/// Optional header that may precede a digraph6 string.
+pub const DIGRAPH6_HEADER: &str = ">>digraph6<<";
+
+// Printable characters used by the format start at '?' (63) and end at '~' (126)
+const BIAS: u8 = 63;
+const MAX_PRINTABLE: u8 = 126;
+
+/// Errors returned when decoding graph6 or digraph6 strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Graph6Error {
+    /// The input contains no data after removing the optional header.
+    Empty,
+    /// A digraph6 string does not start with `&`.
+    MissingDigraphPrefix,
+    /// A byte outside the printable range `63..=126` was found at the given position.
+    InvalidByte { position: usize, byte: u8 },
+    /// The node count is truncated or cannot be represented with `u32` keys.
+    InvalidNodeCount,
+    /// The adjacency data does not have the length implied by the node count.
+    InvalidLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for Graph6Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Graph6Error::Empty => write!(f, "Empty graph6 string"),
+            Graph6Error::MissingDigraphPrefix => write!(f, "digraph6 string must start with '&'"),
+            Graph6Error::InvalidByte { position, byte } => {
+                write!(f, "Invalid byte {} at position {}", byte, position)
+            }
+            Graph6Error::InvalidNodeCount => write!(f, "Invalid node count"),
+            Graph6Error::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid data length: expected {} bytes, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Graph6Error {}
+
+/// Decodes a graph6 string into node keys and edge pairs.
+///
+/// graph6 is the compact format used by nauty and many combinatorics datasets to store
+/// undirected simple graphs. Since ADE graphs are directed, every undirected edge `{i, j}`
+/// is returned as the two directed edges `(i, j)` and `(j, i)`.
+///
+/// The optional [`GRAPH6_HEADER`] and surrounding whitespace (e.g. the trailing newline of
+/// a line read from a file) are ignored.
+///
+/// # Returns
+///
+/// A tuple `(node_keys, edge_pairs)` with sequential keys `0..n`, in the format accepted
+/// by `build_graph`.
+///
+/// # Errors
+///
+/// Returns a [`Graph6Error`] if the string is empty, contains bytes outside the printable
+/// range, or its length does not match the encoded node count.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::from_graph6;
+///
+/// // The path 0 - 1 - 2
+/// let (nodes, edges) = from_graph6("Bg").unwrap();
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1), (1, 0), (1, 2), (2, 1)]);
+/// ```
+pub fn from_graph6(input: &str) -> Result<GraphData, Graph6Error> {
+    let bytes = strip(input, GRAPH6_HEADER)?;
+    let (n, offset) = decode_node_count(bytes)?;
+    let data = &bytes[offset..];
+
+    let bit_count = bit_count(n as u64 * n.saturating_sub(1) as u64 / 2)?;
+    let bits = decode_bits(data, offset, bit_count)?;
+
+    let mut edges = Vec::new();
+    let mut k = 0;
+    for j in 1..n {
+        for i in 0..j {
+            if bits(k) {
+                edges.push((i as u32, j as u32));
+                edges.push((j as u32, i as u32));
+            }
+            k += 1;
+        }
+    }
+
+    Ok(((0..n as u32).collect(), edges))
+}
+
+/// Decodes a digraph6 string into node keys and edge pairs.
+///
+/// digraph6 is the directed counterpart of graph6: it stores the full adjacency matrix
+/// row by row, so self-loops are supported. The string must start with `&`, optionally
+/// preceded by [`DIGRAPH6_HEADER`].
+///
+/// # Returns
+///
+/// A tuple `(node_keys, edge_pairs)` with sequential keys `0..n`, in the format accepted
+/// by `build_graph`.
+///
+/// # Errors
+///
+/// Returns a [`Graph6Error`] if the `&` prefix is missing, the string contains bytes
+/// outside the printable range, or its length does not match the encoded node count.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::from_digraph6;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::GraphViewTrait;
+///
+/// // A directed triangle 0 -> 1 -> 2 -> 0
+/// let (nodes, edges) = from_digraph6("&BP_").unwrap();
+/// let graph = build_graph::<Node, Edge>(nodes, edges);
+///
+/// assert!(graph.has_edge(0, 1));
+/// assert!(graph.has_edge(1, 2));
+/// assert!(graph.has_edge(2, 0));
+/// assert!(!graph.has_edge(1, 0));
+/// ```
+pub fn from_digraph6(input: &str) -> Result<GraphData, Graph6Error> {
+    let bytes = strip(input, DIGRAPH6_HEADER)?;
+    let bytes = match bytes.split_first() {
+        Some((b'&', rest)) if !rest.is_empty() => rest,
+        _ => return Err(Graph6Error::MissingDigraphPrefix),
+    };
+    let (n, offset) = decode_node_count(bytes)?;
+    let data = &bytes[offset..];
+
+    let bits = decode_bits(data, offset + 1, bit_count(n as u64 * n as u64)?)?;
+
+    let mut edges = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            if bits(i * n + j) {
+                edges.push((i as u32, j as u32));
+            }
+        }
+    }
+
+    Ok(((0..n as u32).collect(), edges))
+}
+
+/// Encodes a graph as a graph6 string.
+///
+/// Edges are treated as undirected: nodes `i` and `j` are adjacent if the graph contains
+/// `(i, j)`, `(j, i)` or both. graph6 cannot represent self-loops, so they are ignored;
+/// use [`to_digraph6`] to preserve edge directions and self-loops.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::{from_graph6, to_graph6};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (2, 1)]);
+/// let encoded = to_graph6(&graph);
+/// assert_eq!(encoded, "Bg");
+///
+/// let (_, edges) = from_graph6(&encoded).unwrap();
+/// assert_eq!(edges.len(), 4); // Both directions of each undirected edge
+/// ```
+pub fn to_graph6<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> String {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let n = graph.node_count();
+    let mut bits = vec![false; n * n.saturating_sub(1) / 2];
+    for edge in graph.get_edges() {
+        let (s, t) = (edge.source() as usize, edge.target() as usize);
+        if s != t {
+            let (i, j) = if s < t { (s, t) } else { (t, s) };
+            bits[j * (j - 1) / 2 + i] = true;
+        }
+    }
+
+    let mut out = encode_node_count(n);
+    encode_bits(&bits, &mut out);
+    String::from_utf8(out).expect("graph6 output is always ASCII")
+}
+
+/// Encodes a graph as a digraph6 string.
+///
+/// The adjacency matrix is written row by row, so edge directions and self-loops are
+/// preserved exactly.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::{from_digraph6, to_digraph6};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+/// let encoded = to_digraph6(&graph);
+/// assert_eq!(encoded, "&BP_");
+///
+/// let (nodes, mut edges) = from_digraph6(&encoded).unwrap();
+/// edges.sort();
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0)]);
+/// ```
+pub fn to_digraph6<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> String {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let n = graph.node_count();
+    let mut bits = vec![false; n * n];
+    for edge in graph.get_edges() {
+        bits[edge.source() as usize * n + edge.target() as usize] = true;
+    }
+
+    let mut out = vec![b'&'];
+    out.extend(encode_node_count(n));
+    encode_bits(&bits, &mut out);
+    String::from_utf8(out).expect("digraph6 output is always ASCII")
+}
+
+// Removes surrounding whitespace and the optional header, rejecting empty input
+fn strip<'a>(input: &'a str, header: &str) -> Result<&'a [u8], Graph6Error> {
+    let trimmed = input.trim();
+    let body = trimmed.strip_prefix(header).unwrap_or(trimmed);
+    if body.is_empty() {
+        return Err(Graph6Error::Empty);
+    }
+    Ok(body.as_bytes())
+}
+
+// Returns the decoded node count and the number of bytes it occupies
+fn decode_node_count(bytes: &[u8]) -> Result<(usize, usize), Graph6Error> {
+    let byte_at = |i: usize| -> Result<u64, Graph6Error> {
+        match bytes.get(i) {
+            Some(&b) if (BIAS..=MAX_PRINTABLE).contains(&b) => Ok((b - BIAS) as u64),
+            Some(&b) => Err(Graph6Error::InvalidByte {
+                position: i,
+                byte: b,
+            }),
+            None => Err(Graph6Error::InvalidNodeCount),
+        }
+    };
+
+    let first = byte_at(0)?;
+    let (n, used) = if first < 63 {
+        (first, 1)
+    } else if byte_at(1)? < 63 {
+        (
+            (1..4).try_fold(0, |acc, i| Ok((acc << 6) | byte_at(i)?))?,
+            4,
+        )
+    } else {
+        (
+            (2..8).try_fold(0, |acc, i| Ok((acc << 6) | byte_at(i)?))?,
+            8,
+        )
+    };
+
+    // Keys go up to n - 1, but ranges of keys are built as 0..n
+    if n > u32::MAX as u64 {
+        return Err(Graph6Error::InvalidNodeCount);
+    }
+    Ok((n as usize, used))
+}
+
+// Converts the number of adjacency bits implied by the node count, which may not fit in
+// memory on narrow targets
+fn bit_count(bits: u64) -> Result<usize, Graph6Error> {
+    usize::try_from(bits).map_err(|_| Graph6Error::InvalidNodeCount)
+}
+
+fn encode_node_count(n: usize) -> Vec<u8> {
+    let n = n as u64;
+    let sextets = |count: u32| {
+        (0..count)
+            .rev()
+            .map(move |i| ((n >> (6 * i)) & 63) as u8 + BIAS)
+    };
+
+    if n <= 62 {
+        vec![n as u8 + BIAS]
+    } else if n <= 258047 {
+        std::iter::once(MAX_PRINTABLE).chain(sextets(3)).collect()
+    } else {
+        [MAX_PRINTABLE, MAX_PRINTABLE]
+            .into_iter()
+            .chain(sextets(6))
+            .collect()
+    }
+}
+
+// Validates the data section and returns an accessor for its `bit_count` bits
+fn decode_bits(
+    data: &[u8],
+    offset: usize,
+    bit_count: usize,
+) -> Result<impl Fn(usize) -> bool + '_, Graph6Error> {
+    let expected = bit_count.div_ceil(6);
+    if data.len() != expected {
+        return Err(Graph6Error::InvalidLength {
+            expected,
+            found: data.len(),
+        });
+    }
+    if let Some(i) = data
+        .iter()
+        .position(|b| !(BIAS..=MAX_PRINTABLE).contains(b))
+    {
+        return Err(Graph6Error::InvalidByte {
+            position: offset + i,
+            byte: data[i],
+        });
+    }
+
+    Ok(move |k: usize| (data[k / 6] - BIAS) & (1 << (5 - k % 6)) != 0)
+}
+
+fn encode_bits(bits: &[bool], out: &mut Vec<u8>) {
+    for chunk in bits.chunks(6) {
+        let mut value = 0u8;
+        for (i, &bit) in chunk.iter().enumerate() {
+            if bit {
+                value |= 1 << (5 - i);
+            }
+        }
+        out.push(value + BIAS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    fn sorted(mut edges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+        edges.sort_unstable();
+        edges
+    }
+
+    #[test]
+    fn test_from_graph6_reference_example() {
+        // Example from the nauty formats documentation: edges 0-2, 0-4, 1-3, 3-4
+        let (nodes, edges) = from_graph6("DQc").unwrap();
+        assert_eq!(nodes, vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            sorted(edges),
+            vec![
+                (0, 2),
+                (0, 4),
+                (1, 3),
+                (2, 0),
+                (3, 1),
+                (3, 4),
+                (4, 0),
+                (4, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_digraph6_reference_example() {
+        // Example from the nauty formats documentation: edges 0->2, 0->4, 3->1, 3->4
+        let (nodes, edges) = from_digraph6("&DI?AO?").unwrap();
+        assert_eq!(nodes, vec![0, 1, 2, 3, 4]);
+        assert_eq!(sorted(edges), vec![(0, 2), (0, 4), (3, 1), (3, 4)]);
+    }
+
+    #[test]
+    fn test_headers_and_whitespace() {
+        assert_eq!(from_graph6(">>graph6<<DQc\n"), from_graph6("DQc"));
+        assert_eq!(
+            from_digraph6(">>digraph6<<&DI?AO?\n"),
+            from_digraph6("&DI?AO?")
+        );
+    }
+
+    #[test]
+    fn test_empty_graphs() {
+        assert_eq!(from_graph6("?").unwrap(), (vec![], vec![]));
+        assert_eq!(from_digraph6("&?").unwrap(), (vec![], vec![]));
+
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        assert_eq!(to_graph6(&graph), "?");
+        assert_eq!(to_digraph6(&graph), "&?");
+    }
+
+    #[test]
+    fn test_to_graph6_ignores_direction_and_self_loops() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4],
+            vec![(0, 2), (4, 0), (1, 3), (3, 4), (4, 3), (1, 1)],
+        );
+        assert_eq!(to_graph6(&graph), "DQc");
+    }
+
+    #[test]
+    fn test_to_digraph6_reference_example() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3, 4], vec![(0, 2), (0, 4), (3, 1), (3, 4)]);
+        assert_eq!(to_digraph6(&graph), "&DI?AO?");
+    }
+
+    #[test]
+    fn test_digraph6_self_loops_round_trip() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 0), (0, 1), (1, 1)]);
+        let (nodes, edges) = from_digraph6(&to_digraph6(&graph)).unwrap();
+        assert_eq!(nodes, vec![0, 1]);
+        assert_eq!(sorted(edges), vec![(0, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_round_trip_all_digraphs_on_three_nodes() {
+        let pairs: Vec<(u32, u32)> = (0..3).flat_map(|i| (0..3).map(move |j| (i, j))).collect();
+
+        for mask in 0u32..(1 << pairs.len()) {
+            let edges: Vec<(u32, u32)> = pairs
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| mask & (1 << bit) != 0)
+                .map(|(_, &pair)| pair)
+                .collect();
+            let graph = build_graph::<Node, Edge>(vec![0, 1, 2], edges.clone());
+
+            let (nodes, decoded) = from_digraph6(&to_digraph6(&graph)).unwrap();
+            assert_eq!(nodes, vec![0, 1, 2]);
+            assert_eq!(sorted(decoded), sorted(edges), "Mismatch for mask {}", mask);
+        }
+    }
+
+    #[test]
+    fn test_large_node_counts() {
+        for n in [62, 63, 100, 258047, 258048] {
+            let encoded = encode_node_count(n);
+            assert_eq!(decode_node_count(&encoded).unwrap(), (n, encoded.len()));
+        }
+
+        let (nodes, edges) = generate_path(100);
+        let graph = build_graph::<Node, Edge>(nodes.clone(), edges.clone());
+        let (decoded_nodes, decoded_edges) = from_digraph6(&to_digraph6(&graph)).unwrap();
+        assert_eq!(decoded_nodes, nodes);
+        assert_eq!(sorted(decoded_edges), sorted(edges));
+    }
+
+    fn generate_path(n: u32) -> (Vec<u32>, Vec<(u32, u32)>) {
+        ((0..n).collect(), (1..n).map(|i| (i - 1, i)).collect())
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(from_graph6(""), Err(Graph6Error::Empty));
+        assert_eq!(from_graph6(">>graph6<<"), Err(Graph6Error::Empty));
+        assert_eq!(from_digraph6("DQc"), Err(Graph6Error::MissingDigraphPrefix));
+        assert_eq!(
+            from_graph6("DQ"),
+            Err(Graph6Error::InvalidLength {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            from_graph6("DQ!"),
+            Err(Graph6Error::InvalidByte {
+                position: 2,
+                byte: b'!'
+            })
+        );
+        assert_eq!(from_graph6("~"), Err(Graph6Error::InvalidNodeCount));
+    }
+
+    #[test]
+    fn test_decode_huge_node_counts() {
+        // 2^36 - 1 and 2^32 nodes do not fit u32 keys
+        assert_eq!(from_graph6("~~~~~~~~"), Err(Graph6Error::InvalidNodeCount));
+        assert_eq!(
+            from_digraph6("&~~C?????"),
+            Err(Graph6Error::InvalidNodeCount)
+        );
+
+        // u32::MAX nodes fit, but the input holds none of their adjacency bits
+        assert!(matches!(
+            from_graph6("~~B~~~~~"),
+            Err(Graph6Error::InvalidLength { found: 0, .. })
+        ));
+        assert!(matches!(
+            from_digraph6("&~~B~~~~~"),
+            Err(Graph6Error::InvalidLength { found: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_encode_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![1, 3, 5], vec![(1, 3)]);
+        assert_panics_with!(to_graph6(&graph), INVALID_KEY_SEQUENCE);
+        assert_panics_with!(to_digraph6(&graph), INVALID_KEY_SEQUENCE);
+    }
+}
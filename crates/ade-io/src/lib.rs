@@ -0,0 +1,17 @@
+pub mod csr;
+pub mod dot;
+pub mod edge_list;
+pub mod graph6;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+
+pub use dot::{from_dot, to_dot, DotConfig, DotError};
+pub use edge_list::{
+    read_adjacency_list, read_edge_list, write_adjacency_list, write_edge_list, EdgeListError,
+};
+pub use graph6::{from_digraph6, from_graph6, to_digraph6, to_graph6, Graph6Error};
+
+/// Node keys and edge pairs, the plain data format accepted by `build_graph`.
+pub type GraphData = (Vec<u32>, Vec<(u32, u32)>);
@@ -0,0 +1,114 @@
+use crate::csr::CsrView;
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// A read-only graph memory-mapped from a CSR file.
+///
+/// Opening the file maps it into memory and validates its header and offsets; nothing is
+/// deserialized or copied. Pages are loaded lazily by the operating system as the graph is
+/// queried, and are shared between processes mapping the same file.
+///
+/// Files are written with [`write_csr`](crate::csr::write_csr). All queries go through the
+/// borrowed [`CsrView`] returned by [`view`](Self::view).
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::csr::write_csr;
+/// use ade_io::mmap::MmapGraph;
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+/// use std::fs::File;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2), (2, 1)]);
+/// let path = std::env::temp_dir().join("ade_io_mmap_doc.csr");
+/// write_csr(&graph, &mut File::create(&path).unwrap()).unwrap();
+///
+/// let mapped = MmapGraph::open(&path).unwrap();
+/// let view = mapped.view();
+/// assert_eq!(view.node_count(), 3);
+/// assert_eq!(view.successors(0), &[1, 2]);
+/// assert_eq!(view.predecessors(1), &[0, 2]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MmapGraph {
+    mmap: Mmap,
+}
+
+impl MmapGraph {
+    /// Memory-maps a CSR file.
+    ///
+    /// The file must not be modified while it is mapped: changes made by other processes
+    /// become visible through the view, and truncating the file makes reads fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, or an error of kind
+    /// [`io::ErrorKind::InvalidData`] wrapping a [`CsrError`](crate::csr::CsrError) if
+    /// the content is not valid CSR data.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        // SAFETY: the map is read-only, and the documented contract requires the file not
+        // to be modified while mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        CsrView::from_bytes(&mmap)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Self { mmap })
+    }
+
+    /// Returns a view for querying the mapped graph.
+    pub fn view(&self) -> CsrView<'_> {
+        CsrView::parse(&self.mmap).expect("CSR data is validated when the file is opened")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csr::{write_csr, CsrError};
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use std::io::Write;
+
+    #[test]
+    fn test_open() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0), (3, 3)]);
+        let path =
+            std::env::temp_dir().join(format!("ade_io_mmap_open_{}.csr", std::process::id()));
+        write_csr(&graph, &mut File::create(&path).unwrap()).unwrap();
+
+        let mapped = MmapGraph::open(&path).unwrap();
+        let view = mapped.view();
+        assert_eq!(view.edge_count(), 4);
+        assert!(view.has_edge(2, 0));
+        assert_eq!(
+            view.to_graph_data(),
+            (vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0), (3, 3)])
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_invalid_file() {
+        let path =
+            std::env::temp_dir().join(format!("ade_io_mmap_invalid_{}.csr", std::process::id()));
+        File::create(&path)
+            .unwrap()
+            .write_all(b"not a graph file")
+            .unwrap();
+
+        let err = MmapGraph::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err.into_inner().unwrap().downcast::<CsrError>().unwrap();
+        assert_eq!(*inner, CsrError::InvalidMagic);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,274 @@
+use crate::GraphData;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use prost::Message;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A directed graph with optional attributes and layout information (`ade.graph.v1.Graph`).
+///
+/// The message types of this module mirror `proto/graph.proto` field by field, so graphs
+/// encoded here can be decoded by code generated from that schema in any other language.
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoGraph {
+    #[prost(message, repeated, tag = "1")]
+    pub nodes: Vec<ProtoNode>,
+    #[prost(message, repeated, tag = "2")]
+    pub edges: Vec<ProtoEdge>,
+    #[prost(map = "string, string", tag = "3")]
+    pub attributes: HashMap<String, String>,
+}
+
+/// A node identified by a unique key (`ade.graph.v1.Node`).
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoNode {
+    #[prost(uint32, tag = "1")]
+    pub key: u32,
+    #[prost(map = "string, string", tag = "2")]
+    pub attributes: HashMap<String, String>,
+    /// Position of the node center, when the graph has been laid out.
+    #[prost(message, optional, tag = "3")]
+    pub position: Option<ProtoPoint>,
+}
+
+/// A directed edge from `source` to `target` (`ade.graph.v1.Edge`).
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoEdge {
+    #[prost(uint32, tag = "1")]
+    pub source: u32,
+    #[prost(uint32, tag = "2")]
+    pub target: u32,
+    #[prost(map = "string, string", tag = "3")]
+    pub attributes: HashMap<String, String>,
+    /// Polyline followed by the edge, when the graph has been laid out.
+    #[prost(message, repeated, tag = "4")]
+    pub points: Vec<ProtoPoint>,
+}
+
+/// A point in layout coordinates (`ade.graph.v1.Point`).
+#[derive(Clone, Copy, PartialEq, Message)]
+pub struct ProtoPoint {
+    #[prost(double, tag = "1")]
+    pub x: f64,
+    #[prost(double, tag = "2")]
+    pub y: f64,
+}
+
+/// Errors returned when decoding protobuf graphs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProtobufError {
+    /// The bytes are not a valid `ade.graph.v1.Graph` message.
+    Decode(prost::DecodeError),
+    /// Two nodes share the same key.
+    DuplicateNode(u32),
+    /// An edge references a node that is not in the message.
+    MissingNode(u32),
+}
+
+impl fmt::Display for ProtobufError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtobufError::Decode(err) => write!(f, "Invalid protobuf message: {}", err),
+            ProtobufError::DuplicateNode(key) => write!(f, "Duplicate node {}", key),
+            ProtobufError::MissingNode(key) => write!(f, "Node {} not found", key),
+        }
+    }
+}
+
+impl std::error::Error for ProtobufError {}
+
+/// Converts a graph into its protobuf message, without attributes or layout.
+///
+/// Nodes and edges are sorted by key so that equal graphs always produce equal messages.
+/// Attributes and positions can be filled in on the returned message before encoding it.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::protobuf::{to_proto, ProtoPoint};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+/// let mut message = to_proto(&graph);
+///
+/// message.nodes[0].attributes.insert("label".into(), "start".into());
+/// message.nodes[0].position = Some(ProtoPoint { x: 10.0, y: 20.0 });
+/// assert_eq!(message.edges[0].source, 0);
+/// assert_eq!(message.edges[0].target, 1);
+/// ```
+pub fn to_proto<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> ProtoGraph {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let mut edge_keys: Vec<(u32, u32)> = graph.get_edges().map(|e| e.key()).collect();
+    edge_keys.sort_unstable();
+
+    ProtoGraph {
+        nodes: keys
+            .into_iter()
+            .map(|key| ProtoNode {
+                key,
+                ..Default::default()
+            })
+            .collect(),
+        edges: edge_keys
+            .into_iter()
+            .map(|(source, target)| ProtoEdge {
+                source,
+                target,
+                ..Default::default()
+            })
+            .collect(),
+        attributes: HashMap::new(),
+    }
+}
+
+/// Extracts node keys and edge pairs from a protobuf message.
+///
+/// # Errors
+///
+/// Returns [`ProtobufError::DuplicateNode`] if two nodes share a key and
+/// [`ProtobufError::MissingNode`] if an edge references an unknown node.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::protobuf::{from_proto, to_proto};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![3, 7], vec![(7, 3)]);
+/// let (nodes, edges) = from_proto(&to_proto(&graph)).unwrap();
+/// assert_eq!(nodes, vec![3, 7]);
+/// assert_eq!(edges, vec![(7, 3)]);
+/// ```
+pub fn from_proto(message: &ProtoGraph) -> Result<GraphData, ProtobufError> {
+    let mut seen = HashSet::with_capacity(message.nodes.len());
+    let mut nodes = Vec::with_capacity(message.nodes.len());
+    for node in &message.nodes {
+        if !seen.insert(node.key) {
+            return Err(ProtobufError::DuplicateNode(node.key));
+        }
+        nodes.push(node.key);
+    }
+
+    let mut edges = Vec::with_capacity(message.edges.len());
+    for edge in &message.edges {
+        for key in [edge.source, edge.target] {
+            if !seen.contains(&key) {
+                return Err(ProtobufError::MissingNode(key));
+            }
+        }
+        edges.push((edge.source, edge.target));
+    }
+
+    Ok((nodes, edges))
+}
+
+/// Encodes a graph as protobuf bytes.
+///
+/// Equivalent to `to_proto(graph).encode_to_vec()`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_io::protobuf::{decode_graph, encode_graph};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let bytes = encode_graph(&graph);
+///
+/// let (nodes, edges) = decode_graph(&bytes).unwrap();
+/// assert_eq!(nodes, vec![0, 1, 2]);
+/// assert_eq!(edges, vec![(0, 1), (1, 2)]);
+/// ```
+pub fn encode_graph<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Vec<u8> {
+    to_proto(graph).encode_to_vec()
+}
+
+/// Decodes protobuf bytes into node keys and edge pairs.
+///
+/// # Errors
+///
+/// Returns [`ProtobufError::Decode`] if the bytes are not a valid message, or any error
+/// reported by [`from_proto`].
+pub fn decode_graph(bytes: &[u8]) -> Result<GraphData, ProtobufError> {
+    let message = ProtoGraph::decode(bytes).map_err(ProtobufError::Decode)?;
+    from_proto(&message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_round_trip() {
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0), (3, 3)]);
+        let (nodes, edges) = decode_graph(&encode_graph(&graph)).unwrap();
+        assert_eq!(nodes, vec![0, 1, 2, 3]);
+        assert_eq!(edges, vec![(0, 1), (1, 2), (2, 0), (3, 3)]);
+    }
+
+    #[test]
+    fn test_attributes_and_layout_round_trip() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        let mut message = to_proto(&graph);
+        message.attributes.insert("name".into(), "example".into());
+        message.nodes[1].position = Some(ProtoPoint { x: 1.5, y: -2.0 });
+        message.edges[0]
+            .attributes
+            .insert("weight".into(), "3".into());
+        message.edges[0].points = vec![
+            ProtoPoint { x: 0.0, y: 0.0 },
+            ProtoPoint { x: 1.5, y: -2.0 },
+        ];
+
+        let decoded = ProtoGraph::decode(message.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.nodes[0].position, None);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        assert!(encode_graph(&graph).is_empty());
+        assert_eq!(decode_graph(&[]).unwrap(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        let duplicate = ProtoGraph {
+            nodes: vec![
+                ProtoNode {
+                    key: 1,
+                    ..Default::default()
+                };
+                2
+            ],
+            ..Default::default()
+        };
+        assert_eq!(from_proto(&duplicate), Err(ProtobufError::DuplicateNode(1)));
+
+        let dangling = ProtoGraph {
+            nodes: vec![ProtoNode {
+                key: 1,
+                ..Default::default()
+            }],
+            edges: vec![ProtoEdge {
+                source: 1,
+                target: 2,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(from_proto(&dangling), Err(ProtobufError::MissingNode(2)));
+
+        assert!(matches!(
+            decode_graph(&[0xff]),
+            Err(ProtobufError::Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::subgraph::matcher;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Tests whether two graphs are isomorphic: whether their nodes can be matched one to one so
+/// that two nodes are joined by an edge exactly when their matches are, in the same
+/// direction.
+///
+/// This is [`is_isomorphic_with`] with every node and every edge compatible.
+///
+/// # Examples
+///
+/// ```
+/// use ade_isomorphism::is_isomorphic;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let path = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let relabelled = build_graph::<Node, Edge>(vec![7, 8, 9], vec![(9, 7), (7, 8)]);
+/// let star = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2)]);
+///
+/// assert!(is_isomorphic(&path, &relabelled));
+/// assert!(!is_isomorphic(&path, &star));
+/// ```
+pub fn is_isomorphic<N1, E1, N2, E2>(
+    g1: &impl GraphViewTrait<N1, E1>,
+    g2: &impl GraphViewTrait<N2, E2>,
+) -> bool
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    is_isomorphic_with(g1, g2, |_, _| true, |_, _| true)
+}
+
+/// Tests whether two graphs are isomorphic, with the given compatibility of nodes and edges.
+///
+/// A node of `g1` can only be matched to a node of `g2` for which `node_match` returns
+/// `true`, and an edge only to an edge for which `edge_match` does, so the isomorphism can
+/// be required to preserve the data of nodes and edges.
+///
+/// Node and edge counts are compared first; then the VF2 search of
+/// [`subgraph_matches_with`](crate::subgraph_matches_with) looks for a match of the whole
+/// of `g1` onto the whole of `g2`, pruned by requiring equal degrees, and stops at the first.
+/// To get the matching itself, take the first item of `subgraph_matches_with` on two graphs
+/// of the same size. Keys need not be sequential.
+///
+/// # Examples
+///
+/// ```
+/// use ade_isomorphism::is_isomorphic_with;
+/// use ade_graph::implementations::{Node, WeightedEdge};
+/// use ade_graph::build::build_graph_weighted;
+/// use ade_graph::WeightedEdgeTrait;
+///
+/// let g1 = build_graph_weighted::<Node, WeightedEdge<u32>>(vec![0, 1, 2], vec![(0, 1, 5), (1, 2, 3)]);
+/// let g2 = build_graph_weighted::<Node, WeightedEdge<u32>>(vec![0, 1, 2], vec![(2, 0, 5), (0, 1, 3)]);
+/// let g3 = build_graph_weighted::<Node, WeightedEdge<u32>>(vec![0, 1, 2], vec![(2, 0, 3), (0, 1, 5)]);
+///
+/// let same_weight = |e1: &WeightedEdge<u32>, e2: &WeightedEdge<u32>| e1.weight() == e2.weight();
+/// assert!(is_isomorphic_with(&g1, &g2, |_, _| true, same_weight));
+/// assert!(!is_isomorphic_with(&g1, &g3, |_, _| true, same_weight));
+/// ```
+pub fn is_isomorphic_with<N1, E1, N2, E2>(
+    g1: &impl GraphViewTrait<N1, E1>,
+    g2: &impl GraphViewTrait<N2, E2>,
+    node_match: impl Fn(&N1, &N2) -> bool,
+    edge_match: impl Fn(&E1, &E2) -> bool,
+) -> bool
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    if g1.node_count() != g2.node_count() || g1.edge_count() != g2.edge_count() {
+        return false;
+    }
+    matcher(g1, g2, node_match, edge_match, true)
+        .next_match()
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{DataNode, Edge, Graph, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use ade_traits::DataNodeTrait;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_same_degrees_but_not_isomorphic() {
+        // Two disjoint directed triangles against a directed hexagon
+        let triangles = build_graph::<Node, Edge>(
+            (0..6).collect(),
+            vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)],
+        );
+        let hexagon = build_graph::<Node, Edge>(
+            (0..6).collect(),
+            vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)],
+        );
+        assert!(!is_isomorphic(&triangles, &hexagon));
+        assert!(is_isomorphic(&hexagon, &hexagon));
+    }
+
+    #[test]
+    fn test_node_data_must_match() {
+        let labelled = |labels: [char; 3]| {
+            Graph::<DataNode<char>, Edge>::new(
+                (0..3)
+                    .map(|key| DataNode::with_data(key, labels[key as usize]))
+                    .collect(),
+                vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 0)],
+            )
+        };
+        let same_label = |a: &DataNode<char>, b: &DataNode<char>| a.data() == b.data();
+
+        assert!(is_isomorphic_with(
+            &labelled(['x', 'y', 'z']),
+            &labelled(['z', 'x', 'y']),
+            same_label,
+            |_, _| true,
+        ));
+
+        // Reflecting the triangle would reverse its edges
+        assert!(!is_isomorphic_with(
+            &labelled(['x', 'y', 'z']),
+            &labelled(['x', 'z', 'y']),
+            same_label,
+            |_, _| true,
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn test_relabelled_graphs_are_isomorphic(
+            graph in arb_graph::<Node, Edge>(10, 30),
+            seed in any::<u64>()
+        ) {
+            // Relabel the keys by a permutation derived from the seed, shifted by 100
+            let keys: Vec<u32> = graph.get_node_keys().collect();
+            let mut shuffled = keys.clone();
+            shuffled.sort_by_key(|&key| (key as u64 + 1).wrapping_mul(seed | 1).rotate_left(17));
+            let relabel: BTreeMap<u32, u32> =
+                keys.iter().zip(&shuffled).map(|(&a, &b)| (a, b + 100)).collect();
+            let edges: Vec<(u32, u32)> = graph
+                .get_edges()
+                .map(|edge| (relabel[&edge.source()], relabel[&edge.target()]))
+                .collect();
+            let relabelled = build_graph::<Node, Edge>(relabel.values().copied().collect(), edges);
+
+            prop_assert!(is_isomorphic(&graph, &relabelled));
+
+            // Removing an edge breaks the isomorphism
+            if let Some(edge) = graph.get_edges().next() {
+                let mut smaller = graph.clone();
+                smaller.remove_edge(edge.source(), edge.target());
+                prop_assert!(!is_isomorphic(&smaller, &relabelled));
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod isomorphism;
+pub mod subgraph;
+mod vf2;
+
+pub use isomorphism::{is_isomorphic, is_isomorphic_with};
+pub use subgraph::{subgraph_matches, subgraph_matches_with, SubgraphMatches};
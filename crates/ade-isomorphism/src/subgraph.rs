@@ -0,0 +1,252 @@
+use crate::vf2::{Adjacency, Matcher};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::BTreeMap;
+
+/// An iterator over the occurrences of a pattern graph in a target graph, created by
+/// [`subgraph_matches`] and [`subgraph_matches_with`].
+///
+/// Each item maps every pattern key to a distinct target key, in ascending order of pattern
+/// key. The occurrences are found lazily, so taking the first one only explores as much of
+/// the target as needed.
+pub struct SubgraphMatches<'a> {
+    matcher: Matcher<'a>,
+}
+
+impl Iterator for SubgraphMatches<'_> {
+    type Item = BTreeMap<u32, u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.matcher.next_match()
+    }
+}
+
+/// Finds the occurrences of `pattern` as an induced subgraph of `target`.
+///
+/// An occurrence maps the nodes of the pattern to distinct nodes of the target such that
+/// two pattern nodes are joined by an edge exactly when their images are joined by an edge
+/// in the same direction, self-loops included. Edges of the target between nodes outside
+/// the occurrence do not matter. A pattern with automorphisms, such as a cycle, occurs once
+/// for each of them on the same target nodes.
+///
+/// This is [`subgraph_matches_with`] with every node and every edge compatible.
+///
+/// # Examples
+///
+/// ```
+/// use ade_isomorphism::subgraph_matches;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // A pattern a → b → c, without the shortcut a → c
+/// let pattern = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let target = build_graph::<Node, Edge>(
+///     vec![10, 11, 12, 13],
+///     vec![(10, 11), (11, 12), (10, 12), (12, 13)],
+/// );
+///
+/// let occurrences: Vec<Vec<u32>> = subgraph_matches(&pattern, &target)
+///     .map(|mapping| mapping.into_values().collect())
+///     .collect();
+/// assert_eq!(occurrences.len(), 2);
+/// assert!(occurrences.contains(&vec![10, 12, 13]));
+/// assert!(occurrences.contains(&vec![11, 12, 13]));
+/// ```
+pub fn subgraph_matches<'a, N1, E1, N2, E2>(
+    pattern: &'a impl GraphViewTrait<N1, E1>,
+    target: &'a impl GraphViewTrait<N2, E2>,
+) -> SubgraphMatches<'a>
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    subgraph_matches_with(pattern, target, |_, _| true, |_, _| true)
+}
+
+/// Finds the occurrences of `pattern` as an induced subgraph of `target`, with the given
+/// compatibility of nodes and edges.
+///
+/// A pattern node can only be mapped to a target node for which `node_match` returns
+/// `true`, and a pattern edge only to a target edge for which `edge_match` does, so
+/// patterns can constrain the data of the nodes and edges they match. Occurrences are as in
+/// [`subgraph_matches`].
+///
+/// The search follows the VF2 algorithm of Cordella et al.: pattern nodes are mapped one at
+/// a time, in an order where each node is joined to an earlier one, to the target neighbors
+/// of the image of that earlier node. A pair is rejected if it breaks an edge with the
+/// mapped nodes, or if the unmapped neighbors of the pattern node, split into those next to
+/// a mapped node and the rest, outnumber the same neighbors of the target node. The worst case is exponential, but the pruning keeps common patterns fast.
+/// Keys need not be sequential.
+///
+/// # Examples
+///
+/// ```
+/// use ade_isomorphism::subgraph_matches_with;
+/// use ade_graph::implementations::{DataNode, Edge, Graph};
+/// use ade_graph::DataNodeTrait;
+///
+/// // Find an edge from a node labelled "a" to a node labelled "b"
+/// let pattern = Graph::<DataNode<&str>, Edge>::new(
+///     vec![DataNode::with_data(0, "a"), DataNode::with_data(1, "b")],
+///     vec![Edge::new(0, 1)],
+/// );
+/// let target = Graph::<DataNode<&str>, Edge>::new(
+///     vec![DataNode::with_data(5, "b"), DataNode::with_data(6, "a"), DataNode::with_data(7, "b")],
+///     vec![Edge::new(5, 6), Edge::new(6, 7)],
+/// );
+///
+/// let mut matches = subgraph_matches_with(&pattern, &target, |p, t| p.data() == t.data(), |_, _| true);
+/// assert_eq!(matches.next().unwrap().into_iter().collect::<Vec<_>>(), vec![(0, 6), (1, 7)]);
+/// assert!(matches.next().is_none());
+/// ```
+pub fn subgraph_matches_with<'a, N1, E1, N2, E2>(
+    pattern: &'a impl GraphViewTrait<N1, E1>,
+    target: &'a impl GraphViewTrait<N2, E2>,
+    node_match: impl Fn(&N1, &N2) -> bool + 'a,
+    edge_match: impl Fn(&E1, &E2) -> bool + 'a,
+) -> SubgraphMatches<'a>
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    SubgraphMatches {
+        matcher: matcher(pattern, target, node_match, edge_match, false),
+    }
+}
+
+// Builds the search, with callbacks on node and edge indices
+pub(crate) fn matcher<'a, N1, E1, N2, E2>(
+    pattern: &'a impl GraphViewTrait<N1, E1>,
+    target: &'a impl GraphViewTrait<N2, E2>,
+    node_match: impl Fn(&N1, &N2) -> bool + 'a,
+    edge_match: impl Fn(&E1, &E2) -> bool + 'a,
+    complete: bool,
+) -> Matcher<'a>
+where
+    N1: NodeTrait,
+    E1: EdgeTrait,
+    N2: NodeTrait,
+    E2: EdgeTrait,
+{
+    let (pattern_adjacency, target_adjacency) = (Adjacency::new(pattern), Adjacency::new(target));
+    let (pattern_keys, target_keys) = (
+        pattern_adjacency.keys.clone(),
+        target_adjacency.keys.clone(),
+    );
+    let (edge_pattern_keys, edge_target_keys) = (pattern_keys.clone(), target_keys.clone());
+    Matcher::new(
+        pattern_adjacency,
+        target_adjacency,
+        Box::new(move |p, t| {
+            node_match(
+                pattern.get_node(pattern_keys[p]),
+                target.get_node(target_keys[t]),
+            )
+        }),
+        Box::new(move |(p, q), (t, u)| {
+            edge_match(
+                pattern.get_edge(edge_pattern_keys[p], edge_pattern_keys[q]),
+                target.get_edge(edge_target_keys[t], edge_target_keys[u]),
+            )
+        }),
+        complete,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    // Every injective map from the pattern keys to the target keys that preserves edges and
+    // non-edges
+    fn naive_matches(
+        pattern: &impl GraphViewTrait<Node, Edge>,
+        target: &impl GraphViewTrait<Node, Edge>,
+    ) -> BTreeSet<Vec<(u32, u32)>> {
+        let mut pattern_keys: Vec<u32> = pattern.get_node_keys().collect();
+        pattern_keys.sort_unstable();
+        let target_keys: Vec<u32> = target.get_node_keys().collect();
+
+        let mut found = BTreeSet::new();
+        let mut images: Vec<Vec<u32>> = vec![Vec::new()];
+        for _ in &pattern_keys {
+            images = images
+                .into_iter()
+                .flat_map(|image| {
+                    target_keys
+                        .iter()
+                        .filter(|key| !image.contains(key))
+                        .map(|&key| [image.clone(), vec![key]].concat())
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+        for image in images {
+            let preserved = pattern_keys.iter().zip(&image).all(|(&p, &t)| {
+                pattern_keys
+                    .iter()
+                    .zip(&image)
+                    .all(|(&q, &u)| pattern.has_edge(p, q) == target.has_edge(t, u))
+            });
+            if preserved {
+                found.insert(pattern_keys.iter().copied().zip(image).collect());
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_triangles_in_complete_graph() {
+        // The induced subgraphs on three nodes of the complete graph with both directions
+        // have six edges, so a directed triangle never occurs there; it occurs three times,
+        // once per rotation, on the triangle of a graph
+        let triangle = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        let mut edges = Vec::new();
+        for u in 0..4 {
+            for v in 0..4 {
+                if u != v {
+                    edges.push((u, v));
+                }
+            }
+        }
+        let complete = build_graph::<Node, Edge>((0..4).collect(), edges);
+        assert_eq!(subgraph_matches(&triangle, &complete).count(), 0);
+
+        let cycle =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 0), (2, 3)]);
+        assert_eq!(subgraph_matches(&triangle, &cycle).count(), 3);
+    }
+
+    #[test]
+    fn test_self_loops_and_empty_pattern() {
+        let pattern = build_graph::<Node, Edge>(vec![0], vec![(0, 0)]);
+        let target = build_graph::<Node, Edge>(vec![4, 5, 6], vec![(4, 5), (5, 5)]);
+        let found: Vec<BTreeMap<u32, u32>> = subgraph_matches(&pattern, &target).collect();
+        assert_eq!(found, vec![BTreeMap::from([(0, 5)])]);
+
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+        assert_eq!(subgraph_matches(&empty, &target).count(), 1);
+        assert_eq!(subgraph_matches(&target, &empty).count(), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_naive_search(
+            pattern in arb_graph::<Node, Edge>(3, 5),
+            target in arb_graph::<Node, Edge>(6, 14)
+        ) {
+            let found: BTreeSet<Vec<(u32, u32)>> = subgraph_matches(&pattern, &target)
+                .map(|mapping| mapping.into_iter().collect())
+                .collect();
+            prop_assert_eq!(found, naive_matches(&pattern, &target));
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+// Marks a node not mapped yet
+const NONE: usize = usize::MAX;
+
+// Compatibility of a pattern node with a target node, by index
+pub(crate) type NodeCheck<'a> = Box<dyn Fn(usize, usize) -> bool + 'a>;
+// Compatibility of a pattern edge with a target edge, by the indices of their endpoints
+pub(crate) type EdgeCheck<'a> = Box<dyn Fn((usize, usize), (usize, usize)) -> bool + 'a>;
+
+// The sorted keys of a graph and its edges by index into them
+pub(crate) struct Adjacency {
+    pub(crate) keys: Vec<u32>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl Adjacency {
+    pub(crate) fn new<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Self {
+        let mut keys: Vec<u32> = graph.get_node_keys().collect();
+        keys.sort_unstable();
+        let index: HashMap<u32, usize> =
+            keys.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+
+        let mut successors = vec![Vec::new(); keys.len()];
+        let mut predecessors = vec![Vec::new(); keys.len()];
+        let mut edges = HashSet::new();
+        for edge in graph.get_edges() {
+            let (source, target) = (index[&edge.source()], index[&edge.target()]);
+            successors[source].push(target);
+            predecessors[target].push(source);
+            edges.insert((source, target));
+        }
+        Self {
+            keys,
+            successors,
+            predecessors,
+            edges,
+        }
+    }
+}
+
+// One level of the search: the target candidates for a pattern node, and the next to try
+struct Frame {
+    candidates: Vec<usize>,
+    next: usize,
+}
+
+// The state of a VF2 search for the induced subgraphs of the target isomorphic to the
+// pattern, extending a partial mapping one pattern node at a time and backtracking from an
+// explicit stack. As in Cordella et al., the unmapped nodes of each graph are split into
+// the terminal sets, `T_out` for the successors and `T_in` for the predecessors of mapped
+// nodes, and the rest, and a pair is only added if every part of the neighborhood of the
+// pattern node fits in the same part of the neighborhood of the target node
+pub(crate) struct Matcher<'a> {
+    pattern: Adjacency,
+    target: Adjacency,
+    node_check: NodeCheck<'a>,
+    edge_check: EdgeCheck<'a>,
+    // Whether every node of the target must be matched, which requires equal degrees
+    complete: bool,
+    order: Vec<usize>,
+    mapping: Vec<usize>,
+    inverse: Vec<usize>,
+    // The terminal sets of both graphs: the number of mapped predecessors and successors of
+    // every node, a node being in `T_out` or `T_in` if it is unmapped and the count is not 0
+    pattern_terminal: Terminal,
+    target_terminal: Terminal,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+impl<'a> Matcher<'a> {
+    pub(crate) fn new(
+        pattern: Adjacency,
+        target: Adjacency,
+        node_check: NodeCheck<'a>,
+        edge_check: EdgeCheck<'a>,
+        complete: bool,
+    ) -> Self {
+        let order = matching_order(&pattern);
+        let mapping = vec![NONE; pattern.keys.len()];
+        let inverse = vec![NONE; target.keys.len()];
+        let pattern_terminal = Terminal::new(pattern.keys.len());
+        let target_terminal = Terminal::new(target.keys.len());
+        Self {
+            pattern,
+            target,
+            node_check,
+            edge_check,
+            complete,
+            order,
+            mapping,
+            inverse,
+            pattern_terminal,
+            target_terminal,
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    // Finds the next complete mapping, as pattern keys to target keys
+    pub(crate) fn next_match(&mut self) -> Option<BTreeMap<u32, u32>> {
+        if !self.started {
+            self.started = true;
+            if self.pattern.keys.len() > self.target.keys.len() {
+                return None;
+            }
+            if self.order.is_empty() {
+                return Some(BTreeMap::new());
+            }
+            let frame = self.frame(0);
+            self.stack.push(frame);
+        }
+
+        while !self.stack.is_empty() {
+            let depth = self.stack.len() - 1;
+            let p = self.order[depth];
+            if self.mapping[p] != NONE {
+                let t = self.mapping[p];
+                self.inverse[t] = NONE;
+                self.mapping[p] = NONE;
+                self.pattern_terminal.remove(&self.pattern, p);
+                self.target_terminal.remove(&self.target, t);
+            }
+
+            let mut chosen = None;
+            while let Some(&t) = self.stack[depth].candidates.get(self.stack[depth].next) {
+                self.stack[depth].next += 1;
+                if self.inverse[t] == NONE && self.is_feasible(p, t) {
+                    chosen = Some(t);
+                    break;
+                }
+            }
+            let Some(t) = chosen else {
+                self.stack.pop();
+                continue;
+            };
+            self.mapping[p] = t;
+            self.inverse[t] = p;
+            self.pattern_terminal.add(&self.pattern, p);
+            self.target_terminal.add(&self.target, t);
+
+            if depth + 1 == self.order.len() {
+                return Some(
+                    self.mapping
+                        .iter()
+                        .enumerate()
+                        .map(|(p, &t)| (self.pattern.keys[p], self.target.keys[t]))
+                        .collect(),
+                );
+            }
+            let frame = self.frame(depth + 1);
+            self.stack.push(frame);
+        }
+        None
+    }
+
+    // The candidates for the pattern node at `depth`: the neighbors of the image of an
+    // already mapped neighbor, in the matching direction, or every target node
+    fn frame(&self, depth: usize) -> Frame {
+        let p = self.order[depth];
+        let mapped_successor = self.pattern.successors[p]
+            .iter()
+            .find(|&&q| self.mapping[q] != NONE);
+        let mapped_predecessor = self.pattern.predecessors[p]
+            .iter()
+            .find(|&&q| self.mapping[q] != NONE);
+        let candidates = match (mapped_successor, mapped_predecessor) {
+            (Some(&q), _) => self.target.predecessors[self.mapping[q]].clone(),
+            (None, Some(&q)) => self.target.successors[self.mapping[q]].clone(),
+            (None, None) => (0..self.target.keys.len()).collect(),
+        };
+        Frame {
+            candidates,
+            next: 0,
+        }
+    }
+
+    // Whether mapping p to t keeps the mapping an isomorphism between the induced subgraphs
+    fn is_feasible(&self, p: usize, t: usize) -> bool {
+        let (pattern, target) = (&self.pattern, &self.target);
+        let degrees = (
+            pattern.successors[p].len(),
+            pattern.predecessors[p].len(),
+            target.successors[t].len(),
+            target.predecessors[t].len(),
+        );
+        let degrees_fit = if self.complete {
+            degrees.0 == degrees.2 && degrees.1 == degrees.3
+        } else {
+            degrees.0 <= degrees.2 && degrees.1 <= degrees.3
+        };
+        if !degrees_fit || !(self.node_check)(p, t) {
+            return false;
+        }
+
+        // Self-loops
+        let loops = (
+            pattern.edges.contains(&(p, p)),
+            target.edges.contains(&(t, t)),
+        );
+        if loops.0 != loops.1 || (loops.0 && !(self.edge_check)((p, p), (t, t))) {
+            return false;
+        }
+
+        // Every edge between p and a mapped pattern node has its image, and back
+        for &q in &pattern.successors[p] {
+            let u = self.mapping[q];
+            if q != p && u != NONE && !self.edges_match((p, q), (t, u)) {
+                return false;
+            }
+        }
+        for &q in &pattern.predecessors[p] {
+            let u = self.mapping[q];
+            if q != p && u != NONE && !self.edges_match((q, p), (u, t)) {
+                return false;
+            }
+        }
+        let preimage_exists = |u: usize, edge: (usize, usize)| {
+            u == t || self.inverse[u] == NONE || pattern.edges.contains(&edge)
+        };
+        let mapped_fit = target.successors[t]
+            .iter()
+            .all(|&u| preimage_exists(u, (p, self.inverse[u])))
+            && target.predecessors[t]
+                .iter()
+                .all(|&u| preimage_exists(u, (self.inverse[u], p)));
+        if !mapped_fit {
+            return false;
+        }
+
+        // Look-ahead: the unmapped neighbors of p in `T_in`, in `T_out` and in neither
+        // can only be mapped to unmapped neighbors of t in the same sets
+        let pattern_counts = self.pattern_terminal.counts(pattern, &self.mapping, p);
+        let target_counts = self.target_terminal.counts(target, &self.inverse, t);
+        if self.complete {
+            pattern_counts == target_counts
+        } else {
+            pattern_counts
+                .iter()
+                .zip(&target_counts)
+                .all(|(pattern_count, target_count)| pattern_count <= target_count)
+        }
+    }
+
+    fn edges_match(&self, pattern_edge: (usize, usize), target_edge: (usize, usize)) -> bool {
+        self.target.edges.contains(&target_edge) && (self.edge_check)(pattern_edge, target_edge)
+    }
+}
+
+// The terminal sets of one graph, as the number of mapped neighbors of every node
+struct Terminal {
+    // Mapped predecessors, non-zero for the nodes of `T_out`
+    out: Vec<usize>,
+    // Mapped successors, non-zero for the nodes of `T_in`
+    into: Vec<usize>,
+}
+
+impl Terminal {
+    fn new(n: usize) -> Self {
+        Self {
+            out: vec![0; n],
+            into: vec![0; n],
+        }
+    }
+
+    fn add(&mut self, graph: &Adjacency, node: usize) {
+        for &successor in &graph.successors[node] {
+            self.out[successor] += 1;
+        }
+        for &predecessor in &graph.predecessors[node] {
+            self.into[predecessor] += 1;
+        }
+    }
+
+    fn remove(&mut self, graph: &Adjacency, node: usize) {
+        for &successor in &graph.successors[node] {
+            self.out[successor] -= 1;
+        }
+        for &predecessor in &graph.predecessors[node] {
+            self.into[predecessor] -= 1;
+        }
+    }
+
+    // The unmapped successors of `node` in `T_in`, in `T_out` and in neither, then the same
+    // for its predecessors
+    fn counts(&self, graph: &Adjacency, mapping: &[usize], node: usize) -> [usize; 6] {
+        let mut counts = [0; 6];
+        let neighbors = [&graph.successors[node], &graph.predecessors[node]];
+        for (side, neighbors) in neighbors.into_iter().enumerate() {
+            for &other in neighbors {
+                if other == node || mapping[other] != NONE {
+                    continue;
+                }
+                let (into, out) = (self.into[other] > 0, self.out[other] > 0);
+                counts[3 * side] += into as usize;
+                counts[3 * side + 1] += out as usize;
+                counts[3 * side + 2] += (!into && !out) as usize;
+            }
+        }
+        counts
+    }
+}
+
+// Orders the pattern nodes so that, within a connected component, every node but the first
+// is joined to an earlier one; each component starts from its node of largest degree
+fn matching_order(pattern: &Adjacency) -> Vec<usize> {
+    let n = pattern.keys.len();
+    let degree = |p: usize| pattern.successors[p].len() + pattern.predecessors[p].len();
+    let mut roots: Vec<usize> = (0..n).collect();
+    roots.sort_by_key(|&p| std::cmp::Reverse(degree(p)));
+
+    let mut seen = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut queue = VecDeque::new();
+    for root in roots {
+        if seen[root] {
+            continue;
+        }
+        seen[root] = true;
+        queue.push_back(root);
+        while let Some(p) = queue.pop_front() {
+            order.push(p);
+            for &q in pattern.successors[p].iter().chain(&pattern.predecessors[p]) {
+                if !seen[q] {
+                    seen[q] = true;
+                    queue.push_back(q);
+                }
+            }
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_look_ahead_rejects_pair() {
+        // Once 0 is mapped to 0, the successor 3 of pattern node 1 is in neither terminal
+        // set, while the successor 2 of target node 1 is in `T_out`: the degrees of 1 and 1
+        // fit, but the pair cannot be extended
+        let pattern = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (1, 3)]);
+        let target = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (1, 2)]);
+        let mut matcher = Matcher::new(
+            Adjacency::new(&pattern),
+            Adjacency::new(&target),
+            Box::new(|_, _| true),
+            Box::new(|_, _| true),
+            false,
+        );
+        assert!(matcher.is_feasible(0, 0));
+        matcher.mapping[0] = 0;
+        matcher.inverse[0] = 0;
+        matcher.pattern_terminal.add(&matcher.pattern, 0);
+        matcher.target_terminal.add(&matcher.target, 0);
+
+        assert_eq!(
+            matcher
+                .pattern_terminal
+                .counts(&matcher.pattern, &matcher.mapping, 1),
+            [0, 0, 1, 0, 0, 0]
+        );
+        assert!(!matcher.is_feasible(1, 1));
+        assert!(matcher.next_match().is_none());
+    }
+}
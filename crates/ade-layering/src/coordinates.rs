@@ -0,0 +1,384 @@
+use crate::layer_positions;
+use crate::ordering::adjacent_layer_edges;
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Assigns a horizontal coordinate to each node of a layered graph with the Brandes–Köpf
+/// algorithm, the third phase of a Sugiyama-style layout.
+///
+/// `order` lists the keys of each layer from left to right, as returned by
+/// [`order_layers`](crate::order_layers), and `widths` gives the width of each node,
+/// indexed by key. Nodes are aligned in vertical blocks with their median neighbors in the
+/// adjacent layers, and blocks are packed as tightly as the widths and `node_separation`
+/// allow. This is done four times, aligning towards the upper or lower layer and from the
+/// left or from the right, and each node gets the average of its two median coordinates,
+/// so that the layout is balanced and parents are centered over their children.
+///
+/// Edges between two dummy nodes, as told by `is_dummy`, form the inner segments of long
+/// edges: they are kept vertical whenever possible, and take precedence over crossing
+/// edges. Only edges between adjacent layers are taken into account.
+///
+/// The result gives the center of each node, indexed by key. The left border of the
+/// leftmost node is at 0, and consecutive nodes of a layer are at least `node_separation`
+/// apart. The assignment runs in `O(n + m)` time per alignment.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the keys of the graph are not sequential or are
+/// not the keys of `order`, each appearing once, and if `widths` does not have one entry
+/// per node.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::horizontal_coordinates;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 is the parent of 1 and 2
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (0, 2)]);
+/// let xs = horizontal_coordinates(&graph, &[vec![0], vec![1, 2]], &[10.0; 3], 10.0, |_| false);
+///
+/// assert_eq!(xs, vec![15.0, 5.0, 25.0]);
+/// ```
+pub fn horizontal_coordinates<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    order: &[Vec<u32>],
+    widths: &[f64],
+    node_separation: f64,
+    is_dummy: impl Fn(u32) -> bool,
+) -> Vec<f64> {
+    let positions = layer_positions(order);
+    let n = positions.len();
+    if n != graph.node_count() || !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    if widths.len() != n {
+        panic!(
+            "Expected a width for each of the {} nodes, got {}",
+            n,
+            widths.len()
+        );
+    }
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut layers = vec![0; n];
+    for (layer, keys) in order.iter().enumerate() {
+        for &key in keys {
+            layers[key as usize] = layer;
+        }
+    }
+    let mut above: Vec<Vec<u32>> = vec![Vec::new(); n];
+    let mut below: Vec<Vec<u32>> = vec![Vec::new(); n];
+    for (upper, lower) in adjacent_layer_edges(graph, &layers) {
+        below[upper as usize].push(lower);
+        above[lower as usize].push(upper);
+    }
+    let conflicts = type1_conflicts(order, &positions, &above, &is_dummy);
+
+    // Upper-left, upper-right, lower-left and lower-right alignments, independent of each
+    // other, so they run on the rayon thread pool with the `parallel` feature
+    let align = |(upward, from_left): (bool, bool)| {
+        let mut layering = order.to_vec();
+        if !upward {
+            layering.reverse();
+        }
+        if !from_left {
+            layering.iter_mut().for_each(|layer| layer.reverse());
+        }
+        let neighbors = if upward { &above } else { &below };
+        let root = vertical_alignment(&layering, neighbors, &conflicts);
+        let mut xs = horizontal_compaction(&layering, &root, widths, node_separation);
+        if !from_left {
+            xs.iter_mut().for_each(|x| *x = -*x);
+        }
+        xs
+    };
+    let directions = [(true, true), (true, false), (false, true), (false, false)];
+    #[cfg(feature = "parallel")]
+    let mut alignments: Vec<Vec<f64>> = directions.into_par_iter().map(align).collect();
+    #[cfg(not(feature = "parallel"))]
+    let mut alignments: Vec<Vec<f64>> = directions.into_iter().map(align).collect();
+
+    // Align the others to the narrowest alignment, by their left or right side
+    let extent = |xs: &[f64]| {
+        let left = (0..n)
+            .map(|v| xs[v] - widths[v] / 2.0)
+            .fold(f64::INFINITY, f64::min);
+        let right = (0..n)
+            .map(|v| xs[v] + widths[v] / 2.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        right - left
+    };
+    let narrowest = (0..4)
+        .min_by(|&a, &b| extent(&alignments[a]).total_cmp(&extent(&alignments[b])))
+        .unwrap();
+    let (min, max) = bounds(&alignments[narrowest]);
+    for (i, xs) in alignments.iter_mut().enumerate() {
+        let delta = if i % 2 == 0 {
+            min - bounds(xs).0
+        } else {
+            max - bounds(xs).1
+        };
+        xs.iter_mut().for_each(|x| *x += delta);
+    }
+
+    let mut xs: Vec<f64> = (0..n)
+        .map(|v| {
+            let mut candidates = [
+                alignments[0][v],
+                alignments[1][v],
+                alignments[2][v],
+                alignments[3][v],
+            ];
+            candidates.sort_by(f64::total_cmp);
+            (candidates[1] + candidates[2]) / 2.0
+        })
+        .collect();
+    let left = (0..n)
+        .map(|v| xs[v] - widths[v] / 2.0)
+        .fold(f64::INFINITY, f64::min);
+    xs.iter_mut().for_each(|x| *x -= left);
+    xs
+}
+
+fn bounds(xs: &[f64]) -> (f64, f64) {
+    xs.iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+            (min.min(x), max.max(x))
+        })
+}
+
+// Marks the edges that cross an inner segment, an edge between two dummy nodes, as
+// (upper, lower) pairs. Such edges are not used for alignment, so that inner segments
+// can stay vertical.
+fn type1_conflicts(
+    order: &[Vec<u32>],
+    positions: &[usize],
+    above: &[Vec<u32>],
+    is_dummy: &impl Fn(u32) -> bool,
+) -> HashSet<(u32, u32)> {
+    let mut conflicts = HashSet::new();
+    for pair in order.windows(2) {
+        let (previous, layer) = (&pair[0], &pair[1]);
+        let (mut k0, mut scan_start) = (0, 0);
+        for (i, &v) in layer.iter().enumerate() {
+            let inner = if is_dummy(v) {
+                above[v as usize].iter().find(|&&u| is_dummy(u))
+            } else {
+                None
+            };
+            let k1 = inner.map_or(previous.len(), |&u| positions[u as usize]);
+            if inner.is_none() && i + 1 < layer.len() {
+                continue;
+            }
+            for &scanned in &layer[scan_start..=i] {
+                for &u in &above[scanned as usize] {
+                    let position = positions[u as usize];
+                    if (position < k0 || k1 < position) && !(is_dummy(u) && is_dummy(scanned)) {
+                        conflicts.insert((u, scanned));
+                    }
+                }
+            }
+            scan_start = i + 1;
+            k0 = k1;
+        }
+    }
+    conflicts
+}
+
+// Aligns each node with one of its median neighbors in the previous layer, unless the
+// edge would cross an alignment already made in this layer or a marked conflict. Returns
+// the root of the block of each node, its topmost node in the sweep direction.
+fn vertical_alignment(
+    layering: &[Vec<u32>],
+    neighbors: &[Vec<u32>],
+    conflicts: &HashSet<(u32, u32)>,
+) -> Vec<u32> {
+    let n = neighbors.len();
+    let mut root: Vec<u32> = (0..n as u32).collect();
+    let mut align: Vec<u32> = (0..n as u32).collect();
+    let mut position = vec![0; n];
+    for layer in layering {
+        for (i, &v) in layer.iter().enumerate() {
+            position[v as usize] = i;
+        }
+    }
+    let has_conflict = |a: u32, b: u32| conflicts.contains(&(a, b)) || conflicts.contains(&(b, a));
+
+    for layer in layering {
+        let mut previous: Option<usize> = None;
+        for &v in layer {
+            let mut ws = neighbors[v as usize].clone();
+            if ws.is_empty() {
+                continue;
+            }
+            ws.sort_by_key(|&w| position[w as usize]);
+            for &w in &ws[(ws.len() - 1) / 2..=ws.len() / 2] {
+                let (vi, wi) = (v as usize, w as usize);
+                if align[vi] == v
+                    && previous.is_none_or(|p| p < position[wi])
+                    && !has_conflict(v, w)
+                {
+                    align[wi] = v;
+                    root[vi] = root[wi];
+                    align[vi] = root[vi];
+                    previous = Some(position[wi]);
+                }
+            }
+        }
+    }
+    root
+}
+
+// Places the blocks from left to right: each block as far left as the blocks on its left
+// allow, then as far right as the blocks on its right allow, to close the gaps
+fn horizontal_compaction(
+    layering: &[Vec<u32>],
+    root: &[u32],
+    widths: &[f64],
+    node_separation: f64,
+) -> Vec<f64> {
+    let n = root.len();
+    let mut separations: HashMap<(u32, u32), f64> = HashMap::new();
+    for layer in layering {
+        for pair in layer.windows(2) {
+            let (u, v) = (pair[0], pair[1]);
+            let separation = widths[u as usize] / 2.0 + node_separation + widths[v as usize] / 2.0;
+            let entry = separations
+                .entry((root[u as usize], root[v as usize]))
+                .or_insert(0.0);
+            *entry = entry.max(separation);
+        }
+    }
+
+    let mut successors: Vec<Vec<(u32, f64)>> = vec![Vec::new(); n];
+    let mut predecessors: Vec<Vec<(u32, f64)>> = vec![Vec::new(); n];
+    let mut in_degrees = vec![0; n];
+    for (&(u, v), &separation) in &separations {
+        successors[u as usize].push((v, separation));
+        predecessors[v as usize].push((u, separation));
+        in_degrees[v as usize] += 1;
+    }
+
+    // The block graph is acyclic, as blocks never cross
+    let mut order: Vec<u32> = (0..n as u32)
+        .filter(|&v| root[v as usize] == v && in_degrees[v as usize] == 0)
+        .collect();
+    let mut i = 0;
+    while i < order.len() {
+        for &(w, _) in &successors[order[i] as usize] {
+            in_degrees[w as usize] -= 1;
+            if in_degrees[w as usize] == 0 {
+                order.push(w);
+            }
+        }
+        i += 1;
+    }
+
+    let mut xs = vec![0.0; n];
+    for &v in &order {
+        xs[v as usize] = predecessors[v as usize]
+            .iter()
+            .map(|&(u, separation)| xs[u as usize] + separation)
+            .fold(0.0, f64::max);
+    }
+    for &v in order.iter().rev() {
+        let limit = successors[v as usize]
+            .iter()
+            .map(|&(w, separation)| xs[w as usize] - separation)
+            .fold(f64::INFINITY, f64::min);
+        if limit.is_finite() {
+            xs[v as usize] = f64::max(xs[v as usize], limit);
+        }
+    }
+
+    (0..n).map(|v| xs[root[v] as usize]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layer_assignment, order_layers, OrderingHeuristic};
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_separation_and_order() {
+        for seed in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(30, 60, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let layers = layer_assignment(&graph);
+            let order = order_layers(&graph, &layers, OrderingHeuristic::Median, 4);
+            let widths: Vec<f64> = (0..30).map(|v| 5.0 + (v % 4) as f64 * 10.0).collect();
+
+            let xs = horizontal_coordinates(&graph, &order, &widths, 8.0, |_| false);
+            for layer in &order {
+                for pair in layer.windows(2) {
+                    let (u, v) = (pair[0] as usize, pair[1] as usize);
+                    let gap = xs[v] - xs[u] - widths[u] / 2.0 - widths[v] / 2.0;
+                    assert!(gap >= 8.0 - 1e-9, "nodes {} and {} overlap", u, v);
+                }
+            }
+            let left = (0..30)
+                .map(|v| xs[v] - widths[v] / 2.0)
+                .fold(f64::INFINITY, f64::min);
+            assert!(left.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_chains_are_straight() {
+        // Two parallel chains and a node attached to the middle of the first
+        let graph = build_graph::<Node, Edge>(
+            (0..7).collect(),
+            vec![(0, 1), (1, 2), (3, 4), (4, 5), (1, 6)],
+        );
+        let order = vec![vec![0, 3], vec![1, 4], vec![2, 6, 5]];
+        let xs = horizontal_coordinates(&graph, &order, &[10.0; 7], 10.0, |_| false);
+        assert_eq!(xs[3], xs[4]);
+        assert_eq!(xs[4], xs[5]);
+        assert_eq!(xs[0], xs[1]);
+    }
+
+    #[test]
+    fn test_inner_segments_stay_vertical() {
+        // 1 and 3 are dummy nodes of the long edge 0 → 5, and the edge 4 → 6 crosses it
+        let graph = build_graph::<Node, Edge>(
+            (0..7).collect(),
+            vec![(0, 1), (1, 3), (3, 5), (2, 4), (4, 6)],
+        );
+        let order = [vec![0, 2], vec![1, 4], vec![6, 3], vec![5]];
+        let xs = horizontal_coordinates(&graph, &order, &[10.0; 7], 10.0, |v| v == 1 || v == 3);
+        assert_eq!(xs[1], xs[3]);
+
+        // Without dummy nodes, 3 is aligned with 4 instead
+        let xs = horizontal_coordinates(&graph, &order, &[10.0; 7], 10.0, |_| false);
+        assert_ne!(xs[1], xs[3]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_panics_with!(
+            horizontal_coordinates(&graph, &[vec![0], vec![1]], &[1.0], 1.0, |_| false),
+            "Expected a width for each of the 2 nodes, got 1"
+        );
+        assert_panics_with!(
+            horizontal_coordinates(&graph, &[vec![0]], &[1.0], 1.0, |_| false),
+            INVALID_KEY_SEQUENCE
+        );
+    }
+}
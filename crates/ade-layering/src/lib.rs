@@ -0,0 +1,12 @@
+pub mod coordinates;
+pub mod longest_path;
+pub mod network_simplex;
+pub mod ordering;
+
+pub use coordinates::horizontal_coordinates;
+pub use longest_path::layer_assignment;
+pub use network_simplex::rank_network_simplex;
+pub use ordering::{
+    crossing_count, layer_positions, order_layers, order_layers_with_progress, OrderingHeuristic,
+    ORDERING_PHASE,
+};
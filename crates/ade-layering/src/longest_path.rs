@@ -0,0 +1,104 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_topological_sort::topological_sort_kahn;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Assigns each node of a directed acyclic graph to a layer, using the longest path from
+/// the sources.
+///
+/// Sources are on layer 0 and every other node is one layer below its lowest predecessor,
+/// so each edge points from a layer to a strictly higher one. The number of layers is the
+/// minimum possible, the length of the longest path plus one, at the cost of wide bottom
+/// layers. This is the first phase of a Sugiyama-style layout. The result is indexed by
+/// node key, so the layer of node `key` is `layers[key as usize]`, ready to be stored with
+/// `HierarchyNode::set_layer`. The assignment runs in `O(m + n log n)` time.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and if
+/// the graph contains a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::layer_assignment;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 → 1 → 2 and 0 → 2, 3 is isolated
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (0, 2)]);
+/// assert_eq!(layer_assignment(&graph), vec![0, 1, 2, 0]);
+/// ```
+pub fn layer_assignment<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<usize> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let order = topological_sort_kahn(graph).unwrap_or_else(|error| panic!("{}", error));
+    let mut layers = vec![0; graph.node_count()];
+    for key in order {
+        let next = layers[key as usize] + 1;
+        for successor in graph.get_successors_keys(key) {
+            let layer = &mut layers[successor as usize];
+            *layer = (*layer).max(next);
+        }
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_longest_path_layers() {
+        for seed in 0..20 {
+            // Keeping only forward edges makes the graph acyclic
+            let (nodes, edges) = generate_random_graph_data(40, 120, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let layers = layer_assignment(&graph);
+
+            for key in graph.get_node_keys() {
+                let layer = layers[key as usize];
+                let mut predecessors = graph.get_predecessors_keys(key).peekable();
+                if predecessors.peek().is_none() {
+                    assert_eq!(layer, 0);
+                }
+                // Each node sits right below its lowest predecessor
+                let lowest = predecessors.map(|p| layers[p as usize]).max();
+                assert!(lowest.is_none_or(|lowest| layer == lowest + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n: u32 = 100_000;
+        let graph =
+            build_graph::<Node, Edge>((0..n).collect(), (1..n).map(|i| (i, i - 1)).collect());
+        let layers = layer_assignment(&graph);
+        assert!(layers.into_iter().eq((0..n as usize).rev()));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 1)]);
+        assert_panics_with!(
+            layer_assignment(&graph),
+            "Graph contains a cycle: 1 → 2 → 1"
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(layer_assignment(&graph), INVALID_KEY_SEQUENCE);
+    }
+}
@@ -0,0 +1,471 @@
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_topological_sort::topological_sort_kahn;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Assigns a rank to each node of a directed acyclic graph with the network simplex
+/// algorithm, as Graphviz `dot` does.
+///
+/// Every edge `e` must span at least `edge_min_len_fn(e)` ranks, and among the rankings
+/// that respect these constraints the result minimizes the total weighted edge length,
+/// the sum over the edges of `edge_weight_fn(e) * (rank(target) - rank(source))`. Compared
+/// with [`layer_assignment`](crate::layer_assignment), this pulls sources towards their
+/// successors and keeps long edges short, so hierarchies are more compact and need fewer
+/// dummy nodes. Heavier edges are kept shorter.
+///
+/// The result is indexed by node key, and the smallest rank of each weakly connected
+/// component is 0. The algorithm starts from a feasible spanning tree of tight edges and
+/// exchanges tree edges until no cut value is negative; each exchange costs `O(n + m)`.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and if
+/// the graph contains a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::{layer_assignment, rank_network_simplex};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 → 1 → 2 → 3 and 4 → 3
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3, 4], vec![(0, 1), (1, 2), (2, 3), (4, 3)]);
+///
+/// // Longest-path layering leaves 4 on the top layer, three layers away from 3
+/// assert_eq!(layer_assignment(&graph), vec![0, 1, 2, 3, 0]);
+/// // Network simplex moves it right above 3
+/// assert_eq!(rank_network_simplex(&graph, |_| 1, |_| 1), vec![0, 1, 2, 3, 2]);
+/// ```
+pub fn rank_network_simplex<N, E>(
+    graph: &impl GraphViewTrait<N, E>,
+    edge_min_len_fn: impl Fn(&E) -> u32,
+    edge_weight_fn: impl Fn(&E) -> u32,
+) -> Vec<usize>
+where
+    N: NodeTrait,
+    E: EdgeTrait,
+{
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    let order = topological_sort_kahn(graph).unwrap_or_else(|error| panic!("{}", error));
+
+    let n = graph.node_count();
+    let mut simplex = Simplex {
+        edges: Vec::new(),
+        incident: vec![Vec::new(); n],
+        ranks: vec![0; n],
+        tree_edge: Vec::new(),
+        tree_adjacency: vec![Vec::new(); n],
+        parent: vec![None; n],
+        low: vec![0; n],
+        lim: vec![0; n],
+        cut_values: Vec::new(),
+        roots: Vec::new(),
+        preorder: Vec::with_capacity(n),
+        postorder: Vec::with_capacity(n),
+    };
+    // Edges in key order, so that ties between entering edges are broken the same way on
+    // every run, whatever the iteration order of the graph
+    let mut edges: Vec<&E> = graph.get_edges().collect();
+    edges.sort_by_key(|edge| (edge.source(), edge.target()));
+    for edge in edges {
+        let (source, target) = (edge.source() as usize, edge.target() as usize);
+        simplex.incident[source].push(simplex.edges.len());
+        simplex.incident[target].push(simplex.edges.len());
+        simplex.edges.push(SimplexEdge {
+            source,
+            target,
+            min_len: edge_min_len_fn(edge) as i64,
+            weight: edge_weight_fn(edge) as i64,
+        });
+    }
+    simplex.tree_edge = vec![false; simplex.edges.len()];
+    simplex.cut_values = vec![0; simplex.edges.len()];
+
+    // Initial feasible ranking: each node as close to the sources as its predecessors allow
+    for key in order {
+        let v = key as usize;
+        for &e in &simplex.incident[v] {
+            let edge = &simplex.edges[e];
+            if edge.source == v {
+                simplex.ranks[edge.target] =
+                    simplex.ranks[edge.target].max(simplex.ranks[v] + edge.min_len);
+            }
+        }
+    }
+
+    simplex.run();
+    simplex.normalized_ranks()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SimplexEdge {
+    source: usize,
+    target: usize,
+    min_len: i64,
+    weight: i64,
+}
+
+// State of the network simplex. Node indices are graph keys and edge indices are positions
+// in `edges`. The spanning forest has one tree per weakly connected component, rooted at
+// `roots`; `low` and `lim` number its nodes in postorder so that `x` is in the subtree of
+// `v` if and only if `low[v] <= lim[x] <= lim[v]`.
+struct Simplex {
+    edges: Vec<SimplexEdge>,
+    incident: Vec<Vec<usize>>,
+    ranks: Vec<i64>,
+    tree_edge: Vec<bool>,
+    tree_adjacency: Vec<Vec<usize>>,
+    // Tree edge from each node to its parent
+    parent: Vec<Option<usize>>,
+    low: Vec<usize>,
+    lim: Vec<usize>,
+    cut_values: Vec<i64>,
+    roots: Vec<usize>,
+    preorder: Vec<usize>,
+    postorder: Vec<usize>,
+}
+
+impl Simplex {
+    fn run(&mut self) {
+        self.feasible_tree();
+        self.init_low_lim();
+        self.init_cut_values();
+        while let Some(leaving) = self.leave_edge() {
+            let entering = self.enter_edge(leaving);
+            self.exchange(leaving, entering);
+        }
+    }
+
+    fn slack(&self, e: usize) -> i64 {
+        let edge = &self.edges[e];
+        self.ranks[edge.target] - self.ranks[edge.source] - edge.min_len
+    }
+
+    fn other(&self, e: usize, v: usize) -> usize {
+        let edge = &self.edges[e];
+        if edge.source == v {
+            edge.target
+        } else {
+            edge.source
+        }
+    }
+
+    fn set_tree_edge(&mut self, e: usize, in_tree: bool) {
+        let SimplexEdge { source, target, .. } = self.edges[e];
+        self.tree_edge[e] = in_tree;
+        for v in [source, target] {
+            if in_tree {
+                self.tree_adjacency[v].push(e);
+            } else {
+                self.tree_adjacency[v].retain(|&f| f != e);
+            }
+        }
+    }
+
+    // Builds a spanning forest of tight edges, shifting the ranks of the partial trees
+    // towards the closest node outside them until one of its edges becomes tight
+    fn feasible_tree(&mut self) {
+        let n = self.ranks.len();
+        let mut in_tree = vec![false; n];
+        for start in 0..n {
+            if in_tree[start] {
+                continue;
+            }
+            in_tree[start] = true;
+            self.roots.push(start);
+            let mut component = vec![start];
+            loop {
+                let mut stack = component.clone();
+                while let Some(v) = stack.pop() {
+                    for i in 0..self.incident[v].len() {
+                        let e = self.incident[v][i];
+                        let w = self.other(e, v);
+                        if !in_tree[w] && self.slack(e) == 0 {
+                            in_tree[w] = true;
+                            self.set_tree_edge(e, true);
+                            component.push(w);
+                            stack.push(w);
+                        }
+                    }
+                }
+
+                // Trees of the previous components have no edge leaving them
+                let closest = (0..self.edges.len())
+                    .filter(|&e| in_tree[self.edges[e].source] != in_tree[self.edges[e].target])
+                    .min_by_key(|&e| self.slack(e));
+                let Some(e) = closest else {
+                    break;
+                };
+                let delta = if in_tree[self.edges[e].source] {
+                    self.slack(e)
+                } else {
+                    -self.slack(e)
+                };
+                for &v in &component {
+                    self.ranks[v] += delta;
+                }
+            }
+        }
+    }
+
+    // Numbers the nodes of the forest and records its parents, preorder and postorder
+    fn init_low_lim(&mut self) {
+        self.preorder.clear();
+        self.postorder.clear();
+        let mut next_lim = 1;
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for i in 0..self.roots.len() {
+            let root = self.roots[i];
+            self.parent[root] = None;
+            self.low[root] = next_lim;
+            self.preorder.push(root);
+            stack.push((root, 0));
+            while let Some(&(v, i)) = stack.last() {
+                if i < self.tree_adjacency[v].len() {
+                    stack.last_mut().unwrap().1 += 1;
+                    let e = self.tree_adjacency[v][i];
+                    if self.parent[v] == Some(e) {
+                        continue;
+                    }
+                    let w = self.other(e, v);
+                    self.parent[w] = Some(e);
+                    self.low[w] = next_lim;
+                    self.preorder.push(w);
+                    stack.push((w, 0));
+                } else {
+                    self.lim[v] = next_lim;
+                    next_lim += 1;
+                    self.postorder.push(v);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    // Computes the cut value of each tree edge from the ones below it: the weight of the
+    // edges crossing the cut in the direction of the tree edge, minus the opposite ones
+    fn init_cut_values(&mut self) {
+        for i in 0..self.postorder.len() {
+            let child = self.postorder[i];
+            let Some(parent_edge) = self.parent[child] else {
+                continue;
+            };
+            let child_is_tail = self.edges[parent_edge].source == child;
+            let mut cut_value = self.edges[parent_edge].weight;
+            for &e in &self.incident[child] {
+                if e == parent_edge {
+                    continue;
+                }
+                let points_to_head = (self.edges[e].source == child) == child_is_tail;
+                let weight = self.edges[e].weight;
+                cut_value += if points_to_head { weight } else { -weight };
+                if self.tree_edge[e] {
+                    let below = self.cut_values[e];
+                    cut_value += if points_to_head { -below } else { below };
+                }
+            }
+            self.cut_values[parent_edge] = cut_value;
+        }
+    }
+
+    fn leave_edge(&self) -> Option<usize> {
+        (0..self.edges.len()).find(|&e| self.tree_edge[e] && self.cut_values[e] < 0)
+    }
+
+    // Finds the edge with the least slack going from the head component to the tail
+    // component that removing the leaving edge would create
+    fn enter_edge(&self, leaving: usize) -> usize {
+        let SimplexEdge { source, target, .. } = self.edges[leaving];
+        let (subtree, flip) = if self.lim[source] > self.lim[target] {
+            (target, true)
+        } else {
+            (source, false)
+        };
+        let in_subtree =
+            |x: usize| self.low[subtree] <= self.lim[x] && self.lim[x] <= self.lim[subtree];
+        (0..self.edges.len())
+            .filter(|&e| {
+                let edge = &self.edges[e];
+                flip == in_subtree(edge.source) && flip != in_subtree(edge.target)
+            })
+            .min_by_key(|&e| self.slack(e))
+            .expect("A tree edge with a negative cut value has a replacement")
+    }
+
+    fn exchange(&mut self, leaving: usize, entering: usize) {
+        self.set_tree_edge(leaving, false);
+        self.set_tree_edge(entering, true);
+        self.init_low_lim();
+        self.init_cut_values();
+
+        // Make the tree edges tight again, walking down from the roots
+        for i in 0..self.preorder.len() {
+            let v = self.preorder[i];
+            if let Some(e) = self.parent[v] {
+                let edge = self.edges[e];
+                self.ranks[v] = if edge.source == v {
+                    self.ranks[edge.target] - edge.min_len
+                } else {
+                    self.ranks[edge.source] + edge.min_len
+                };
+            }
+        }
+    }
+
+    // Shifts the ranks of each tree so that its smallest rank is 0
+    fn normalized_ranks(&self) -> Vec<usize> {
+        let mut minimum = vec![i64::MAX; self.ranks.len()];
+        let mut root_of = vec![0; self.ranks.len()];
+        for &v in &self.preorder {
+            root_of[v] = match self.parent[v] {
+                Some(e) => root_of[self.other(e, v)],
+                None => v,
+            };
+            minimum[root_of[v]] = minimum[root_of[v]].min(self.ranks[v]);
+        }
+        (0..self.ranks.len())
+            .map(|v| (self.ranks[v] - minimum[root_of[v]]) as usize)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_assignment;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Graph, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    fn cost(graph: &Graph<Node, Edge>, ranks: &[i64], weight: impl Fn(&Edge) -> u32) -> i64 {
+        graph
+            .get_edges()
+            .map(|e| weight(e) as i64 * (ranks[e.target() as usize] - ranks[e.source() as usize]))
+            .sum()
+    }
+
+    // Smallest cost over all the feasible rankings with ranks up to max_rank. An optimal
+    // ranking is spanned by tight edges, so it fits in (n - 1) times the largest min_len.
+    fn brute_force(
+        graph: &Graph<Node, Edge>,
+        min_len: impl Fn(&Edge) -> u32 + Copy,
+        weight: impl Fn(&Edge) -> u32 + Copy,
+        max_rank: i64,
+    ) -> i64 {
+        let n = graph.node_count();
+        let mut ranks = vec![0; n];
+        let mut best = i64::MAX;
+        loop {
+            let feasible = graph.get_edges().all(|e| {
+                ranks[e.target() as usize] - ranks[e.source() as usize] >= min_len(e) as i64
+            });
+            if feasible {
+                best = best.min(cost(graph, &ranks, weight));
+            }
+            // Next ranking, counting in base max_rank + 1
+            let Some(i) = (0..n).find(|&i| ranks[i] < max_rank) else {
+                return best;
+            };
+            ranks[i] += 1;
+            ranks[..i].iter_mut().for_each(|r| *r = 0);
+        }
+    }
+
+    #[test]
+    fn test_optimal_on_small_dags() {
+        let min_len = |e: &Edge| 1 + (e.source() + e.target()) % 2;
+        let weight = |e: &Edge| 1 + (e.source() * 3 + e.target()) % 4;
+        for seed in 0..30 {
+            let (nodes, edges) = generate_random_graph_data(5, 7, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let ranks = rank_network_simplex(&graph, min_len, weight);
+            let ranks: Vec<i64> = ranks.into_iter().map(|r| r as i64).collect();
+            for e in graph.get_edges() {
+                assert!(
+                    ranks[e.target() as usize] - ranks[e.source() as usize] >= min_len(e) as i64
+                );
+            }
+            assert_eq!(
+                cost(&graph, &ranks, weight),
+                brute_force(&graph, min_len, weight, 8)
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_worse_than_longest_path() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(60, 150, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let simplex: Vec<i64> = rank_network_simplex(&graph, |_| 1, |_| 1)
+                .into_iter()
+                .map(|r| r as i64)
+                .collect();
+            let longest: Vec<i64> = layer_assignment(&graph)
+                .into_iter()
+                .map(|r| r as i64)
+                .collect();
+            assert!(cost(&graph, &simplex, |_| 1) <= cost(&graph, &longest, |_| 1));
+        }
+    }
+
+    #[test]
+    fn test_components_and_weights() {
+        // Two components, each starting at rank 0
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3, 4], vec![(0, 1), (2, 3), (2, 4), (3, 4)]);
+        assert_eq!(
+            rank_network_simplex(&graph, |_| 1, |_| 1),
+            vec![0, 1, 0, 1, 2]
+        );
+
+        // A heavy edge is kept short at the expense of the light ones
+        let graph =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (0, 3), (3, 2)]);
+        let heavy = |e: &Edge| if e.key() == (0, 3) { 10 } else { 1 };
+        let ranks = rank_network_simplex(&graph, |e| if e.key() == (3, 2) { 2 } else { 1 }, heavy);
+        assert_eq!((ranks[0], ranks[2], ranks[3]), (0, 3, 1));
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(40, 100, seed);
+            let edges: Vec<(u32, u32)> = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let reversed: Vec<(u32, u32)> = edges.iter().rev().copied().collect();
+            let graph = build_graph::<Node, Edge>(nodes.clone(), edges);
+            let ranks = rank_network_simplex(&graph, |_| 1, |_| 1);
+
+            for _ in 0..5 {
+                let copy = build_graph::<Node, Edge>(nodes.clone(), reversed.clone());
+                assert_eq!(rank_network_simplex(&copy, |_| 1, |_| 1), ranks);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+        assert_panics_with!(
+            rank_network_simplex(&graph, |_| 1, |_| 1),
+            "Graph contains a cycle: 0 → 1 → 0"
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(
+            rank_network_simplex(&graph, |_| 1, |_| 1),
+            INVALID_KEY_SEQUENCE
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use ade_common::{Cancelled, NoProgress, PhaseProgress, Progress, INVALID_KEY_SEQUENCE};
+use ade_matching::bipartite_crossing_count;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Name of the phase reported by [`order_layers_with_progress`].
+pub const ORDERING_PHASE: &str = "ordering";
+
+/// The heuristic used by [`order_layers`] to place a node relative to its neighbors in the
+/// adjacent layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderingHeuristic {
+    /// The average position of the neighbors.
+    #[default]
+    Barycenter,
+    /// The weighted median position of the neighbors, as in Graphviz `dot`, which is less
+    /// sensitive to a few distant neighbors.
+    Median,
+}
+
+/// Orders the nodes within each layer to reduce the number of edge crossings, the second
+/// phase of a Sugiyama-style layout.
+///
+/// `layers` gives the layer of each node, indexed by key, as returned by
+/// [`layer_assignment`](crate::layer_assignment). Starting from the nodes of each layer in
+/// ascending key order, each sweep reorders the layers from top to bottom, placing nodes
+/// by the positions of their neighbors in the layer above, and then from bottom to top,
+/// using the layer below. Nodes without neighbors in the fixed layer keep their position.
+/// After `sweeps` sweeps, the order with the fewest crossings seen is returned.
+///
+/// The result lists the keys of each layer from left to right. Only edges between adjacent
+/// layers are taken into account, so longer edges should be split with dummy nodes first.
+/// [`layer_positions`] turns the result into the position of each node in its layer, the
+/// value to store with `HierarchyNode::set_rank`.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and if
+/// `layers` does not have one entry per node.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::{crossing_count, layer_assignment, order_layers, OrderingHeuristic};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 → 3 and 1 → 2 cross when both layers are in key order
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 3), (1, 2)]);
+/// let layers = layer_assignment(&graph);
+/// assert_eq!(crossing_count(&graph, &[vec![0, 1], vec![2, 3]]), 1);
+///
+/// let order = order_layers(&graph, &layers, OrderingHeuristic::Barycenter, 4);
+/// assert_eq!(order, vec![vec![0, 1], vec![3, 2]]);
+/// assert_eq!(crossing_count(&graph, &order), 0);
+/// ```
+pub fn order_layers<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    layers: &[usize],
+    heuristic: OrderingHeuristic,
+    sweeps: usize,
+) -> Vec<Vec<u32>> {
+    match order_layers_with_progress(graph, layers, heuristic, sweeps, &mut NoProgress) {
+        Ok(order) => order,
+        Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+    }
+}
+
+/// Orders the nodes within each layer to reduce edge crossings, reporting progress and
+/// allowing cancellation.
+///
+/// Behaves like [`order_layers`], and reports to `progress` the fraction of the layer
+/// reorderings of all sweeps done so far under the phase [`ORDERING_PHASE`]. Cancellation
+/// is checked after every layer, which keeps a cancelled call short on large graphs.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if `progress` was cancelled before the last sweep completed.
+///
+/// # Panics
+///
+/// Panics as [`order_layers`] does.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::{layer_assignment, order_layers_with_progress, OrderingHeuristic};
+/// use ade_common::{CancellationToken, Cancelled};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 3), (1, 2)]);
+/// let layers = layer_assignment(&graph);
+///
+/// let mut last = 0.0;
+/// let mut progress = |_: &str, fraction: f64| last = fraction;
+/// let order = order_layers_with_progress(&graph, &layers, OrderingHeuristic::Median, 4, &mut progress)?;
+/// assert_eq!(order, vec![vec![0, 1], vec![3, 2]]);
+/// assert_eq!(last, 1.0);
+///
+/// let mut token = CancellationToken::new();
+/// token.cancel();
+/// let result = order_layers_with_progress(&graph, &layers, OrderingHeuristic::Median, 4, &mut token);
+/// assert_eq!(result, Err(Cancelled));
+/// # Ok::<(), Cancelled>(())
+/// ```
+pub fn order_layers_with_progress<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    layers: &[usize],
+    heuristic: OrderingHeuristic,
+    sweeps: usize,
+    progress: &mut impl Progress,
+) -> Result<Vec<Vec<u32>>, Cancelled> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    if layers.len() != graph.node_count() {
+        panic!(
+            "Expected a layer for each of the {} nodes, got {}",
+            graph.node_count(),
+            layers.len()
+        );
+    }
+
+    let layer_count = layers.iter().max().map_or(0, |&max| max + 1);
+    let mut order: Vec<Vec<u32>> = vec![Vec::new(); layer_count];
+    for key in 0..layers.len() as u32 {
+        order[layers[key as usize]].push(key);
+    }
+
+    // Neighbors of each node in the layer above and in the layer below
+    let mut above: Vec<Vec<u32>> = vec![Vec::new(); layers.len()];
+    let mut below: Vec<Vec<u32>> = vec![Vec::new(); layers.len()];
+    for (upper, lower) in adjacent_layer_edges(graph, layers) {
+        below[upper as usize].push(lower);
+        above[lower as usize].push(upper);
+    }
+
+    let mut positions = layer_positions(&order);
+    let mut best = order.clone();
+    let mut best_crossings = count_crossings(&order, &positions, &below);
+    let mut phase = PhaseProgress::new(ORDERING_PHASE, sweeps * 2 * layer_count.saturating_sub(1));
+    if progress.is_cancelled() {
+        return Err(Cancelled);
+    }
+    for _ in 0..sweeps {
+        if best_crossings == 0 {
+            break;
+        }
+        for layer in order.iter_mut().skip(1) {
+            reorder(layer, &mut positions, &above, heuristic);
+            phase.advance(1, progress)?;
+        }
+        for layer in order.iter_mut().rev().skip(1) {
+            reorder(layer, &mut positions, &below, heuristic);
+            phase.advance(1, progress)?;
+        }
+
+        let crossings = count_crossings(&order, &positions, &below);
+        if crossings < best_crossings {
+            best_crossings = crossings;
+            best.clone_from(&order);
+        }
+    }
+    phase.finish(progress);
+    Ok(best)
+}
+
+/// Returns the position of each node within its layer, indexed by key, for an order
+/// returned by [`order_layers`].
+///
+/// # Panics
+///
+/// Panics if the keys of `order` are not `0..n`, each appearing once.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::layer_positions;
+///
+/// assert_eq!(layer_positions(&[vec![1], vec![2, 0]]), vec![1, 0, 0]);
+/// ```
+pub fn layer_positions(order: &[Vec<u32>]) -> Vec<usize> {
+    let n = order.iter().map(Vec::len).sum();
+    let mut positions = vec![usize::MAX; n];
+    for (position, &key) in order.iter().flat_map(|layer| layer.iter().enumerate()) {
+        match positions.get_mut(key as usize) {
+            Some(slot) if *slot == usize::MAX => *slot = position,
+            _ => panic!("{}", INVALID_KEY_SEQUENCE),
+        }
+    }
+    positions
+}
+
+/// Counts the pairs of edges that cross when the layers are drawn in the given order.
+///
+/// `order` lists the keys of each layer from left to right, as returned by
+/// [`order_layers`]. Only edges between adjacent layers are counted. Counting runs in
+/// `O(m log n)` time.
+///
+/// # Panics
+///
+/// Panics if the keys of `order` are not the keys of the graph, each appearing once.
+///
+/// # Examples
+///
+/// ```
+/// use ade_layering::crossing_count;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 3), (1, 2)]);
+/// assert_eq!(crossing_count(&graph, &[vec![0, 1], vec![2, 3]]), 1);
+/// assert_eq!(crossing_count(&graph, &[vec![0, 1], vec![3, 2]]), 0);
+/// ```
+pub fn crossing_count<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    order: &[Vec<u32>],
+) -> usize {
+    let positions = layer_positions(order);
+    if positions.len() != graph.node_count() || !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let mut layers = vec![0; positions.len()];
+    for (layer, keys) in order.iter().enumerate() {
+        for &key in keys {
+            layers[key as usize] = layer;
+        }
+    }
+    let mut below: Vec<Vec<u32>> = vec![Vec::new(); positions.len()];
+    for (upper, lower) in adjacent_layer_edges(graph, &layers) {
+        below[upper as usize].push(lower);
+    }
+    count_crossings(order, &positions, &below)
+}
+
+// Edges between adjacent layers, as (upper, lower) pairs whatever their direction
+pub(crate) fn adjacent_layer_edges<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    layers: &[usize],
+) -> Vec<(u32, u32)> {
+    graph
+        .get_edges()
+        .filter_map(|edge| {
+            let (source, target) = (edge.source(), edge.target());
+            let (s, t) = (layers[source as usize], layers[target as usize]);
+            if t == s + 1 {
+                Some((source, target))
+            } else if s == t + 1 {
+                Some((target, source))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// Sorts the movable nodes of a layer by the position of their neighbors in the fixed
+// layer; nodes without neighbors there stay where they are
+fn reorder(
+    layer: &mut [u32],
+    positions: &mut [usize],
+    neighbors: &[Vec<u32>],
+    heuristic: OrderingHeuristic,
+) {
+    // The value of each node only reads the fixed layer, so the values of a layer are
+    // computed on the rayon thread pool with the `parallel` feature
+    let value = |&key: &u32| {
+        let mut fixed: Vec<usize> = neighbors[key as usize]
+            .iter()
+            .map(|&neighbor| positions[neighbor as usize])
+            .collect();
+        if fixed.is_empty() {
+            return None;
+        }
+        fixed.sort_unstable();
+        let value = match heuristic {
+            OrderingHeuristic::Barycenter => {
+                fixed.iter().sum::<usize>() as f64 / fixed.len() as f64
+            }
+            OrderingHeuristic::Median => weighted_median(&fixed),
+        };
+        Some((value, key))
+    };
+    #[cfg(feature = "parallel")]
+    let mut movable: Vec<(f64, u32)> = layer.par_iter().filter_map(value).collect();
+    #[cfg(not(feature = "parallel"))]
+    let mut movable: Vec<(f64, u32)> = layer.iter().filter_map(value).collect();
+    // The sort is stable, so ties keep their current relative order
+    movable.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut movable = movable.into_iter().map(|(_, key)| key);
+    for slot in layer.iter_mut() {
+        if !neighbors[*slot as usize].is_empty() {
+            *slot = movable.next().unwrap();
+        }
+    }
+    for (position, &key) in layer.iter().enumerate() {
+        positions[key as usize] = position;
+    }
+}
+
+// Median of sorted positions; with an even count, the two middle positions are weighted
+// towards the side where the neighbors are packed more tightly
+fn weighted_median(positions: &[usize]) -> f64 {
+    let m = positions.len() / 2;
+    if positions.len() % 2 == 1 {
+        return positions[m] as f64;
+    }
+    let (low, high) = (positions[m - 1] as f64, positions[m] as f64);
+    if positions.len() == 2 {
+        return (low + high) / 2.0;
+    }
+    let left = low - positions[0] as f64;
+    let right = positions[positions.len() - 1] as f64 - high;
+    if left + right == 0.0 {
+        (low + high) / 2.0
+    } else {
+        (low * right + high * left) / (left + right)
+    }
+}
+
+// Counts crossings between each pair of adjacent layers, drawn as a two-layer bipartite
+// graph; the pairs are independent, so they are counted on the rayon thread pool with the
+// `parallel` feature
+fn count_crossings(order: &[Vec<u32>], positions: &[usize], below: &[Vec<u32>]) -> usize {
+    #[cfg(feature = "parallel")]
+    let pairs = order.par_windows(2);
+    #[cfg(not(feature = "parallel"))]
+    let pairs = order.windows(2);
+    pairs
+        .map(|pair| {
+            let edges: Vec<(usize, usize)> = pair[0]
+                .iter()
+                .flat_map(|&key| {
+                    below[key as usize]
+                        .iter()
+                        .map(move |&target| (positions[key as usize], positions[target as usize]))
+                })
+                .collect();
+            bipartite_crossing_count(&edges)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layer_assignment;
+    use ade_common::{assert_panics_with, CancellationToken};
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    // Crossings counted pair by pair
+    fn naive_crossings(graph: &impl GraphViewTrait<Node, Edge>, order: &[Vec<u32>]) -> usize {
+        let positions = layer_positions(order);
+        let mut layers = vec![0; positions.len()];
+        for (layer, keys) in order.iter().enumerate() {
+            keys.iter().for_each(|&key| layers[key as usize] = layer);
+        }
+        let edges: Vec<(usize, usize)> = adjacent_layer_edges(graph, &layers)
+            .into_iter()
+            .map(|(u, v)| (u as usize, v as usize))
+            .collect();
+        let mut count = 0;
+        for (i, &(u1, v1)) in edges.iter().enumerate() {
+            for &(u2, v2) in &edges[i + 1..] {
+                let upper = positions[u1] as i64 - positions[u2] as i64;
+                let lower = positions[v1] as i64 - positions[v2] as i64;
+                if layers[u1] == layers[u2] && upper * lower < 0 {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_sweeps_reduce_crossings() {
+        for seed in 0..20 {
+            // Keep only the edges between adjacent layers of a random DAG
+            let (nodes, edges) = generate_random_graph_data(40, 160, seed);
+            let edges: Vec<(u32, u32)> = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let layers = layer_assignment(&graph);
+
+            let initial = order_layers(&graph, &layers, OrderingHeuristic::Barycenter, 0);
+            let before = crossing_count(&graph, &initial);
+            assert_eq!(before, naive_crossings(&graph, &initial));
+
+            for heuristic in [OrderingHeuristic::Barycenter, OrderingHeuristic::Median] {
+                let order = order_layers(&graph, &layers, heuristic, 8);
+                let after = crossing_count(&graph, &order);
+                assert_eq!(after, naive_crossings(&graph, &order));
+                assert!(after <= before);
+
+                let positions = layer_positions(&order);
+                for key in 0..40 {
+                    assert_eq!(order[layers[key]][positions[key]], key as u32);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_untangles_two_layers() {
+        // Each node of the top layer connects to the mirrored node below
+        let graph = build_graph::<Node, Edge>(
+            (0..8).collect(),
+            vec![(0, 7), (1, 6), (2, 5), (3, 4), (0, 6)],
+        );
+        let layers = layer_assignment(&graph);
+        assert_eq!(
+            crossing_count(&graph, &[vec![0, 1, 2, 3], vec![4, 5, 6, 7]]),
+            8
+        );
+        for heuristic in [OrderingHeuristic::Barycenter, OrderingHeuristic::Median] {
+            let order = order_layers(&graph, &layers, heuristic, 2);
+            assert_eq!(crossing_count(&graph, &order), 0);
+        }
+    }
+
+    #[test]
+    fn test_weighted_median() {
+        assert_eq!(weighted_median(&[4]), 4.0);
+        assert_eq!(weighted_median(&[1, 2, 6]), 2.0);
+        assert_eq!(weighted_median(&[1, 5]), 3.0);
+        // The neighbors on the left are packed, so the median leans left
+        assert_eq!(weighted_median(&[0, 1, 3, 9]), 9.0 / 7.0);
+    }
+
+    #[test]
+    fn test_progress_and_cancellation() {
+        let (nodes, edges) = generate_random_graph_data(40, 160, 3);
+        let edges: Vec<(u32, u32)> = edges.into_iter().filter(|(u, v)| u < v).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let layers = layer_assignment(&graph);
+        let heuristic = OrderingHeuristic::Barycenter;
+
+        let mut reports = Vec::new();
+        let mut progress = |phase: &str, fraction: f64| reports.push((phase.to_string(), fraction));
+        let order = order_layers_with_progress(&graph, &layers, heuristic, 4, &mut progress);
+        assert_eq!(order, Ok(order_layers(&graph, &layers, heuristic, 4)));
+        assert!(reports.iter().all(|(phase, _)| phase == ORDERING_PHASE));
+        assert!(reports.windows(2).all(|pair| pair[0].1 < pair[1].1));
+        assert_eq!(reports.last().unwrap().1, 1.0);
+
+        // Cancel from the callback at the first report
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        let progress = move |_: &str, fraction: f64| {
+            if fraction < 1.0 {
+                handle.cancel();
+            }
+        };
+        let mut progress = progress.with_cancellation(token);
+        assert_eq!(
+            order_layers_with_progress(&graph, &layers, heuristic, 4, &mut progress),
+            Err(Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_panics_with!(
+            order_layers(&graph, &[0], OrderingHeuristic::Median, 1),
+            "Expected a layer for each of the 2 nodes, got 1"
+        );
+        assert_panics_with!(
+            crossing_count(&graph, &[vec![0], vec![0]]),
+            INVALID_KEY_SEQUENCE
+        );
+    }
+}
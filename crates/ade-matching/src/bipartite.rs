@@ -0,0 +1,133 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{HashMap, VecDeque};
+
+/// Tests whether a graph is bipartite, with edge directions ignored, and returns its
+/// two-coloring.
+///
+/// A graph is bipartite when its nodes can be split in two sides such that every edge joins
+/// the two sides, which is the case exactly when it has no cycle of odd length, with edge
+/// directions ignored. A self-loop is a cycle of length one, so a graph with a self-loop is
+/// not bipartite.
+///
+/// Each connected component is colored by a breadth-first search from its smallest key,
+/// which goes on the first side, in `O(n + m)` time. Keys need not be sequential.
+///
+/// # Returns
+///
+/// The two sides, each with its keys in ascending order, or `None` if the graph is not
+/// bipartite.
+///
+/// # Examples
+///
+/// ```
+/// use ade_matching::is_bipartite;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // A square 0 - 1 - 2 - 3 - 0 is bipartite, a triangle is not
+/// let square = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (2, 1), (2, 3), (0, 3)]);
+/// assert_eq!(is_bipartite(&square), Some((vec![0, 2], vec![1, 3])));
+///
+/// let triangle = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+/// assert_eq!(is_bipartite(&triangle), None);
+/// ```
+pub fn is_bipartite<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Option<(Vec<u32>, Vec<u32>)> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+
+    let mut side: HashMap<u32, bool> = HashMap::with_capacity(keys.len());
+    let mut queue = VecDeque::new();
+    for &root in &keys {
+        if side.contains_key(&root) {
+            continue;
+        }
+        side.insert(root, false);
+        queue.push_back(root);
+        while let Some(key) = queue.pop_front() {
+            let color = side[&key];
+            let neighbors = graph
+                .get_successors_keys(key)
+                .chain(graph.get_predecessors_keys(key));
+            for neighbor in neighbors {
+                match side.get(&neighbor) {
+                    Some(&other) if other == color => return None,
+                    Some(_) => {}
+                    None => {
+                        side.insert(neighbor, !color);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    Some(keys.into_iter().partition(|key| !side[key]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_components_and_non_sequential_keys() {
+        // A path 30 → 10 ← 20, an isolated node 5 and an even cycle 40 → 50 → 60 → 70 → 40
+        let graph = build_graph::<Node, Edge>(
+            vec![5, 10, 20, 30, 40, 50, 60, 70],
+            vec![(30, 10), (20, 10), (40, 50), (50, 60), (60, 70), (70, 40)],
+        );
+        assert_eq!(
+            is_bipartite(&graph),
+            Some((vec![5, 10, 40, 60], vec![20, 30, 50, 70]))
+        );
+    }
+
+    #[test]
+    fn test_odd_cycles_and_self_loops() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 1)]);
+        assert_eq!(is_bipartite(&graph), None);
+
+        // Opposite edges form a cycle of length two, which is even
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+        assert_eq!(is_bipartite(&graph), Some((vec![0], vec![1])));
+
+        let pentagon = build_graph::<Node, Edge>(
+            (0..5).collect(),
+            vec![(0, 1), (1, 2), (2, 3), (3, 4), (0, 4)],
+        );
+        assert_eq!(is_bipartite(&pentagon), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_coloring_is_proper(graph in arb_graph::<Node, Edge>(12, 14)) {
+            match is_bipartite(&graph) {
+                Some((first, second)) => {
+                    prop_assert_eq!(first.len() + second.len(), graph.node_count());
+                    for edge in graph.get_edges() {
+                        let (u, v) = edge.key();
+                        prop_assert_ne!(first.contains(&u), first.contains(&v));
+                    }
+                }
+                // No assignment of sides is proper, checked by brute force
+                None => {
+                    let keys: Vec<u32> = graph.get_node_keys().collect();
+                    let proper = (0..1u32 << keys.len()).any(|mask| {
+                        let first = |key: u32| {
+                            mask & (1 << keys.iter().position(|&k| k == key).unwrap()) != 0
+                        };
+                        graph
+                            .get_edges()
+                            .all(|edge| first(edge.source()) != first(edge.target()))
+                    });
+                    prop_assert!(!proper);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/// Counts the pairs of edges that cross in a two-layer drawing of a bipartite graph.
+///
+/// Each edge is given as the pair of the positions of its endpoints, the first in the upper
+/// layer and the second in the lower one, with both layers drawn as parallel lines. Two
+/// edges cross when their upper and lower positions are in opposite orders; edges sharing an
+/// endpoint never cross. This is the quantity that layer ordering heuristics, which
+/// reorder one layer of a layered drawing at a time, try to reduce.
+///
+/// Sorting the edges by upper position leaves a sequence of lower positions whose
+/// inversions are the crossings, counted with a Fenwick tree in `O(m log m)` time.
+///
+/// # Examples
+///
+/// ```
+/// use ade_matching::bipartite_crossing_count;
+///
+/// // 0 - 1 crosses both 1 - 0 and 2 - 0, which share their lower endpoint
+/// assert_eq!(bipartite_crossing_count(&[(0, 1), (1, 0), (2, 0)]), 2);
+/// assert_eq!(bipartite_crossing_count(&[(0, 0), (0, 1), (1, 1)]), 0);
+/// ```
+pub fn bipartite_crossing_count(edges: &[(usize, usize)]) -> usize {
+    let mut edges = edges.to_vec();
+    edges.sort_unstable();
+    let size = edges.iter().map(|&(_, lower)| lower + 1).max().unwrap_or(0);
+
+    // Fenwick tree over the lower positions seen so far
+    let mut tree = vec![0usize; size + 1];
+    let mut crossings = 0;
+    for (seen, &(_, position)) in edges.iter().enumerate() {
+        let mut not_after = 0;
+        let mut i = position + 1;
+        while i > 0 {
+            not_after += tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        crossings += seen - not_after;
+        let mut i = position + 1;
+        while i < tree.len() {
+            tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_matches_pairwise_count(
+            edges in prop::collection::vec((0usize..8, 0usize..8), 0..30)
+        ) {
+            let mut expected = 0;
+            for (i, &(u1, v1)) in edges.iter().enumerate() {
+                for &(u2, v2) in &edges[i + 1..] {
+                    if (u1 < u2 && v1 > v2) || (u1 > u2 && v1 < v2) {
+                        expected += 1;
+                    }
+                }
+            }
+            prop_assert_eq!(bipartite_crossing_count(&edges), expected);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::is_bipartite;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{HashMap, VecDeque};
+
+// Marks a node without mate, or a node of the first side not in the current layered graph
+const NONE: u32 = u32::MAX;
+
+/// Computes a maximum matching of a bipartite graph with the Hopcroft–Karp algorithm.
+///
+/// A matching is a set of edges without common endpoints, and a maximum matching has as
+/// many edges as possible; assigning workers to jobs, for example, is a maximum matching of
+/// the graph joining every worker to the jobs they can take. Edge directions are ignored,
+/// and the two sides are those of [`is_bipartite`].
+///
+/// Each phase of the algorithm finds, by breadth-first search, the length of the shortest
+/// augmenting paths, alternating between unmatched and matched edges, then augments the
+/// matching along a maximal set of disjoint such paths. There are `O(√n)` phases, for
+/// `O(m √n)` time overall. The search never recurses. Keys need not be sequential.
+///
+/// # Returns
+///
+/// The keys of the matched edges, in ascending order, or `None` if the graph is not
+/// bipartite. Of two opposite edges joining the same nodes, the one from the smaller key
+/// is returned.
+///
+/// # Examples
+///
+/// ```
+/// use ade_matching::hopcroft_karp;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // Workers 0, 1 and 2, and jobs 3, 4 and 5; worker 0 must leave job 3 to worker 1
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4, 5],
+///     vec![(0, 3), (0, 4), (1, 3), (2, 4), (2, 5)],
+/// );
+/// let matching = hopcroft_karp(&graph).unwrap();
+///
+/// assert_eq!(matching.len(), 3);
+/// assert!(matching.contains(&(1, 3)));
+///
+/// let triangle = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+/// assert_eq!(hopcroft_karp(&triangle), None);
+/// ```
+pub fn hopcroft_karp<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Option<Vec<(u32, u32)>> {
+    let (left, right) = is_bipartite(graph)?;
+    let right_index: HashMap<u32, u32> = right
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i as u32))
+        .collect();
+    let neighbors: Vec<Vec<u32>> = left
+        .iter()
+        .map(|&key| {
+            let mut list: Vec<u32> = graph
+                .get_successors_keys(key)
+                .chain(graph.get_predecessors_keys(key))
+                .map(|neighbor| right_index[&neighbor])
+                .collect();
+            list.sort_unstable();
+            list.dedup();
+            list
+        })
+        .collect();
+
+    let mut mate_of_left = vec![NONE; left.len()];
+    let mut mate_of_right = vec![NONE; right.len()];
+    while let Some(mut layer) = layers(&neighbors, &mate_of_left, &mate_of_right) {
+        let mut next = vec![0; left.len()];
+        for start in 0..left.len() as u32 {
+            if mate_of_left[start as usize] == NONE {
+                augment(
+                    start,
+                    &neighbors,
+                    &mut layer,
+                    &mut next,
+                    &mut mate_of_left,
+                    &mut mate_of_right,
+                );
+            }
+        }
+    }
+
+    let mut matching: Vec<(u32, u32)> = mate_of_left
+        .iter()
+        .enumerate()
+        .filter(|&(_, &mate)| mate != NONE)
+        .map(|(i, &mate)| {
+            let (u, v) = (left[i], right[mate as usize]);
+            let (low, high) = (u.min(v), u.max(v));
+            if graph.has_edge(low, high) {
+                (low, high)
+            } else {
+                (high, low)
+            }
+        })
+        .collect();
+    matching.sort_unstable();
+    Some(matching)
+}
+
+// Distances of the nodes of the first side from its unmatched nodes, along alternating
+// paths; `None` if no augmenting path is left
+fn layers(neighbors: &[Vec<u32>], mate_of_left: &[u32], mate_of_right: &[u32]) -> Option<Vec<u32>> {
+    let mut layer = vec![NONE; neighbors.len()];
+    let mut queue = VecDeque::new();
+    for (i, &mate) in mate_of_left.iter().enumerate() {
+        if mate == NONE {
+            layer[i] = 0;
+            queue.push_back(i);
+        }
+    }
+
+    let mut found = false;
+    while let Some(u) = queue.pop_front() {
+        for &v in &neighbors[u] {
+            let w = mate_of_right[v as usize];
+            if w == NONE {
+                found = true;
+            } else if layer[w as usize] == NONE {
+                layer[w as usize] = layer[u] + 1;
+                queue.push_back(w as usize);
+            }
+        }
+    }
+    found.then_some(layer)
+}
+
+// Looks for an augmenting path from `start` through the layers, and flips it if found.
+// Nodes of the first side that lead nowhere are dropped from the layers, and `next` skips
+// the neighbors already tried, so every edge is tried once per phase.
+fn augment(
+    start: u32,
+    neighbors: &[Vec<u32>],
+    layer: &mut [u32],
+    next: &mut [usize],
+    mate_of_left: &mut [u32],
+    mate_of_right: &mut [u32],
+) {
+    // Nodes of the first side on the path, and the node of the second side chosen for each
+    let mut stack = vec![start];
+    let mut chosen: Vec<u32> = Vec::new();
+    while let Some(&u) = stack.last() {
+        let u = u as usize;
+        let Some(&v) = neighbors[u].get(next[u]) else {
+            layer[u] = NONE;
+            stack.pop();
+            chosen.pop();
+            continue;
+        };
+        next[u] += 1;
+
+        let w = mate_of_right[v as usize];
+        if w == NONE {
+            chosen.push(v);
+            for (&u, &v) in stack.iter().zip(&chosen) {
+                mate_of_left[u as usize] = v;
+                mate_of_right[v as usize] = u;
+            }
+            return;
+        }
+        if layer[w as usize] == layer[u] + 1 {
+            stack.push(w);
+            chosen.push(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use proptest::prelude::*;
+
+    // Largest set of edges without common endpoints, by brute force
+    fn brute_force(edges: &[(u32, u32)]) -> usize {
+        (0..1u32 << edges.len())
+            .filter(|mask| {
+                let mut used = Vec::new();
+                edges.iter().enumerate().all(|(i, &(u, v))| {
+                    if mask & (1 << i) == 0 {
+                        return true;
+                    }
+                    let free = !used.contains(&u) && !used.contains(&v);
+                    used.extend([u, v]);
+                    free
+                })
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_opposite_edges_and_non_sequential_keys() {
+        // The path 10 - 20 - 30 - 40, with 20 → 10 and 10 → 20 both present
+        let graph = build_graph::<Node, Edge>(
+            vec![10, 20, 30, 40],
+            vec![(20, 10), (10, 20), (30, 20), (30, 40)],
+        );
+        assert_eq!(hopcroft_karp(&graph), Some(vec![(10, 20), (30, 40)]));
+
+        let empty = build_graph::<Node, Edge>(vec![], vec![]);
+        assert_eq!(hopcroft_karp(&empty), Some(vec![]));
+    }
+
+    #[test]
+    fn test_long_augmenting_path() {
+        // A path 0 - 1 - ... - 9 has a single perfect matching, whatever the edge directions
+        let graph = build_graph::<Node, Edge>(
+            (0..10).collect(),
+            (0..9)
+                .map(|i| if i % 3 == 0 { (i + 1, i) } else { (i, i + 1) })
+                .collect(),
+        );
+        let matching = hopcroft_karp(&graph).unwrap();
+        assert_eq!(matching, vec![(1, 0), (2, 3), (4, 5), (7, 6), (8, 9)]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(
+            sides in (1u32..6, 1u32..6),
+            pairs in prop::collection::vec((0u32..6, 0u32..6), 0..12)
+        ) {
+            // Nodes 0..a on one side and 10..10 + b on the other
+            let (a, b) = sides;
+            let mut edges: Vec<(u32, u32)> = pairs
+                .into_iter()
+                .map(|(u, v)| (u % a, 10 + v % b))
+                .collect();
+            edges.sort_unstable();
+            edges.dedup();
+            let keys = (0..a).chain(10..10 + b).collect();
+            let graph = build_graph::<Node, Edge>(keys, edges.clone());
+
+            let matching = hopcroft_karp(&graph).unwrap();
+            prop_assert_eq!(matching.len(), brute_force(&edges));
+            let mut endpoints: Vec<u32> = matching.iter().flat_map(|&(u, v)| [u, v]).collect();
+            endpoints.sort_unstable();
+            endpoints.dedup();
+            prop_assert_eq!(endpoints.len(), 2 * matching.len());
+            prop_assert!(matching.iter().all(|&(u, v)| graph.has_edge(u, v)));
+        }
+    }
+}
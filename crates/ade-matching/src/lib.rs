@@ -0,0 +1,7 @@
+pub mod bipartite;
+pub mod crossings;
+pub mod hopcroft_karp;
+
+pub use bipartite::is_bipartite;
+pub use crossings::bipartite_crossing_count;
+pub use hopcroft_karp::hopcroft_karp;
@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+use std::ops::{Add, Sub};
+
+/// The requirements on edge capacities and flow values of maximum flow algorithms.
+///
+/// A capacity can be added, subtracted and compared, and its [`Default`] value is the
+/// empty flow. It is implemented for every type with these properties, including all the
+/// primitive integer and floating point types.
+///
+/// With floating point capacities, rounding errors may leave flows slightly off, and `NaN`
+/// capacities give unspecified results.
+pub trait Capacity:
+    Copy + Debug + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Default
+{
+}
+
+impl<C: Copy + Debug + PartialOrd + Add<Output = C> + Sub<Output = C> + Default> Capacity for C {}
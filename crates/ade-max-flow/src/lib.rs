@@ -0,0 +1,7 @@
+pub mod capacity;
+pub mod dinic;
+pub mod max_flow;
+
+pub use capacity::Capacity;
+pub use dinic::dinic;
+pub use max_flow::MaxFlow;
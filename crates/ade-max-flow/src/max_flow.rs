@@ -0,0 +1,86 @@
+use crate::Capacity;
+use std::collections::HashMap;
+
+/// A maximum flow between two nodes of a graph, with a minimum cut that proves it maximum.
+///
+/// The flow assigns to every edge an amount between zero and its capacity, such that every
+/// node other than the source and the sink has as much flow coming in as going out. Its
+/// value is the net flow leaving the source.
+///
+/// The minimum cut splits the nodes in two sides: the nodes that can still be reached from
+/// the source through edges with spare capacity, or edges carrying flow backwards, and the
+/// others, which include the sink. Every edge from the source side to the sink side is
+/// saturated, so the capacity of the cut equals the value of the flow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow<C> {
+    value: C,
+    flows: HashMap<(u32, u32), C>,
+    source_side: Vec<u32>,
+    sink_side: Vec<u32>,
+}
+
+impl<C: Capacity> MaxFlow<C> {
+    pub(crate) fn new(
+        value: C,
+        flows: HashMap<(u32, u32), C>,
+        source_side: Vec<u32>,
+        sink_side: Vec<u32>,
+    ) -> Self {
+        Self {
+            value,
+            flows,
+            source_side,
+            sink_side,
+        }
+    }
+
+    /// Returns the value of the flow, the net amount leaving the source.
+    pub fn value(&self) -> C {
+        self.value
+    }
+
+    /// Returns the flow along the edge from `source` to `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the edge is not in the graph.
+    pub fn flow(&self, source: u32, target: u32) -> C {
+        *self
+            .flows
+            .get(&(source, target))
+            .unwrap_or_else(|| panic!("Edge {}→{} not found", source, target))
+    }
+
+    /// Returns the flow along every edge of the graph, by edge key.
+    pub fn flows(&self) -> &HashMap<(u32, u32), C> {
+        &self.flows
+    }
+
+    /// Returns the keys of the nodes on the source side of the minimum cut, in ascending
+    /// order.
+    pub fn source_side(&self) -> &[u32] {
+        &self.source_side
+    }
+
+    /// Returns the keys of the nodes on the sink side of the minimum cut, in ascending
+    /// order.
+    pub fn sink_side(&self) -> &[u32] {
+        &self.sink_side
+    }
+
+    /// Returns the keys of the edges from the source side to the sink side of the minimum
+    /// cut, in ascending order. Their capacities add up to the value of the flow.
+    pub fn cut_edges(&self) -> Vec<(u32, u32)> {
+        let mut edges: Vec<(u32, u32)> = self
+            .flows
+            .keys()
+            .filter(|&&(source, target)| {
+                self.source_side.binary_search(&source).is_ok()
+                    && self.sink_side.binary_search(&target).is_ok()
+            })
+            .copied()
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{compare_weights, SpanningForest};
+use ade_shortest_paths::Weight;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, WeightedEdgeTrait};
+use ade_union_find::UnionFind;
+use std::collections::HashMap;
+
+/// Computes a minimum spanning forest with Kruskal's algorithm.
+///
+/// Edge directions are ignored, as in the `UndirectedView` of `ade-graph`: the edges are
+/// considered from the lightest to the heaviest, and an edge is selected when it joins two
+/// trees of the forest built so far, which a [`UnionFind`] tells in almost constant time.
+/// Self-loops are never selected, and of two opposite or parallel edges at most one is.
+/// Ties are broken by edge key, so the result is deterministic. `NaN` weights are heavier
+/// than any other, as with [`f64::total_cmp`].
+///
+/// The weight of every edge is given by `weight`, so edges without weights can be used with
+/// costs kept elsewhere. Sorting the edges dominates, in `O(m log m)` time. Keys need not be
+/// sequential.
+///
+/// # Returns
+///
+/// The [`SpanningForest`] with the selected edges, from the lightest to the heaviest.
+///
+/// # Examples
+///
+/// ```
+/// use ade_mst::kruskal;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::EdgeTrait;
+///
+/// // A square 0 - 1 - 2 - 3 - 0 with the diagonal 0 - 2
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)],
+/// );
+/// let cost = |source: u32, target: u32| (source + target) as i32;
+/// let forest = kruskal(&graph, |edge| cost(edge.source(), edge.target()));
+///
+/// assert_eq!(forest.edges(), &[(0, 1), (0, 2), (3, 0)]);
+/// assert_eq!(forest.total_weight(), 6);
+/// ```
+pub fn kruskal<N: NodeTrait, E: EdgeTrait, W: Weight>(
+    graph: &impl GraphViewTrait<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> SpanningForest<W> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let index: HashMap<u32, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i as u32))
+        .collect();
+
+    let mut edges: Vec<((u32, u32), W)> = graph
+        .get_edges()
+        .filter(|edge| edge.source() != edge.target())
+        .map(|edge| (edge.key(), weight(edge)))
+        .collect();
+    edges.sort_by(|a, b| compare_weights(&a.1, &b.1).then(a.0.cmp(&b.0)));
+
+    let mut sets = UnionFind::new(keys.len());
+    let mut forest = SpanningForest::new();
+    for ((source, target), weight) in edges {
+        if sets.set_count() == 1 {
+            break;
+        }
+        if sets.union(index[&source], index[&target]) {
+            forest.push((source, target), weight);
+        }
+    }
+    forest
+}
+
+/// Computes a minimum spanning forest of a graph whose edges carry their weights.
+///
+/// This is [`kruskal`] with the weights given by [`WeightedEdgeTrait::weight`].
+///
+/// # Examples
+///
+/// ```
+/// use ade_mst::minimum_spanning_forest;
+/// use ade_graph::build::build_graph_weighted;
+/// use ade_graph::implementations::{Node, WeightedEdge};
+///
+/// let graph = build_graph_weighted::<Node, WeightedEdge<f64>>(
+///     vec![0, 1, 2],
+///     vec![(0, 1, 2.5), (1, 2, 1.0), (2, 0, 3.0)],
+/// );
+/// let forest = minimum_spanning_forest(&graph);
+///
+/// assert_eq!(forest.edges(), &[(1, 2), (0, 1)]);
+/// assert_eq!(forest.total_weight(), 3.5);
+/// ```
+pub fn minimum_spanning_forest<N: NodeTrait, E: WeightedEdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> SpanningForest<E::Weight>
+where
+    E::Weight: Weight,
+{
+    kruskal(graph, |edge| edge.weight())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prim;
+    use ade_graph::implementations::{Edge, Graph, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_graph;
+    use proptest::prelude::*;
+
+    fn cost(edge: &Edge) -> i64 {
+        ((edge.source() * 7 + edge.target() * 3) % 11) as i64
+    }
+
+    // Smallest total weight over all the edge subsets that join as many nodes as the graph
+    // without closing a cycle
+    fn brute_force(graph: &Graph<Node, Edge>) -> i64 {
+        let mut keys: Vec<u32> = graph.get_node_keys().collect();
+        keys.sort_unstable();
+        let edges: Vec<&Edge> = graph
+            .get_edges()
+            .filter(|edge| edge.source() != edge.target())
+            .collect();
+        let position = |key: u32| keys.binary_search(&key).unwrap() as u32;
+
+        let mut best: Option<(usize, i64)> = None;
+        for subset in 0..1u32 << edges.len() {
+            let mut sets = UnionFind::new(keys.len());
+            let mut total = 0;
+            let mut acyclic = true;
+            for (i, edge) in edges.iter().enumerate() {
+                if subset & (1 << i) != 0 {
+                    acyclic &= sets.union(position(edge.source()), position(edge.target()));
+                    total += cost(edge);
+                }
+            }
+            let size = subset.count_ones() as usize;
+            if acyclic && best.is_none_or(|(s, t)| size > s || (size == s && total < t)) {
+                best = Some((size, total));
+            }
+        }
+        best.map_or(0, |(_, total)| total)
+    }
+
+    // Connected components with edge directions ignored
+    fn component_count(graph: &Graph<Node, Edge>) -> usize {
+        let mut keys: Vec<u32> = graph.get_node_keys().collect();
+        keys.sort_unstable();
+        let mut sets = UnionFind::new(keys.len());
+        for edge in graph.get_edges() {
+            let source = keys.binary_search(&edge.source()).unwrap() as u32;
+            let target = keys.binary_search(&edge.target()).unwrap() as u32;
+            sets.union(source, target);
+        }
+        sets.set_count()
+    }
+
+    #[test]
+    fn test_forest_of_disconnected_graph() {
+        // Two components, the second with opposite edges and a self-loop
+        let graph = build_graph::<Node, Edge>(
+            vec![1, 4, 6, 10, 11],
+            vec![(1, 4), (4, 6), (6, 1), (10, 11), (11, 10), (11, 11)],
+        );
+        let forest = kruskal(&graph, |edge| edge.target() as i32);
+
+        assert_eq!(forest.edges(), &[(6, 1), (1, 4), (11, 10)]);
+        assert_eq!(forest.edge_count(), 3);
+        assert_eq!(forest.total_weight(), 15);
+    }
+
+    #[test]
+    fn test_nan_weights_come_last() {
+        // The triangle 0 - 1 - 2 with a NaN weight on 0 - 1
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 0)]);
+        let weight = |edge: &Edge| match edge.key() {
+            (0, 1) => f64::NAN,
+            (1, 2) => 2.0,
+            _ => 1.0,
+        };
+        let forest = kruskal(&graph, weight);
+        assert_eq!(forest.edges(), &[(2, 0), (1, 2)]);
+        assert_eq!(prim(&graph, weight).edges(), forest.edges());
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        let forest = kruskal(&graph, |_| 1.0);
+        assert!(forest.edges().is_empty());
+        assert_eq!(forest.total_weight(), 0.0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force_and_prim(graph in arb_graph::<Node, Edge>(7, 10)) {
+            let forest = kruskal(&graph, cost);
+            let components = component_count(&graph);
+            prop_assert_eq!(forest.edge_count(), graph.node_count() - components);
+            prop_assert_eq!(forest.total_weight(), brute_force(&graph));
+            prop_assert_eq!(prim(&graph, cost).total_weight(), forest.total_weight());
+        }
+    }
+}
@@ -0,0 +1,23 @@
+pub mod kruskal;
+pub mod prim;
+pub mod spanning_forest;
+
+pub use kruskal::{kruskal, minimum_spanning_forest};
+pub use prim::prim;
+pub use spanning_forest::SpanningForest;
+
+use std::cmp::Ordering;
+
+// A total order on weights, where a weight that is not comparable with itself, such as a
+// floating point NaN, is heavier than any other, as with `f64::total_cmp`. Sorting with
+// `partial_cmp(..).unwrap_or(Equal)` is not a total order when NaN is present, so the result
+// would depend on the input order.
+pub(crate) fn compare_weights<W: PartialOrd>(a: &W, b: &W) -> Ordering {
+    let is_nan = |w: &W| w.partial_cmp(w).is_none();
+    match (is_nan(a), is_nan(b)) {
+        (false, false) => a.partial_cmp(b).unwrap(),
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+    }
+}
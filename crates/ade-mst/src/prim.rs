@@ -0,0 +1,141 @@
+use crate::{compare_weights, SpanningForest};
+use ade_shortest_paths::Weight;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Computes a minimum spanning forest with Prim's algorithm.
+///
+/// Edge directions are ignored, as in [`kruskal`](crate::kruskal). A tree is grown from the
+/// smallest key not yet reached, by repeatedly selecting the lightest edge that leaves it,
+/// until every node of its connected component is reached; then the next tree starts.
+/// Self-loops are never selected, ties are broken by edge key, and `NaN` weights are heavier
+/// than any other.
+///
+/// The candidate edges are kept in a binary heap, in `O(m log m)` time. Both Prim's
+/// algorithm and [`kruskal`](crate::kruskal) return a forest with the same total weight, and
+/// the same edges when weights are distinct. Keys need not be sequential.
+///
+/// # Returns
+///
+/// The [`SpanningForest`] with the selected edges, tree by tree, in the order in which they
+/// were reached.
+///
+/// # Examples
+///
+/// ```
+/// use ade_mst::prim;
+/// use ade_graph::build::build_graph_weighted;
+/// use ade_graph::implementations::{Node, WeightedEdge};
+/// use ade_graph::WeightedEdgeTrait;
+///
+/// // A path 0 - 1 - 2 - 3 and the shortcut 3 → 0
+/// let graph = build_graph_weighted::<Node, WeightedEdge<u32>>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1, 4), (1, 2, 1), (2, 3, 5), (3, 0, 2)],
+/// );
+/// let forest = prim(&graph, |edge| edge.weight());
+///
+/// assert_eq!(forest.edges(), &[(3, 0), (0, 1), (1, 2)]);
+/// assert_eq!(forest.total_weight(), 7);
+/// ```
+pub fn prim<N: NodeTrait, E: EdgeTrait, W: Weight>(
+    graph: &impl GraphViewTrait<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> SpanningForest<W> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+
+    let mut reached: HashSet<u32> = HashSet::with_capacity(keys.len());
+    let mut candidates = BinaryHeap::new();
+    let mut forest = SpanningForest::new();
+    for root in keys {
+        if !reached.insert(root) {
+            continue;
+        }
+        push_incident_edges(graph, root, &weight, &reached, &mut candidates);
+        while let Some(candidate) = candidates.pop() {
+            if reached.insert(candidate.node) {
+                forest.push(candidate.key, candidate.weight);
+                push_incident_edges(graph, candidate.node, &weight, &reached, &mut candidates);
+            }
+        }
+    }
+    forest
+}
+
+// An edge leaving the tree, to the node it would reach; the heap pops the lightest first,
+// then the smallest key
+struct Candidate<W> {
+    weight: W,
+    key: (u32, u32),
+    node: u32,
+}
+
+impl<W: Weight> Ord for Candidate<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_weights(&other.weight, &self.weight).then(other.key.cmp(&self.key))
+    }
+}
+
+impl<W: Weight> PartialOrd for Candidate<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> PartialEq for Candidate<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for Candidate<W> {}
+
+// Adds the edges between `node` and the nodes not yet reached, in either direction
+fn push_incident_edges<N: NodeTrait, E: EdgeTrait, W: Weight>(
+    graph: &impl GraphViewTrait<N, E>,
+    node: u32,
+    weight: &impl Fn(&E) -> W,
+    reached: &HashSet<u32>,
+    candidates: &mut BinaryHeap<Candidate<W>>,
+) {
+    let incident = graph.get_out_edges(node).chain(graph.get_in_edges(node));
+    for (edge, other) in incident {
+        let other = other.key();
+        if !reached.contains(&other) {
+            candidates.push(Candidate {
+                weight: weight(edge),
+                key: edge.key(),
+                node: other,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_forest_of_disconnected_graph() {
+        // Two components, the second with opposite edges and a self-loop
+        let graph = build_graph::<Node, Edge>(
+            vec![1, 4, 6, 10, 11],
+            vec![(1, 4), (4, 6), (6, 1), (10, 11), (11, 10), (11, 11)],
+        );
+        let forest = prim(&graph, |edge| edge.target() as i32);
+
+        assert_eq!(forest.edges(), &[(6, 1), (1, 4), (11, 10)]);
+        assert_eq!(forest.total_weight(), 15);
+    }
+
+    #[test]
+    fn test_ties_are_broken_by_edge_key() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(2, 0), (0, 1), (1, 2)]);
+        let forest = prim(&graph, |_| 1);
+        assert_eq!(forest.into_edges(), vec![(0, 1), (1, 2)]);
+    }
+}
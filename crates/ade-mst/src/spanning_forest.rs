@@ -0,0 +1,50 @@
+use ade_shortest_paths::Weight;
+
+/// A minimum spanning forest: for every connected component of a graph, with edge
+/// directions ignored, a tree of edges joining all its nodes with the smallest total weight.
+///
+/// A connected graph has a single tree, its minimum spanning tree, with one edge less than
+/// nodes. Edges are identified by their keys `(source, target)` in the graph, whatever the
+/// direction in which the tree traverses them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningForest<W> {
+    edges: Vec<(u32, u32)>,
+    total_weight: W,
+}
+
+impl<W: Weight> SpanningForest<W> {
+    // Creates an empty forest, to which edges are added as they are selected
+    pub(crate) fn new() -> Self {
+        Self {
+            edges: Vec::new(),
+            total_weight: W::default(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, key: (u32, u32), weight: W) {
+        self.edges.push(key);
+        self.total_weight = self.total_weight + weight;
+    }
+
+    /// Returns the keys of the selected edges, in the order in which they were selected.
+    pub fn edges(&self) -> &[(u32, u32)] {
+        &self.edges
+    }
+
+    /// Consumes the forest and returns the keys of the selected edges.
+    pub fn into_edges(self) -> Vec<(u32, u32)> {
+        self.edges
+    }
+
+    /// Returns the number of selected edges, which is the number of nodes minus the number
+    /// of connected components.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the sum of the weights of the selected edges, or the [`Default`] weight if
+    /// there are none.
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+}
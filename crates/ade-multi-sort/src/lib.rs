@@ -1,17 +1,386 @@
-pub fn multi_sort<T>(items: &mut [T], metrics: &[Box<dyn Fn(&T) -> i32>]) {
-    items.sort_by(|a, b| {
-        for metric in metrics {
-            let ord = metric(a).cmp(&metric(b));
-            if ord != std::cmp::Ordering::Equal {
+use std::cmp::Ordering;
+
+/// A single sort key: compares two items by one criterion.
+///
+/// Implemented for closures returning any [`Ord`] type, which sort in ascending order, and
+/// for [`Key`], which adds a [`Direction`].
+pub trait SortKey<T> {
+    /// The value extracted from each item.
+    type Value;
+
+    /// Extracts the key value of an item.
+    fn extract(&self, item: &T) -> Self::Value;
+
+    /// Compares two extracted values.
+    fn compare_values(&self, a: &Self::Value, b: &Self::Value) -> Ordering;
+
+    /// Compares two items by this key.
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.compare_values(&self.extract(a), &self.extract(b))
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> SortKey<T> for F {
+    type Value = K;
+
+    fn extract(&self, item: &T) -> K {
+        self(item)
+    }
+
+    fn compare_values(&self, a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// The order of a sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Smallest keys first.
+    #[default]
+    Ascending,
+    /// Largest keys first.
+    Descending,
+}
+
+/// A key extractor with an explicit sort direction.
+///
+/// Descending keys are compared in reverse, so there is no need to negate values, which
+/// overflows on `i32::MIN` and is not possible for non-numeric keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::{multi_sort, desc};
+///
+/// let mut values = vec![3, i32::MIN, 7];
+/// multi_sort(&mut values, (desc(|v: &i32| *v),));
+///
+/// assert_eq!(values, vec![7, 3, i32::MIN]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Key<F> {
+    extract: F,
+    direction: Direction,
+}
+
+impl<F> Key<F> {
+    /// Creates a key sorted in the given direction.
+    pub fn new(extract: F, direction: Direction) -> Self {
+        Self { extract, direction }
+    }
+
+    /// Returns the direction of the key.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> SortKey<T> for Key<F> {
+    type Value = K;
+
+    fn extract(&self, item: &T) -> K {
+        (self.extract)(item)
+    }
+
+    fn compare_values(&self, a: &K, b: &K) -> Ordering {
+        let ord = a.cmp(b);
+        match self.direction {
+            Direction::Ascending => ord,
+            Direction::Descending => ord.reverse(),
+        }
+    }
+}
+
+/// Creates an ascending key, see [`Key`].
+pub fn asc<F>(extract: F) -> Key<F> {
+    Key::new(extract, Direction::Ascending)
+}
+
+/// Creates a descending key, see [`Key`].
+pub fn desc<F>(extract: F) -> Key<F> {
+    Key::new(extract, Direction::Descending)
+}
+
+/// A list of sort keys, compared in order.
+///
+/// Items are compared with the first key; the following keys are only used to break ties.
+/// The trait is implemented for tuples of up to eight [`SortKey`]s, such as closures
+/// returning any [`Ord`] type or [`Key`]s, and for slices of keys, such as boxed
+/// [`Metric`]s, when the number of keys is only known at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::SortSpec;
+/// use std::cmp::Ordering;
+///
+/// let spec = (|s: &&str| s.len(), |s: &&str| s.to_string());
+///
+/// assert_eq!(spec.compare(&"b", &"aa"), Ordering::Less);
+/// assert_eq!(spec.compare(&"b", &"a"), Ordering::Greater);
+/// ```
+pub trait SortSpec<T> {
+    /// The values of all the keys of an item, as cached by [`multi_sort_cached`].
+    type Keys;
+
+    /// Compares two items, key by key.
+    ///
+    /// Later keys are only extracted when the earlier ones are equal.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Extracts the values of all the keys of an item.
+    fn extract(&self, item: &T) -> Self::Keys;
+
+    /// Compares two sets of extracted values, key by key.
+    fn compare_keys(&self, a: &Self::Keys, b: &Self::Keys) -> Ordering;
+}
+
+macro_rules! impl_sort_spec_for_tuple {
+    ($(($k:ident, $i:tt)),+) => {
+        impl<T, $($k: SortKey<T>),+> SortSpec<T> for ($($k,)+) {
+            type Keys = ($($k::Value,)+);
+
+            fn compare(&self, a: &T, b: &T) -> Ordering {
+                Ordering::Equal $(.then_with(|| self.$i.compare(a, b)))+
+            }
+
+            fn extract(&self, item: &T) -> Self::Keys {
+                ($(self.$i.extract(item),)+)
+            }
+
+            fn compare_keys(&self, a: &Self::Keys, b: &Self::Keys) -> Ordering {
+                Ordering::Equal $(.then_with(|| self.$i.compare_values(&a.$i, &b.$i)))+
+            }
+        }
+    };
+}
+
+impl_sort_spec_for_tuple!((K0, 0));
+impl_sort_spec_for_tuple!((K0, 0), (K1, 1));
+impl_sort_spec_for_tuple!((K0, 0), (K1, 1), (K2, 2));
+impl_sort_spec_for_tuple!((K0, 0), (K1, 1), (K2, 2), (K3, 3));
+impl_sort_spec_for_tuple!((K0, 0), (K1, 1), (K2, 2), (K3, 3), (K4, 4));
+impl_sort_spec_for_tuple!((K0, 0), (K1, 1), (K2, 2), (K3, 3), (K4, 4), (K5, 5));
+impl_sort_spec_for_tuple!(
+    (K0, 0),
+    (K1, 1),
+    (K2, 2),
+    (K3, 3),
+    (K4, 4),
+    (K5, 5),
+    (K6, 6)
+);
+impl_sort_spec_for_tuple!(
+    (K0, 0),
+    (K1, 1),
+    (K2, 2),
+    (K3, 3),
+    (K4, 4),
+    (K5, 5),
+    (K6, 6),
+    (K7, 7)
+);
+
+/// A boxed key extractor, for sort keys chosen at runtime.
+pub type Metric<T, K = i32> = Box<dyn Fn(&T) -> K>;
+
+impl<T, S: SortKey<T>> SortSpec<T> for [S] {
+    type Keys = Vec<S::Value>;
+
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        for key in self {
+            let ord = key.compare(a, b);
+            if ord != Ordering::Equal {
                 return ord;
             }
         }
-        std::cmp::Ordering::Equal
-    });
+        Ordering::Equal
+    }
+
+    fn extract(&self, item: &T) -> Self::Keys {
+        self.iter().map(|key| key.extract(item)).collect()
+    }
+
+    fn compare_keys(&self, a: &Self::Keys, b: &Self::Keys) -> Ordering {
+        for ((key, a), b) in self.iter().zip(a).zip(b) {
+            let ord = key.compare_values(a, b);
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    }
 }
 
-#[cfg(test)]
+impl<T, S: SortSpec<T> + ?Sized> SortSpec<T> for &S {
+    type Keys = S::Keys;
+
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (**self).compare(a, b)
+    }
+
+    fn extract(&self, item: &T) -> Self::Keys {
+        (**self).extract(item)
+    }
+
+    fn compare_keys(&self, a: &Self::Keys, b: &Self::Keys) -> Ordering {
+        (**self).compare_keys(a, b)
+    }
+}
+
+/// A float key ordered with [`f64::total_cmp`] or [`f32::total_cmp`].
+///
+/// Floats are not [`Ord`] because of `NaN`; wrapping them in `TotalOrder` makes them usable
+/// as sort keys. Negative values sort before positive ones, and `NaN` sorts after infinity.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::{multi_sort, TotalOrder};
+///
+/// let mut weights = vec![0.5, -1.0, 2.25];
+/// multi_sort(&mut weights, (|w: &f64| TotalOrder(*w),));
+///
+/// assert_eq!(weights, vec![-1.0, 0.5, 2.25]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TotalOrder<F>(pub F);
+
+macro_rules! impl_total_order {
+    ($($float:ty),+) => {
+        $(
+            impl PartialEq for TotalOrder<$float> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for TotalOrder<$float> {}
+
+            impl PartialOrd for TotalOrder<$float> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for TotalOrder<$float> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+        )+
+    };
+}
+
+impl_total_order!(f32, f64);
+
+/// Sorts a slice by multiple keys.
+///
+/// Items are ordered by the first key of `spec`, then by the second key among items with
+/// equal first keys, and so on. Keys can be of any [`Ord`] type and are extracted by plain
+/// closures, so there is no dynamic dispatch when `spec` is a tuple. Use [`asc`] and
+/// [`desc`] to set the direction of each key.
+///
+/// The sort is stable: items with equal keys keep their relative order. See
+/// [`multi_sort_unstable`] for a faster sort that does not preserve it.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::multi_sort;
+///
+/// let mut words = vec!["pear", "fig", "apple", "kiwi"];
+///
+/// // By length, then alphabetically
+/// multi_sort(&mut words, (|w: &&str| w.len(), |w: &&str| w.to_string()));
+///
+/// assert_eq!(words, vec!["fig", "kiwi", "pear", "apple"]);
+/// ```
+///
+/// Keys chosen at runtime can be passed as a slice of boxed extractors:
+///
+/// ```
+/// use ade_multi_sort::{multi_sort, Metric};
+///
+/// let mut points = vec![(2, 3), (1, 5), (2, 1)];
+/// let metrics: Vec<Metric<(i32, i32)>> = vec![Box::new(|p| p.0), Box::new(|p| p.1)];
+///
+/// multi_sort(&mut points, metrics.as_slice());
+///
+/// assert_eq!(points, vec![(1, 5), (2, 1), (2, 3)]);
+/// ```
+pub fn multi_sort<T>(items: &mut [T], spec: impl SortSpec<T>) {
+    items.sort_by(|a, b| spec.compare(a, b));
+}
+
+/// Sorts a slice by multiple keys, without preserving the order of equal items.
+///
+/// Same as [`multi_sort`], but based on [`slice::sort_unstable_by`], which is faster
+/// and does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::{multi_sort_unstable, asc, desc};
+///
+/// let mut points = vec![(1, 2), (0, 5), (1, 7)];
+/// multi_sort_unstable(&mut points, (asc(|p: &(i32, i32)| p.0), desc(|p: &(i32, i32)| p.1)));
+///
+/// assert_eq!(points, vec![(0, 5), (1, 7), (1, 2)]);
+/// ```
+pub fn multi_sort_unstable<T>(items: &mut [T], spec: impl SortSpec<T>) {
+    items.sort_unstable_by(|a, b| spec.compare(a, b));
+}
+
+/// Sorts a slice by multiple keys, extracting the keys of each item only once.
+///
+/// [`multi_sort`] extracts keys on every comparison, that is O(n log n) times per key.
+/// This variant extracts all the keys of every item once, then sorts the cached values,
+/// like [`slice::sort_by_cached_key`]. Prefer it when keys are expensive to compute, for
+/// example when they walk a graph. It allocates memory for the cached keys.
+///
+/// The sort is stable.
+///
+/// # Examples
+///
+/// ```
+/// use ade_multi_sort::{multi_sort_cached, desc};
+/// use std::cell::Cell;
+///
+/// let calls = Cell::new(0);
+/// let mut values = vec![5, 3, 9, 1, 7];
+///
+/// multi_sort_cached(&mut values, (desc(|v: &i32| {
+///     calls.set(calls.get() + 1);
+///     *v
+/// }),));
+///
+/// assert_eq!(values, vec![9, 7, 5, 3, 1]);
+/// assert_eq!(calls.get(), 5);
+/// ```
+pub fn multi_sort_cached<T, S: SortSpec<T>>(items: &mut [T], spec: S) {
+    let mut indices: Vec<(S::Keys, usize)> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (spec.extract(item), index))
+        .collect();
+
+    // Ties are broken by original position, which keeps the sort stable
+    indices.sort_unstable_by(|(a, i), (b, j)| spec.compare_keys(a, b).then(i.cmp(j)));
 
+    // Apply the permutation in place: the item for position i may have been swapped
+    // away already, in which case follow the chain of earlier swaps
+    for i in 0..items.len() {
+        let mut index = indices[i].1;
+        while index < i {
+            index = indices[index].1;
+        }
+        indices[i].1 = index;
+        items.swap(i, index);
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -30,10 +399,7 @@ mod tests {
             Point { x: 1, y: 2 },
         ];
 
-        multi_sort(
-            &mut points,
-            &[Box::new(|p: &Point| p.x), Box::new(|p: &Point| p.y)],
-        );
+        multi_sort(&mut points, (|p: &Point| p.x, |p: &Point| p.y));
 
         let expected = vec![
             Point { x: 1, y: 2 },
@@ -48,7 +414,162 @@ mod tests {
     #[test]
     fn test_multi_sort_empty() {
         let mut points: Vec<Point> = Vec::new();
-        multi_sort(&mut points, &[Box::new(|p: &Point| p.x)]);
+        multi_sort(&mut points, (|p: &Point| p.x,));
         assert!(points.is_empty());
     }
+
+    #[test]
+    fn test_multi_sort_mixed_key_types() {
+        let mut items = vec![(1.5, 'b'), (0.5, 'b'), (2.0, 'a')];
+
+        multi_sort(
+            &mut items,
+            (|i: &(f64, char)| i.1, |i: &(f64, char)| TotalOrder(i.0)),
+        );
+
+        assert_eq!(items, vec![(2.0, 'a'), (0.5, 'b'), (1.5, 'b')]);
+    }
+
+    #[test]
+    fn test_multi_sort_directions() {
+        let mut points = vec![
+            Point { x: 2, y: 3 },
+            Point { x: 1, y: 5 },
+            Point { x: i32::MIN, y: 0 },
+            Point { x: 2, y: 1 },
+        ];
+
+        multi_sort(&mut points, (desc(|p: &Point| p.x), asc(|p: &Point| p.y)));
+
+        let expected = vec![
+            Point { x: 2, y: 1 },
+            Point { x: 2, y: 3 },
+            Point { x: 1, y: 5 },
+            Point { x: i32::MIN, y: 0 },
+        ];
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn test_multi_sort_is_stable() {
+        let mut items: Vec<(u32, usize)> = (0..100).map(|i| (i % 3, i as usize)).collect();
+
+        multi_sort(&mut items, (desc(|i: &(u32, usize)| i.0),));
+
+        for pair in items.windows(2) {
+            assert!(pair[0].0 > pair[1].0 || pair[0].1 < pair[1].1);
+        }
+    }
+
+    #[test]
+    fn test_multi_sort_unstable() {
+        let mut words = vec!["bb", "a", "ccc", "dd"];
+        multi_sort_unstable(
+            &mut words,
+            (desc(|w: &&str| w.len()), |w: &&str| w.to_string()),
+        );
+        assert_eq!(words, vec!["ccc", "bb", "dd", "a"]);
+    }
+
+    #[test]
+    fn test_multi_sort_runtime_directions() {
+        let mut points = vec![
+            Point { x: 1, y: 2 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: 9 },
+        ];
+        let keys: Vec<Key<Metric<Point>>> = vec![
+            Key::new(Box::new(|p: &Point| p.x), Direction::Descending),
+            Key::new(Box::new(|p: &Point| p.y), Direction::Ascending),
+        ];
+
+        multi_sort(&mut points, keys.as_slice());
+
+        assert_eq!(
+            points,
+            vec![
+                Point { x: 1, y: 1 },
+                Point { x: 1, y: 2 },
+                Point { x: 0, y: 9 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_sort_cached_matches_multi_sort() {
+        let items: Vec<(i32, i32)> = (0..200).map(|i| ((i * 37) % 11, (i * 13) % 7)).collect();
+        let spec = (asc(|p: &(i32, i32)| p.0), desc(|p: &(i32, i32)| p.1));
+
+        let mut expected = items.clone();
+        multi_sort(&mut expected, spec);
+        let mut cached = items;
+        multi_sort_cached(&mut cached, spec);
+
+        assert_eq!(cached, expected);
+    }
+
+    #[test]
+    fn test_multi_sort_cached_extracts_once() {
+        let calls = std::cell::Cell::new(0);
+        let mut points: Vec<Point> = (0..50).rev().map(|i| Point { x: i % 5, y: i }).collect();
+
+        multi_sort_cached(
+            &mut points,
+            (
+                |p: &Point| {
+                    calls.set(calls.get() + 1);
+                    p.x
+                },
+                |p: &Point| p.y,
+            ),
+        );
+
+        assert_eq!(calls.get(), 50);
+        assert_eq!(points[0], Point { x: 0, y: 0 });
+        assert_eq!(points[49], Point { x: 4, y: 49 });
+    }
+
+    #[test]
+    fn test_multi_sort_cached_runtime_keys() {
+        let mut points = vec![
+            Point { x: 1, y: 2 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: 9 },
+        ];
+        let metrics: Vec<Metric<Point>> =
+            vec![Box::new(|p: &Point| p.x), Box::new(|p: &Point| p.y)];
+
+        multi_sort_cached(&mut points, metrics.as_slice());
+
+        assert_eq!(
+            points,
+            vec![
+                Point { x: 0, y: 9 },
+                Point { x: 1, y: 1 },
+                Point { x: 1, y: 2 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_sort_boxed_metrics() {
+        let mut points = vec![
+            Point { x: 1, y: 2 },
+            Point { x: 1, y: 1 },
+            Point { x: 0, y: 9 },
+        ];
+        let metrics: Vec<Metric<Point>> =
+            vec![Box::new(|p: &Point| p.x), Box::new(|p: &Point| p.y)];
+
+        multi_sort(&mut points, metrics.as_slice());
+
+        assert_eq!(
+            points,
+            vec![
+                Point { x: 0, y: 9 },
+                Point { x: 1, y: 1 },
+                Point { x: 1, y: 2 }
+            ]
+        );
+    }
 }
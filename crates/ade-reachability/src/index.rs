@@ -0,0 +1,332 @@
+use ade_topological_sort::{topological_sort_kahn, TopoSortError};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+// Marks a chain that a node cannot reach
+const UNREACHABLE: u32 = u32::MAX;
+
+/// A precomputed index answering reachability queries on a directed acyclic graph.
+///
+/// The index is built once, after which [`can_reach`] answers each query without
+/// traversing the graph, which suits workloads such as dependency analysis that issue
+/// millions of queries on the same DAG. Two labels are stored for each node:
+///
+/// * the interval `[pre, post]` of a depth-first traversal and the position in a
+///   topological order. These answer most queries with two comparisons: a descendant in the
+///   depth-first forest is reachable, and a node earlier in the topological order is not.
+/// * for a decomposition of the DAG into chains, which are paths of the graph, the first
+///   position reachable on every chain. A node is reachable if it does not come before the
+///   first reachable position on its own chain.
+///
+/// A node only stores labels for the chains it reaches, so the chain labels take memory
+/// proportional to the number of reachable (node, chain) pairs, at most `n · k` for `n`
+/// nodes and `k` chains, and answer a query in `O(log k)` time. `k` is at least the width of
+/// the DAG, its largest set of mutually unreachable nodes, so the index is best suited to
+/// DAGs that are deep rather than wide, but a wide DAG whose nodes reach few chains stays
+/// small. The chains are built greedily and may exceed the width.
+///
+/// Reachability follows paths of at least one edge, as in
+/// [`TransitiveClosure::reaches`](crate::TransitiveClosure::reaches): no node reaches
+/// itself in a DAG.
+///
+/// [`can_reach`]: ReachabilityIndex::can_reach
+///
+/// # Examples
+///
+/// ```
+/// use ade_reachability::ReachabilityIndex;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 → 1 → 3 and 0 → 2 → 3 → 4
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 4],
+///     vec![(0, 1), (1, 3), (0, 2), (2, 3), (3, 4)],
+/// );
+/// let index = ReachabilityIndex::new(&graph).unwrap();
+///
+/// assert!(index.can_reach(0, 4));
+/// assert!(index.can_reach(2, 4));
+/// assert!(!index.can_reach(1, 2));
+/// assert!(!index.can_reach(4, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    // Position of every node in a topological order
+    order: Vec<u32>,
+    pre: Vec<u32>,
+    post: Vec<u32>,
+    chain: Vec<u32>,
+    position: Vec<u32>,
+    chain_count: usize,
+    // The labels of node `i` are `labels[offsets[i]..offsets[i + 1]]`: the chains it
+    // reaches, in increasing order, with the first position it reaches on each of them
+    offsets: Vec<usize>,
+    labels: Vec<(u32, u32)>,
+}
+
+impl ReachabilityIndex {
+    /// Builds the index of a directed acyclic graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TopoSortError::CycleDetected`] with one of the cycles of the graph if it
+    /// is not acyclic, self-loops included, and [`TopoSortError::NonSequentialKeys`] if
+    /// the graph does not have sequential keys.
+    pub fn new<N: NodeTrait, E: EdgeTrait>(
+        dag: &impl GraphViewTrait<N, E>,
+    ) -> Result<Self, TopoSortError> {
+        let sorted = topological_sort_kahn(dag)?;
+        let n = sorted.len();
+        let successors: Vec<Vec<u32>> = (0..n as u32)
+            .map(|key| {
+                let mut keys: Vec<u32> = dag.get_successors_keys(key).collect();
+                keys.sort_unstable();
+                keys
+            })
+            .collect();
+
+        let mut order = vec![0; n];
+        for (index, &key) in sorted.iter().enumerate() {
+            order[key as usize] = index as u32;
+        }
+        let (pre, post) = dfs_intervals(&sorted, &successors);
+        let (chain, position, chain_count) = chain_decomposition(&sorted, &successors);
+
+        // Rows are built in reverse topological order, then flattened in key order
+        let mut rows: Vec<Vec<(u32, u32)>> = vec![Vec::new(); n];
+        let mut first = vec![UNREACHABLE; chain_count];
+        let mut touched: Vec<u32> = Vec::new();
+        for &key in sorted.iter().rev() {
+            let key = key as usize;
+            for &successor in &successors[key] {
+                let successor = successor as usize;
+                let reached = std::iter::once((chain[successor], position[successor]))
+                    .chain(rows[successor].iter().copied());
+                for (on_chain, at) in reached {
+                    let slot = &mut first[on_chain as usize];
+                    if *slot == UNREACHABLE {
+                        touched.push(on_chain);
+                    }
+                    *slot = (*slot).min(at);
+                }
+            }
+            touched.sort_unstable();
+            rows[key] = touched
+                .drain(..)
+                .map(|on_chain| {
+                    let at = std::mem::replace(&mut first[on_chain as usize], UNREACHABLE);
+                    (on_chain, at)
+                })
+                .collect();
+        }
+
+        let mut offsets = Vec::with_capacity(n + 1);
+        offsets.push(0);
+        let mut labels = Vec::with_capacity(rows.iter().map(Vec::len).sum());
+        for row in rows {
+            labels.extend(row);
+            offsets.push(labels.len());
+        }
+
+        Ok(ReachabilityIndex {
+            order,
+            pre,
+            post,
+            chain,
+            position,
+            chain_count,
+            offsets,
+            labels,
+        })
+    }
+
+    /// Returns `true` if `target` can be reached from `source` through at least one edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` is not a node of the graph.
+    pub fn can_reach(&self, source: u32, target: u32) -> bool {
+        for key in [source, target] {
+            if key as usize >= self.order.len() {
+                panic!("Node {} not found", key);
+            }
+        }
+        let (source, target) = (source as usize, target as usize);
+
+        if self.order[source] >= self.order[target] {
+            return false;
+        }
+        if self.pre[source] < self.pre[target] && self.post[target] < self.post[source] {
+            return true;
+        }
+        let labels = &self.labels[self.offsets[source]..self.offsets[source + 1]];
+        match labels.binary_search_by_key(&self.chain[target], |&(chain, _)| chain) {
+            Ok(index) => labels[index].1 <= self.position[target],
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the number of nodes of the graph.
+    pub fn node_count(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns the number of chains of the decomposition, which bounds the number of labels
+    /// stored for each node.
+    pub fn chain_count(&self) -> usize {
+        self.chain_count
+    }
+}
+
+// Numbers the nodes in preorder and postorder along a depth-first forest, starting from
+// the nodes in topological order so that every root is a source or not yet visited
+fn dfs_intervals(sorted: &[u32], successors: &[Vec<u32>]) -> (Vec<u32>, Vec<u32>) {
+    let n = sorted.len();
+    let mut pre = vec![UNREACHABLE; n];
+    let mut post = vec![0; n];
+    let (mut pre_counter, mut post_counter) = (0, 0);
+
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    for &root in sorted {
+        if pre[root as usize] != UNREACHABLE {
+            continue;
+        }
+        pre[root as usize] = pre_counter;
+        pre_counter += 1;
+        stack.push((root as usize, 0));
+
+        while let Some((key, next)) = stack.last_mut() {
+            match successors[*key].get(*next) {
+                Some(&successor) => {
+                    *next += 1;
+                    let successor = successor as usize;
+                    if pre[successor] == UNREACHABLE {
+                        pre[successor] = pre_counter;
+                        pre_counter += 1;
+                        stack.push((successor, 0));
+                    }
+                }
+                None => {
+                    post[*key] = post_counter;
+                    post_counter += 1;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    (pre, post)
+}
+
+// Splits the nodes into paths of the graph: every node not yet on a chain, in topological
+// order, starts a chain that follows the first free successor for as long as there is one
+fn chain_decomposition(sorted: &[u32], successors: &[Vec<u32>]) -> (Vec<u32>, Vec<u32>, usize) {
+    let n = sorted.len();
+    let mut chain = vec![UNREACHABLE; n];
+    let mut position = vec![0; n];
+    let mut chain_count = 0;
+
+    for &start in sorted {
+        let mut key = start as usize;
+        if chain[key] != UNREACHABLE {
+            continue;
+        }
+        let mut length = 0;
+        loop {
+            chain[key] = chain_count;
+            position[key] = length;
+            length += 1;
+            match successors[key]
+                .iter()
+                .find(|&&successor| chain[successor as usize] == UNREACHABLE)
+            {
+                Some(&successor) => key = successor as usize,
+                None => break,
+            }
+        }
+        chain_count += 1;
+    }
+    (chain, position, chain_count as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transitive_closure;
+    use ade_common::assert_panics_with;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph::utils::strategies::arb_dag;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_queries_across_chains() {
+        // Two chains 0 → 1 → 2 and 3 → 4 → 5, crossed by 1 → 4 and 3 → 2
+        let graph = build_graph::<Node, Edge>(
+            (0..6).collect(),
+            vec![(0, 1), (1, 2), (3, 4), (4, 5), (1, 4), (3, 2)],
+        );
+        let index = ReachabilityIndex::new(&graph).unwrap();
+
+        assert_eq!(index.node_count(), 6);
+        assert_eq!(index.chain_count(), 2);
+        assert!(index.can_reach(0, 5) && index.can_reach(3, 2));
+        assert!(!index.can_reach(4, 2) && !index.can_reach(0, 3));
+        assert!(!index.can_reach(2, 2));
+    }
+
+    #[test]
+    fn test_errors_and_panics() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+        assert_eq!(
+            ReachabilityIndex::new(&graph).unwrap_err(),
+            TopoSortError::CycleDetected {
+                witness: vec![0, 1, 0],
+                cyclic_nodes: vec![0, 1]
+            }
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_eq!(
+            ReachabilityIndex::new(&graph).unwrap_err(),
+            TopoSortError::NonSequentialKeys {
+                key: 2,
+                node_count: 2
+            }
+        );
+
+        let index = ReachabilityIndex::new(&build_graph::<Node, Edge>(vec![0], vec![])).unwrap();
+        assert_panics_with!(index.can_reach(0, 1), "Node 1 not found");
+    }
+
+    #[test]
+    fn test_wide_dag_stays_small() {
+        // A root above 20 000 leaves, each on its own chain: a dense table would hold
+        // 400 million labels
+        let n = 20_001;
+        let graph =
+            build_graph::<Node, Edge>((0..n).collect(), (1..n).map(|leaf| (0, leaf)).collect());
+        let index = ReachabilityIndex::new(&graph).unwrap();
+
+        assert_eq!(index.chain_count(), n as usize - 1);
+        assert!(index.labels.len() < n as usize);
+        assert!(index.can_reach(0, n - 1));
+        assert!(!index.can_reach(1, 2));
+    }
+
+    proptest! {
+        #[test]
+        fn test_index_matches_transitive_closure(graph in arb_dag::<Node, Edge>(40, 120)) {
+            let index = ReachabilityIndex::new(&graph).unwrap();
+            let closure = transitive_closure(&graph);
+            let n = graph.node_count() as u32;
+            for source in 0..n {
+                for target in 0..n {
+                    prop_assert_eq!(
+                        index.can_reach(source, target),
+                        closure.reaches(source, target)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod closure;
+pub mod index;
+pub mod reduction;
+
+pub use closure::{transitive_closure, TransitiveClosure};
+pub use index::ReachabilityIndex;
+pub use reduction::transitive_reduction;
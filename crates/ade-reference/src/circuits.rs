@@ -0,0 +1,98 @@
+use crate::reachability::sorted_keys;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Enumerates the elementary circuits of a graph by exploring every simple path.
+///
+/// Each circuit is found once, from its smallest node: for every start node, all the simple
+/// paths through larger nodes are followed, and a circuit is recorded whenever a path can
+/// close back on the start. Keys do not need to be sequential. Runs in exponential time and
+/// is only meant for small graphs.
+///
+/// Circuits use the same format as `ade-elementary-circuits`: the first node is repeated at
+/// the end, so a self-loop on `v` is `[v, v]`. Each circuit starts from its smallest key,
+/// and circuits are sorted.
+///
+/// # Examples
+///
+/// ```
+/// use ade_reference::elementary_circuits;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2],
+///     vec![(0, 1), (1, 0), (1, 2), (2, 0), (2, 2)],
+/// );
+///
+/// assert_eq!(
+///     elementary_circuits(&graph),
+///     vec![vec![0, 1, 0], vec![0, 1, 2, 0], vec![2, 2]]
+/// );
+/// ```
+pub fn elementary_circuits<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<Vec<u32>> {
+    let mut circuits = Vec::new();
+    for start in sorted_keys(graph) {
+        let mut path = vec![start];
+        extend_path(graph, &mut path, &mut circuits);
+    }
+    circuits.sort();
+    circuits
+}
+
+// Follows every simple path that extends `path` through nodes larger than its start
+fn extend_path<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    path: &mut Vec<u32>,
+    circuits: &mut Vec<Vec<u32>>,
+) {
+    let start = path[0];
+    let last = path[path.len() - 1];
+
+    let mut successors: Vec<u32> = graph.get_successors_keys(last).collect();
+    successors.sort_unstable();
+    for next in successors {
+        if next == start {
+            let mut circuit = path.clone();
+            circuit.push(start);
+            circuits.push(circuit);
+        } else if next > start && !path.contains(&next) {
+            path.push(next);
+            extend_path(graph, path, circuits);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_complete_graph() {
+        // A complete graph on 4 nodes has 6 + 8 + 6 circuits of length 2, 3 and 4
+        let nodes: Vec<u32> = (0..4).collect();
+        let edges = nodes
+            .iter()
+            .flat_map(|&i| nodes.iter().filter(move |&&j| i != j).map(move |&j| (i, j)))
+            .collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        assert_eq!(elementary_circuits(&graph).len(), 20);
+    }
+
+    #[test]
+    fn test_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![10, 30, 20], vec![(30, 10), (10, 20), (20, 30)]);
+        assert_eq!(elementary_circuits(&graph), vec![vec![10, 20, 30, 10]]);
+    }
+
+    #[test]
+    fn test_acyclic_graph() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (0, 2)]);
+        assert!(elementary_circuits(&graph).is_empty());
+    }
+}
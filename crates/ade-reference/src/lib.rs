@@ -0,0 +1,7 @@
+pub mod circuits;
+pub mod reachability;
+pub mod scc;
+
+pub use circuits::elementary_circuits;
+pub use reachability::{is_reachable, transitive_closure};
+pub use scc::strongly_connected_components;
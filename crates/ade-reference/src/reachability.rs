@@ -0,0 +1,137 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Computes the transitive closure of a graph with the Floyd–Warshall algorithm.
+///
+/// Maps every node to the set of nodes reachable from it through a path of at least one
+/// edge: a node only reaches itself if it lies on a cycle, self-loops included. Keys do not
+/// need to be sequential. Runs in O(n³) time and O(n²) memory.
+///
+/// # Examples
+///
+/// ```
+/// use ade_reference::transitive_closure;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use std::collections::BTreeSet;
+///
+/// let graph = build_graph::<Node, Edge>(vec![3, 5, 9], vec![(3, 5), (5, 9), (9, 9)]);
+/// let closure = transitive_closure(&graph);
+///
+/// assert_eq!(closure[&3], BTreeSet::from([5, 9]));
+/// assert_eq!(closure[&9], BTreeSet::from([9]));
+/// ```
+pub fn transitive_closure<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> BTreeMap<u32, BTreeSet<u32>> {
+    let keys = sorted_keys(graph);
+    let index: BTreeMap<u32, usize> = keys.iter().enumerate().map(|(i, &k)| (k, i)).collect();
+    let n = keys.len();
+
+    let mut reach = vec![vec![false; n]; n];
+    for edge in graph.get_edges() {
+        reach[index[&edge.source()]][index[&edge.target()]] = true;
+    }
+    for k in 0..n {
+        let through = reach[k].clone();
+        for row in reach.iter_mut().filter(|row| row[k]) {
+            for (cell, &next) in row.iter_mut().zip(&through) {
+                *cell |= next;
+            }
+        }
+    }
+
+    keys.iter()
+        .enumerate()
+        .map(|(i, &key)| {
+            let reachable = (0..n).filter(|&j| reach[i][j]).map(|j| keys[j]).collect();
+            (key, reachable)
+        })
+        .collect()
+}
+
+/// Returns `true` if `target` can be reached from `source` through at least one edge.
+///
+/// Explores every path from `source` with a plain depth-first search.
+///
+/// # Panics
+///
+/// Panics if `source` is not a node of the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_reference::is_reachable;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+///
+/// assert!(is_reachable(&graph, 0, 2));
+/// assert!(!is_reachable(&graph, 2, 0));
+/// assert!(!is_reachable(&graph, 0, 0));
+/// ```
+pub fn is_reachable<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    source: u32,
+    target: u32,
+) -> bool {
+    if !graph.has_node(source) {
+        panic!("Node {} not found", source);
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut stack: Vec<u32> = graph.get_successors_keys(source).collect();
+    while let Some(key) = stack.pop() {
+        if key == target {
+            return true;
+        }
+        if visited.insert(key) {
+            stack.extend(graph.get_successors_keys(key));
+        }
+    }
+    false
+}
+
+pub(crate) fn sorted_keys<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<u32> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_closure_matches_is_reachable() {
+        let graph = build_graph::<Node, Edge>(
+            vec![1, 4, 6, 8, 10],
+            vec![(1, 4), (4, 6), (6, 4), (8, 8), (10, 1), (6, 10)],
+        );
+        let closure = transitive_closure(&graph);
+
+        for source in graph.get_node_keys() {
+            for target in graph.get_node_keys() {
+                assert_eq!(
+                    closure[&source].contains(&target),
+                    is_reachable(&graph, source, target),
+                    "{} -> {}",
+                    source,
+                    target
+                );
+            }
+        }
+        assert_eq!(closure[&8], BTreeSet::from([8]));
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        assert!(transitive_closure(&graph).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::reachability::transitive_closure;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::BTreeSet;
+
+/// Finds the strongly connected components of a graph from its transitive closure.
+///
+/// Two distinct nodes belong to the same component when each one can reach the other;
+/// every node belongs to a component, possibly alone. Keys do not need to be sequential.
+/// Runs in O(n³) time.
+///
+/// Components are returned in ascending order of their smallest key, and keys are sorted
+/// within each component, so results can be compared with `==` after sorting the output of
+/// the algorithm under test the same way.
+///
+/// # Examples
+///
+/// ```
+/// use ade_reference::strongly_connected_components;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![7, 2, 4], vec![(2, 7), (7, 2), (7, 4)]);
+///
+/// assert_eq!(strongly_connected_components(&graph), vec![vec![2, 7], vec![4]]);
+/// ```
+pub fn strongly_connected_components<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<Vec<u32>> {
+    let closure = transitive_closure(graph);
+
+    let mut assigned = BTreeSet::new();
+    let mut components = Vec::new();
+    for (&key, reachable) in &closure {
+        if assigned.contains(&key) {
+            continue;
+        }
+
+        // Keys are visited in ascending order, so `key` is the smallest of its component
+        let mut component = vec![key];
+        component.extend(
+            reachable
+                .iter()
+                .copied()
+                .filter(|&other| other != key && closure[&other].contains(&key)),
+        );
+        component.sort_unstable();
+
+        assigned.extend(component.iter().copied());
+        components.push(component);
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_components() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (5, 5)],
+        );
+
+        assert_eq!(
+            strongly_connected_components(&graph),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        assert!(strongly_connected_components(&graph).is_empty());
+    }
+}
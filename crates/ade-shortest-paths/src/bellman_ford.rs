@@ -0,0 +1,233 @@
+use crate::{ShortestPaths, Weight};
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+use std::fmt;
+
+/// The error returned by [`bellman_ford`] when a cycle of negative total weight is
+/// reachable from the source, so that shortest paths are not defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle {
+    cycle: Path,
+}
+
+impl NegativeCycle {
+    /// Returns the negative cycle, as a closed path starting and ending at its smallest key.
+    pub fn cycle(&self) -> &Path {
+        &self.cycle
+    }
+
+    /// Consumes the error and returns the negative cycle.
+    pub fn into_cycle(self) -> Path {
+        self.cycle
+    }
+}
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Negative cycle {}", self.cycle)
+    }
+}
+
+impl std::error::Error for NegativeCycle {}
+
+/// Finds the shortest paths from a source node to every node of a graph whose edge weights
+/// may be negative, using the Bellman-Ford algorithm.
+///
+/// Each edge is weighted by `weight`. The algorithm relaxes every edge up to `n` times, so
+/// it runs in `O(n · m)` time, and stops as soon as a round changes nothing.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Errors
+///
+/// Returns a [`NegativeCycle`] with one of the cycles of negative total weight reachable
+/// from the source, if any. Negative cycles that cannot be reached from the source do not
+/// affect the result.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys, and if
+/// `source` is not a node of the graph.
+///
+/// # Examples
+///
+/// ```
+/// use ade_shortest_paths::bellman_ford;
+/// use ade_graph::build::build_graph_weighted;
+/// use ade_graph::implementations::{Node, WeightedEdge};
+/// use ade_graph::WeightedEdgeTrait;
+///
+/// let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1, 4), (0, 2, 1), (2, 1, -2), (1, 3, 1)],
+/// );
+///
+/// let paths = bellman_ford(&graph, 0, |edge| edge.weight()).unwrap();
+/// assert_eq!(paths.distance(3), Some(0));
+/// assert_eq!(paths.path_to(3).unwrap(), vec![0, 2, 1, 3]);
+/// ```
+///
+/// ```
+/// use ade_shortest_paths::bellman_ford;
+/// use ade_graph::build::build_graph_weighted;
+/// use ade_graph::implementations::{Node, WeightedEdge};
+/// use ade_graph::WeightedEdgeTrait;
+///
+/// // 1 → 2 → 3 → 1 has total weight -1
+/// let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+///     vec![0, 1, 2, 3],
+///     vec![(0, 1, 1), (1, 2, 1), (2, 3, -4), (3, 1, 2)],
+/// );
+///
+/// let error = bellman_ford(&graph, 0, |edge| edge.weight()).unwrap_err();
+/// assert_eq!(error.cycle(), &vec![1, 2, 3, 1]);
+/// ```
+pub fn bellman_ford<N: NodeTrait, E: EdgeTrait, W: Weight>(
+    graph: &impl GraphViewTrait<N, E>,
+    source: u32,
+    weight: impl Fn(&E) -> W,
+) -> Result<ShortestPaths<W>, NegativeCycle> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+    if !graph.has_node(source) {
+        panic!("Node {} not found", source);
+    }
+
+    let n = graph.node_count();
+    let edges: Vec<(u32, u32, W)> = graph
+        .get_edges()
+        .map(|edge| (edge.source(), edge.target(), weight(edge)))
+        .collect();
+    let mut paths = ShortestPaths::new(source, n);
+
+    // Without negative cycles, n - 1 rounds are enough; a change in the n-th round proves
+    // that a negative cycle is reachable
+    let mut relaxed = None;
+    for _ in 0..n {
+        relaxed = None;
+        for &(u, v, w) in &edges {
+            if paths.relax(u, v, w) {
+                relaxed = Some(v);
+            }
+        }
+        if relaxed.is_none() {
+            return Ok(paths);
+        }
+    }
+
+    let cycle = negative_cycle(&paths, relaxed.unwrap(), n);
+    Err(NegativeCycle { cycle })
+}
+
+// Follows predecessors from a node relaxed in the last round, which leads into a negative
+// cycle after at most n steps, and collects that cycle
+fn negative_cycle<W: Weight>(paths: &ShortestPaths<W>, relaxed: u32, n: usize) -> Path {
+    let predecessor = |key: u32| {
+        paths
+            .predecessor(key)
+            .expect("Nodes relaxed in the last round lead back to a negative cycle")
+    };
+
+    let mut start = relaxed;
+    for _ in 0..n {
+        start = predecessor(start);
+    }
+
+    let mut keys = vec![start];
+    let mut key = predecessor(start);
+    while key != start {
+        keys.push(key);
+        key = predecessor(key);
+    }
+    keys.push(start);
+    keys.reverse();
+    Path::new(keys).rotated_to_min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::build::{build_graph, build_graph_weighted};
+    use ade_graph::implementations::{Edge, Node, WeightedEdge};
+    use ade_traits::WeightedEdgeTrait;
+
+    #[test]
+    fn test_unweighted_distances() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (0, 2)]);
+        let paths = bellman_ford(&graph, 0, |_| 1u32).unwrap();
+
+        assert_eq!(paths.source(), 0);
+        assert_eq!(paths.distances(), &[Some(0), Some(1), Some(1), None]);
+        assert_eq!(paths.path_to(0).unwrap(), vec![0]);
+        assert_eq!(paths.path_to(2).unwrap(), vec![0, 2]);
+        assert_eq!(paths.path_to(3), None);
+    }
+
+    #[test]
+    fn test_negative_edges_without_cycle() {
+        let graph = build_graph_weighted::<Node, WeightedEdge<f64>>(
+            vec![0, 1, 2, 3],
+            vec![
+                (0, 1, 2.0),
+                (1, 2, -1.5),
+                (0, 2, 1.0),
+                (2, 3, 0.5),
+                (3, 1, 3.0),
+            ],
+        );
+        let paths = bellman_ford(&graph, 0, |e| e.weight()).unwrap();
+
+        assert_eq!(paths.distance(2), Some(0.5));
+        assert_eq!(paths.distance(3), Some(1.0));
+        let path = paths.path_to(3).unwrap();
+        assert_eq!(path, vec![0, 1, 2, 3]);
+        assert_eq!(path.total_weight(&graph), 1.0);
+    }
+
+    #[test]
+    fn test_negative_cycles() {
+        // Negative self-loop
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+            vec![0, 1],
+            vec![(0, 1, 1), (1, 1, -1)],
+        );
+        let error = bellman_ford(&graph, 0, |e| e.weight()).unwrap_err();
+        assert_eq!(error.into_cycle(), vec![1, 1]);
+
+        // Negative cycle through the source
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+            vec![0, 1, 2],
+            vec![(0, 1, -1), (1, 2, -1), (2, 0, -1)],
+        );
+        let error = bellman_ford(&graph, 1, |e| e.weight()).unwrap_err();
+        assert_eq!(error.cycle(), &vec![0, 1, 2, 0]);
+        assert_eq!(error.to_string(), "Negative cycle 0 → 1 → 2 → 0");
+        assert!(error.cycle().total_weight(&graph) < 0);
+    }
+
+    #[test]
+    fn test_unreachable_negative_cycle() {
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+            vec![0, 1, 2],
+            vec![(1, 2, -1), (2, 1, -1)],
+        );
+        let paths = bellman_ford(&graph, 0, |e| e.weight()).unwrap();
+        assert_eq!(paths.distances(), &[Some(0), None, None]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        assert_panics_with!(bellman_ford(&graph, 2, |_| 1), "Node 2 not found");
+
+        let paths = bellman_ford(&graph, 0, |_| 1).unwrap();
+        assert_panics_with!(paths.distance(5), "Node 5 not found");
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(bellman_ford(&graph, 1, |_| 1), INVALID_KEY_SEQUENCE);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{bellman_ford, NegativeCycle, Weight};
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+
+/// The shortest path distances between all pairs of nodes of a graph, computed by
+/// [`floyd_warshall`].
+///
+/// Distances are stored in a flat row-major matrix indexed by node keys, which must be
+/// sequential: the distance from `source` to `target` is at index `source * n + target`.
+/// The matrix also records the first step of a shortest path for each pair, so that the
+/// paths themselves can be rebuilt with [`path`](DistanceMatrix::path).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<W> {
+    node_count: usize,
+    distances: Vec<Option<W>>,
+    next: Vec<Option<u32>>,
+}
+
+impl<W: Weight> DistanceMatrix<W> {
+    /// Returns the number of nodes, the number of rows and columns of the matrix.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns the length of the shortest path from `source` to `target`, or `None` if
+    /// `target` is not reachable from `source`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` is not a node of the graph.
+    pub fn distance(&self, source: u32, target: u32) -> Option<W> {
+        self.distances[self.index(source, target)]
+    }
+
+    /// Returns the flat distance matrix, in row-major order.
+    pub fn distances(&self) -> &[Option<W>] {
+        &self.distances
+    }
+
+    /// Consumes the matrix and returns the flat distances, in row-major order.
+    pub fn into_distances(self) -> Vec<Option<W>> {
+        self.distances
+    }
+
+    /// Returns a shortest path from `source` to `target`, or `None` if `target` is not
+    /// reachable from `source`. The path from a node to itself has a single node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` is not a node of the graph.
+    pub fn path(&self, source: u32, target: u32) -> Option<Path> {
+        self.distance(source, target)?;
+
+        let mut keys = vec![source];
+        let mut key = source;
+        while key != target {
+            key = self.next[self.index(key, target)].expect("Reachable pairs have a next step");
+            keys.push(key);
+        }
+        Some(Path::new(keys))
+    }
+
+    // Position of the pair in the flat matrices
+    fn index(&self, source: u32, target: u32) -> usize {
+        for key in [source, target] {
+            if key as usize >= self.node_count {
+                panic!("Node {} not found", key);
+            }
+        }
+        source as usize * self.node_count + target as usize
+    }
+}
+
+/// Finds the shortest paths between all pairs of nodes of a graph, using the
+/// Floyd–Warshall algorithm.
+///
+/// Each edge is weighted by `weight`, and weights may be negative. The algorithm runs in
+/// `O(n³)` time and stores two `n × n` matrices, so it suits small and dense graphs, for
+/// instance to compute layout quality metrics or centralities.
+///
+/// # Requirements
+///
+/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
+///
+/// # Errors
+///
+/// Returns a [`NegativeCycle`] if the graph has a cycle of negative total weight.
+///
+/// # Panics
+///
+/// Panics with [`INVALID_KEY_SEQUENCE`] if the graph does not have sequential keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_shortest_paths::floyd_warshall;
+/// use ade_graph::build::build_graph;
+/// use ade_graph::implementations::{Node, Edge};
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2)]);
+/// let matrix = floyd_warshall(&graph, |_| 1).unwrap();
+///
+/// assert_eq!(matrix.distances(), &[
+///     Some(0), Some(1), Some(2),
+///     None,    Some(0), Some(1),
+///     None,    None,    Some(0),
+/// ]);
+/// assert_eq!(matrix.path(0, 2).unwrap(), vec![0, 1, 2]);
+/// assert_eq!(matrix.path(2, 0), None);
+/// ```
+pub fn floyd_warshall<N: NodeTrait, E: EdgeTrait, W: Weight>(
+    graph: &impl GraphViewTrait<N, E>,
+    weight: impl Fn(&E) -> W,
+) -> Result<DistanceMatrix<W>, NegativeCycle> {
+    if !graph.has_sequential_keys() {
+        panic!("{}", INVALID_KEY_SEQUENCE);
+    }
+
+    let n = graph.node_count();
+    let zero = W::default();
+    let mut distances: Vec<Option<W>> = vec![None; n * n];
+    let mut next: Vec<Option<u32>> = vec![None; n * n];
+
+    for key in 0..n {
+        distances[key * n + key] = Some(zero);
+        next[key * n + key] = Some(key as u32);
+    }
+    for edge in graph.get_edges() {
+        let w = weight(edge);
+        let index = edge.source() as usize * n + edge.target() as usize;
+        // A self-loop only shortens the empty path if it is negative
+        if edge.source() != edge.target() || w < zero {
+            distances[index] = Some(w);
+            next[index] = Some(edge.target());
+        }
+    }
+
+    // A node at negative distance from itself is on a negative closed walk, which
+    // contains a negative cycle reachable from it. Distances along such walks shrink
+    // exponentially with k, so the check runs after each pass, before they overflow.
+    let negative_node = |distances: &[Option<W>]| {
+        (0..n).find(|&key| distances[key * n + key].is_some_and(|d| d < zero))
+    };
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = distances[i * n + k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(kj) = distances[k * n + j] else {
+                    continue;
+                };
+                let candidate = ik + kj;
+                if distances[i * n + j].is_none_or(|ij| candidate < ij) {
+                    distances[i * n + j] = Some(candidate);
+                    next[i * n + j] = next[i * n + k];
+                }
+            }
+        }
+
+        if let Some(key) = negative_node(&distances) {
+            let cycle = bellman_ford(graph, key as u32, weight)
+                .expect_err("A node at negative distance from itself reaches a negative cycle");
+            return Err(cycle);
+        }
+    }
+
+    Ok(DistanceMatrix {
+        node_count: n,
+        distances,
+        next,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+    use ade_graph::build::{build_graph, build_graph_weighted};
+    use ade_graph::implementations::{Edge, Node, WeightedEdge};
+    use ade_traits::WeightedEdgeTrait;
+
+    #[test]
+    fn test_matches_bellman_ford() {
+        let graph = build_graph_weighted::<Node, WeightedEdge<i64>>(
+            vec![0, 1, 2, 3, 4],
+            vec![
+                (0, 1, 3),
+                (0, 2, 8),
+                (0, 4, -4),
+                (1, 3, 1),
+                (1, 4, 7),
+                (2, 1, 4),
+                (3, 0, 2),
+                (3, 2, -5),
+                (4, 3, 6),
+                (2, 2, 5),
+            ],
+        );
+        let matrix = floyd_warshall(&graph, |e| e.weight()).unwrap();
+
+        for source in 0..5 {
+            let paths = bellman_ford(&graph, source, |e| e.weight()).unwrap();
+            for target in 0..5 {
+                assert_eq!(matrix.distance(source, target), paths.distance(target));
+                let path = matrix.path(source, target).unwrap();
+                assert_eq!(path.first(), Some(source));
+                assert_eq!(path.last(), Some(target));
+                assert_eq!(path.validate(&graph), Ok(()));
+                assert_eq!(Some(path.total_weight(&graph)), paths.distance(target));
+            }
+        }
+        assert_eq!(matrix.distance(2, 2), Some(0));
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(
+            vec![0, 1, 2, 3],
+            vec![(0, 1, 1), (2, 3, -2), (3, 2, 1)],
+        );
+        let error = floyd_warshall(&graph, |e| e.weight()).unwrap_err();
+        assert_eq!(error.into_cycle(), vec![2, 3, 2]);
+
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>(vec![0], vec![(0, 0, -1)]);
+        let error = floyd_warshall(&graph, |e| e.weight()).unwrap_err();
+        assert_eq!(error.into_cycle(), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_dense_negative_graph_does_not_overflow() {
+        let n = 40;
+        let edges = (0..n)
+            .flat_map(|u| (0..n).filter(move |&v| v != u).map(move |v| (u, v, -1)))
+            .collect();
+        let graph = build_graph_weighted::<Node, WeightedEdge<i32>>((0..n).collect(), edges);
+        let cycle = floyd_warshall(&graph, |e| e.weight())
+            .unwrap_err()
+            .into_cycle();
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(cycle.len() >= 3);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+        let matrix = floyd_warshall(&graph, |_| 1).unwrap();
+        assert_eq!(matrix.node_count(), 2);
+        assert_panics_with!(matrix.distance(0, 2), "Node 2 not found");
+        assert_eq!(
+            matrix.into_distances(),
+            vec![Some(0), Some(1), None, Some(0)]
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        assert_panics_with!(floyd_warshall(&graph, |_| 1), INVALID_KEY_SEQUENCE);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod bellman_ford;
+pub mod floyd_warshall;
+pub mod shortest_paths;
+pub mod weight;
+
+pub use bellman_ford::{bellman_ford, NegativeCycle};
+pub use floyd_warshall::{floyd_warshall, DistanceMatrix};
+pub use shortest_paths::ShortestPaths;
+pub use weight::Weight;
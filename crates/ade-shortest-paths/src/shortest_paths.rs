@@ -0,0 +1,89 @@
+use crate::Weight;
+use ade_traits::Path;
+
+/// The shortest paths from a source node to every other node of a graph.
+///
+/// Nodes are indexed by their keys, which must be sequential. For each node reachable from
+/// the source, the structure stores its distance from the source and its predecessor along
+/// one shortest path, from which the whole path is rebuilt by
+/// [`path_to`](ShortestPaths::path_to).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPaths<W> {
+    source: u32,
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<u32>>,
+}
+
+impl<W: Weight> ShortestPaths<W> {
+    // Creates the initial state of a search from source, where only source is reached
+    pub(crate) fn new(source: u32, node_count: usize) -> Self {
+        let mut distances = vec![None; node_count];
+        distances[source as usize] = Some(W::default());
+        Self {
+            source,
+            distances,
+            predecessors: vec![None; node_count],
+        }
+    }
+
+    // Records that target is reached through source with the given distance, if shorter
+    pub(crate) fn relax(&mut self, source: u32, target: u32, weight: W) -> bool {
+        let Some(distance) = self.distances[source as usize] else {
+            return false;
+        };
+        let candidate = distance + weight;
+        let current = &mut self.distances[target as usize];
+        if current.is_some_and(|current| current <= candidate) {
+            return false;
+        }
+        *current = Some(candidate);
+        self.predecessors[target as usize] = Some(source);
+        true
+    }
+
+    pub(crate) fn predecessor(&self, key: u32) -> Option<u32> {
+        self.predecessors[key as usize]
+    }
+
+    /// Returns the key of the source node.
+    pub fn source(&self) -> u32 {
+        self.source
+    }
+
+    /// Returns the length of the shortest path from the source to `target`, or `None` if
+    /// `target` is not reachable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` is not a node of the graph.
+    pub fn distance(&self, target: u32) -> Option<W> {
+        *self
+            .distances
+            .get(target as usize)
+            .unwrap_or_else(|| panic!("Node {} not found", target))
+    }
+
+    /// Returns the distances from the source, indexed by node key.
+    pub fn distances(&self) -> &[Option<W>] {
+        &self.distances
+    }
+
+    /// Returns a shortest path from the source to `target`, or `None` if `target` is not
+    /// reachable. The path to the source itself has a single node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` is not a node of the graph.
+    pub fn path_to(&self, target: u32) -> Option<Path> {
+        self.distance(target)?;
+
+        let mut keys = vec![target];
+        let mut key = target;
+        while key != self.source {
+            key = self.predecessors[key as usize].expect("Reachable nodes have a predecessor");
+            keys.push(key);
+        }
+        keys.reverse();
+        Some(Path::new(keys))
+    }
+}
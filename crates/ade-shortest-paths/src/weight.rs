@@ -0,0 +1,14 @@
+use std::fmt::Debug;
+use std::ops::Add;
+
+/// The requirements on edge weights and path lengths of shortest path algorithms.
+///
+/// A weight can be added and compared, and its [`Default`] value is the length of an empty
+/// path. It is implemented for every type with these properties, including all the
+/// primitive integer and floating point types.
+///
+/// Comparisons involving `NaN` are always false, so `NaN` floating point weights give
+/// unspecified results.
+pub trait Weight: Copy + Debug + PartialOrd + Add<Output = Self> + Default {}
+
+impl<W: Copy + Debug + PartialOrd + Add<Output = W> + Default> Weight for W {}
@@ -3,6 +3,7 @@ use ade_graph::utils::build::build_graph;
 use ade_graph_generators::generate_random_graph_data;
 use ade_strongly_connected_components::scc;
 use ade_strongly_connected_components::scc_iterative;
+use ade_strongly_connected_components::scc_tarjan;
 use criterion::{criterion_group, criterion_main, Criterion};
 use std::hint::black_box;
 
@@ -30,9 +31,22 @@ fn benchmark_strongly_connected_components_iter(c: &mut Criterion) {
     });
 }
 
+fn benchmark_strongly_connected_components_tarjan(c: &mut Criterion) {
+    let (nodes, edges) = generate_random_graph_data(20, 100, 123);
+    let graph = build_graph::<Node, Edge>(nodes, edges);
+
+    c.bench_function("scc_20_nodes_100_edges_tarjan", |b| {
+        b.iter(|| {
+            let components = scc_tarjan(black_box(&graph));
+            black_box(components.len())
+        })
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_strongly_connected_components,
-    benchmark_strongly_connected_components_iter
+    benchmark_strongly_connected_components_iter,
+    benchmark_strongly_connected_components_tarjan
 );
 criterion_main!(benches);
@@ -0,0 +1,71 @@
+use crate::{scc, scc_iterative, scc_tarjan, SmallGraph};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// The algorithms that [`scc_with`] can use to find strongly connected components.
+///
+/// All of them return the same components, in possibly different orders, and run in
+/// `O(n + m)` time, so the choice only matters for performance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SccAlgorithm {
+    /// Pearce's recursive algorithm, [`scc`]. Very deep graphs may overflow the stack.
+    PearceRecursive,
+    /// Pearce's iterative algorithm, [`scc_iterative`], the fastest on large graphs with
+    /// sequential keys.
+    #[default]
+    PearceIterative,
+    /// Tarjan's algorithm, [`scc_tarjan`].
+    Tarjan,
+    /// The bitmask kernel of [`SmallGraph`] on graphs with at most
+    /// [`SMALL_GRAPH_MAX_NODES`](crate::SMALL_GRAPH_MAX_NODES) nodes and sequential keys,
+    /// and [`PearceIterative`](Self::PearceIterative) on other graphs. The fastest on
+    /// tiny graphs, with components in ascending order of their smallest key.
+    Bitmask,
+}
+
+impl SccAlgorithm {
+    /// All the algorithms, to run or compare them in turn.
+    pub const ALL: [SccAlgorithm; 4] = [
+        SccAlgorithm::PearceRecursive,
+        SccAlgorithm::PearceIterative,
+        SccAlgorithm::Tarjan,
+        SccAlgorithm::Bitmask,
+    ];
+}
+
+/// Finds all strongly connected components of a graph with the given algorithm.
+///
+/// # Returns
+///
+/// A vector of strongly connected components, each a vector of node keys. The order of
+/// components and the order of nodes within each component depend on the algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::{scc_with, SccAlgorithm};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 0), (1, 2)]);
+///
+/// for algorithm in SccAlgorithm::ALL {
+///     let mut components = scc_with(&graph, algorithm);
+///     components.iter_mut().for_each(|c| c.sort_unstable());
+///     components.sort_unstable();
+///     assert_eq!(components, vec![vec![0, 1], vec![2]]);
+/// }
+/// ```
+pub fn scc_with<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    algorithm: SccAlgorithm,
+) -> Vec<Vec<u32>> {
+    match algorithm {
+        SccAlgorithm::PearceRecursive => scc(graph),
+        SccAlgorithm::PearceIterative => scc_iterative(graph),
+        SccAlgorithm::Tarjan => scc_tarjan(graph),
+        SccAlgorithm::Bitmask => match SmallGraph::new(graph) {
+            Some(small) => small.scc(),
+            None => scc_iterative(graph),
+        },
+    }
+}
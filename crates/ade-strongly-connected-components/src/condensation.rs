@@ -0,0 +1,165 @@
+use crate::scc_iterative;
+use ade_graph::implementations::Graph;
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+
+/// Builds the condensation of a graph, the directed acyclic graph of its strongly
+/// connected components.
+///
+/// The condensation has one node per component and an edge from a component to another
+/// whenever an edge of the graph joins their nodes. Edges within a component are dropped
+/// and edges between the same two components are merged, so the condensation has no
+/// self-loops. New nodes and edges are created with [`NodeTrait::new`] and
+/// [`EdgeTrait::new`].
+///
+/// Components are numbered in topological order: every edge of the condensation goes from
+/// a component to a component with a larger key, so the keys `0, 1, 2, ...` are already a
+/// topological order of the components. Among the valid numberings, the one where
+/// components with smaller nodes come first is chosen, so the result is deterministic.
+/// Keys of the graph need not be sequential.
+///
+/// # Returns
+///
+/// The condensation, with sequential keys, and the members of each component, indexed by
+/// component key, each in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::condensation;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::GraphViewTrait;
+///
+/// // 3 → 1 → 2 → 1, and both 3 and the cycle lead to 0
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3],
+///     vec![(3, 1), (1, 2), (2, 1), (2, 0), (3, 0)],
+/// );
+/// let (dag, members) = condensation(&graph);
+///
+/// assert_eq!(members, vec![vec![3], vec![1, 2], vec![0]]);
+/// assert_eq!(dag.node_count(), 3);
+/// assert!(dag.has_edge(0, 1) && dag.has_edge(0, 2) && dag.has_edge(1, 2));
+/// assert_eq!(dag.get_edges().count(), 3);
+/// ```
+pub fn condensation<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> (Graph<N, E>, Vec<Vec<u32>>) {
+    // Components numbered by their smallest node
+    let mut components = scc_iterative(graph);
+    for component in &mut components {
+        component.sort_unstable();
+    }
+    components.sort_unstable_by_key(|component| component[0]);
+    let component_of: HashMap<u32, usize> = components
+        .iter()
+        .enumerate()
+        .flat_map(|(i, component)| component.iter().map(move |&key| (key, i)))
+        .collect();
+
+    let edges: BTreeSet<(usize, usize)> = graph
+        .get_edges()
+        .map(|edge| (component_of[&edge.source()], component_of[&edge.target()]))
+        .filter(|(source, target)| source != target)
+        .collect();
+
+    // Smallest topological order of the components
+    let mut successors = vec![Vec::new(); components.len()];
+    let mut in_degrees = vec![0; components.len()];
+    for &(source, target) in &edges {
+        successors[source].push(target);
+        in_degrees[target] += 1;
+    }
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..components.len())
+        .filter(|&i| in_degrees[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut rank = vec![0; components.len()];
+    let mut order = Vec::with_capacity(components.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        rank[i] = order.len() as u32;
+        order.push(i);
+        for &successor in &successors[i] {
+            in_degrees[successor] -= 1;
+            if in_degrees[successor] == 0 {
+                ready.push(Reverse(successor));
+            }
+        }
+    }
+
+    let nodes = (0..components.len() as u32).map(N::new).collect();
+    let edges = edges
+        .into_iter()
+        .map(|(source, target)| E::new(rank[source], rank[target]))
+        .collect();
+    let members = order
+        .into_iter()
+        .map(|i| std::mem::take(&mut components[i]))
+        .collect();
+    (Graph::new(nodes, edges), members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_condensation_random_graphs() {
+        for seed in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(60, 90, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let (dag, members) = condensation(&graph);
+
+            let mut component_of = vec![0; graph.node_count()];
+            for (i, component) in members.iter().enumerate() {
+                for &key in component {
+                    component_of[key as usize] = i as u32;
+                }
+            }
+            assert_eq!(
+                members.iter().map(Vec::len).sum::<usize>(),
+                graph.node_count()
+            );
+            assert_eq!(dag.node_count(), members.len());
+
+            // Every edge maps inside a component or to a condensation edge, and back
+            for edge in graph.get_edges() {
+                let (source, target) = (
+                    component_of[edge.source() as usize],
+                    component_of[edge.target() as usize],
+                );
+                assert!(source == target || dag.has_edge(source, target));
+            }
+            for edge in dag.get_edges() {
+                assert!(edge.source() < edge.target());
+                assert!(members[edge.source() as usize].iter().any(|&u| {
+                    graph
+                        .get_successors_keys(u)
+                        .any(|v| component_of[v as usize] == edge.target())
+                }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_condensation_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(
+            vec![10, 20, 30],
+            vec![(30, 20), (20, 30), (20, 10), (10, 10)],
+        );
+        let (dag, members) = condensation(&graph);
+        assert_eq!(members, vec![vec![20, 30], vec![10]]);
+        assert_eq!(
+            dag.get_edges().map(|e| e.key()).collect::<Vec<_>>(),
+            vec![(0, 1)]
+        );
+
+        let (dag, members) = condensation(&build_graph::<Node, Edge>(vec![], vec![]));
+        assert!(dag.is_empty() && members.is_empty());
+    }
+}
@@ -1,5 +1,21 @@
+pub mod algorithm;
+pub mod condensation;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 pub mod pearce_iterative;
 pub mod pearce_recursive;
+pub mod small_graph;
+pub mod tarjan;
+pub mod weakly_connected;
 
-pub use pearce_iterative::scc_iterative;
+pub use algorithm::{scc_with, SccAlgorithm};
+pub use condensation::condensation;
+#[cfg(feature = "parallel")]
+pub use parallel::scc_parallel;
+pub use pearce_iterative::{
+    scc_iterative, scc_iterative_with, scc_iterative_with_progress, SccWorkspace, SCC_PHASE,
+};
 pub use pearce_recursive::scc;
+pub use small_graph::{SmallGraph, SMALL_GRAPH_MAX_NODES};
+pub use tarjan::scc_tarjan;
+pub use weakly_connected::weakly_connected_components;
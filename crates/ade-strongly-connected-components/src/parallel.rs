@@ -0,0 +1,329 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use rayon::Scope;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+use std::sync::Mutex;
+
+// Label of the nodes whose component is known
+const DONE: u32 = u32::MAX;
+
+/// Finds all strongly connected components of a graph on the rayon thread pool, with the
+/// forward-backward algorithm and trimming.
+///
+/// Each step works on a partition of the nodes known to contain whole components. It first
+/// trims the nodes without predecessors or without successors in the partition, which are
+/// components of their own, repeatedly. It then picks a pivot and searches forward and
+/// backward from it within the partition: the nodes reached both ways form the component of
+/// the pivot, and the nodes reached only forward, only backward or not at all are three new
+/// partitions, since no component spans two of them. Partitions are independent, so they are
+/// processed as parallel tasks; chains of small components are mostly removed by trimming,
+/// and large components split off in a few steps.
+///
+/// The work is `O(m log n)` expected, more than the `O(n + m)` of
+/// [`scc_iterative`](crate::scc_iterative), so this only pays off on large graphs with enough
+/// threads. Adjacency is copied once into compact arrays, and the graph itself is only read
+/// on the calling thread. Keys need not be sequential. Runs on the current rayon pool and
+/// requires the `parallel` feature.
+///
+/// # Returns
+///
+/// A vector of strongly connected components, each a vector of node keys in ascending
+/// order. Components are sorted by their smallest key, so the result does not depend on
+/// the number of threads.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::scc_parallel;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(
+///     vec![0, 1, 2, 3, 7],
+///     vec![(0, 1), (1, 2), (2, 0), (2, 3), (3, 7), (7, 3)],
+/// );
+///
+/// assert_eq!(scc_parallel(&graph), vec![vec![0, 1, 2], vec![3, 7]]);
+/// ```
+pub fn scc_parallel<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<Vec<u32>> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let shared = Shared::new(graph, &keys);
+
+    let found = Mutex::new(Vec::new());
+    let all = (0..keys.len() as u32).collect();
+    let label = shared.fresh_label();
+    rayon::scope(|scope| split(scope, &shared, &found, all, label));
+
+    let mut components: Vec<Vec<u32>> = found
+        .into_inner()
+        .expect("No thread panics while holding the lock")
+        .into_iter()
+        .map(|component: Vec<u32>| {
+            let mut component: Vec<u32> = component.into_iter().map(|i| keys[i as usize]).collect();
+            component.sort_unstable();
+            component
+        })
+        .collect();
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+}
+
+// Successor or predecessor lists of all nodes, by index into the sorted keys
+struct Adjacency {
+    offsets: Vec<usize>,
+    neighbors: Vec<u32>,
+}
+
+impl Adjacency {
+    fn new(n: usize, edges: &[(u32, u32)]) -> Self {
+        let mut offsets = vec![0; n + 1];
+        for &(source, _) in edges {
+            offsets[source as usize + 1] += 1;
+        }
+        for i in 0..n {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut next = offsets.clone();
+        let mut neighbors = vec![0; edges.len()];
+        for &(source, target) in edges {
+            neighbors[next[source as usize]] = target;
+            next[source as usize] += 1;
+        }
+        Self { offsets, neighbors }
+    }
+
+    fn of(&self, v: u32) -> &[u32] {
+        &self.neighbors[self.offsets[v as usize]..self.offsets[v as usize + 1]]
+    }
+}
+
+// State shared by all tasks. Every node is owned by the task of the partition holding it,
+// and only that task changes its label and degrees; labels are never reused, so a task can
+// tell its nodes from the others without synchronizing.
+struct Shared {
+    successors: Adjacency,
+    predecessors: Adjacency,
+    labels: Vec<AtomicU32>,
+    // Degrees within the partition, excluding self-loops, while trimming
+    in_degrees: Vec<AtomicU32>,
+    out_degrees: Vec<AtomicU32>,
+    next_label: AtomicU32,
+}
+
+impl Shared {
+    fn new<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>, keys: &[u32]) -> Self {
+        let n = keys.len();
+        let index: HashMap<u32, u32> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, &key)| (key, i as u32))
+            .collect();
+        let edges: Vec<(u32, u32)> = graph
+            .get_edges()
+            .map(|edge| (index[&edge.source()], index[&edge.target()]))
+            .collect();
+        let reversed: Vec<(u32, u32)> = edges
+            .iter()
+            .map(|&(source, target)| (target, source))
+            .collect();
+        let atomics = || (0..n).map(|_| AtomicU32::new(0)).collect();
+        Self {
+            successors: Adjacency::new(n, &edges),
+            predecessors: Adjacency::new(n, &reversed),
+            labels: atomics(),
+            in_degrees: atomics(),
+            out_degrees: atomics(),
+            next_label: AtomicU32::new(0),
+        }
+    }
+
+    fn fresh_label(&self) -> u32 {
+        self.next_label.fetch_add(1, Relaxed)
+    }
+
+    fn label(&self, v: u32) -> u32 {
+        self.labels[v as usize].load(Relaxed)
+    }
+
+    fn set_label(&self, v: u32, label: u32) {
+        self.labels[v as usize].store(label, Relaxed);
+    }
+}
+
+// Finds the components of the partition of `nodes`, all labelled `label`, spawning a task
+// for each smaller partition it splits into
+fn split<'s>(
+    scope: &Scope<'s>,
+    shared: &'s Shared,
+    found: &'s Mutex<Vec<Vec<u32>>>,
+    nodes: Vec<u32>,
+    label: u32,
+) {
+    let mut components = Vec::new();
+    let nodes = trim(shared, nodes, label, &mut components);
+
+    if !nodes.is_empty() {
+        // A pivot spread over the partition, so that splits are balanced on average
+        let pivot = nodes[(label as usize).wrapping_mul(2_654_435_761) % nodes.len()];
+        let (forward, backward, component) = (
+            shared.fresh_label(),
+            shared.fresh_label(),
+            shared.fresh_label(),
+        );
+
+        shared.set_label(pivot, forward);
+        let mut stack = vec![pivot];
+        while let Some(v) = stack.pop() {
+            for &w in shared.successors.of(v) {
+                if shared.label(w) == label {
+                    shared.set_label(w, forward);
+                    stack.push(w);
+                }
+            }
+        }
+        shared.set_label(pivot, component);
+        stack.push(pivot);
+        while let Some(v) = stack.pop() {
+            for &w in shared.predecessors.of(v) {
+                let reached = shared.label(w);
+                if reached == forward {
+                    shared.set_label(w, component);
+                    stack.push(w);
+                } else if reached == label {
+                    shared.set_label(w, backward);
+                    stack.push(w);
+                }
+            }
+        }
+
+        let mut parts = [Vec::new(), Vec::new(), Vec::new()];
+        let mut pivot_component = Vec::new();
+        for v in nodes {
+            match shared.label(v) {
+                l if l == component => {
+                    shared.set_label(v, DONE);
+                    pivot_component.push(v);
+                }
+                l if l == forward => parts[0].push(v),
+                l if l == backward => parts[1].push(v),
+                _ => parts[2].push(v),
+            }
+        }
+        components.push(pivot_component);
+
+        for (part, part_label) in parts.into_iter().zip([forward, backward, label]) {
+            if !part.is_empty() {
+                scope.spawn(move |scope| split(scope, shared, found, part, part_label));
+            }
+        }
+    }
+
+    found
+        .lock()
+        .expect("No thread panics while holding the lock")
+        .append(&mut components);
+}
+
+// Removes, repeatedly, the nodes without predecessors or without successors in the
+// partition, each a component of its own, and returns the nodes left
+fn trim(shared: &Shared, nodes: Vec<u32>, label: u32, components: &mut Vec<Vec<u32>>) -> Vec<u32> {
+    let in_partition = |v: u32, w: u32| w != v && shared.label(w) == label;
+    let mut queue = Vec::new();
+    for &v in &nodes {
+        let in_degree = shared
+            .predecessors
+            .of(v)
+            .iter()
+            .filter(|&&w| in_partition(v, w))
+            .count();
+        let out_degree = shared
+            .successors
+            .of(v)
+            .iter()
+            .filter(|&&w| in_partition(v, w))
+            .count();
+        shared.in_degrees[v as usize].store(in_degree as u32, Relaxed);
+        shared.out_degrees[v as usize].store(out_degree as u32, Relaxed);
+        if in_degree == 0 || out_degree == 0 {
+            queue.push(v);
+        }
+    }
+
+    while let Some(v) = queue.pop() {
+        // A node can be queued once for each degree reaching zero
+        if shared.label(v) != label {
+            continue;
+        }
+        shared.set_label(v, DONE);
+        components.push(vec![v]);
+        for &w in shared.successors.of(v) {
+            if in_partition(v, w) && shared.in_degrees[w as usize].fetch_sub(1, Relaxed) == 1 {
+                queue.push(w);
+            }
+        }
+        for &w in shared.predecessors.of(v) {
+            if in_partition(v, w) && shared.out_degrees[w as usize].fetch_sub(1, Relaxed) == 1 {
+                queue.push(w);
+            }
+        }
+    }
+    nodes
+        .into_iter()
+        .filter(|&v| shared.label(v) == label)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scc_iterative;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+    use proptest::prelude::*;
+
+    // Components with sorted nodes, sorted by their smallest node
+    fn normalized(mut components: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        components.sort_unstable_by_key(|component| component[0]);
+        components
+    }
+
+    #[test]
+    fn test_random_graphs() {
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(2_000, 2_400, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            assert_eq!(scc_parallel(&graph), normalized(scc_iterative(&graph)));
+        }
+    }
+
+    #[test]
+    fn test_long_cycle_and_chain() {
+        // A cycle cannot be trimmed, and a chain is trimmed down from both ends
+        let n: u32 = 100_000;
+        let cycle =
+            build_graph::<Node, Edge>((0..n).collect(), (0..n).map(|i| (i, (i + 1) % n)).collect());
+        assert_eq!(scc_parallel(&cycle), vec![(0..n).collect::<Vec<_>>()]);
+
+        let chain = build_graph::<Node, Edge>(
+            (0..n).map(|i| 3 * i).collect(),
+            (1..n).map(|i| (3 * (i - 1), 3 * i)).collect(),
+        );
+        assert_eq!(scc_parallel(&chain).len(), n as usize);
+    }
+
+    proptest! {
+        #[test]
+        fn test_agrees_with_scc_iterative(
+            graph in ade_graph::utils::strategies::arb_graph::<Node, Edge>(30, 80)
+        ) {
+            prop_assert_eq!(scc_parallel(&graph), normalized(scc_iterative(&graph)));
+        }
+    }
+}
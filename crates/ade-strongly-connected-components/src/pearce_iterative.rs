@@ -1,6 +1,12 @@
-use ade_common::INVALID_KEY_SEQUENCE;
+use ade_common::{Cancelled, NoProgress, PhaseProgress, Progress};
+use ade_graph::normalize::{normalize_graph_keys, GraphRefOrOwned};
 use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
-use std::collections::HashMap;
+
+/// Name of the phase reported by [`scc_iterative_with_progress`].
+pub const SCC_PHASE: &str = "strongly connected components";
+
+// Number of visited nodes between two progress updates
+const CHECK_INTERVAL: usize = 1024;
 
 // Struct containing mutable state of the SCC algorithm
 struct SccState {
@@ -25,6 +31,67 @@ impl SccState {
             c: n as isize - 1,
         }
     }
+
+    // Prepares the state for a graph with n nodes, keeping the allocated buffers
+    fn reset(&mut self, n: usize) {
+        self.v_s_front.clear();
+        self.v_s_back.clear();
+        self.i_s.clear();
+        self.rindex.clear();
+        self.rindex.resize(n, 0);
+        self.root.clear();
+        self.root.resize(n, false);
+        self.index = 1;
+        self.c = n as isize - 1;
+    }
+}
+
+/// Reusable buffers for [`scc_iterative_with`].
+///
+/// Computing strongly connected components needs several buffers sized to the number of
+/// nodes. A workspace keeps them alive between calls, so that code running SCC many times
+/// (for example on a sequence of subgraphs) does not allocate them again at every call.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::{scc_iterative_with, SccWorkspace};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let mut workspace = SccWorkspace::new();
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 0), (1, 2)]);
+/// assert_eq!(scc_iterative_with(&graph, &mut workspace).len(), 2);
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1)]);
+/// assert_eq!(workspace.run(&graph).len(), 2);
+/// ```
+pub struct SccWorkspace {
+    state: SccState,
+}
+
+impl SccWorkspace {
+    /// Creates an empty workspace. Buffers grow on first use.
+    pub fn new() -> Self {
+        Self {
+            state: SccState::new(0),
+        }
+    }
+
+    /// Finds all strongly connected components of `graph`, as [`scc_iterative`] does.
+    pub fn run<N: NodeTrait, E: EdgeTrait>(
+        &mut self,
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> Vec<Vec<u32>> {
+        scc_iterative_with(graph, self)
+    }
+}
+
+impl Default for SccWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Finds all strongly connected components (SCCs) in a directed graph using an iterative approach.
@@ -53,17 +120,14 @@ impl SccState {
 /// of node keys (`u32`). The order of components and the order of nodes within each component
 /// is not specified.
 ///
-/// # Requirements
-///
-/// **The graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
-/// If the graph has non-sequential keys, the function will panic with [`INVALID_KEY_SEQUENCE`].
+/// # Keys
 ///
-/// Use [`has_sequential_keys`](GraphViewTrait::has_sequential_keys) to check if a graph
-/// meets this requirement before calling this function.
-///
-/// # Panics
-///
-/// Panics if the graph does not have sequential keys starting from 0.
+/// The algorithm indexes its buffers by node key, so it runs directly on graphs with
+/// sequential keys starting from 0 (i.e., 0, 1, 2, 3, ..., n-1). Other graphs are first
+/// copied with renumbered keys by
+/// [`normalize_graph_keys`](ade_graph::normalize::normalize_graph_keys), and the
+/// components are translated back to the original keys, which costs an extra copy of the
+/// graph.
 ///
 /// # Examples
 ///
@@ -123,49 +187,139 @@ impl SccState {
 /// assert_eq!(components.len(), 4);
 /// ```
 ///
-/// # Example: Non-sequential keys will panic
-///
-/// ```should_panic
+/// ```
 /// use ade_strongly_connected_components::pearce_iterative::scc_iterative;
 /// use ade_graph::implementations::{Node, Edge};
 /// use ade_graph::utils::build::build_graph;
 ///
-/// // This graph has non-sequential keys (1, 3, 5) and will panic
+/// // Non-sequential keys (1, 3, 5) are renumbered internally
 /// let graph = build_graph::<Node, Edge>(
 ///     vec![1, 3, 5],
 ///     vec![(1, 3), (3, 5), (5, 1)],
 /// );
 ///
-/// // This will panic with INVALID_KEY_SEQUENCE
-/// scc_iterative(&graph);
+/// let mut components = scc_iterative(&graph);
+/// components[0].sort_unstable();
+/// assert_eq!(components, vec![vec![1, 3, 5]]);
 /// ```
 pub fn scc_iterative<N: NodeTrait, E: EdgeTrait>(
     graph: &impl GraphViewTrait<N, E>,
 ) -> Vec<Vec<u32>> {
-    // Panic if the graph does not have sequential keys
-    if !graph.has_sequential_keys() {
-        panic!("{}", INVALID_KEY_SEQUENCE);
+    scc_iterative_with(graph, &mut SccWorkspace::new())
+}
+
+/// Finds all strongly connected components, reusing the buffers of `workspace`.
+///
+/// Behaves exactly like [`scc_iterative`], but the per-node buffers are taken from
+/// `workspace` and left there for the next call.
+pub fn scc_iterative_with<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    workspace: &mut SccWorkspace,
+) -> Vec<Vec<u32>> {
+    match scc_iterative_with_progress(graph, workspace, &mut NoProgress) {
+        Ok(components) => components,
+        Err(Cancelled) => unreachable!("NoProgress is never cancelled"),
+    }
+}
+
+/// Finds all strongly connected components, reporting progress and allowing cancellation.
+///
+/// Behaves like [`scc_iterative_with`], and reports to `progress` the fraction of nodes
+/// visited so far under the phase [`SCC_PHASE`]. The search polls
+/// [`is_cancelled`](Progress::is_cancelled) while it runs, and stops as soon as it returns
+/// `true`, which is useful on graphs with millions of nodes.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if `progress` was cancelled before the search completed.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::{scc_iterative_with_progress, SccWorkspace};
+/// use ade_common::{CancellationToken, Cancelled};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let nodes: Vec<u32> = (0..1000).collect();
+/// let edges = (0..1000).map(|i| (i, (i + 1) % 1000)).collect();
+/// let graph = build_graph::<Node, Edge>(nodes, edges);
+/// let mut workspace = SccWorkspace::new();
+///
+/// let mut last = 0.0;
+/// let mut progress = |_: &str, fraction: f64| last = fraction;
+/// let components = scc_iterative_with_progress(&graph, &mut workspace, &mut progress)?;
+/// assert_eq!(components.len(), 1);
+/// assert_eq!(last, 1.0);
+///
+/// let mut token = CancellationToken::new();
+/// token.cancel();
+/// let result = scc_iterative_with_progress(&graph, &mut workspace, &mut token);
+/// assert_eq!(result, Err(Cancelled));
+/// # Ok::<(), Cancelled>(())
+/// ```
+pub fn scc_iterative_with_progress<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    workspace: &mut SccWorkspace,
+    progress: &mut impl Progress,
+) -> Result<Vec<Vec<u32>>, Cancelled> {
+    match normalize_graph_keys(graph) {
+        (GraphRefOrOwned::Owned(normalized), Some(index_to_key)) => {
+            let mut components = scc_sequential(&normalized, workspace, progress)?;
+            for key in components.iter_mut().flatten() {
+                *key = index_to_key[*key as usize];
+            }
+            Ok(components)
+        }
+        _ => scc_sequential(graph, workspace, progress),
+    }
+}
+
+// Pearce's iterative algorithm on a graph with sequential keys
+fn scc_sequential<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    workspace: &mut SccWorkspace,
+    progress: &mut impl Progress,
+) -> Result<Vec<Vec<u32>>, Cancelled> {
+    // Keys are sequential, so nodes can be visited by key without iterating the graph
+    let n: usize = graph.node_count();
+    let mut phase = PhaseProgress::new(SCC_PHASE, n);
+    if progress.is_cancelled() {
+        return Err(Cancelled);
     }
 
-    let nodes = graph.get_nodes();
-    let n: usize = graph.get_nodes().count();
-    let mut state = SccState::new(n);
+    let state = &mut workspace.state;
+    state.reset(n);
 
-    for v in nodes {
-        if state.rindex[v.key() as usize] == 0 {
-            visit(v, &mut state, graph)
+    for v in 0..n as u32 {
+        if state.rindex[v as usize] == 0 {
+            visit(v, state, graph, &mut phase, progress)?;
         }
     }
+    phase.finish(progress);
 
+    // Visits the nodes reachable from v, advancing the phase by one step per visited node
     fn visit<N: NodeTrait, E: EdgeTrait>(
-        v: &N,
+        v: u32,
         state: &mut SccState,
         graph: &impl GraphViewTrait<N, E>,
-    ) {
-        begin_visiting(v.key(), state);
+        phase: &mut PhaseProgress,
+        progress: &mut impl Progress,
+    ) -> Result<(), Cancelled> {
+        begin_visiting(v, state);
+        let mut visited = 1;
         while !state.v_s_front.is_empty() {
+            let before = state.v_s_front.len();
             visit_loop(state, graph);
+            if state.v_s_front.len() > before {
+                visited += 1;
+                if visited == CHECK_INTERVAL {
+                    phase.advance(visited, progress)?;
+                    visited = 0;
+                }
+            }
         }
+        phase.advance(visited, progress)
     }
 
     fn visit_loop<N: NodeTrait, E: EdgeTrait>(
@@ -252,28 +406,35 @@ pub fn scc_iterative<N: NodeTrait, E: EdgeTrait>(
         state.index += 1;
     }
 
+    // Component ids are assigned downwards from n - 1, so they can index a vector directly
     fn get_components(rindex: &[u32]) -> Vec<Vec<u32>> {
-        let mut map: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut buckets: Vec<Vec<u32>> = vec![Vec::new(); rindex.len()];
 
         for (i, &val) in rindex.iter().enumerate() {
-            map.entry(val).or_default().push(i as u32);
+            buckets[val as usize].push(i as u32);
         }
 
-        map.into_values().collect()
+        buckets.retain(|component| !component.is_empty());
+        buckets
     }
 
-    get_components(&state.rindex)
+    Ok(get_components(&state.rindex))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::scc;
-    use ade_common::{self, assert_panics_with};
-    use ade_graph::{implementations::{Edge, Node}, utils::build::build_graph};
+    use crate::{scc, scc_tarjan, scc_with, SccAlgorithm};
+    use ade_graph::utils::strategies::{arb_dag, arb_graph, arb_strongly_connected};
+    use ade_graph::{
+        implementations::{Edge, Node},
+        normalize::with_normalized_keys,
+        utils::build::build_graph,
+    };
     use ade_graph_generators::generate_random_graph_data;
+    use proptest::prelude::*;
 
-    fn sort_components(components: &mut Vec<Vec<u32>>) {
+    fn sort_components(components: &mut [Vec<u32>]) {
         for component in components.iter_mut() {
             component.sort_unstable();
         }
@@ -420,14 +581,16 @@ mod tests {
             let mut components = scc(&graph);
             sort_components(&mut components);
 
-            let mut components_iter = scc_iterative(&graph);
-            sort_components(&mut components_iter);
+            for algorithm in SccAlgorithm::ALL {
+                let mut components_with = scc_with(&graph, algorithm);
+                sort_components(&mut components_with);
 
-            assert_eq!(
-                components, components_iter,
-                "Mismatch for graph with {} nodes, {} edges, seed {}",
-                nodes_count, edges_count, seed
-            );
+                assert_eq!(
+                    components, components_with,
+                    "Mismatch for {:?} on graph with {} nodes, {} edges, seed {}",
+                    algorithm, nodes_count, edges_count, seed
+                );
+            }
         }
     }
 
@@ -448,22 +611,161 @@ mod tests {
             let mut components = scc(&graph);
             sort_components(&mut components);
 
-            let mut components_iter = scc_iterative(&graph);
-            sort_components(&mut components_iter);
+            for algorithm in SccAlgorithm::ALL {
+                let mut components_with = scc_with(&graph, algorithm);
+                sort_components(&mut components_with);
+
+                assert_eq!(
+                    components, components_with,
+                    "Mismatch for {:?} con seed={:?}, nodi={}, edges={}",
+                    algorithm, seed, nodes_count, edges_count
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_scc_iterative_order_on_small_graphs() {
+        // Components come in topological order, not in order of their smallest key as with
+        // SccAlgorithm::Bitmask
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 2), (1, 0)]);
+        assert_eq!(scc_iterative(&graph), vec![vec![1], vec![0], vec![2]]);
+        assert_eq!(
+            scc_with(&graph, SccAlgorithm::Bitmask),
+            vec![vec![0], vec![1], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_scc_iterative_with_reused_workspace() {
+        let mut workspace = SccWorkspace::new();
+
+        for (nodes_count, edges_count, seed) in
+            [(50, 200, 1), (10, 30, 2), (120, 400, 3), (1, 1, 4)]
+        {
+            let (nodes, edges) = generate_random_graph_data(nodes_count, edges_count, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let mut expected = scc_iterative(&graph);
+            sort_components(&mut expected);
+
+            let mut components = workspace.run(&graph);
+            sort_components(&mut components);
+
+            assert_eq!(components, expected);
+        }
+    }
+
+    #[test]
+    fn test_scc_iterative_with_progress() {
+        let (nodes, edges) = generate_random_graph_data(5000, 20000, 7);
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+        let mut workspace = SccWorkspace::new();
+
+        let mut reports = Vec::new();
+        let mut progress = |phase: &str, fraction: f64| {
+            assert_eq!(phase, SCC_PHASE);
+            reports.push(fraction);
+        };
+        let mut components =
+            scc_iterative_with_progress(&graph, &mut workspace, &mut progress).unwrap();
+        sort_components(&mut components);
+
+        let mut expected = scc_iterative(&graph);
+        sort_components(&mut expected);
+        assert_eq!(components, expected);
+
+        assert!(reports.len() > 1);
+        assert!(reports.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(reports.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn test_scc_iterative_cancelled_while_running() {
+        // Cancels itself after the first report
+        struct CancelAfterReport(bool);
+
+        impl Progress for CancelAfterReport {
+            fn report(&mut self, _phase: &str, _fraction: f64) {
+                self.0 = true;
+            }
 
-            assert_eq!(
-                components, components_iter,
-                "Mismatch con seed={:?}, nodi={}, edges={}",
-                seed, nodes_count, edges_count
-            );
+            fn is_cancelled(&self) -> bool {
+                self.0
+            }
         }
+
+        let nodes: Vec<u32> = (0..10_000).collect();
+        let edges = (0..10_000).map(|i| (i, (i + 1) % 10_000)).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let mut progress = CancelAfterReport(false);
+        let result = scc_iterative_with_progress(&graph, &mut SccWorkspace::new(), &mut progress);
+        assert_eq!(result, Err(Cancelled));
     }
 
     #[test]
     fn test_scc_iterative_non_sequential_keys() {
-        use ade_common::assert_panics_with;
+        let graph =
+            build_graph::<Node, Edge>(vec![1, 3, 5, 8], vec![(1, 3), (3, 5), (5, 1), (5, 8)]);
+        let mut components = scc_iterative(&graph);
+        sort_components(&mut components);
+        assert_eq!(components, vec![vec![1, 3, 5], vec![8]]);
+
+        // Large enough to skip the small graph kernel
+        let (nodes, edges) = generate_random_graph_data(500, 1500, 3);
+        let shift = |key: u32| key * 7 + 100;
+        let shifted = build_graph::<Node, Edge>(
+            nodes.into_iter().map(shift).collect(),
+            edges
+                .into_iter()
+                .map(|(u, v)| (shift(u), shift(v)))
+                .collect(),
+        );
+        let mut expected = scc(&shifted);
+        sort_components(&mut expected);
+        let mut components = scc_iterative_with(&shifted, &mut SccWorkspace::new());
+        sort_components(&mut components);
+        assert_eq!(components, expected);
+    }
+
+    #[test]
+    fn test_scc_iterative_through_with_normalized_keys() {
+        let graph = build_graph::<Node, Edge>(
+            vec![10, 20, 30, 40],
+            vec![(10, 20), (20, 10), (20, 30), (30, 40), (40, 30)],
+        );
+        let mut components = with_normalized_keys(&graph, scc_iterative);
+        sort_components(&mut components);
+        assert_eq!(components, vec![vec![10, 20], vec![30, 40]]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_scc_matches_reference(graph in arb_graph::<Node, Edge>(100, 300)) {
+            let expected = ade_reference::strongly_connected_components(&graph);
+            let mut recursive = scc(&graph);
+            sort_components(&mut recursive);
+            let mut components = scc_iterative(&graph);
+            sort_components(&mut components);
+            let mut tarjan = scc_tarjan(&graph);
+            sort_components(&mut tarjan);
+            prop_assert_eq!(&components, &expected);
+            prop_assert_eq!(&recursive, &expected);
+            prop_assert_eq!(&tarjan, &expected);
+        }
 
-        let graph = build_graph::<Node, Edge>(vec![1, 3, 5], vec![(1, 3), (3, 5), (5, 1)]);
-        assert_panics_with!(scc_iterative(&graph), ade_common::INVALID_KEY_SEQUENCE);
+        #[test]
+        fn test_scc_iterative_on_dag(graph in arb_dag::<Node, Edge>(100, 300)) {
+            let components = scc_iterative(&graph);
+            prop_assert_eq!(components.len(), graph.node_count());
+        }
+
+        #[test]
+        fn test_scc_iterative_on_strongly_connected(
+            graph in arb_strongly_connected::<Node, Edge>(100, 100)
+        ) {
+            prop_assert_eq!(scc_iterative(&graph).len(), 1);
+        }
     }
 }
@@ -91,7 +91,7 @@ pub fn scc<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Vec
     let mut rindex: HashMap<u32, usize> = graph.get_nodes().map(|node| (node.key(), 0)).collect();
     let mut stack: Vec<u32> = Vec::new();
     let mut index: usize = 1;
-    let mut c: usize = graph.get_nodes().count() - 1;
+    let mut c: usize = graph.node_count() - 1;
 
     for node in graph.get_nodes() {
         if rindex[&node.key()] == 0 {
@@ -161,10 +161,13 @@ fn components(rindex: &HashMap<u32, usize>) -> Vec<Vec<u32>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ade_graph::{implementations::{Edge, Node}, utils::build::build_graph};
+    use ade_graph::{
+        implementations::{Edge, Node},
+        utils::build::build_graph,
+    };
     //use graph_generators::generate_random_graph_data;
 
-    fn sort_components(components: &mut Vec<Vec<u32>>) {
+    fn sort_components(components: &mut [Vec<u32>]) {
         for component in components.iter_mut() {
             component.sort_unstable();
         }
@@ -173,7 +176,8 @@ mod tests {
 
     #[test]
     fn test_scc_1() {
-        let graph = build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 1), (3, 4)]);
+        let graph =
+            build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 1), (3, 4)]);
         let components = scc(&graph);
         assert_eq!(components.len(), 2);
         // Test that there are components [1, 2, 3] and [4]
@@ -192,14 +196,16 @@ mod tests {
 
     #[test]
     fn test_scc_3() {
-        let graph = build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 4), (4, 1)]);
+        let graph =
+            build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 4), (4, 1)]);
         let components = scc(&graph);
         assert_eq!(components.len(), 1);
     }
 
     #[test]
     fn test_scc_4() {
-        let graph = build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 4), (4, 3)]);
+        let graph =
+            build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (2, 3), (3, 4), (4, 3)]);
         let components = scc(&graph);
         assert_eq!(components.len(), 3);
         // Test that there are components [1], [2] and [3, 4]
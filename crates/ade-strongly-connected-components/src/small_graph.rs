@@ -0,0 +1,260 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+
+/// Maximum number of nodes of a [`SmallGraph`].
+pub const SMALL_GRAPH_MAX_NODES: usize = 64;
+
+/// A graph with at most 64 nodes, stored as one `u64` adjacency bitmask per node.
+///
+/// Bit `w` of the mask of node `v` is set if the graph has the edge `v → w`. With this
+/// representation, reachability, strongly connected components and cycle detection become
+/// a few thousand word operations with no allocation, which is much faster than the
+/// general algorithms on tiny graphs.
+///
+/// The transitive closure is computed once, when the graph is built, so that reachability
+/// queries are a single mask lookup. [`scc_with`](crate::scc_with) switches to this
+/// representation automatically for graphs with at most [`SMALL_GRAPH_MAX_NODES`] nodes
+/// when given [`SccAlgorithm::Bitmask`](crate::SccAlgorithm::Bitmask).
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::SmallGraph;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 0), (1, 2)]);
+/// let small = SmallGraph::new(&graph).unwrap();
+///
+/// assert!(small.has_cycle());
+/// assert!(small.is_reachable(0, 2));
+/// assert!(!small.is_reachable(2, 0));
+/// assert_eq!(small.scc().len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmallGraph {
+    node_count: usize,
+    successors: [u64; SMALL_GRAPH_MAX_NODES],
+    reach: [u64; SMALL_GRAPH_MAX_NODES],
+}
+
+impl SmallGraph {
+    /// Builds the bitmask representation of a graph.
+    ///
+    /// Returns `None` if the graph has more than [`SMALL_GRAPH_MAX_NODES`] nodes or
+    /// does not have sequential keys.
+    pub fn new<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Option<Self> {
+        if graph.node_count() > SMALL_GRAPH_MAX_NODES || !graph.has_sequential_keys() {
+            return None;
+        }
+        Some(Self::from_sequential(graph))
+    }
+
+    // Builds the masks of a graph already known to have sequential keys and at most 64 nodes
+    pub(crate) fn from_sequential<N: NodeTrait, E: EdgeTrait>(
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> Self {
+        let node_count = graph.node_count();
+        let mut successors = [0; SMALL_GRAPH_MAX_NODES];
+        for (v, mask) in successors.iter_mut().enumerate().take(node_count) {
+            for w in graph.get_successors_keys(v as u32) {
+                *mask |= 1 << w;
+            }
+        }
+
+        // Warshall's algorithm, one row per word
+        let mut reach = successors;
+        for k in 0..node_count {
+            let bit = 1u64 << k;
+            let reach_k = reach[k];
+            for row in reach.iter_mut().take(node_count) {
+                if *row & bit != 0 {
+                    *row |= reach_k;
+                }
+            }
+        }
+        Self {
+            node_count,
+            successors,
+            reach,
+        }
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns the successors of a node as a bitmask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    pub fn successors(&self, key: u32) -> u64 {
+        self.successors[self.check_node(key)]
+    }
+
+    /// Returns the transitive closure of the graph.
+    ///
+    /// Bit `w` of entry `v` is set if `w` can be reached from `v` through a path of at
+    /// least one edge, so `v` itself is only included when it lies on a cycle.
+    /// Entries past [`node_count`](Self::node_count) are zero.
+    pub fn transitive_closure(&self) -> [u64; SMALL_GRAPH_MAX_NODES] {
+        self.reach
+    }
+
+    /// Returns `true` if `target` can be reached from `source` through at least one edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either node does not exist.
+    pub fn is_reachable(&self, source: u32, target: u32) -> bool {
+        let source = self.check_node(source);
+        let target = self.check_node(target);
+        self.reach[source] & (1 << target) != 0
+    }
+
+    /// Returns `true` if the graph contains a cycle, self-loops included.
+    pub fn has_cycle(&self) -> bool {
+        (0..self.node_count).any(|v| self.reach[v] & (1 << v) != 0)
+    }
+
+    /// Finds the strongly connected components of the graph.
+    ///
+    /// Components are returned in ascending order of their smallest key, and keys are
+    /// sorted within each component.
+    pub fn scc(&self) -> Vec<Vec<u32>> {
+        let n = self.node_count;
+        let mut reach = self.reach;
+        for (v, row) in reach.iter_mut().enumerate().take(n) {
+            *row |= 1 << v;
+        }
+
+        let mut components = Vec::new();
+        let mut assigned: u64 = 0;
+        for v in 0..n {
+            if assigned & (1 << v) != 0 {
+                continue;
+            }
+
+            // Nodes reachable from v that can also reach v
+            let mut component: u64 = 0;
+            let mut candidates = reach[v];
+            while candidates != 0 {
+                let w = candidates.trailing_zeros() as usize;
+                candidates &= candidates - 1;
+                if reach[w] & (1 << v) != 0 {
+                    component |= 1 << w;
+                }
+            }
+
+            assigned |= component;
+            components.push(keys(component));
+        }
+        components
+    }
+
+    fn check_node(&self, key: u32) -> usize {
+        if key as usize >= self.node_count {
+            panic!("Node {} not found", key);
+        }
+        key as usize
+    }
+}
+
+// Lists the keys of the bits set in a mask, in ascending order
+fn keys(mut mask: u64) -> Vec<u32> {
+    let mut keys = Vec::with_capacity(mask.count_ones() as usize);
+    while mask != 0 {
+        keys.push(mask.trailing_zeros());
+        mask &= mask - 1;
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scc;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    fn sort_components(components: &mut [Vec<u32>]) {
+        for component in components.iter_mut() {
+            component.sort_unstable();
+        }
+        components.sort_unstable_by_key(|g| g[0]);
+    }
+
+    #[test]
+    fn test_new() {
+        let graph = build_graph::<Node, Edge>((0..64).collect(), vec![(0, 63), (63, 0)]);
+        let small = SmallGraph::new(&graph).unwrap();
+        assert_eq!(small.successors(63), 1);
+        assert_eq!(small.successors(0), 1 << 63);
+        assert_eq!(small.scc().len(), 63);
+
+        let graph = build_graph::<Node, Edge>((0..65).collect(), vec![]);
+        assert!(SmallGraph::new(&graph).is_none());
+
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![]);
+        assert!(SmallGraph::new(&graph).is_none());
+    }
+
+    #[test]
+    fn test_cycles_and_reachability() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 3)]);
+        let small = SmallGraph::new(&graph).unwrap();
+        assert!(!small.has_cycle());
+        assert!(small.is_reachable(0, 3));
+        assert!(!small.is_reachable(3, 0));
+        assert!(!small.is_reachable(0, 0));
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 1)]);
+        let small = SmallGraph::new(&graph).unwrap();
+        assert!(small.has_cycle());
+        assert!(small.is_reachable(1, 1));
+    }
+
+    #[test]
+    fn test_scc_matches_recursive() {
+        for seed in 0..200 {
+            let nodes_count = 1 + (seed as usize % 64);
+            let (nodes, edges) = generate_random_graph_data(nodes_count, nodes_count * 2, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let mut expected = scc(&graph);
+            sort_components(&mut expected);
+
+            assert_eq!(
+                SmallGraph::new(&graph).unwrap().scc(),
+                expected,
+                "seed={}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_transitive_closure_matches_reference() {
+        for seed in 0..50 {
+            let nodes_count = 1 + (seed as usize % 20);
+            let (nodes, edges) = generate_random_graph_data(nodes_count, nodes_count * 2, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+
+            let closure = SmallGraph::new(&graph).unwrap().transitive_closure();
+            for (key, reachable) in ade_reference::transitive_closure(&graph) {
+                let mask = reachable.iter().fold(0u64, |mask, &k| mask | 1 << k);
+                assert_eq!(closure[key as usize], mask, "seed={} node={}", seed, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        let small = SmallGraph::new(&graph).unwrap();
+        assert!(small.scc().is_empty());
+        assert!(!small.has_cycle());
+    }
+}
@@ -0,0 +1,151 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashMap;
+
+// Marks nodes not yet visited in `index`
+const UNVISITED: usize = usize::MAX;
+
+/// Finds all strongly connected components (SCCs) in a directed graph using Tarjan's
+/// algorithm.
+///
+/// This is R. Tarjan's classic algorithm, "Depth-First Search and Linear Graph Algorithms"
+/// (SIAM Journal on Computing 1, 1972, 146-160). It keeps a depth-first index and a lowlink
+/// per node, and an explicit stack replaces recursion, so deep graphs cannot overflow the
+/// call stack. It runs in `O(n + m)` time, like Pearce's versions, [`scc`](crate::scc) and
+/// [`scc_iterative`](crate::scc_iterative), and returns the same components, which makes it
+/// useful to benchmark and cross-validate them.
+///
+/// Keys need not be sequential.
+///
+/// # Returns
+///
+/// A vector of strongly connected components, each a vector of node keys. Components are
+/// in reverse topological order: a component comes before every component with an edge to
+/// it. The order of nodes within each component is not specified.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::scc_tarjan;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 1 -> 2 -> 3 -> 1 is a cycle, leading to 4
+/// let graph = build_graph::<Node, Edge>(
+///     vec![1, 2, 3, 4],
+///     vec![(1, 2), (2, 3), (3, 1), (3, 4)],
+/// );
+///
+/// let mut components = scc_tarjan(&graph);
+/// assert_eq!(components[0], vec![4]);
+/// components[1].sort_unstable();
+/// assert_eq!(components[1], vec![1, 2, 3]);
+/// ```
+pub fn scc_tarjan<N: NodeTrait, E: EdgeTrait>(graph: &impl GraphViewTrait<N, E>) -> Vec<Vec<u32>> {
+    let keys: Vec<u32> = graph.get_node_keys().collect();
+    let position: HashMap<u32, usize> = keys.iter().enumerate().map(|(i, &key)| (key, i)).collect();
+    let successors: Vec<Vec<usize>> = keys
+        .iter()
+        .map(|&key| {
+            graph
+                .get_successors_keys(key)
+                .map(|w| position[&w])
+                .collect()
+        })
+        .collect();
+
+    let n = keys.len();
+    let mut index = vec![UNVISITED; n];
+    let mut lowlink = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    // Nodes being explored, with the position of their next successor
+    let mut frames: Vec<(usize, usize)> = Vec::new();
+    for root in 0..n {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        frames.push((root, 0));
+        while let Some((v, next)) = frames.last_mut() {
+            let v = *v;
+            if *next == 0 {
+                index[v] = next_index;
+                lowlink[v] = next_index;
+                next_index += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+
+            if let Some(&w) = successors[v].get(*next) {
+                *next += 1;
+                if index[w] == UNVISITED {
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[v]);
+            }
+            if lowlink[v] == index[v] {
+                let mut component = Vec::new();
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    component.push(keys[w]);
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+
+    #[test]
+    fn test_tarjan_reverse_topological_order() {
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![
+                (0, 1),
+                (1, 0),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 2),
+                (5, 5),
+                (5, 0),
+            ],
+        );
+        let components = scc_tarjan(&graph);
+        assert_eq!(components.len(), 3);
+
+        let component_of = |key: u32| components.iter().position(|c| c.contains(&key)).unwrap();
+        for edge in graph.get_edges() {
+            assert!(component_of(edge.source()) >= component_of(edge.target()));
+        }
+    }
+
+    #[test]
+    fn test_tarjan_deep_path() {
+        let n: u32 = 100_000;
+        let graph = build_graph::<Node, Edge>(
+            (0..n).collect(),
+            (1..n).map(|i| (i - 1, i)).chain([(n - 1, 0)]).collect(),
+        );
+        assert_eq!(scc_tarjan(&graph).len(), 1);
+    }
+}
@@ -0,0 +1,87 @@
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use ade_union_find::UnionFind;
+use std::collections::HashMap;
+
+/// Finds the weakly connected components of a directed graph, the connected components of
+/// the graph with edge directions ignored.
+///
+/// Two nodes are in the same component if a path joins them when edges can be followed in
+/// both directions. Components are merged with a [`UnionFind`] structure, with path
+/// compression and union by rank, so the function runs in `O(n + m α(n))` time without
+/// building the undirected graph. Keys need not be sequential.
+///
+/// # Returns
+///
+/// A vector of components, each a vector of node keys in ascending order. Components are
+/// sorted by their smallest key.
+///
+/// # Examples
+///
+/// ```
+/// use ade_strongly_connected_components::weakly_connected_components;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 1 → 2 ← 3 is weakly connected, though 1 cannot reach 3
+/// let graph = build_graph::<Node, Edge>(vec![1, 2, 3, 4], vec![(1, 2), (3, 2)]);
+///
+/// let components = weakly_connected_components(&graph);
+/// assert_eq!(components, vec![vec![1, 2, 3], vec![4]]);
+/// ```
+pub fn weakly_connected_components<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<Vec<u32>> {
+    let mut keys: Vec<u32> = graph.get_node_keys().collect();
+    keys.sort_unstable();
+    let position: HashMap<u32, u32> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, &key)| (key, i as u32))
+        .collect();
+
+    let mut sets = UnionFind::new(keys.len());
+    for edge in graph.get_edges() {
+        sets.union(position[&edge.source()], position[&edge.target()]);
+    }
+
+    sets.into_groups()
+        .into_iter()
+        .map(|group| group.into_iter().map(|i| keys[i as usize]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scc;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_matches_scc_of_doubled_edges() {
+        for seed in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(200, 150, seed);
+            let doubled = edges.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+            let mut expected = scc(&build_graph::<Node, Edge>(nodes.clone(), doubled));
+            for component in &mut expected {
+                component.sort_unstable();
+            }
+            expected.sort_unstable_by_key(|component| component[0]);
+
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            assert_eq!(weakly_connected_components(&graph), expected);
+        }
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        // Unions along a long path must not build deep trees
+        let n: u32 = 100_000;
+        let graph =
+            build_graph::<Node, Edge>((0..n).collect(), (1..n).map(|i| (i, i - 1)).collect());
+        let components = weakly_connected_components(&graph);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), n as usize);
+    }
+}
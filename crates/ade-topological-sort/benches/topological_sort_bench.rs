@@ -21,8 +21,7 @@ fn benchmark_topological_sort(c: &mut Criterion) {
 
     c.bench_function("topological_sort", |b| {
         b.iter(|| {
-            let sorting =
-                topological_sort::<Node, Edge, u32, _>(&graph, Some(|n: &Node| (n.key() as u32)));
+            let sorting = topological_sort::<Node, Edge, u32, _>(&graph, Some(|n: &Node| n.key()));
             black_box(sorting)
         })
     });
@@ -0,0 +1,42 @@
+use crate::CYCLE_ERROR_MSG;
+use ade_common::INVALID_KEY_SEQUENCE;
+use ade_traits::Path;
+use std::fmt;
+
+/// Errors returned by [`topological_sort`](crate::topological_sort),
+/// [`TopoSortWorkspace::run`](crate::TopoSortWorkspace::run),
+/// [`topological_sort_kahn`](crate::topological_sort_kahn),
+/// [`topological_sort_lexicographic`](crate::topological_sort_lexicographic) and
+/// [`topological_generations`](crate::topological_generations).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopoSortError {
+    /// The graph contains a cycle, so it has no topological order. `witness` is one of the
+    /// cycles, as a closed sequence of keys starting and ending at its smallest key, and
+    /// `cyclic_nodes` holds the keys of every node on at least one cycle, self-loops
+    /// included, in ascending order. Nodes that are only reachable from a cycle are not
+    /// in `cyclic_nodes`.
+    CycleDetected {
+        witness: Vec<u32>,
+        cyclic_nodes: Vec<u32>,
+    },
+    /// The graph does not have sequential keys `0..node_count`: `key` is one of its keys
+    /// outside that range.
+    NonSequentialKeys { key: u32, node_count: usize },
+}
+
+impl fmt::Display for TopoSortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopoSortError::CycleDetected { witness, .. } => {
+                write!(f, "{}: {}", CYCLE_ERROR_MSG, Path::from(witness.as_slice()))
+            }
+            TopoSortError::NonSequentialKeys { key, node_count } => write!(
+                f,
+                "{}, found key {} in a graph of {} nodes",
+                INVALID_KEY_SEQUENCE, key, node_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TopoSortError {}
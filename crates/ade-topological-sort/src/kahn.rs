@@ -0,0 +1,559 @@
+use crate::{check_keys, TopoSortError};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Performs a topological sort with Kahn's algorithm.
+///
+/// Nodes are emitted once all their predecessors have been, so the algorithm only keeps
+/// in-degree counters and never recurses. Among the nodes ready to be emitted, the one with
+/// the smallest key comes first, so the result is the lexicographically smallest
+/// topological order. The sort runs in `O(m + n log n)` time. Use
+/// [`topological_sort_lexicographic`] to order nodes by another criterion.
+///
+/// # Errors
+///
+/// Returns [`TopoSortError::CycleDetected`] with one of the cycles of the graph and the
+/// keys of every node on a cycle if it is not acyclic, and
+/// [`TopoSortError::NonSequentialKeys`] if the keys of the graph are not `0..node_count`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_topological_sort::{topological_sort_kahn, TopoSortError};
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(2, 0), (0, 1), (3, 1)]);
+/// assert_eq!(topological_sort_kahn(&graph), Ok(vec![2, 0, 3, 1]));
+///
+/// // 1 → 2 → 1 is a cycle, 3 only follows it
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (1, 2), (2, 1), (2, 3)]);
+/// assert_eq!(
+///     topological_sort_kahn(&graph),
+///     Err(TopoSortError::CycleDetected { witness: vec![1, 2, 1], cyclic_nodes: vec![1, 2] })
+/// );
+/// ```
+pub fn topological_sort_kahn<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<Vec<u32>, TopoSortError> {
+    topological_sort_lexicographic(graph, |_| ())
+}
+
+/// Computes the lexicographically smallest topological order of a graph, comparing nodes
+/// by `key_fn` and then by key.
+///
+/// Among all the valid orders, the result is the one whose sequence of `(key_fn(node),
+/// node.key())` pairs is smallest, so it is unique and depends neither on the order in
+/// which the graph stores its nodes and edges nor on hashing. This is a stronger guarantee
+/// than the `key_fn` of [`topological_sort`](crate::topological_sort), which only orders
+/// the branches of a depth-first search. The sort uses Kahn's algorithm with a binary heap
+/// of ready nodes and runs in `O(m + n log n)` time.
+///
+/// # Errors
+///
+/// Returns [`TopoSortError::CycleDetected`] with one of the cycles of the graph and the
+/// keys of every node on a cycle if it is not acyclic, and
+/// [`TopoSortError::NonSequentialKeys`] if the keys of the graph are not `0..node_count`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_topological_sort::topological_sort_lexicographic;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+/// use ade_graph::NodeTrait;
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(3, 0), (1, 2)]);
+///
+/// // Smallest keys first
+/// let order = topological_sort_lexicographic(&graph, |_| ()).unwrap();
+/// assert_eq!(order, vec![1, 2, 3, 0]);
+///
+/// // Largest keys first
+/// let order = topological_sort_lexicographic(&graph, |n: &Node| std::cmp::Reverse(n.key())).unwrap();
+/// assert_eq!(order, vec![3, 1, 2, 0]);
+/// ```
+pub fn topological_sort_lexicographic<N, E, K, F>(
+    graph: &impl GraphViewTrait<N, E>,
+    key_fn: F,
+) -> Result<Vec<u32>, TopoSortError>
+where
+    N: NodeTrait,
+    E: EdgeTrait,
+    K: Ord,
+    F: Fn(&N) -> K,
+{
+    check_keys(graph)?;
+
+    let n = graph.node_count();
+    let mut in_degrees = vec![0usize; n];
+    for key in graph.get_node_keys() {
+        in_degrees[key as usize] = graph.in_degree(key);
+    }
+
+    let priority = |key: u32| Reverse((key_fn(graph.get_node(key)), key));
+    let mut ready: BinaryHeap<Reverse<(K, u32)>> = (0..n as u32)
+        .filter(|&key| in_degrees[key as usize] == 0)
+        .map(priority)
+        .collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse((_, key))) = ready.pop() {
+        order.push(key);
+        for successor in graph.get_successors_keys(key) {
+            let degree = &mut in_degrees[successor as usize];
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(priority(successor));
+            }
+        }
+    }
+
+    if order.len() == n {
+        return Ok(order);
+    }
+    Err(cycle_error(graph, &in_degrees))
+}
+
+/// Groups the nodes of a directed acyclic graph into topological generations.
+///
+/// The first generation holds the nodes without predecessors, and each following one the
+/// nodes whose predecessors all lie in earlier generations. No edge joins two nodes of the
+/// same generation, so the nodes of a generation can be processed in parallel once the
+/// previous ones are done, and the generations are the layers of a longest-path layering
+/// where sources come first. Concatenating the generations gives a topological order.
+///
+/// This is Kahn's algorithm processed one generation at a time, in `O(n log n + m)` time.
+///
+/// # Returns
+///
+/// The generations in order, each a vector of node keys in ascending order.
+///
+/// # Errors
+///
+/// Returns [`TopoSortError::CycleDetected`] with one of the cycles of the graph and the
+/// keys of every node on a cycle if it is not acyclic, and
+/// [`TopoSortError::NonSequentialKeys`] if the keys of the graph are not `0..node_count`.
+///
+/// # Examples
+///
+/// ```
+/// use ade_topological_sort::topological_generations;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// // 0 and 3 have no predecessors, 2 waits for both 0 and 1
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2, 3], vec![(0, 1), (0, 2), (1, 2), (3, 1)]);
+/// assert_eq!(topological_generations(&graph), Ok(vec![vec![0, 3], vec![1], vec![2]]));
+/// ```
+pub fn topological_generations<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<Vec<Vec<u32>>, TopoSortError> {
+    check_keys(graph)?;
+
+    let n = graph.node_count();
+    let mut in_degrees = vec![0usize; n];
+    for key in graph.get_node_keys() {
+        in_degrees[key as usize] = graph.in_degree(key);
+    }
+
+    let mut generations = Vec::new();
+    let mut current: Vec<u32> = (0..n as u32)
+        .filter(|&key| in_degrees[key as usize] == 0)
+        .collect();
+    let mut emitted = 0;
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &key in &current {
+            for successor in graph.get_successors_keys(key) {
+                let degree = &mut in_degrees[successor as usize];
+                *degree -= 1;
+                if *degree == 0 {
+                    next.push(successor);
+                }
+            }
+        }
+        next.sort_unstable();
+        emitted += current.len();
+        generations.push(current);
+        current = next;
+    }
+
+    if emitted == n {
+        return Ok(generations);
+    }
+    Err(cycle_error(graph, &in_degrees))
+}
+
+// The error for the nodes left with predecessors by Kahn's algorithm
+fn cycle_error<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    in_degrees: &[usize],
+) -> TopoSortError {
+    TopoSortError::CycleDetected {
+        witness: cycle_witness(graph, in_degrees),
+        cyclic_nodes: cyclic_nodes(graph, in_degrees),
+    }
+}
+
+// The in-degrees left by Kahn's algorithm, counting only the predecessors it could not
+// emit: the nodes left above zero are those on a cycle or downstream of one
+pub(crate) fn leftover_in_degrees<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Vec<usize> {
+    let n = graph.node_count();
+    let mut in_degrees = vec![0usize; n];
+    for key in graph.get_node_keys() {
+        in_degrees[key as usize] = graph.in_degree(key);
+    }
+    let mut ready: Vec<u32> = (0..n as u32)
+        .filter(|&key| in_degrees[key as usize] == 0)
+        .collect();
+    while let Some(key) = ready.pop() {
+        for successor in graph.get_successors_keys(key) {
+            let degree = &mut in_degrees[successor as usize];
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push(successor);
+            }
+        }
+    }
+    in_degrees
+}
+
+// The keys, in ascending order, of the nodes left by Kahn's algorithm that lie on a cycle:
+// those in a strongly connected component of several nodes, found with an iterative
+// Tarjan search over the leftover nodes, and those with a self-loop. Nodes that are only
+// downstream of a cycle are left out.
+pub(crate) fn cyclic_nodes<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    in_degrees: &[usize],
+) -> Vec<u32> {
+    const UNVISITED: usize = usize::MAX;
+    let n = in_degrees.len();
+    let left = |key: u32| in_degrees[key as usize] > 0;
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut cyclic = vec![false; n];
+    let mut stack: Vec<u32> = Vec::new();
+    let mut counter = 0;
+
+    for root in (0..n as u32).filter(|&key| left(key)) {
+        if index[root as usize] != UNVISITED {
+            continue;
+        }
+        // Each frame holds a node, its leftover successors and the next one to visit
+        let mut frames: Vec<(u32, Vec<u32>, usize)> = Vec::new();
+        let mut next = Some(root);
+        loop {
+            if let Some(key) = next.take() {
+                let i = key as usize;
+                index[i] = counter;
+                low[i] = counter;
+                counter += 1;
+                stack.push(key);
+                on_stack[i] = true;
+                let successors = graph
+                    .get_successors_keys(key)
+                    .filter(|&successor| left(successor))
+                    .collect();
+                frames.push((key, successors, 0));
+            }
+            let Some((key, successors, position)) = frames.last_mut() else {
+                break;
+            };
+            let i = *key as usize;
+            if let Some(&successor) = successors.get(*position) {
+                *position += 1;
+                let j = successor as usize;
+                if index[j] == UNVISITED {
+                    next = Some(successor);
+                } else if on_stack[j] {
+                    low[i] = low[i].min(index[j]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some((parent, _, _)) = frames.last() {
+                let p = *parent as usize;
+                low[p] = low[p].min(low[i]);
+            }
+            if low[i] == index[i] {
+                let start = stack.iter().rposition(|&k| k as usize == i).unwrap();
+                let component = stack.split_off(start);
+                for &k in &component {
+                    on_stack[k as usize] = false;
+                    cyclic[k as usize] = component.len() > 1 || graph.has_edge(k, k);
+                }
+            }
+        }
+    }
+    (0..n as u32).filter(|&key| cyclic[key as usize]).collect()
+}
+
+// One cycle among the nodes left with predecessors by Kahn's algorithm, closed and
+// starting at its smallest key. Each of these nodes has a predecessor among them, so
+// walking back from any of them along the smallest such predecessor ends in a cycle.
+fn cycle_witness<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    in_degrees: &[usize],
+) -> Vec<u32> {
+    let left = |key: u32| in_degrees[key as usize] > 0;
+    let mut step = vec![usize::MAX; in_degrees.len()];
+    let mut walk = Vec::new();
+    let mut key = (0..in_degrees.len() as u32).find(|&key| left(key)).unwrap();
+    while step[key as usize] == usize::MAX {
+        step[key as usize] = walk.len();
+        walk.push(key);
+        key = graph
+            .get_predecessors_keys(key)
+            .filter(|&predecessor| left(predecessor))
+            .min()
+            .unwrap();
+    }
+
+    // The walk goes against the edges, so the cycle is read backwards
+    let mut keys = walk.split_off(step[key as usize]);
+    keys.push(key);
+    keys.reverse();
+    Path::new(keys).rotated_to_min().into_keys()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_graph::implementations::{Edge, Node};
+    use ade_graph::utils::build::build_graph;
+    use ade_graph_generators::generate_random_graph_data;
+
+    #[test]
+    fn test_kahn_random_dags() {
+        for seed in 0..20 {
+            // Keeping only forward edges makes the graph acyclic
+            let (nodes, edges) = generate_random_graph_data(50, 300, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let order = topological_sort_kahn(&graph).unwrap();
+
+            let mut position = vec![0; order.len()];
+            for (i, &key) in order.iter().enumerate() {
+                position[key as usize] = i;
+            }
+            for edge in graph.get_edges() {
+                assert!(position[edge.source() as usize] < position[edge.target() as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kahn_deep_chain() {
+        let n: u32 = 200_000;
+        let graph =
+            build_graph::<Node, Edge>((0..n).collect(), (1..n).map(|i| (i, i - 1)).collect());
+        let order = topological_sort_kahn(&graph).unwrap();
+        assert!(order.into_iter().eq((0..n).rev()));
+    }
+
+    #[test]
+    fn test_kahn_cycle_witness() {
+        // Two cycles joined by 2, and a self-loop on 5
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5, 6],
+            vec![
+                (0, 1),
+                (1, 0),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 3),
+                (5, 5),
+                (6, 0),
+            ],
+        );
+        let error = topological_sort_kahn(&graph).unwrap_err();
+        assert_eq!(
+            error,
+            TopoSortError::CycleDetected {
+                witness: vec![0, 1, 0],
+                cyclic_nodes: vec![0, 1, 3, 4, 5]
+            }
+        );
+        assert_eq!(error.to_string(), "Graph contains a cycle: 0 → 1 → 0");
+
+        // Only the self-loop is left once the other cycles are broken
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 5)],
+        );
+        assert_eq!(
+            topological_sort_lexicographic(&graph, |_| ()),
+            Err(TopoSortError::CycleDetected {
+                witness: vec![5, 5],
+                cyclic_nodes: vec![5]
+            })
+        );
+    }
+
+    #[test]
+    fn test_kahn_cyclic_nodes_leave_out_downstream_nodes() {
+        // Two disjoint cycles 0 → 1 → 0 and 2 → 3 → 4 → 2, both leading to 5
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (1, 0), (2, 3), (3, 4), (4, 2), (1, 5), (4, 5)],
+        );
+        for result in [
+            topological_sort_kahn(&graph),
+            topological_generations(&graph).map(|generations| generations.concat()),
+        ] {
+            let Err(TopoSortError::CycleDetected { cyclic_nodes, .. }) = result else {
+                panic!("Expected a cycle, got {:?}", result);
+            };
+            assert_eq!(cyclic_nodes, vec![0, 1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn test_kahn_witness_is_a_cycle() {
+        for seed in 0..50 {
+            let (nodes, edges) = generate_random_graph_data(30, 45, seed);
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let Err(TopoSortError::CycleDetected {
+                witness,
+                cyclic_nodes,
+            }) = topological_sort_kahn(&graph)
+            else {
+                continue;
+            };
+
+            // A node is on a cycle if it can reach itself
+            let on_cycle = |key: u32| {
+                let mut seen = vec![false; graph.node_count()];
+                let mut stack: Vec<u32> = graph.get_successors_keys(key).collect();
+                while let Some(next) = stack.pop() {
+                    if !std::mem::replace(&mut seen[next as usize], true) {
+                        stack.extend(graph.get_successors_keys(next));
+                    }
+                }
+                seen[key as usize]
+            };
+            let expected: Vec<u32> = (0..graph.node_count() as u32)
+                .filter(|&key| on_cycle(key))
+                .collect();
+            assert_eq!(cyclic_nodes, expected);
+
+            assert_eq!(witness.first(), witness.last());
+            assert_eq!(witness.iter().min(), witness.first());
+            assert!(witness
+                .windows(2)
+                .all(|pair| graph.has_edge(pair[0], pair[1])));
+        }
+    }
+
+    #[test]
+    fn test_lexicographic_is_smallest() {
+        // Compare against a brute force over all the permutations of small random DAGs
+        for seed in 0..10 {
+            let (nodes, edges) = generate_random_graph_data(6, 8, seed);
+            let edges: Vec<(u32, u32)> = edges.into_iter().filter(|(u, v)| u > v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges.clone());
+            let key_fn = |n: &Node| n.key() % 3;
+
+            let mut smallest: Option<Vec<(u32, u32)>> = None;
+            let mut permutation: Vec<u32> = (0..6).collect();
+            permutations(&mut permutation, 0, &mut |order| {
+                let position = |key: u32| order.iter().position(|&k| k == key).unwrap();
+                if edges.iter().all(|&(u, v)| position(u) < position(v)) {
+                    let keyed: Vec<(u32, u32)> = order.iter().map(|&k| (k % 3, k)).collect();
+                    if smallest.as_ref().is_none_or(|s| keyed < *s) {
+                        smallest = Some(keyed);
+                    }
+                }
+            });
+            let expected: Vec<u32> = smallest.unwrap().into_iter().map(|(_, k)| k).collect();
+            assert_eq!(
+                topological_sort_lexicographic(&graph, key_fn).unwrap(),
+                expected
+            );
+        }
+    }
+
+    // Calls f on every permutation of keys[start..]
+    fn permutations(keys: &mut [u32], start: usize, f: &mut impl FnMut(&[u32])) {
+        if start == keys.len() {
+            return f(keys);
+        }
+        for i in start..keys.len() {
+            keys.swap(start, i);
+            permutations(keys, start + 1, f);
+            keys.swap(start, i);
+        }
+    }
+
+    #[test]
+    fn test_generations_random_dags() {
+        for seed in 0..20 {
+            let (nodes, edges) = generate_random_graph_data(50, 300, seed);
+            let edges = edges.into_iter().filter(|(u, v)| u < v).collect();
+            let graph = build_graph::<Node, Edge>(nodes, edges);
+            let generations = topological_generations(&graph).unwrap();
+
+            let mut generation = vec![0; graph.node_count()];
+            for (i, keys) in generations.iter().enumerate() {
+                assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+                for &key in keys {
+                    generation[key as usize] = i;
+                }
+            }
+            assert_eq!(
+                generations.iter().map(Vec::len).sum::<usize>(),
+                graph.node_count()
+            );
+            // Each node comes right after its latest predecessor
+            for key in graph.get_node_keys() {
+                let expected = graph
+                    .get_predecessors_keys(key)
+                    .map(|predecessor| generation[predecessor as usize] + 1)
+                    .max()
+                    .unwrap_or(0);
+                assert_eq!(generation[key as usize], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generations_cycle_and_empty() {
+        let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(0, 1), (1, 2), (2, 2)]);
+        assert_eq!(
+            topological_generations(&graph),
+            Err(TopoSortError::CycleDetected {
+                witness: vec![2, 2],
+                cyclic_nodes: vec![2]
+            })
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![], vec![]);
+        assert_eq!(topological_generations(&graph), Ok(vec![]));
+
+        let graph = build_graph::<Node, Edge>(vec![3], vec![]);
+        assert_eq!(
+            topological_generations(&graph),
+            Err(TopoSortError::NonSequentialKeys {
+                key: 3,
+                node_count: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_kahn_non_sequential_keys() {
+        let graph = build_graph::<Node, Edge>(vec![1, 2], vec![(1, 2)]);
+        let error = TopoSortError::NonSequentialKeys {
+            key: 2,
+            node_count: 2,
+        };
+        assert_eq!(topological_sort_kahn(&graph), Err(error.clone()));
+        assert_eq!(topological_sort_lexicographic(&graph, |_| ()), Err(error));
+    }
+}
@@ -1,8 +1,14 @@
-use std::cmp::Reverse;
-use ade_common::INVALID_KEY_SEQUENCE;
-use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait, Path};
 use fixedbitset::FixedBitSet;
+use std::cmp::Reverse;
+
+pub mod error;
+pub mod kahn;
+
+pub use error::TopoSortError;
+pub use kahn::{topological_generations, topological_sort_kahn, topological_sort_lexicographic};
 
+/// The message that starts the description of a cycle error.
 pub const CYCLE_ERROR_MSG: &str = "Graph contains a cycle";
 
 /// Performs a topological sort on a directed acyclic graph (DAG).
@@ -25,22 +31,22 @@ pub const CYCLE_ERROR_MSG: &str = "Graph contains a cycle";
 ///
 /// * `graph` - A reference to a graph that implements [`GraphViewTrait`]
 /// * `key_fn` - An optional function to determine ordering when multiple valid topological
-///              orderings exist. Nodes will be processed in descending order of their keys.
+///   orderings exist. Nodes will be processed in descending order of their keys.
 ///
 /// # Returns
 ///
-/// Returns `Ok(Vec<u32>)` containing the node keys in topological order, or
-/// `Err(String)` if the graph contains a cycle.
-///
-/// # Panics
-///
-/// Panics if the graph does not have sequential keys starting from 0.
-/// Use [`has_sequential_keys`](GraphViewTrait::has_sequential_keys) to verify this requirement.
+/// Returns `Ok(Vec<u32>)` containing the node keys in topological order, or a
+/// [`TopoSortError`] if the graph cannot be sorted.
 ///
 /// # Errors
 ///
-/// Returns an error with message [`CYCLE_ERROR_MSG`] if the graph contains a cycle.
-/// A cycle makes topological sorting impossible since there would be no valid linear ordering.
+/// Returns [`TopoSortError::CycleDetected`] with one of the cycles of the graph, and the
+/// keys of every node on a cycle, if it contains any. A cycle makes topological sorting impossible since there would be no
+/// valid linear ordering.
+///
+/// Returns [`TopoSortError::NonSequentialKeys`] if the keys of the graph are not
+/// `0..node_count`. Use [`has_sequential_keys`](GraphViewTrait::has_sequential_keys) to
+/// verify this requirement.
 ///
 /// # Examples
 ///
@@ -88,7 +94,7 @@ pub const CYCLE_ERROR_MSG: &str = "Graph contains a cycle";
 /// Detecting cycles:
 ///
 /// ```
-/// use ade_topological_sort::{topological_sort, CYCLE_ERROR_MSG};
+/// use ade_topological_sort::{topological_sort, TopoSortError};
 /// use ade_graph::implementations::{Graph, Node, Edge};
 ///
 /// // Create a graph with a cycle: 0 -> 1 -> 0
@@ -98,97 +104,210 @@ pub const CYCLE_ERROR_MSG: &str = "Graph contains a cycle";
 /// );
 ///
 /// let result = topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None);
-/// assert!(result.is_err());
-/// assert_eq!(result.unwrap_err(), CYCLE_ERROR_MSG);
+/// assert_eq!(
+///     result,
+///     Err(TopoSortError::CycleDetected { witness: vec![0, 1, 0], cyclic_nodes: vec![0, 1] })
+/// );
 /// ```
 pub fn topological_sort<N, E, K, F>(
     graph: &impl GraphViewTrait<N, E>,
     key_fn: Option<F>,
-) -> Result<Vec<u32>, String>
+) -> Result<Vec<u32>, TopoSortError>
 where
     N: NodeTrait,
     E: EdgeTrait,
     K: Ord,
     F: Fn(&N) -> K,
 {
-    fn visit<N, E, K, F>(
-        mut nodes: Vec<&N>,
+    let mut workspace = TopoSortWorkspace::new();
+    sort_into(graph, key_fn, &mut workspace)?;
+    Ok(workspace.result)
+}
+
+/// Reusable buffers for topological sorting.
+///
+/// Keeps the visited sets, the search stacks and the result buffer between calls, so that sorting many graphs
+/// in a loop does not allocate them again. The order computed by [`run`](Self::run) is
+/// borrowed from the workspace and valid until the next call.
+///
+/// # Examples
+///
+/// ```
+/// use ade_topological_sort::TopoSortWorkspace;
+/// use ade_graph::implementations::{Node, Edge};
+/// use ade_graph::utils::build::build_graph;
+///
+/// let mut workspace = TopoSortWorkspace::new();
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(2, 1), (1, 0)]);
+/// let sorted = workspace.run::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None).unwrap();
+/// assert_eq!(sorted, &[2, 1, 0]);
+///
+/// let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+/// assert!(workspace.run::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None).is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct TopoSortWorkspace {
+    visiting: FixedBitSet,
+    visited: FixedBitSet,
+    result: Vec<u32>,
+    // Nodes waiting to be visited, and the explicit DFS stack
+    pending: Vec<u32>,
+    frames: Vec<(u32, usize)>,
+}
+
+impl TopoSortWorkspace {
+    /// Creates an empty workspace. Buffers grow on first use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts `graph` topologically, as [`topological_sort`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TopoSortError`] if the graph contains a cycle or does not have
+    /// sequential keys starting from 0.
+    pub fn run<N, E, K, F>(
+        &mut self,
         graph: &impl GraphViewTrait<N, E>,
-        visiting: &mut FixedBitSet,
-        visited: &mut FixedBitSet,
-        result: &mut Vec<u32>,
-        key_fn: &Option<F>,
-    ) -> Result<(), String>
+        key_fn: Option<F>,
+    ) -> Result<&[u32], TopoSortError>
     where
         N: NodeTrait,
         E: EdgeTrait,
         K: Ord,
         F: Fn(&N) -> K,
     {
+        sort_into(graph, key_fn, self)?;
+        Ok(&self.result)
+    }
+}
+
+// Writes the topological order of the graph into the workspace result
+fn sort_into<N, E, K, F>(
+    graph: &impl GraphViewTrait<N, E>,
+    key_fn: Option<F>,
+    workspace: &mut TopoSortWorkspace,
+) -> Result<(), TopoSortError>
+where
+    N: NodeTrait,
+    E: EdgeTrait,
+    K: Ord,
+    F: Fn(&N) -> K,
+{
+    // Pushes the keys of the given nodes on the pending stack, so that they are popped in
+    // descending order of key_fn
+    fn push_ordered<'a, N, K, F>(
+        nodes: impl Iterator<Item = &'a N>,
+        pending: &mut Vec<u32>,
+        key_fn: &Option<F>,
+    ) where
+        N: NodeTrait + 'a,
+        K: Ord,
+        F: Fn(&N) -> K,
+    {
+        let start = pending.len();
         match key_fn {
             Some(f) => {
+                let mut nodes: Vec<&N> = nodes.collect();
                 nodes.sort_by_key(|n| Reverse(f(n)));
+                pending.extend(nodes.iter().rev().map(|n| n.key()));
+            }
+            None => {
+                pending.extend(nodes.map(|n| n.key()));
+                pending[start..].reverse();
             }
-            None => (),
         }
+    }
 
-        for node in nodes {
-            dfs(node.key(), graph, visiting, visited, result, key_fn)?;
-        }
+    check_keys(graph)?;
+    let node_count = graph.node_count();
 
-        Ok(())
-    }
+    let TopoSortWorkspace {
+        visiting,
+        visited,
+        result,
+        pending,
+        frames,
+    } = workspace;
+    result.clear();
+    result.reserve(node_count);
+    pending.clear();
+    frames.clear();
+
+    // Reset bit sets for visiting and visited nodes
+    visiting.clear();
+    visiting.grow(node_count);
+    visited.clear();
+    visited.grow(node_count);
+
+    // Depth-first search with an explicit stack, so that long chains cannot overflow the
+    // call stack. Each frame holds a node being visited and the length of the pending stack
+    // before its successors were pushed; the nodes below that mark belong to the outer frames.
+    push_ordered(graph.get_nodes(), pending, &key_fn);
+    loop {
+        let start = frames.last().map_or(0, |&(_, start)| start);
+
+        if pending.len() == start {
+            match frames.pop() {
+                Some((node_key, _)) => {
+                    let idx = node_key as usize;
+                    visiting.set(idx, false);
+                    visited.set(idx, true);
+                    result.push(node_key);
+                    continue;
+                }
+                None => break,
+            }
+        }
 
-    fn dfs<N, E, K, F>(
-        node_key: u32,
-        graph: &impl GraphViewTrait<N, E>,
-        visiting: &mut FixedBitSet,
-        visited: &mut FixedBitSet,
-        result: &mut Vec<u32>,
-        key_fn: &Option<F>,
-    ) -> Result<(), String>
-    where
-        N: NodeTrait,
-        E: EdgeTrait,
-        K: Ord,
-        F: Fn(&N) -> K,
-    {
+        let node_key = pending.pop().unwrap();
         let idx = node_key as usize;
 
         if visited[idx] {
-            return Ok(());
+            continue;
         }
 
         if visiting[idx] {
-            return Err(CYCLE_ERROR_MSG.into());
+            // The node is on the DFS stack: the frames from it to the top close a cycle
+            let position = frames
+                .iter()
+                .rposition(|&(key, _)| key == node_key)
+                .unwrap();
+            let mut keys: Vec<u32> = frames[position..].iter().map(|&(key, _)| key).collect();
+            keys.push(node_key);
+            let witness = Path::new(keys).rotated_to_min().into_keys();
+            let cyclic_nodes = kahn::cyclic_nodes(graph, &kahn::leftover_in_degrees(graph));
+            return Err(TopoSortError::CycleDetected {
+                witness,
+                cyclic_nodes,
+            });
         }
 
         visiting.set(idx, true);
-
-        visit(graph.get_successors(node_key).collect(), graph, visiting, visited, result, key_fn)?;
-
-        visiting.set(idx, false);
-        visited.set(idx, true);
-        result.push(node_key);
-        Ok(())
+        frames.push((node_key, pending.len()));
+        push_ordered(graph.get_successors(node_key), pending, &key_fn);
     }
 
-    // Panic if the graph does not have sequential keys
-    if !graph.has_sequential_keys() {
-        panic!("{}", INVALID_KEY_SEQUENCE);
-    }
+    result.reverse();
+    Ok(())
+}
 
+// Fails with the smallest key outside 0..n if the graph does not have sequential keys
+pub(crate) fn check_keys<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<(), TopoSortError> {
+    if graph.has_sequential_keys() {
+        return Ok(());
+    }
     let node_count = graph.node_count();
-    let mut result = Vec::with_capacity(node_count);
-
-    // Initialize bit sets for visiting and visited nodes
-    let mut visiting = FixedBitSet::with_capacity(node_count);
-    let mut visited = FixedBitSet::with_capacity(node_count);
-
-    visit(graph.get_nodes().collect(), graph, &mut visiting, &mut visited, &mut result, &key_fn)?;
-
-    result.reverse();
-    Ok(result)
+    let key = graph
+        .get_node_keys()
+        .filter(|&key| key as usize >= node_count)
+        .min()
+        .expect("Non-sequential keys include one outside 0..n");
+    Err(TopoSortError::NonSequentialKeys { key, node_count })
 }
 
 #[cfg(test)]
@@ -217,13 +336,19 @@ mod tests {
 
     #[test]
     fn test_topological_sort_non_sequential_keys() {
-        use ade_common::assert_panics_with;
-
         let graph = build_graph(vec![1, 3, 5], vec![(1, 3), (3, 5), (5, 1)]);
-        assert_panics_with!(
-            topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None),
-            ade_common::INVALID_KEY_SEQUENCE
+        let error =
+            topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None).unwrap_err();
+        assert_eq!(
+            error,
+            TopoSortError::NonSequentialKeys {
+                key: 3,
+                node_count: 3
+            }
         );
+        assert!(error
+            .to_string()
+            .starts_with(ade_common::INVALID_KEY_SEQUENCE));
     }
 
     #[test]
@@ -237,67 +362,81 @@ mod tests {
         let graph = Graph::<Node, Edge>::new(vec![n1, n2], vec![e1, e2]);
 
         let result = topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), CYCLE_ERROR_MSG);
+        let error = result.unwrap_err();
+        assert_eq!(
+            error,
+            TopoSortError::CycleDetected {
+                witness: vec![0, 1, 0],
+                cyclic_nodes: vec![0, 1]
+            }
+        );
+        assert_eq!(error.to_string(), "Graph contains a cycle: 0 → 1 → 0");
+    }
+
+    #[test]
+    fn test_topological_sort_cycle_witness() {
+        // The cycle 2 -> 3 -> 4 -> 2 is reached from 0 through 1, and leads to 5
+        let graph = build_graph::<Node, Edge>(
+            vec![0, 1, 2, 3, 4, 5],
+            vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 2), (4, 5)],
+        );
+        let sort_fn = |n: &Node| n.key();
+        assert_eq!(
+            topological_sort(&graph, Some(sort_fn)),
+            Err(TopoSortError::CycleDetected {
+                witness: vec![2, 3, 4, 2],
+                cyclic_nodes: vec![2, 3, 4]
+            })
+        );
+
+        let graph = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 1)]);
+        assert_eq!(
+            topological_sort(&graph, Some(sort_fn)),
+            Err(TopoSortError::CycleDetected {
+                witness: vec![1, 1],
+                cyclic_nodes: vec![1]
+            })
+        );
     }
 
     #[test]
     fn test_topological_sort_with_compare_by_key_1() {
         let graph1 = build_graph(vec![0, 1, 2], vec![(0, 1), (0, 2)]);
 
-        let sort_fn = |n: &Node| (n.key() as u32);
+        let sort_fn = |n: &Node| n.key();
         let reverse_sort_fn = |n: &Node| -(n.key() as i32);
 
         assert_eq!(
-            topological_sort::<Node, Edge, u32, _>(
-                &graph1,
-                Some(sort_fn)
-            )
-            .unwrap(),
+            topological_sort::<Node, Edge, u32, _>(&graph1, Some(sort_fn)).unwrap(),
             vec![0, 1, 2]
         );
 
         assert_eq!(
-            topological_sort::<Node, Edge, i32, _>
-                (&graph1,
-                Some(reverse_sort_fn)
-            ).unwrap(),
+            topological_sort::<Node, Edge, i32, _>(&graph1, Some(reverse_sort_fn)).unwrap(),
             vec![0, 2, 1]
         );
 
         let graph2 = build_graph(vec![0, 1, 2], vec![(0, 2), (1, 2)]);
 
         assert_eq!(
-            topological_sort::<Node, Edge, u32, _>(
-                &graph2,
-                Some(sort_fn)
-            ).unwrap(),
+            topological_sort::<Node, Edge, u32, _>(&graph2, Some(sort_fn)).unwrap(),
             vec![0, 1, 2]
         );
 
         assert_eq!(
-            topological_sort::<Node, Edge, i32, _>(
-                &graph2,
-                Some(reverse_sort_fn)
-            ).unwrap(),
+            topological_sort::<Node, Edge, i32, _>(&graph2, Some(reverse_sort_fn)).unwrap(),
             vec![1, 0, 2]
         );
 
         let graph3 = build_graph(vec![0, 1, 2, 3, 4], vec![(0, 1), (0, 4), (2, 4), (2, 3)]);
 
         assert_eq!(
-            topological_sort::<Node, Edge, u32, _>(
-                &graph3,
-                Some(sort_fn)
-            ).unwrap(),
+            topological_sort::<Node, Edge, u32, _>(&graph3, Some(sort_fn)).unwrap(),
             vec![0, 1, 2, 3, 4]
         );
 
         assert_eq!(
-            topological_sort::<Node, Edge, i32, _>(
-                &graph3,
-                Some(reverse_sort_fn)
-            ).unwrap(),
+            topological_sort::<Node, Edge, i32, _>(&graph3, Some(reverse_sort_fn)).unwrap(),
             vec![2, 3, 0, 4, 1]
         );
     }
@@ -307,19 +446,65 @@ mod tests {
         let (nodes, edges) = generate_random_graph_data(20, 20, 3);
         let graph = build_graph(nodes, edges);
 
-        let sort_fn = |n: &Node| (n.key() as u32);
+        let sort_fn = |n: &Node| n.key();
         let reverse_sort_fn = |n: &Node| -(n.key() as i32);
-        
-        let sorting = topological_sort::<Node, Edge, u32, _>(
-            &graph,
-            Some(sort_fn)
-        );
+
+        let sorting = topological_sort::<Node, Edge, u32, _>(&graph, Some(sort_fn));
         assert!(sorting.is_ok());
 
-        let sorting = topological_sort::<Node, Edge, i32, _>(
-            &graph,
-            Some(reverse_sort_fn)
-        );
+        let sorting = topological_sort::<Node, Edge, i32, _>(&graph, Some(reverse_sort_fn));
         assert!(sorting.is_ok());
     }
+
+    #[test]
+    fn test_topological_sort_workspace_reuse() {
+        let mut workspace = TopoSortWorkspace::new();
+        let sort_fn = |n: &Node| n.key();
+
+        let graph1 =
+            build_graph::<Node, Edge>(vec![0, 1, 2, 3, 4], vec![(0, 1), (0, 4), (2, 4), (2, 3)]);
+        assert_eq!(
+            workspace.run(&graph1, Some(sort_fn)).unwrap(),
+            &[0, 1, 2, 3, 4]
+        );
+
+        let graph2 = build_graph::<Node, Edge>(vec![0, 1], vec![(0, 1), (1, 0)]);
+        assert_eq!(
+            workspace.run(&graph2, Some(sort_fn)).unwrap_err(),
+            TopoSortError::CycleDetected {
+                witness: vec![0, 1, 0],
+                cyclic_nodes: vec![0, 1]
+            }
+        );
+
+        let graph3 = build_graph::<Node, Edge>(vec![0, 1, 2], vec![(2, 0), (0, 1)]);
+        assert_eq!(workspace.run(&graph3, Some(sort_fn)).unwrap(), &[2, 0, 1]);
+    }
+
+    #[test]
+    fn test_topological_sort_long_chain() {
+        let n: u32 = 200_000;
+        let nodes: Vec<u32> = (0..n).collect();
+        let edges: Vec<(u32, u32)> = (1..n).map(|i| (i, i - 1)).collect();
+        let graph = build_graph::<Node, Edge>(nodes, edges);
+
+        let sorted = topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None).unwrap();
+        let expected: Vec<u32> = (0..n).rev().collect();
+        assert_eq!(sorted, expected);
+
+        // Closing the chain makes it a cycle
+        let mut graph = graph;
+        graph.add_edge(Edge::new(0, n - 1));
+        let error =
+            topological_sort::<Node, Edge, u32, fn(&Node) -> u32>(&graph, None).unwrap_err();
+        let TopoSortError::CycleDetected {
+            witness,
+            cyclic_nodes,
+        } = error
+        else {
+            panic!("Expected a cycle, got {:?}", error);
+        };
+        assert_eq!(witness.len(), n as usize + 1);
+        assert_eq!(cyclic_nodes.len(), n as usize);
+    }
 }
@@ -0,0 +1,317 @@
+use crate::{EdgeTrait, GraphViewTrait, NodeTrait};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A violation of the invariants of [`GraphViewTrait`], found by [`check_graph_consistency`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsistencyError {
+    /// `node_count` differs from the number of keys yielded by `get_node_keys`.
+    NodeCountMismatch { node_count: usize, keys: usize },
+    /// `is_empty` disagrees with `node_count`.
+    IsEmptyMismatch { is_empty: bool, node_count: usize },
+    /// `get_node_keys` yields the same key more than once.
+    DuplicateNodeKey(u32),
+    /// `get_node_keys` yields a key for which `has_node` returns `false`.
+    NodeNotFound(u32),
+    /// `get_node` returns a node whose key differs from the requested one.
+    NodeKeyMismatch { key: u32, found: u32 },
+    /// `get_nodes` does not yield the same keys as `get_node_keys`.
+    NodesMismatch,
+    /// `get_edges` yields the same edge more than once.
+    DuplicateEdge { source: u32, target: u32 },
+    /// `get_edges` yields an edge with an endpoint that is not a node of the graph.
+    DanglingEdge { source: u32, target: u32 },
+    /// `has_edge` returns `false` for an edge yielded by `get_edges`.
+    EdgeNotFound { source: u32, target: u32 },
+    /// `get_edge` returns an edge whose key differs from the requested one.
+    EdgeKeyMismatch {
+        source: u32,
+        target: u32,
+        found: (u32, u32),
+    },
+    /// The successors of `source` do not match the edges leaving it: `target` is listed by
+    /// only one of `get_successors_keys` and `get_edges`, or listed twice.
+    SuccessorMismatch { source: u32, target: u32 },
+    /// The predecessors of `target` do not match the edges entering it: `source` is listed
+    /// by only one of `get_predecessors_keys` and `get_edges`, or listed twice.
+    PredecessorMismatch { source: u32, target: u32 },
+    /// `get_successors` or `get_predecessors` disagrees with the corresponding keys iterator.
+    NeighborsMismatch(u32),
+    /// `has_sequential_keys` disagrees with the keys yielded by `get_node_keys`.
+    SequentialKeysMismatch { has_sequential_keys: bool },
+}
+
+impl fmt::Display for ConsistencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsistencyError::NodeCountMismatch { node_count, keys } => write!(
+                f,
+                "node_count returns {} but get_node_keys yields {} keys",
+                node_count, keys
+            ),
+            ConsistencyError::IsEmptyMismatch {
+                is_empty,
+                node_count,
+            } => write!(
+                f,
+                "is_empty returns {} but node_count returns {}",
+                is_empty, node_count
+            ),
+            ConsistencyError::DuplicateNodeKey(key) => {
+                write!(f, "get_node_keys yields node {} more than once", key)
+            }
+            ConsistencyError::NodeNotFound(key) => {
+                write!(
+                    f,
+                    "has_node returns false for node {} yielded by get_node_keys",
+                    key
+                )
+            }
+            ConsistencyError::NodeKeyMismatch { key, found } => {
+                write!(f, "get_node({}) returns node {}", key, found)
+            }
+            ConsistencyError::NodesMismatch => {
+                write!(f, "get_nodes and get_node_keys yield different nodes")
+            }
+            ConsistencyError::DuplicateEdge { source, target } => {
+                write!(
+                    f,
+                    "get_edges yields edge {} -> {} more than once",
+                    source, target
+                )
+            }
+            ConsistencyError::DanglingEdge { source, target } => {
+                write!(f, "edge {} -> {} references a missing node", source, target)
+            }
+            ConsistencyError::EdgeNotFound { source, target } => write!(
+                f,
+                "has_edge returns false for edge {} -> {} yielded by get_edges",
+                source, target
+            ),
+            ConsistencyError::EdgeKeyMismatch {
+                source,
+                target,
+                found,
+            } => write!(
+                f,
+                "get_edge({}, {}) returns edge {} -> {}",
+                source, target, found.0, found.1
+            ),
+            ConsistencyError::SuccessorMismatch { source, target } => write!(
+                f,
+                "successors of node {} disagree with get_edges on node {}",
+                source, target
+            ),
+            ConsistencyError::PredecessorMismatch { source, target } => write!(
+                f,
+                "predecessors of node {} disagree with get_edges on node {}",
+                target, source
+            ),
+            ConsistencyError::NeighborsMismatch(key) => write!(
+                f,
+                "neighbor nodes and neighbor keys of node {} disagree",
+                key
+            ),
+            ConsistencyError::SequentialKeysMismatch {
+                has_sequential_keys,
+            } => write!(
+                f,
+                "has_sequential_keys returns {} but the node keys say otherwise",
+                has_sequential_keys
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConsistencyError {}
+
+/// Checks that the methods of a [`GraphViewTrait`] implementation agree with each other.
+///
+/// The algorithms of the ADE crates rely on invariants that the trait cannot express in its
+/// signatures: for example, every edge yielded by `get_edges` must be reported by
+/// `has_edge`, and must appear among the successors of its source and the predecessors of
+/// its target. This function verifies those invariants on a concrete graph, which is useful
+/// when writing a custom adapter. It checks that:
+///
+/// * `node_count`, `is_empty`, `get_node_keys`, `get_nodes`, `has_node` and `get_node`
+///   describe the same set of nodes, without duplicates;
+/// * every edge of `get_edges` connects existing nodes, is reported by `has_edge` and
+///   returned by `get_edge`, and appears once;
+/// * `get_successors_keys` and `get_predecessors_keys` list exactly the edges of
+///   `get_edges`, once each, and agree with `get_successors` and `get_predecessors`;
+/// * `has_sequential_keys` is `true` if and only if the keys are `0..n`.
+///
+/// The check visits every node and edge, and is meant for tests.
+///
+/// # Errors
+///
+/// Returns the first [`ConsistencyError`] found.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{Graph, Node, Edge};
+/// use ade_traits::check_graph_consistency;
+///
+/// let graph = Graph::<Node, Edge>::new(
+///     vec![Node::new(0), Node::new(1), Node::new(2)],
+///     vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 2)],
+/// );
+///
+/// assert_eq!(check_graph_consistency(&graph), Ok(()));
+/// ```
+pub fn check_graph_consistency<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<(), ConsistencyError> {
+    let keys = check_nodes(graph)?;
+    let edges = check_edges(graph, &keys)?;
+
+    for &key in &keys {
+        check_neighbors(graph, key, &edges)?;
+    }
+
+    // Neighbor lists only contain edges, each once, so they cover all the edges exactly
+    // when their total length is the number of edges
+    let successors: usize = keys
+        .iter()
+        .map(|&k| graph.get_successors_keys(k).count())
+        .sum();
+    let predecessors: usize = keys
+        .iter()
+        .map(|&k| graph.get_predecessors_keys(k).count())
+        .sum();
+    if successors != edges.len() || predecessors != edges.len() {
+        return Err(missing_neighbor(graph, &edges));
+    }
+
+    let sequential = (0..keys.len() as u32).all(|key| keys.contains(&key));
+    if graph.has_sequential_keys() != sequential {
+        return Err(ConsistencyError::SequentialKeysMismatch {
+            has_sequential_keys: graph.has_sequential_keys(),
+        });
+    }
+
+    Ok(())
+}
+
+// Checks the node methods and returns the set of node keys
+fn check_nodes<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+) -> Result<HashSet<u32>, ConsistencyError> {
+    let mut keys = HashSet::new();
+    for key in graph.get_node_keys() {
+        if !keys.insert(key) {
+            return Err(ConsistencyError::DuplicateNodeKey(key));
+        }
+        if !graph.has_node(key) {
+            return Err(ConsistencyError::NodeNotFound(key));
+        }
+        let found = graph.get_node(key).key();
+        if found != key {
+            return Err(ConsistencyError::NodeKeyMismatch { key, found });
+        }
+    }
+
+    let node_count = graph.node_count();
+    if node_count != keys.len() {
+        return Err(ConsistencyError::NodeCountMismatch {
+            node_count,
+            keys: keys.len(),
+        });
+    }
+    if graph.is_empty() != (node_count == 0) {
+        return Err(ConsistencyError::IsEmptyMismatch {
+            is_empty: graph.is_empty(),
+            node_count,
+        });
+    }
+
+    let nodes: HashSet<u32> = graph.get_nodes().map(|node| node.key()).collect();
+    if nodes != keys || graph.get_nodes().count() != keys.len() {
+        return Err(ConsistencyError::NodesMismatch);
+    }
+
+    Ok(keys)
+}
+
+// Checks the edge methods and returns the set of edge keys
+fn check_edges<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    keys: &HashSet<u32>,
+) -> Result<HashSet<(u32, u32)>, ConsistencyError> {
+    let mut edges = HashSet::new();
+    for edge in graph.get_edges() {
+        let (source, target) = edge.key();
+        if !edges.insert((source, target)) {
+            return Err(ConsistencyError::DuplicateEdge { source, target });
+        }
+        if !keys.contains(&source) || !keys.contains(&target) {
+            return Err(ConsistencyError::DanglingEdge { source, target });
+        }
+        if !graph.has_edge(source, target) {
+            return Err(ConsistencyError::EdgeNotFound { source, target });
+        }
+        let found = graph.get_edge(source, target).key();
+        if found != (source, target) {
+            return Err(ConsistencyError::EdgeKeyMismatch {
+                source,
+                target,
+                found,
+            });
+        }
+    }
+    Ok(edges)
+}
+
+// Checks that the neighbors of a node are edges of the graph, listed once
+fn check_neighbors<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    key: u32,
+    edges: &HashSet<(u32, u32)>,
+) -> Result<(), ConsistencyError> {
+    let mut successors = HashSet::new();
+    for target in graph.get_successors_keys(key) {
+        if !edges.contains(&(key, target)) || !successors.insert(target) {
+            return Err(ConsistencyError::SuccessorMismatch {
+                source: key,
+                target,
+            });
+        }
+    }
+
+    let mut predecessors = HashSet::new();
+    for source in graph.get_predecessors_keys(key) {
+        if !edges.contains(&(source, key)) || !predecessors.insert(source) {
+            return Err(ConsistencyError::PredecessorMismatch {
+                source,
+                target: key,
+            });
+        }
+    }
+
+    let successor_nodes: HashSet<u32> = graph.get_successors(key).map(|n| n.key()).collect();
+    let predecessor_nodes: HashSet<u32> = graph.get_predecessors(key).map(|n| n.key()).collect();
+    if successor_nodes != successors || predecessor_nodes != predecessors {
+        return Err(ConsistencyError::NeighborsMismatch(key));
+    }
+
+    Ok(())
+}
+
+// Finds an edge missing from the neighbor lists, once the counts are known to differ
+fn missing_neighbor<N: NodeTrait, E: EdgeTrait>(
+    graph: &impl GraphViewTrait<N, E>,
+    edges: &HashSet<(u32, u32)>,
+) -> ConsistencyError {
+    let mut sorted: Vec<(u32, u32)> = edges.iter().copied().collect();
+    sorted.sort_unstable();
+    for (source, target) in sorted {
+        if !graph.get_successors_keys(source).any(|k| k == target) {
+            return ConsistencyError::SuccessorMismatch { source, target };
+        }
+        if !graph.get_predecessors_keys(target).any(|k| k == source) {
+            return ConsistencyError::PredecessorMismatch { source, target };
+        }
+    }
+    unreachable!("neighbor counts differ but every edge is listed")
+}
@@ -0,0 +1,62 @@
+use crate::{EdgeTrait, NodeTrait};
+
+/// A node carrying a payload, such as a label or a metadata struct.
+///
+/// The payload type is chosen by the implementation. Implementations should also override
+/// [`NodeTrait::with_key`], so that the payload survives transformations that change keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::DataNode;
+/// use ade_traits::{DataNodeTrait, NodeTrait};
+///
+/// let mut node = DataNode::with_data(1, "parser");
+/// assert_eq!(*node.data(), "parser");
+///
+/// *node.data_mut() = "lexer";
+/// assert_eq!(*node.with_key(5).data(), "lexer");
+/// ```
+pub trait DataNodeTrait: NodeTrait {
+    /// The type of the payload.
+    type Data;
+
+    /// Creates a new node with the given key and payload.
+    fn with_data(key: u32, data: Self::Data) -> Self;
+
+    /// Returns the payload of this node.
+    fn data(&self) -> &Self::Data;
+
+    /// Returns the payload of this node, for modification.
+    fn data_mut(&mut self) -> &mut Self::Data;
+}
+
+/// An edge carrying a payload, such as a label or a metadata struct.
+///
+/// The payload type is chosen by the implementation. Implementations should also override
+/// [`EdgeTrait::with_endpoints`], so that the payload survives transformations that change
+/// keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::DataEdge;
+/// use ade_traits::{DataEdgeTrait, EdgeTrait};
+///
+/// let edge = DataEdge::with_data(1, 2, String::from("calls"));
+/// assert_eq!(edge.key(), (1, 2));
+/// assert_eq!(edge.with_endpoints(3, 4).data(), "calls");
+/// ```
+pub trait DataEdgeTrait: EdgeTrait {
+    /// The type of the payload.
+    type Data;
+
+    /// Creates a new edge from source to target with the given payload.
+    fn with_data(source: u32, target: u32, data: Self::Data) -> Self;
+
+    /// Returns the payload of this edge.
+    fn data(&self) -> &Self::Data;
+
+    /// Returns the payload of this edge, for modification.
+    fn data_mut(&mut self) -> &mut Self::Data;
+}
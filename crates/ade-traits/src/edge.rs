@@ -102,4 +102,51 @@ pub trait EdgeTrait: Debug + Clone {
     /// assert_ne!(edge.key(), reverse_edge.key());
     /// ```
     fn key(&self) -> (u32, u32);
+
+    /// Returns a copy of this edge between new endpoints.
+    ///
+    /// Graph transformations that change keys, such as renumbering, use this method to
+    /// rebuild edges. The default implementation calls [`new`](EdgeTrait::new); edges that
+    /// carry a weight or other data should override it to keep them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::Edge;
+    /// use ade_traits::EdgeTrait;
+    ///
+    /// let edge = Edge::new(1, 2).with_endpoints(3, 4);
+    /// assert_eq!(edge.key(), (3, 4));
+    /// ```
+    fn with_endpoints(&self, source: u32, target: u32) -> Self {
+        Self::new(source, target)
+    }
+}
+
+/// An edge carrying a weight, such as a cost, a distance or a capacity.
+///
+/// Most algorithms that need edge weights, such as shortest paths or maximum flows, take a
+/// weight closure, to which `|edge| edge.weight()` passes the weight stored by this trait.
+/// A few, such as [`Path::total_weight`](crate::Path::total_weight), bound their edge type
+/// by it directly. The weight type is chosen by the implementation, and is usually a number.
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::WeightedEdge;
+/// use ade_traits::{EdgeTrait, WeightedEdgeTrait};
+///
+/// let edge = WeightedEdge::with_weight(1, 2, 2.5);
+/// assert_eq!(edge.key(), (1, 2));
+/// assert_eq!(edge.weight(), 2.5);
+/// ```
+pub trait WeightedEdgeTrait: EdgeTrait {
+    /// The type of the weight.
+    type Weight: Copy + Debug;
+
+    /// Creates a new edge from source to target with the given weight.
+    fn with_weight(source: u32, target: u32, weight: Self::Weight) -> Self;
+
+    /// Returns the weight of this edge.
+    fn weight(&self) -> Self::Weight;
 }
@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// An error returned by the fallible modifications of a graph, such as
+/// [`GraphMutTrait::try_add_edge`](crate::GraphMutTrait::try_add_edge).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// The operation references a node that is not in the graph.
+    MissingNode(u32),
+    /// The operation references an edge that is not in the graph, given as
+    /// `(source, target)`.
+    MissingEdge(u32, u32),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::MissingNode(key) => write!(f, "Node {} not found", key),
+            GraphError::MissingEdge(source, target) => {
+                write!(f, "Edge {}→{} not found", source, target)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GraphError::MissingNode(3).to_string(), "Node 3 not found");
+        assert_eq!(
+            GraphError::MissingEdge(1, 2).to_string(),
+            "Edge 1→2 not found"
+        );
+    }
+}
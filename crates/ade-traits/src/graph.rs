@@ -30,6 +30,10 @@ use crate::{EdgeTrait, NodeTrait};
 pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     /// Returns the number of nodes in the graph.
     ///
+    /// The default implementation counts the node keys; implementations that store the
+    /// number of nodes override it to answer in `O(1)` time, so algorithms should call it
+    /// rather than counting [`get_nodes`](Self::get_nodes).
+    ///
     /// # Examples
     ///
     /// ```
@@ -39,7 +43,30 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     /// let graph = Graph::<Node, Edge>::new(vec![Node::new(1), Node::new(2), Node::new(3)], vec![]);
     /// assert_eq!(graph.node_count(), 3);
     /// ```
-    fn node_count(&self) -> usize;
+    fn node_count(&self) -> usize {
+        self.get_node_keys().count()
+    }
+
+    /// Returns the number of edges in the graph.
+    ///
+    /// The default implementation counts [`get_edges`](Self::get_edges); implementations that
+    /// store their edges override it to answer in `O(1)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::<Node, Edge>::new(
+    ///     vec![Node::new(1), Node::new(2)],
+    ///     vec![Edge::new(1, 2), Edge::new(2, 1), Edge::new(2, 2)],
+    /// );
+    /// assert_eq!(graph.edge_count(), 3);
+    /// ```
+    fn edge_count(&self) -> usize {
+        self.get_edges().count()
+    }
 
     /// Returns `true` if the graph contains no nodes.
     ///
@@ -314,7 +341,7 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     /// # Parameters
     ///
     /// * `node_key` - The key of the node whose predecessor keys to retrieve
-    /// 
+    ///
     /// # Panics
     ///
     /// Panics if the node with the given key does not exist in the graph.
@@ -345,7 +372,7 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     /// # Parameters
     ///
     /// * `node_key` - The key of the node whose successor keys to retrieve
-    /// 
+    ///
     /// # Panics
     ///
     /// Panics if the node with the given key does not exist in the graph.
@@ -368,6 +395,175 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     /// ```
     fn get_successors_keys(&self, node_key: u32) -> impl Iterator<Item = u32> + '_;
 
+    /// Returns an iterator over the edges leaving a node, each with its target node.
+    ///
+    /// Weighted algorithms can read the data of every outgoing edge while traversing,
+    /// instead of calling [`get_edge`](Self::get_edge) for every successor. The default
+    /// implementation does exactly that; implementations that store edges next to their
+    /// adjacency override it to skip the lookup.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, WeightedEdge};
+    /// use ade_traits::{GraphViewTrait, NodeTrait, WeightedEdgeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![WeightedEdge::with_weight(1, 2, 5), WeightedEdge::with_weight(1, 3, 7)],
+    /// );
+    ///
+    /// let mut out: Vec<(u32, u32)> = graph
+    ///     .get_out_edges(1)
+    ///     .map(|(edge, target)| (target.key(), edge.weight()))
+    ///     .collect();
+    /// out.sort_unstable();
+    /// assert_eq!(out, vec![(2, 5), (3, 7)]);
+    /// ```
+    fn get_out_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        self.get_successors_keys(node_key)
+            .map(move |target| (self.get_edge(node_key, target), self.get_node(target)))
+    }
+
+    /// Returns an iterator over the edges entering a node, each with its source node.
+    ///
+    /// This is the counterpart of [`get_out_edges`](Self::get_out_edges) for predecessors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, WeightedEdge};
+    /// use ade_traits::{GraphViewTrait, NodeTrait, WeightedEdgeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![WeightedEdge::with_weight(1, 3, 5), WeightedEdge::with_weight(2, 3, 7)],
+    /// );
+    ///
+    /// let total: u32 = graph.get_in_edges(3).map(|(edge, _)| edge.weight()).sum();
+    /// assert_eq!(total, 12);
+    /// ```
+    fn get_in_edges<'a>(&'a self, node_key: u32) -> impl Iterator<Item = (&'a E, &'a N)>
+    where
+        N: 'a,
+        E: 'a,
+    {
+        self.get_predecessors_keys(node_key)
+            .map(move |source| (self.get_edge(source, node_key), self.get_node(source)))
+    }
+
+    /// Returns the number of predecessors of a node.
+    ///
+    /// The default implementation counts [`get_predecessors_keys`](Self::get_predecessors_keys);
+    /// implementations that store adjacency sets override it to answer in `O(1)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![Edge::new(1, 3), Edge::new(2, 3)],
+    /// );
+    /// assert_eq!(graph.in_degree(3), 2);
+    /// assert_eq!(graph.in_degree(1), 0);
+    /// ```
+    fn in_degree(&self, node_key: u32) -> usize {
+        self.get_predecessors_keys(node_key).count()
+    }
+
+    /// Returns the number of successors of a node.
+    ///
+    /// The default implementation counts [`get_successors_keys`](Self::get_successors_keys);
+    /// implementations that store adjacency sets override it to answer in `O(1)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![Edge::new(1, 2), Edge::new(1, 3)],
+    /// );
+    /// assert_eq!(graph.out_degree(1), 2);
+    /// assert_eq!(graph.out_degree(3), 0);
+    /// ```
+    fn out_degree(&self, node_key: u32) -> usize {
+        self.get_successors_keys(node_key).count()
+    }
+
+    /// Returns the number of edges incident to a node, the sum of its in-degree and
+    /// out-degree. A self-loop counts twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2)],
+    ///     vec![Edge::new(1, 2), Edge::new(2, 2)],
+    /// );
+    /// assert_eq!(graph.degree(1), 1);
+    /// assert_eq!(graph.degree(2), 3);
+    /// ```
+    fn degree(&self, node_key: u32) -> usize {
+        self.in_degree(node_key) + self.out_degree(node_key)
+    }
+
+    /// Returns the degrees of all nodes, as given by [`degree`](Self::degree), in
+    /// non-increasing order.
+    ///
+    /// Two isomorphic graphs have the same degree sequence, which makes it a cheap first
+    /// test before comparing their structure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{EdgeTrait, GraphViewTrait, NodeTrait};
+    ///
+    /// let graph = Graph::new(
+    ///     vec![Node::new(1), Node::new(2), Node::new(3)],
+    ///     vec![Edge::new(1, 2), Edge::new(1, 3), Edge::new(2, 3)],
+    /// );
+    /// assert_eq!(graph.degree_sequence(), vec![2, 2, 2]);
+    /// ```
+    fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees: Vec<usize> = self.get_node_keys().map(|key| self.degree(key)).collect();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+
     /// Creates a filtered view of the graph containing only the specified nodes.
     ///
     /// The filtered view includes only the nodes whose keys are in `node_keys`,
@@ -381,10 +577,10 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     ///
     /// A new graph view containing only the specified nodes and edges between them.
     ///
-    /// # Requirements
+    /// # Key spaces
     ///
-    /// **The base graph must have sequential keys starting from 0** (i.e., 0, 1, 2, 3, ..., n-1).
-    /// If the graph has non-sequential keys, the behavior is undefined and will panic with [`INVALID_KEY_SEQUENCE`][ade_common::INVALID_KEY_SEQUENCE].
+    /// The graph may have any keys. Graphs with sequential keys (0, 1, 2, ..., n-1) are
+    /// filtered through a bitset indexed by key, other graphs through a hash set of keys.
     ///
     /// # Examples
     ///
@@ -397,9 +593,6 @@ pub trait GraphViewTrait<N: NodeTrait, E: EdgeTrait> {
     ///     vec![Edge::new(0, 1), Edge::new(1, 2), Edge::new(2, 3), Edge::new(0, 3)],
     /// );
     ///
-    /// // Verify the graph has sequential keys (required for filter)
-    /// assert!(graph.has_sequential_keys());
-    ///
     /// // Create a filtered view with only nodes 0, 1, and 2
     /// let filtered = graph.filter(&[0, 1, 2]);
     ///
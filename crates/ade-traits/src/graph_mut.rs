@@ -0,0 +1,182 @@
+use crate::{EdgeTrait, GraphError, GraphViewTrait, NodeTrait};
+
+/// A trait for directed graphs that can be modified in place.
+///
+/// This trait extends [`GraphViewTrait`] with the operations that add and remove nodes and
+/// edges, so that generic code, such as generators, builders or cycle removal, can construct
+/// and transform graphs of any implementation. Adding an edge updates the successors and
+/// predecessors of its endpoints, and removing a node removes all its edges.
+///
+/// Only [`add_node`](Self::add_node), [`remove_node`](Self::remove_node),
+/// [`add_edge`](Self::add_edge) and [`remove_edge`](Self::remove_edge) are required; the
+/// other methods are built on them, and implementations may override them with faster
+/// versions.
+///
+/// # Type Parameters
+///
+/// * `N` - The node type, which must implement [`NodeTrait`]
+/// * `E` - The edge type, which must implement [`EdgeTrait`]
+///
+/// # Examples
+///
+/// ```
+/// use ade_graph::implementations::{DenseGraph, Graph, Node, Edge};
+/// use ade_traits::{EdgeTrait, GraphMutTrait, GraphViewTrait, NodeTrait};
+///
+/// // Adds a directed cycle through `n` new nodes, starting from key `first`
+/// fn add_cycle<G: GraphMutTrait<Node, Edge>>(graph: &mut G, first: u32, n: u32) {
+///     for key in first..first + n {
+///         graph.add_node(Node::new(key));
+///     }
+///     for i in 0..n {
+///         graph.add_edge(Edge::new(first + i, first + (i + 1) % n));
+///     }
+/// }
+///
+/// let mut graph = Graph::<Node, Edge>::new(vec![], vec![]);
+/// add_cycle(&mut graph, 0, 3);
+/// assert!(graph.has_edge(2, 0));
+///
+/// let mut dense = DenseGraph::<Node, Edge>::new(vec![], vec![]);
+/// add_cycle(&mut dense, 0, 3);
+/// assert_eq!(dense.edge_count(), 3);
+/// ```
+pub trait GraphMutTrait<N: NodeTrait, E: EdgeTrait>: GraphViewTrait<N, E> {
+    /// Adds a node to the graph.
+    ///
+    /// If a node with the same key already exists, it is replaced and returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(old_node)` - If a node with the same key already existed
+    /// * `None` - If no node with this key existed
+    fn add_node(&mut self, node: N) -> Option<N>;
+
+    /// Removes a node from the graph, together with all its incoming and outgoing edges.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(node)` - If the node existed, returns the removed node
+    /// * `None` - If no node with this key existed
+    fn remove_node(&mut self, key: u32) -> Option<N>;
+
+    /// Adds an edge to the graph, from its source to its target.
+    ///
+    /// If an edge with the same `(source, target)` key already exists, it is replaced and
+    /// returned. Self-loops are supported.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(old_edge)` - If an edge with the same key already existed
+    /// * `None` - If no edge with this key existed
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph. Use
+    /// [`try_add_edge`](Self::try_add_edge) to get an error instead.
+    fn add_edge(&mut self, edge: E) -> Option<E>;
+
+    /// Removes the edge from `source` to `target`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(edge)` - If the edge existed, returns the removed edge
+    /// * `None` - If no edge with this key existed
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the source or target node does not exist in the graph. Use
+    /// [`try_remove_edge`](Self::try_remove_edge) to get an error instead.
+    fn remove_edge(&mut self, source: u32, target: u32) -> Option<E>;
+
+    /// Removes all nodes and edges from the graph.
+    ///
+    /// The default implementation removes the nodes one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{GraphMutTrait, GraphViewTrait};
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![Edge::new(0, 1)]);
+    /// graph.clear();
+    /// assert!(graph.is_empty());
+    /// ```
+    fn clear(&mut self) {
+        let keys: Vec<u32> = self.get_node_keys().collect();
+        for key in keys {
+            self.remove_node(key);
+        }
+    }
+
+    /// Adds an edge to the graph, or returns an error if one of its endpoints is missing.
+    ///
+    /// This is the fallible version of [`add_edge`](Self::add_edge), for edges coming from
+    /// untrusted input. The graph is unchanged when an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] with the key of the source, or else of the
+    /// target, if it does not exist in the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{GraphError, GraphMutTrait, GraphViewTrait};
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![]);
+    ///
+    /// assert!(graph.try_add_edge(Edge::new(0, 1)).unwrap().is_none());
+    /// assert_eq!(graph.try_add_edge(Edge::new(1, 2)).unwrap_err(), GraphError::MissingNode(2));
+    /// assert_eq!(graph.edge_count(), 1);
+    /// ```
+    fn try_add_edge(&mut self, edge: E) -> Result<Option<E>, GraphError> {
+        check_endpoints(self, edge.source(), edge.target())?;
+        Ok(self.add_edge(edge))
+    }
+
+    /// Removes the edge from `source` to `target`, or returns an error if it does not exist.
+    ///
+    /// This is the fallible version of [`remove_edge`](Self::remove_edge).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::MissingNode`] with the key of the source, or else of the
+    /// target, if it does not exist in the graph, and [`GraphError::MissingEdge`] if both
+    /// nodes exist but the edge does not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::{Graph, Node, Edge};
+    /// use ade_traits::{GraphError, GraphMutTrait};
+    ///
+    /// let mut graph = Graph::<Node, Edge>::new(vec![Node::new(0), Node::new(1)], vec![Edge::new(0, 1)]);
+    ///
+    /// assert!(graph.try_remove_edge(0, 1).is_ok());
+    /// assert_eq!(graph.try_remove_edge(0, 1).unwrap_err(), GraphError::MissingEdge(0, 1));
+    /// assert_eq!(graph.try_remove_edge(3, 1).unwrap_err(), GraphError::MissingNode(3));
+    /// ```
+    fn try_remove_edge(&mut self, source: u32, target: u32) -> Result<E, GraphError> {
+        check_endpoints(self, source, target)?;
+        self.remove_edge(source, target)
+            .ok_or(GraphError::MissingEdge(source, target))
+    }
+}
+
+// Checks that both endpoints of an edge exist, the source first
+fn check_endpoints<N: NodeTrait, E: EdgeTrait, G: GraphViewTrait<N, E> + ?Sized>(
+    graph: &G,
+    source: u32,
+    target: u32,
+) -> Result<(), GraphError> {
+    match [source, target]
+        .into_iter()
+        .find(|&key| !graph.has_node(key))
+    {
+        Some(key) => Err(GraphError::MissingNode(key)),
+        None => Ok(()),
+    }
+}
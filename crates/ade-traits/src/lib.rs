@@ -1,7 +1,17 @@
+pub mod consistency;
+pub mod data;
 pub mod edge;
+pub mod error;
 pub mod graph;
+pub mod graph_mut;
 pub mod node;
+pub mod path;
 
-pub use edge::EdgeTrait;
+pub use consistency::{check_graph_consistency, ConsistencyError};
+pub use data::{DataEdgeTrait, DataNodeTrait};
+pub use edge::{EdgeTrait, WeightedEdgeTrait};
+pub use error::GraphError;
 pub use graph::GraphViewTrait;
+pub use graph_mut::GraphMutTrait;
 pub use node::NodeTrait;
+pub use path::{Path, PathError};
@@ -106,7 +106,7 @@ pub trait NodeTrait: Debug + Clone {
     /// let mut node = Node::new(2);
     /// node.add_predecessor(1);
     /// assert!(node.predecessors().contains(&1));
-    /// 
+    ///
     /// // Adding again has no effect
     /// node.add_predecessor(1);
     /// assert_eq!(node.predecessors().len(), 1);
@@ -127,7 +127,7 @@ pub trait NodeTrait: Debug + Clone {
     /// let mut node = Node::new(1);
     /// node.add_successor(2);
     /// assert!(node.successors().contains(&2));
-    /// 
+    ///
     /// // Adding again has no effect
     /// node.add_successor(2);
     /// assert_eq!(node.successors().len(), 1);
@@ -149,7 +149,7 @@ pub trait NodeTrait: Debug + Clone {
     /// node.add_predecessor(1);
     /// node.remove_predecessor(1);
     /// assert!(!node.predecessors().contains(&1));
-    /// 
+    ///
     /// // Removing again has no effect
     /// node.remove_predecessor(1);
     /// assert_eq!(node.predecessors().len(), 0);
@@ -171,10 +171,33 @@ pub trait NodeTrait: Debug + Clone {
     /// node.add_successor(2);
     /// node.remove_successor(2);
     /// assert!(!node.successors().contains(&2));
-    /// 
+    ///
     /// // Removing again has no effect
     /// node.remove_successor(2);
     /// assert_eq!(node.successors().len(), 0);
     /// ```
     fn remove_successor(&mut self, key: u32);
+
+    /// Returns a copy of this node with a new key and no predecessors or successors.
+    ///
+    /// Graph transformations that change keys, such as renumbering, use this method to
+    /// rebuild nodes. The default implementation calls [`new`](NodeTrait::new); nodes that
+    /// carry data should override it to keep their data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_graph::implementations::Node;
+    /// use ade_traits::NodeTrait;
+    ///
+    /// let mut node = Node::new(1);
+    /// node.add_successor(2);
+    ///
+    /// let moved = node.with_key(7);
+    /// assert_eq!(moved.key(), 7);
+    /// assert!(moved.successors().is_empty());
+    /// ```
+    fn with_key(&self, key: u32) -> Self {
+        Self::new(key)
+    }
 }
@@ -0,0 +1,333 @@
+use crate::{EdgeTrait, GraphViewTrait, NodeTrait, WeightedEdgeTrait};
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::Sum;
+
+/// A walk through a graph, as the ordered keys of the nodes it visits.
+///
+/// A path with `k + 1` nodes follows `k` edges, from each node to the next. A path is
+/// *closed* when it ends where it starts, as the elementary circuits returned by
+/// `ade-elementary-circuits` do: the circuit 0 → 1 → 2 → 0 is the closed path
+/// `[0, 1, 2, 0]`, and a self-loop on `v` is `[v, v]`.
+///
+/// Creating a path does not check it against a graph, use [`validate`](Path::validate) for
+/// that. Paths compare with each other, and with vectors of keys.
+///
+/// # Examples
+///
+/// ```
+/// use ade_traits::Path;
+///
+/// let path = Path::new(vec![0, 1, 2, 0]);
+///
+/// assert_eq!(path.len(), 3);
+/// assert_eq!(path.edges().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (2, 0)]);
+/// assert!(path.is_closed());
+/// assert!(path.is_simple());
+/// assert_eq!(path, vec![0, 1, 2, 0]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Path {
+    keys: Vec<u32>,
+}
+
+impl Path {
+    /// Creates a path visiting `keys` in order.
+    pub fn new(keys: Vec<u32>) -> Self {
+        Self { keys }
+    }
+
+    /// Returns the keys of the nodes visited by the path, in order.
+    pub fn keys(&self) -> &[u32] {
+        &self.keys
+    }
+
+    /// Consumes the path and returns its keys.
+    pub fn into_keys(self) -> Vec<u32> {
+        self.keys
+    }
+
+    /// Returns the number of edges the path follows, one less than its number of nodes.
+    pub fn len(&self) -> usize {
+        self.keys.len().saturating_sub(1)
+    }
+
+    /// Returns `true` if the path follows no edge.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the first node of the path, if any.
+    pub fn first(&self) -> Option<u32> {
+        self.keys.first().copied()
+    }
+
+    /// Returns the last node of the path, if any.
+    pub fn last(&self) -> Option<u32> {
+        self.keys.last().copied()
+    }
+
+    /// Returns the `(source, target)` keys of the edges followed by the path, in order.
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.keys.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    /// Returns `true` if the path follows at least one edge and ends where it starts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_traits::Path;
+    ///
+    /// assert!(Path::new(vec![3, 3]).is_closed());
+    /// assert!(!Path::new(vec![3]).is_closed());
+    /// assert!(!Path::new(vec![0, 1, 2]).is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        !self.is_empty() && self.first() == self.last()
+    }
+
+    /// Returns `true` if no node is visited twice, except for the last node of a closed
+    /// path, which repeats the first one.
+    ///
+    /// Simple closed paths are elementary circuits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_traits::Path;
+    ///
+    /// assert!(Path::new(vec![0, 1, 2]).is_simple());
+    /// assert!(Path::new(vec![0, 1, 2, 0]).is_simple());
+    /// assert!(!Path::new(vec![0, 1, 0, 2]).is_simple());
+    /// ```
+    pub fn is_simple(&self) -> bool {
+        let nodes = if self.is_closed() {
+            &self.keys[..self.keys.len() - 1]
+        } else {
+            &self.keys[..]
+        };
+        find_repeated(nodes).is_none()
+    }
+
+    /// Returns the first node visited twice, which closes a cycle within the path.
+    ///
+    /// A closed path is itself a cycle, so its first node is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_traits::Path;
+    ///
+    /// assert_eq!(Path::new(vec![0, 1, 2, 1, 3]).find_cycle(), Some(1));
+    /// assert_eq!(Path::new(vec![0, 1, 2]).find_cycle(), None);
+    /// ```
+    pub fn find_cycle(&self) -> Option<u32> {
+        find_repeated(&self.keys)
+    }
+
+    /// Returns the same closed path, rotated to start and end at its smallest key.
+    ///
+    /// All the rotations of a circuit describe the same circuit; this method picks one of
+    /// them, so that circuits can be compared. Paths that are not closed are returned
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ade_traits::Path;
+    ///
+    /// assert_eq!(Path::new(vec![2, 0, 1, 2]).rotated_to_min(), vec![0, 1, 2, 0]);
+    /// assert_eq!(Path::new(vec![2, 0, 1]).rotated_to_min(), vec![2, 0, 1]);
+    /// ```
+    pub fn rotated_to_min(&self) -> Path {
+        if !self.is_closed() {
+            return self.clone();
+        }
+        let nodes = &self.keys[..self.keys.len() - 1];
+        let start = (0..nodes.len()).min_by_key(|&i| nodes[i]).unwrap_or(0);
+
+        let mut keys: Vec<u32> = nodes[start..]
+            .iter()
+            .chain(&nodes[..start])
+            .copied()
+            .collect();
+        keys.push(nodes[start]);
+        Path::new(keys)
+    }
+
+    /// Sums the weights of the edges followed by the path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path follows an edge that is not in the graph.
+    pub fn weight<N: NodeTrait, E: EdgeTrait, W: Sum<W>>(
+        &self,
+        graph: &impl GraphViewTrait<N, E>,
+        weight: impl Fn(&E) -> W,
+    ) -> W {
+        self.edges()
+            .map(|(source, target)| weight(graph.get_edge(source, target)))
+            .sum()
+    }
+
+    /// Sums the weights of the edges followed by the path, for graphs with weighted edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path follows an edge that is not in the graph.
+    pub fn total_weight<N: NodeTrait, E: WeightedEdgeTrait>(
+        &self,
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> E::Weight
+    where
+        E::Weight: Sum,
+    {
+        self.weight(graph, E::weight)
+    }
+
+    /// Checks that the path has at least one node, and that its nodes and edges are in the
+    /// graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found, as a [`PathError`].
+    pub fn validate<N: NodeTrait, E: EdgeTrait>(
+        &self,
+        graph: &impl GraphViewTrait<N, E>,
+    ) -> Result<(), PathError> {
+        if self.keys.is_empty() {
+            return Err(PathError::Empty);
+        }
+        if let Some(&key) = self.keys.iter().find(|&&key| !graph.has_node(key)) {
+            return Err(PathError::NodeNotFound(key));
+        }
+        match self
+            .edges()
+            .find(|&(source, target)| !graph.has_edge(source, target))
+        {
+            Some((source, target)) => Err(PathError::EdgeNotFound(source, target)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Returns the first key that already appeared earlier in nodes
+fn find_repeated(nodes: &[u32]) -> Option<u32> {
+    let mut seen = HashSet::with_capacity(nodes.len());
+    nodes.iter().copied().find(|&key| !seen.insert(key))
+}
+
+impl From<Vec<u32>> for Path {
+    fn from(keys: Vec<u32>) -> Self {
+        Self::new(keys)
+    }
+}
+
+impl From<&[u32]> for Path {
+    fn from(keys: &[u32]) -> Self {
+        Self::new(keys.to_vec())
+    }
+}
+
+impl From<Path> for Vec<u32> {
+    fn from(path: Path) -> Self {
+        path.keys
+    }
+}
+
+impl AsRef<[u32]> for Path {
+    fn as_ref(&self) -> &[u32] {
+        &self.keys
+    }
+}
+
+impl PartialEq<Vec<u32>> for Path {
+    fn eq(&self, other: &Vec<u32>) -> bool {
+        &self.keys == other
+    }
+}
+
+impl PartialEq<[u32]> for Path {
+    fn eq(&self, other: &[u32]) -> bool {
+        self.keys == other
+    }
+}
+
+impl<'a> IntoIterator for &'a Path {
+    type Item = &'a u32;
+    type IntoIter = std::slice::Iter<'a, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.iter()
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.keys.iter().enumerate() {
+            if i > 0 {
+                write!(f, " → ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem found by [`Path::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// The path has no nodes.
+    Empty,
+    /// The path visits a node that is not in the graph.
+    NodeNotFound(u32),
+    /// The path follows an edge that is not in the graph, given as `(source, target)`.
+    EdgeNotFound(u32, u32),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::Empty => write!(f, "Path has no nodes"),
+            PathError::NodeNotFound(key) => write!(f, "Node {} not found", key),
+            PathError::EdgeNotFound(source, target) => {
+                write!(f, "Edge {}→{} not found", source, target)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lengths() {
+        assert_eq!(Path::new(vec![]).len(), 0);
+        assert!(Path::new(vec![]).is_empty());
+        assert_eq!(Path::new(vec![4]).len(), 0);
+        assert_eq!(Path::new(vec![4, 5]).len(), 1);
+        assert_eq!(Path::new(vec![4]).first(), Some(4));
+        assert_eq!(Path::new(vec![]).last(), None);
+    }
+
+    #[test]
+    fn test_cycles() {
+        assert!(Path::new(vec![2, 2]).is_simple());
+        assert_eq!(Path::new(vec![2, 2]).find_cycle(), Some(2));
+        assert!(!Path::new(vec![0, 1, 2, 1, 0]).is_simple());
+        assert_eq!(Path::new(vec![]).find_cycle(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Path::new(vec![1, 2, 3]).to_string(), "1 → 2 → 3");
+        assert_eq!(
+            PathError::EdgeNotFound(1, 2).to_string(),
+            "Edge 1→2 not found"
+        );
+    }
+}
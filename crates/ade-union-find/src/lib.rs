@@ -0,0 +1,3 @@
+pub mod union_find;
+
+pub use union_find::UnionFind;
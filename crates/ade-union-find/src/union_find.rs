@@ -0,0 +1,199 @@
+/// A union-find (disjoint set) structure over the keys `0..n`.
+///
+/// Each key starts in a set of its own. [`union`](UnionFind::union) merges the sets of two
+/// keys and [`find`](UnionFind::find) returns the representative of the set of a key, so
+/// two keys are in the same set when they share a representative. Path compression and
+/// union by rank keep the trees flat, so any sequence of `m` operations runs in
+/// `O(m α(n))` time, where `α` is the inverse Ackermann function.
+///
+/// Keys are `u32`, like node keys in graphs, and parents and ranks are stored in two
+/// vectors, so the structure takes five bytes per key. Graphs whose keys are not sequential
+/// can map them to `0..n` first.
+///
+/// # Examples
+///
+/// ```
+/// use ade_union_find::UnionFind;
+///
+/// let mut sets = UnionFind::new(5);
+/// assert!(sets.union(0, 1));
+/// assert!(sets.union(3, 4));
+/// assert!(!sets.union(1, 0));
+///
+/// assert!(sets.same_set(0, 1));
+/// assert!(!sets.same_set(1, 3));
+/// assert_eq!(sets.into_groups(), vec![vec![0, 1], vec![2], vec![3, 4]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<u32>,
+    rank: Vec<u8>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// Creates a structure over the keys `0..n`, each in a set of its own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds `u32::MAX`.
+    pub fn new(n: usize) -> Self {
+        assert!(u32::try_from(n).is_ok(), "Too many keys: {}", n);
+        Self {
+            parent: (0..n).map(|key| key as u32).collect(),
+            rank: vec![0; n],
+            count: n,
+        }
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns `true` if the structure has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the representative of the set of `key`.
+    ///
+    /// Every key visited on the way to the representative is linked directly to it, so
+    /// later calls are faster. The representative of a set may change after a
+    /// [`union`](UnionFind::union).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not in `0..n`.
+    pub fn find(&mut self, key: u32) -> u32 {
+        self.check(key);
+        let mut root = key;
+        while self.parent[root as usize] != root {
+            root = self.parent[root as usize];
+        }
+        let mut key = key;
+        while self.parent[key as usize] != root {
+            let next = self.parent[key as usize];
+            self.parent[key as usize] = root;
+            key = next;
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, attaching the shallower tree under the deeper one.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the sets were merged, `false` if `a` and `b` were already in the same set.
+    /// An edge whose endpoints are already in the same set closes a cycle, which is how
+    /// Kruskal's algorithm and cycle detection in undirected graphs use it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not in `0..n`.
+    pub fn union(&mut self, a: u32, b: u32) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (low, high) = if self.rank[a as usize] < self.rank[b as usize] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[low as usize] = high;
+        if self.rank[low as usize] == self.rank[high as usize] {
+            self.rank[high as usize] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+
+    /// Returns `true` if `a` and `b` are in the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not in `0..n`.
+    pub fn same_set(&mut self, a: u32, b: u32) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Consumes the structure and returns its sets.
+    ///
+    /// # Returns
+    ///
+    /// A vector of sets, each a vector of keys in ascending order. Sets are sorted by their
+    /// smallest key.
+    pub fn into_groups(mut self) -> Vec<Vec<u32>> {
+        // Keys are visited in ascending order, so groups come out sorted
+        let mut group_of_root = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<u32>> = Vec::with_capacity(self.count);
+        for key in 0..self.len() as u32 {
+            let root = self.find(key) as usize;
+            if group_of_root[root] == usize::MAX {
+                group_of_root[root] = groups.len();
+                groups.push(Vec::new());
+            }
+            groups[group_of_root[root]].push(key);
+        }
+        groups
+    }
+
+    // Panics with the standard message if `key` is not in 0..n
+    fn check(&self, key: u32) {
+        if key as usize >= self.parent.len() {
+            panic!("Key {} not found", key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ade_common::assert_panics_with;
+
+    #[test]
+    fn test_union_and_groups() {
+        let mut sets = UnionFind::new(8);
+        assert_eq!(sets.set_count(), 8);
+        for (a, b) in [(7, 2), (2, 5), (0, 6), (5, 7)] {
+            sets.union(a, b);
+        }
+        assert_eq!(sets.set_count(), 5);
+        assert!(sets.same_set(7, 5));
+        assert!(!sets.same_set(0, 2));
+        assert_eq!(
+            sets.into_groups(),
+            vec![vec![0, 6], vec![1], vec![2, 5, 7], vec![3], vec![4]]
+        );
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        // Unions along a long path must not build deep trees
+        let n: u32 = 1_000_000;
+        let mut sets = UnionFind::new(n as usize);
+        for key in 1..n {
+            assert!(sets.union(key, key - 1));
+        }
+        assert_eq!(sets.set_count(), 1);
+        assert!(sets.same_set(0, n - 1));
+        assert_eq!(sets.into_groups()[0].len(), n as usize);
+    }
+
+    #[test]
+    fn test_empty_and_missing_key() {
+        let sets = UnionFind::new(0);
+        assert!(sets.is_empty());
+        assert!(sets.into_groups().is_empty());
+
+        let mut sets = UnionFind::new(3);
+        assert_panics_with!(sets.find(3), "Key 3 not found");
+        assert_panics_with!(sets.union(0, 4), "Key 4 not found");
+    }
+}